@@ -0,0 +1,87 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+pub(crate) fn bit_flags(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    match expand_bit_flags(input) {
+        Ok(ts) => ts.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn expand_bit_flags(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let target = &input.ident;
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            target,
+            "BitFlags can only be derived on enums",
+        ));
+    };
+
+    let mut type_name = to_snake_case(&target.to_string());
+    for attr in input.attrs.iter().filter(|a| a.path().is_ident("bit_flags")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                type_name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else {
+                Err(meta.error("unsupported bit_flags attribute"))
+            }
+        })?;
+    }
+
+    let mut variants = vec![];
+    let mut bits = vec![];
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "BitFlags variants cannot have fields",
+            ));
+        }
+
+        let Some((_, discriminant)) = &variant.discriminant else {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "BitFlags variants must have an explicit bit value",
+            ));
+        };
+
+        variants.push(&variant.ident);
+        bits.push(discriminant);
+    }
+
+    Ok(quote! {
+        impl ::creed::r#type::BitFlag for #target {
+            const TYPE_NAME: &'static str = #type_name;
+
+            fn bits(&self) -> i64 {
+                match self {
+                    #(Self::#variants => (#bits) as i64,)*
+                }
+            }
+
+            fn all() -> &'static [Self] {
+                &[#(Self::#variants),*]
+            }
+        }
+    })
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                result.push('_');
+            }
+
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
@@ -1,4 +1,5 @@
 extern crate proc_macro;
+mod bit_flags;
 mod common;
 mod into_identifier;
 mod migrate;
@@ -12,6 +13,11 @@ pub fn into_identifier_derive_fn(input: TokenStream) -> TokenStream {
     into_identifier::into_identifier(input)
 }
 
+#[proc_macro_derive(BitFlags, attributes(bit_flags))]
+pub fn bit_flags_derive_fn(input: TokenStream) -> TokenStream {
+    bit_flags::bit_flags(input)
+}
+
 #[proc_macro]
 pub fn migrator(input: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(input as migrate::MigrateInput);
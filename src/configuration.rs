@@ -1,15 +1,50 @@
+use crate::ResultCache;
+use regex::Regex;
 use std::fmt::{Debug, Formatter};
+use std::time::Duration;
 
 type SchemaAssetFilterType = dyn (Fn(&str) -> bool) + Sync + Send;
 
+/// How the duplicate column names of a result set (ie: when selecting `*` from a join) are handled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicateColumnStrategy {
+    /// Column names are kept as returned by the server:
+    /// only the first of the duplicate columns is addressable by name.
+    #[default]
+    Keep,
+    /// Duplicate columns are prefixed with their table name or alias (ie: `u.id`)
+    /// where exposed by the driver, falling back to a positional suffix otherwise.
+    TablePrefix,
+    /// Duplicate columns but the first are suffixed with their occurrence (ie: `id`, `id_1`).
+    PositionalSuffix,
+}
+
 pub struct Configuration {
     schema_assets_filter: Box<SchemaAssetFilterType>,
+    schema_assets_allowlist: Vec<Regex>,
+    schema_assets_denylist: Vec<Regex>,
+    duplicate_column_strategy: DuplicateColumnStrategy,
+    auto_index_foreign_keys: bool,
+    redact_traced_sql: bool,
+    savepoint_prefix: String,
+    result_cache: Option<Box<dyn ResultCache>>,
+    advisory_locks_table: Option<String>,
+    advisory_lock_timeout: Option<Duration>,
 }
 
 impl Configuration {
     pub fn new() -> Self {
         Self {
             schema_assets_filter: Box::new(|_| true),
+            schema_assets_allowlist: vec![],
+            schema_assets_denylist: vec![],
+            duplicate_column_strategy: DuplicateColumnStrategy::default(),
+            auto_index_foreign_keys: false,
+            redact_traced_sql: false,
+            savepoint_prefix: "CREED_SAVEPOINT_".to_string(),
+            result_cache: None,
+            advisory_locks_table: None,
+            advisory_lock_timeout: Some(Duration::from_secs(3600)),
         }
     }
 
@@ -21,11 +56,134 @@ impl Configuration {
     pub fn get_schema_assets_filter(&self) -> &SchemaAssetFilterType {
         &self.schema_assets_filter
     }
+
+    /// Sets the patterns of the schema assets (tables, views, sequences) to be introspected.
+    /// If not empty, only the assets whose name matches at least one of the patterns are included.
+    pub fn set_schema_assets_allowlist(mut self, allowlist: Vec<Regex>) -> Self {
+        self.schema_assets_allowlist = allowlist;
+        self
+    }
+
+    pub fn get_schema_assets_allowlist(&self) -> &[Regex] {
+        &self.schema_assets_allowlist
+    }
+
+    /// Sets the patterns of the schema assets to be excluded from introspection.
+    /// The denylist takes precedence over the allowlist.
+    pub fn set_schema_assets_denylist(mut self, denylist: Vec<Regex>) -> Self {
+        self.schema_assets_denylist = denylist;
+        self
+    }
+
+    pub fn get_schema_assets_denylist(&self) -> &[Regex] {
+        &self.schema_assets_denylist
+    }
+
+    /// Whether the asset with the given name passes the allowlist, the denylist
+    /// and the schema assets filter.
+    pub fn is_schema_asset_included(&self, name: &str) -> bool {
+        (self.schema_assets_allowlist.is_empty()
+            || self
+                .schema_assets_allowlist
+                .iter()
+                .any(|r| r.is_match(name)))
+            && !self.schema_assets_denylist.iter().any(|r| r.is_match(name))
+            && (self.schema_assets_filter)(name)
+    }
+
+    /// Sets how duplicate column names are disambiguated in the result sets
+    /// returned by the connection query methods.
+    pub fn set_duplicate_column_strategy(mut self, strategy: DuplicateColumnStrategy) -> Self {
+        self.duplicate_column_strategy = strategy;
+        self
+    }
+
+    pub fn get_duplicate_column_strategy(&self) -> DuplicateColumnStrategy {
+        self.duplicate_column_strategy
+    }
+
+    /// Sets whether an index is added on the foreign key columns not covered by one
+    /// when creating or altering tables, on platforms requiring it (MySQL).
+    pub fn set_auto_index_foreign_keys(mut self, enabled: bool) -> Self {
+        self.auto_index_foreign_keys = enabled;
+        self
+    }
+
+    pub fn get_auto_index_foreign_keys(&self) -> bool {
+        self.auto_index_foreign_keys
+    }
+
+    /// Sets whether the string literals of the SQL recorded on the
+    /// tracing spans are replaced by a placeholder (requires the `tracing` feature).
+    pub fn set_redact_traced_sql(mut self, redact: bool) -> Self {
+        self.redact_traced_sql = redact;
+        self
+    }
+
+    pub fn get_redact_traced_sql(&self) -> bool {
+        self.redact_traced_sql
+    }
+
+    /// Sets the prefix of the savepoints created for nested transactions,
+    /// followed by the transaction nesting level (ie: `creed_sp_2`).
+    /// Defaults to `CREED_SAVEPOINT_`.
+    pub fn set_savepoint_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.savepoint_prefix = prefix.into();
+        self
+    }
+
+    pub fn get_savepoint_prefix(&self) -> &str {
+        &self.savepoint_prefix
+    }
+
+    /// Sets the cache consulted by [`Connection::fetch_all`](crate::Connection::fetch_all)
+    /// before querying the database (ie: an [`InMemoryResultCache`](crate::InMemoryResultCache)).
+    pub fn set_result_cache(mut self, cache: impl ResultCache + 'static) -> Self {
+        self.result_cache = Some(Box::new(cache));
+        self
+    }
+
+    pub fn get_result_cache(&self) -> Option<&dyn ResultCache> {
+        self.result_cache.as_deref()
+    }
+
+    /// Sets the table used to emulate advisory locks on platforms not supporting them (ie: SQLite).
+    /// The table is created on first use, and is a regular table of the database schema.
+    /// Advisory locks are not supported on such platforms unless a table is set.
+    pub fn set_advisory_locks_table(mut self, table: Option<&str>) -> Self {
+        self.advisory_locks_table = table.map(ToString::to_string);
+        self
+    }
+
+    pub fn get_advisory_locks_table(&self) -> Option<&str> {
+        self.advisory_locks_table.as_deref()
+    }
+
+    /// Sets the time after which an advisory lock emulated through a table (ie: on SQLite)
+    /// is considered stale and can be acquired by another connection, as the lock is not
+    /// released if the holding process dies. Defaults to one hour, `None` disables the expiration.
+    pub fn set_advisory_lock_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.advisory_lock_timeout = timeout;
+        self
+    }
+
+    pub fn get_advisory_lock_timeout(&self) -> Option<Duration> {
+        self.advisory_lock_timeout
+    }
 }
 
 impl Debug for Configuration {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("Configuration").finish()
+        f.debug_struct("Configuration")
+            .field("schema_assets_allowlist", &self.schema_assets_allowlist)
+            .field("schema_assets_denylist", &self.schema_assets_denylist)
+            .field("duplicate_column_strategy", &self.duplicate_column_strategy)
+            .field("auto_index_foreign_keys", &self.auto_index_foreign_keys)
+            .field("redact_traced_sql", &self.redact_traced_sql)
+            .field("savepoint_prefix", &self.savepoint_prefix)
+            .field("result_cache", &self.result_cache.is_some())
+            .field("advisory_lock_timeout", &self.advisory_lock_timeout)
+            .finish_non_exhaustive()
     }
 }
 
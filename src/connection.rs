@@ -2,20 +2,25 @@ use crate::driver::statement::Statement;
 use crate::driver::statement_result::StatementResult;
 use crate::driver::Driver;
 use crate::event::ConnectionEvent;
-use crate::parameter::{IntoParameters, NO_PARAMS};
-use crate::platform::DatabasePlatform;
-use crate::r#type::IntoType;
+use crate::parameter::{IntoParameter, IntoParameters, NO_PARAMS};
+use crate::platform::{DatabasePlatform, UpsertReporting};
+use crate::query::{MergeBuilder, QueryBuilder, UpsertResult};
+use crate::r#type::{IntoType, DATETIMETZ};
+use crate::result_cache::{get_written_tables, is_cacheable};
 use crate::schema::SchemaManager;
 use crate::util::PlatformBox;
 use crate::{
-    params, Configuration, ConnectionOptions, Error, EventDispatcher, Parameters, Result, Row,
-    TypedValueMap, Value, ValueMap,
+    params, Configuration, ConnectionOptions, Error, EventDispatcher, Parameter, Parameters,
+    Result, ResultCacheKey, ReusableStatement, Row, TypedValueMap, Value, ValueMap,
 };
+use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use log::debug;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Read;
+use std::path::Path;
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 /// The main database connection struct.
 ///
@@ -40,6 +45,8 @@ pub struct Connection {
     platform: Option<PlatformBox>,
     event_manager: Arc<EventDispatcher>,
     transaction_nesting_level: AtomicUsize,
+    features: Mutex<HashMap<String, bool>>,
+    advisory_lock_owner: String,
 }
 
 impl Connection {
@@ -67,6 +74,8 @@ impl Connection {
             platform,
             event_manager,
             transaction_nesting_level: AtomicUsize::default(),
+            features: Mutex::default(),
+            advisory_lock_owner: generate_advisory_lock_owner(),
         }
     }
 
@@ -102,6 +111,8 @@ impl Connection {
             driver: Some(driver),
             event_manager,
             transaction_nesting_level: AtomicUsize::default(),
+            features: Mutex::default(),
+            advisory_lock_owner: generate_advisory_lock_owner(),
         })
     }
 
@@ -136,6 +147,19 @@ impl Connection {
             .map(|platform| platform.create_schema_manager(self))
     }
 
+    /// Creates a new query builder for the connection platform.
+    ///
+    /// # Errors
+    ///
+    /// The connection must be connected to the server for this method to succeed.
+    /// Otherwise, will return a "Not Connected" Error.
+    pub fn create_query_builder(&self) -> Result<QueryBuilder<'_>> {
+        self.platform
+            .as_ref()
+            .map(|platform| QueryBuilder::new(platform.as_dyn()))
+            .ok_or_else(Error::not_connected)
+    }
+
     /// Returns the database platform.
     ///
     /// # Errors
@@ -162,19 +186,236 @@ impl Connection {
                 .await
                 .ok()?
                 .and_then(|row| row.get(0).cloned().ok())
-                .and_then(|v| match v {
-                    Value::String(res) => Some(res),
-                    Value::Bytes(v) => {
-                        let mut s = String::new();
-                        v.as_slice().read_to_string(&mut s).ok().map(|_| s)
-                    }
-                    _ => None,
-                })
+                .and_then(value_to_string)
         } else {
             None
         }
     }
 
+    /// Gets the current replication log position of the server, to be used
+    /// as a reference point when taking consistent snapshots.
+    ///
+    /// On MySQL the executed GTID set is returned if GTIDs are enabled,
+    /// the binlog coordinates (`file:position`) otherwise.
+    /// On PostgreSQL the current WAL LSN is returned.
+    ///
+    /// Returns `None` if the platform does not support replication
+    /// or if the server is not a replication source (the binary log is disabled).
+    pub async fn replication_position(&self) -> Result<Option<String>> {
+        let platform = self.get_platform()?;
+        let Some(sql) = platform.get_replication_position_sql() else {
+            return Ok(None);
+        };
+
+        let Some(row) = self.query(sql, params!()).await?.fetch_one().await? else {
+            return Ok(None);
+        };
+
+        if let Ok(gtid_set) = row.get("Executed_Gtid_Set") {
+            if let Some(gtid_set) = value_to_string(gtid_set.clone()).filter(|s| !s.is_empty()) {
+                return Ok(Some(gtid_set));
+            }
+        }
+
+        if let (Ok(file), Ok(position)) = (row.get("File"), row.get("Position")) {
+            return Ok(value_to_string(file.clone())
+                .filter(|file| !file.is_empty())
+                .map(|file| format!("{}:{}", file, position)));
+        }
+
+        Ok(row.get(0).cloned().ok().and_then(value_to_string))
+    }
+
+    /// Reads the planner estimated cost of the given query, without executing it.
+    ///
+    /// The cost is read from the JSON execution plan: on PostgreSQL it is the total cost
+    /// of the root plan node, on MySQL the query cost of the query block.
+    /// Returns `None` if the platform does not expose the plan cost (ie: SQLite).
+    pub async fn estimate_cost<St: Into<String>, P: IntoParameters>(
+        &self,
+        sql: St,
+        params: P,
+    ) -> Result<Option<f64>> {
+        let platform = self.get_platform()?;
+        let Some(sql) = platform.get_estimated_cost_sql(&sql.into()) else {
+            return Ok(None);
+        };
+
+        let Some(row) = self.query(sql, params).await?.fetch_one().await? else {
+            return Ok(None);
+        };
+
+        let plan = match row.get(0)? {
+            Value::Json(plan) => plan.clone(),
+            value => match value_to_string(value.clone()) {
+                Some(plan) => serde_json::from_str(&plan)?,
+                None => return Ok(None),
+            },
+        };
+
+        let cost = plan
+            .pointer("/0/Plan/Total Cost")
+            .or_else(|| plan.pointer("/query_block/cost_info/query_cost"));
+
+        Ok(match cost {
+            Some(serde_json::Value::Number(cost)) => cost.as_f64(),
+            Some(serde_json::Value::String(cost)) => cost.parse().ok(),
+            _ => None,
+        })
+    }
+
+    /// Acquires the advisory lock identified by the given key, waiting for
+    /// it to be released if held by another session.
+    ///
+    /// Advisory locks are application-defined: they do not lock any database object.
+    /// On SQLite they are only available if an [emulation table](Configuration::set_advisory_locks_table)
+    /// is configured, shared by all the connections to the same database file: a lock held
+    /// for longer than the configured [advisory lock timeout](Configuration::set_advisory_lock_timeout)
+    /// can be taken over.
+    pub async fn advisory_lock(&self, key: i64) -> Result<()> {
+        while !self.acquire_advisory_lock(key, true).await? {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+
+        Ok(())
+    }
+
+    /// Tries to acquire the advisory lock identified by the given key, without waiting.
+    /// Returns whether the lock has been acquired.
+    pub async fn try_advisory_lock(&self, key: i64) -> Result<bool> {
+        self.acquire_advisory_lock(key, false).await
+    }
+
+    /// Releases the advisory lock identified by the given key.
+    /// Returns false if the lock was not held by this connection.
+    pub async fn advisory_unlock(&self, key: i64) -> Result<bool> {
+        let platform = self.get_platform()?;
+        let sql = platform.get_advisory_unlock_sql(
+            key,
+            &self.advisory_lock_owner,
+            self.configuration.get_advisory_locks_table(),
+        )?;
+        self.query_advisory_lock(sql).await
+    }
+
+    async fn acquire_advisory_lock(&self, key: i64, wait: bool) -> Result<bool> {
+        let platform = self.get_platform()?;
+        let sql = platform.get_advisory_lock_sql(
+            key,
+            &self.advisory_lock_owner,
+            self.configuration.get_advisory_locks_table(),
+            self.configuration.get_advisory_lock_timeout(),
+            wait,
+        )?;
+        self.query_advisory_lock(sql).await
+    }
+
+    async fn query_advisory_lock(&self, sql: String) -> Result<bool> {
+        let platform = self.get_platform()?;
+        let table_sql = self
+            .configuration
+            .get_advisory_locks_table()
+            .and_then(|table| platform.get_advisory_locks_table_sql(table));
+        if let Some(table_sql) = table_sql {
+            self.execute_statement(table_sql, params!()).await?;
+        }
+
+        let row = self.query(sql, params!()).await?.fetch_one().await?;
+        Ok(row.is_some_and(|row| row.get(0).is_ok_and(bool::from)))
+    }
+
+    /// Whether the given server feature is available (ie: `extension:pg_stat_statements`).
+    /// See [`DatabasePlatform::get_feature_probe_sql`] for the supported features.
+    ///
+    /// Features are probed once: the result is cached for the lifetime of the connection.
+    pub async fn feature(&self, name: &str) -> Result<bool> {
+        if let Some(available) = self.features.lock().unwrap().get(name) {
+            return Ok(*available);
+        }
+
+        let platform = self.get_platform()?;
+        let sql = platform.get_feature_probe_sql(name)?;
+        let available = self
+            .query(sql, params!())
+            .await?
+            .fetch_one()
+            .await?
+            .is_some();
+        self.features
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), available);
+
+        Ok(available)
+    }
+
+    /// Reads the current timestamp from the database server clock.
+    /// Useful to detect clock skews between the client and the server.
+    ///
+    /// On platforms whose current timestamp is not time zone aware, the UTC
+    /// timestamp is read instead (ie: `UTC_TIMESTAMP()` on MySQL).
+    pub async fn server_now(&self) -> Result<DateTime<Utc>> {
+        let platform = self.get_platform()?;
+        let utc_timestamp_sql = platform.get_current_utc_timestamp_sql();
+        let sql = platform.get_dummy_select_sql(Some(
+            utc_timestamp_sql.unwrap_or_else(|| platform.get_current_timestamp_sql()),
+        ));
+        let value = self
+            .query(sql, params!())
+            .await?
+            .fetch_one()
+            .await?
+            .and_then(|row| row.get(0).cloned().ok())
+            .unwrap_or(Value::NULL);
+
+        match self.convert_value(&value, DATETIMETZ)? {
+            // Values without time zone are read in the local time zone.
+            Value::DateTime(dt) if utc_timestamp_sql.is_some() => Ok(dt.naive_local().and_utc()),
+            Value::DateTime(dt) => Ok(dt.with_timezone(&Utc)),
+            value => Err(Error::conversion_failed_invalid_type(
+                &value,
+                DATETIMETZ,
+                &["DateTime"],
+            )),
+        }
+    }
+
+    /// Writes the blob stored in `column` of the row identified by `rowid` into the file at `path`,
+    /// streaming it without loading it entirely in memory. Supported by SQLite only.
+    ///
+    /// This is a blocking operation, as the copy is performed synchronously on the
+    /// connection: in async code, consider running it through `tokio::task::block_in_place`.
+    /// Returns the number of bytes written.
+    pub fn blob_to_file<P: AsRef<Path>>(
+        &self,
+        table: &str,
+        column: &str,
+        rowid: i64,
+        path: P,
+    ) -> Result<u64> {
+        let driver = self.driver.as_ref().ok_or_else(Error::not_connected)?;
+        driver.blob_to_file(table, column, rowid, path.as_ref())
+    }
+
+    /// Reads the file at `path` into the blob stored in `column` of the row identified by `rowid`,
+    /// streaming it without loading it entirely in memory. Supported by SQLite only.
+    ///
+    /// The blob is written within a savepoint, so that a failed write does not leave
+    /// a partially written blob.
+    /// This is a blocking operation, as the copy is performed synchronously on the
+    /// connection: in async code, consider running it through `tokio::task::block_in_place`.
+    /// Returns the number of bytes read.
+    pub fn blob_from_file<P: AsRef<Path>>(
+        &self,
+        table: &str,
+        column: &str,
+        rowid: i64,
+        path: P,
+    ) -> Result<u64> {
+        let driver = self.driver.as_ref().ok_or_else(Error::not_connected)?;
+        driver.blob_from_file(table, column, rowid, path.as_ref())
+    }
+
     /// Initiate the connection to the SQL server.
     ///
     /// # Events
@@ -215,6 +456,22 @@ impl Connection {
         }
 
         let driver = Arc::new(Driver::create(&self.connection_options).await?);
+        if self.platform.is_none() {
+            if let Some(name) = self.connection_options.platform_name.as_deref() {
+                let platform = driver
+                    .create_platform_by_name(
+                        self.connection_options
+                            .scheme
+                            .as_deref()
+                            .unwrap_or_default(),
+                        name,
+                        self.event_manager.clone(),
+                    )
+                    .await?;
+                let _ = self.platform.insert(Arc::new(platform));
+            }
+        }
+
         if self.platform.is_none() {
             let platform = Arc::new(driver.create_platform(self.event_manager.clone()).await);
             let _ = self.platform.insert(platform);
@@ -241,7 +498,20 @@ impl Connection {
     /// object will.
     pub fn prepare<St: Into<String>>(&self, sql: St) -> Result<Box<dyn Statement<'_> + '_>> {
         let driver = self.driver.as_ref().ok_or_else(Error::not_connected)?;
-        driver.prepare(sql)
+        driver.prepare(self.tag_sql(sql.into()))
+    }
+
+    /// Prepares a statement which can be executed many times through the
+    /// returned handle, without being prepared again on each execution.
+    ///
+    /// Prepared statements are not cached by the connection: calling this
+    /// method again with the same SQL prepares a new statement.
+    pub fn prepare_reusable<St: Into<String>>(&self, sql: St) -> Result<ReusableStatement<'_>> {
+        let driver = self.driver.as_ref().ok_or_else(Error::not_connected)?;
+        let sql = sql.into();
+        let statement = driver.prepare(self.tag_sql(sql.clone()))?;
+
+        Ok(ReusableStatement::new(sql, statement, self))
     }
 
     /// Executes an SQL statement, returning a result set as a StatementResult object.
@@ -252,7 +522,16 @@ impl Connection {
     ) -> Result<StatementResult> {
         let driver = self.driver.as_ref().ok_or_else(Error::not_connected)?;
         let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
-        driver.query(sql, params.into_parameters(platform)?).await
+        let sql = sql.into();
+        self.invalidate_result_cache(&sql);
+
+        let sql = self.tag_sql(sql);
+        let params = params.into_parameters(platform)?;
+        let result = self
+            .instrument("query", &sql, driver.query(sql.as_str(), params))
+            .await?;
+
+        Ok(result.deduplicate_columns(self.configuration.get_duplicate_column_strategy()))
     }
 
     /// Executes an SQL statement with the given parameters and returns the number of affected rows.
@@ -262,6 +541,8 @@ impl Connection {
     /// - DCL statements: GRANT, REVOKE, etc.
     /// - Session control statements: ALTER SESSION, SET, DECLARE, etc.
     /// - Other statements that don't yield a row set.
+    ///
+    /// If a result cache is configured, it is notified of the table written by DML statements.
     pub async fn execute_statement<St: Into<String>, P: IntoParameters>(
         &self,
         sql: St,
@@ -270,35 +551,220 @@ impl Connection {
         let driver = self.driver.as_ref().ok_or_else(Error::not_connected)?;
         let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
 
-        let stmt = driver.prepare(sql)?;
-        stmt.execute(params.into_parameters(platform)?).await
+        let sql = sql.into();
+        self.invalidate_result_cache(&sql);
+
+        let sql = self.tag_sql(sql);
+        let params = params.into_parameters(platform)?;
+        self.instrument("execute", &sql, async {
+            let stmt = driver.prepare(sql.as_str())?;
+            stmt.execute(params).await
+        })
+        .await
     }
 
     /// Inserts a record into the given table.
+    ///
+    /// Columns set to [`Value::Default`] are filled with their default value:
+    /// the DEFAULT keyword is used where supported, otherwise the column is omitted.
     pub async fn insert(&self, table: &str, values: TypedValueMap<'_>) -> Result<usize> {
+        let (sql, params) = self.get_insert_sql(table, values)?;
+        self.execute_statement(sql, Parameters::from(params)).await
+    }
+
+    /// Inserts a record into the given table, returning the value of the given column
+    /// of the inserted row through the RETURNING clause.
+    ///
+    /// Returns an error if the platform does not support INSERT ... RETURNING.
+    pub async fn insert_returning(
+        &self,
+        table: &str,
+        values: TypedValueMap<'_>,
+        column: &str,
+    ) -> Result<Option<Value>> {
+        let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
+        if !platform.supports_insert_returning() {
+            return Err(Error::platform_feature_unsupported(
+                "INSERT ... RETURNING is not supported by this platform.",
+            ));
+        }
+
+        let (sql, params) = self.get_insert_sql(table, values)?;
+        let sql = format!("{} RETURNING {}", sql, platform.quote_identifier(column));
+        let row = self
+            .query(sql, Parameters::from(params))
+            .await?
+            .fetch_one()
+            .await?;
+
+        row.map(|row| row.get(0).cloned()).transpose()
+    }
+
+    fn get_insert_sql(
+        &self,
+        table: &str,
+        values: TypedValueMap<'_>,
+    ) -> Result<(String, Vec<Parameter>)> {
         if values.is_empty() {
-            self.execute_statement(format!("INSERT INTO {} () VALUES ()", table), NO_PARAMS)
-                .await
-        } else {
-            let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
+            return Ok((format!("INSERT INTO {} () VALUES ()", table), vec![]));
+        }
+
+        let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
+
+        let mut columns = Vec::with_capacity(values.len());
+        let mut set = Vec::with_capacity(values.len());
+        let mut params = Vec::with_capacity(values.len());
+        for (column, value) in values.into_iter() {
+            if matches!(value.value, Value::Default) {
+                if !platform.supports_default_in_insert_values() {
+                    continue;
+                }
+
+                set.push("DEFAULT");
+            } else {
+                set.push("?");
+                params.push(value.into_parameter(platform)?);
+            }
 
-            let set = vec!["?"; values.len()].join(", ");
-            let columns = values
-                .keys()
-                .map(|k| platform.quote_identifier(k))
-                .join(", ");
+            columns.push(platform.quote_identifier(column));
+        }
 
-            self.execute_statement(
+        if columns.is_empty() {
+            return Ok((
                 format!(
-                    "INSERT INTO {} ({}) VALUES ({})",
-                    platform.quote_identifier(table),
-                    columns,
-                    set
+                    "INSERT INTO {} DEFAULT VALUES",
+                    platform.quote_identifier(table)
                 ),
-                values.into_values(),
-            )
-            .await
+                params,
+            ));
+        }
+
+        Ok((
+            format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                platform.quote_identifier(table),
+                columns.join(", "),
+                set.join(", ")
+            ),
+            params,
+        ))
+    }
+
+    /// Inserts multiple records into the given table.
+    ///
+    /// Rows are inserted with multi-row INSERT statements, split in chunks to
+    /// not exceed the maximum number of parameters bound by the platform.
+    /// All the rows must have the same columns of the first one.
+    /// Values set to [`Value::Default`] are rendered inline as the DEFAULT keyword;
+    /// on platforms not supporting it, consecutive rows defaulting the same columns
+    /// are inserted together omitting them, preserving the order of the rows.
+    /// Returns the total number of inserted rows.
+    pub async fn insert_many(&self, table: &str, rows: Vec<TypedValueMap<'_>>) -> Result<usize> {
+        let Some(first) = rows.first() else {
+            return Ok(0);
+        };
+
+        let columns = first.keys().copied().collect::<Vec<_>>();
+        if columns.is_empty() {
+            let mut inserted = 0;
+            for row in rows {
+                inserted += self.insert(table, row).await?;
+            }
+
+            return Ok(inserted);
         }
+
+        if rows.iter().any(|row| {
+            row.len() != columns.len() || columns.iter().any(|c| !row.as_map().contains_key(c))
+        }) {
+            return Err(Error::row_columns_mismatch(table));
+        }
+
+        let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
+        let omit_defaults = !platform.supports_default_in_insert_values();
+        let mut groups: Vec<(Vec<&str>, Vec<TypedValueMap>)> = vec![];
+        for row in rows {
+            let row_columns = columns
+                .iter()
+                .copied()
+                .filter(|c| !omit_defaults || !matches!(row.as_map()[c].value, Value::Default))
+                .collect::<Vec<_>>();
+
+            match groups.last_mut() {
+                Some((group_columns, group_rows)) if *group_columns == row_columns => {
+                    group_rows.push(row)
+                }
+                _ => groups.push((row_columns, vec![row])),
+            }
+        }
+
+        let mut inserted = 0;
+        for (columns, rows) in groups {
+            inserted += self.insert_rows(table, &columns, &rows).await?;
+        }
+
+        Ok(inserted)
+    }
+
+    /// Inserts the given columns of the rows with multi-row INSERT statements.
+    async fn insert_rows(
+        &self,
+        table: &str,
+        columns: &[&str],
+        rows: &[TypedValueMap<'_>],
+    ) -> Result<usize> {
+        let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
+        let table = platform.quote_identifier(table);
+        if columns.is_empty() {
+            let mut inserted = 0;
+            for _ in rows {
+                inserted += self
+                    .execute_statement(format!("INSERT INTO {} DEFAULT VALUES", table), NO_PARAMS)
+                    .await?;
+            }
+
+            return Ok(inserted);
+        }
+
+        let chunk_size = get_rows_per_statement(columns.len(), platform.get_max_bind_parameters())?;
+        let quoted_columns = columns
+            .iter()
+            .map(|k| platform.quote_identifier(k))
+            .join(", ");
+
+        let mut inserted = 0;
+        for chunk in rows.chunks(chunk_size) {
+            let mut params = Vec::with_capacity(chunk.len() * columns.len());
+            let mut values = Vec::with_capacity(chunk.len());
+            for row in chunk {
+                let mut placeholders = Vec::with_capacity(columns.len());
+                for column in columns {
+                    let value = &row.as_map()[column];
+                    if matches!(value.value, Value::Default) {
+                        placeholders.push("DEFAULT");
+                    } else {
+                        placeholders.push("?");
+                        params.push(value.clone().into_parameter(platform)?);
+                    }
+                }
+
+                values.push(format!("({})", placeholders.join(", ")));
+            }
+
+            inserted += self
+                .execute_statement(
+                    format!(
+                        "INSERT INTO {} ({}) VALUES {}",
+                        table,
+                        quoted_columns,
+                        values.join(", ")
+                    ),
+                    Parameters::from(params),
+                )
+                .await?;
+        }
+
+        Ok(inserted)
     }
 
     /// Executes an SQL DELETE statement on a table.
@@ -309,26 +775,195 @@ impl Connection {
         }
 
         let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
+        let criteria = criteria.into_iter().collect::<BTreeMap<_, _>>();
         let columns = criteria
             .keys()
             .map(|k| format!("{} = ?", platform.quote_identifier(k)))
             .join(" AND ");
+        let params = criteria
+            .into_values()
+            .map(|value| value.into_parameter(platform))
+            .collect::<Result<Vec<_>>>()?;
 
         self.execute_statement(
             format!("DELETE FROM {} WHERE {}", table, columns),
-            criteria.into_values(),
+            Parameters::from(params),
+        )
+        .await
+    }
+
+    /// Executes an SQL UPDATE statement on a table.
+    /// Table expression and columns are not escaped and are not safe for user-input.
+    ///
+    /// Columns are sorted by name, and parameters are bound in placeholder order:
+    /// the SET values first, then the WHERE criteria.
+    /// Columns set to [`Value::Default`] are reset to their default value with the DEFAULT keyword.
+    pub async fn update(
+        &self,
+        table: &str,
+        values: TypedValueMap<'_>,
+        criteria: TypedValueMap<'_>,
+    ) -> Result<usize> {
+        if criteria.is_empty() {
+            return Err(Error::empty_criteria());
+        }
+
+        if values.is_empty() {
+            return Ok(0);
+        }
+
+        let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
+        let values = values.into_iter().collect::<BTreeMap<_, _>>();
+        let criteria = criteria.into_iter().collect::<BTreeMap<_, _>>();
+
+        let mut set = Vec::with_capacity(values.len());
+        let mut params = Vec::with_capacity(values.len() + criteria.len());
+        for (column, value) in values {
+            if !matches!(value.value, Value::Default) {
+                set.push(format!("{} = ?", platform.quote_identifier(column)));
+                params.push(value);
+            } else if platform.supports_default_in_insert_values() {
+                set.push(format!("{} = DEFAULT", platform.quote_identifier(column)));
+            } else {
+                return Err(Error::platform_feature_unsupported(
+                    "DEFAULT keyword is not supported by this platform.",
+                ));
+            }
+        }
+
+        let set = set.join(", ");
+        let conditions = criteria
+            .keys()
+            .map(|k| format!("{} = ?", platform.quote_identifier(k)))
+            .join(" AND ");
+        let params = params
+            .into_iter()
+            .chain(criteria.into_values())
+            .map(|value| value.into_parameter(platform))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.execute_statement(
+            format!("UPDATE {} SET {} WHERE {}", table, set, conditions),
+            Parameters::from(params),
         )
         .await
     }
 
+    /// Executes a merge statement, translated to the upsert form if
+    /// the platform does not support MERGE.
+    pub async fn merge(&self, merge: &MergeBuilder) -> Result<usize> {
+        let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
+        let sql = platform.get_merge_sql(merge)?;
+
+        self.execute_statement(sql, Parameters::from(merge.get_parameters()))
+            .await
+    }
+
+    /// Executes a batch of merge statements as upserts, reporting how many rows
+    /// have been inserted, updated or skipped (ie: DO NOTHING or INSERT IGNORE).
+    ///
+    /// The breakdown depends on the platform capabilities:
+    /// * PostgreSQL reports exact counts through the RETURNING clause;
+    /// * MySQL infers them from the affected row count: an update which leaves
+    ///   the row unchanged is reported as skipped;
+    /// * SQLite cannot tell an insertion from an update when a statement has both
+    ///   branches: those rows are counted in `inserted_or_updated`.
+    ///
+    /// Statements are executed one by one: wrap the call in a transaction to apply them atomically.
+    /// An error is returned before executing any statement if one of them binds more
+    /// parameters than supported by the platform.
+    pub async fn upsert_many(&self, merges: &[MergeBuilder]) -> Result<UpsertResult> {
+        let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
+        let reporting = platform.get_upsert_reporting();
+        let mut result = UpsertResult::default();
+
+        let max_parameters = platform.get_max_bind_parameters();
+        if let Some(count) = merges
+            .iter()
+            .map(|merge| merge.get_parameters().len())
+            .find(|count| *count > max_parameters)
+        {
+            return Err(Error::too_many_parameters(count, max_parameters));
+        }
+
+        for merge in merges {
+            let sql = platform.get_upsert_sql(merge)?;
+            let params = Parameters::from(merge.get_parameters());
+
+            match reporting {
+                UpsertReporting::ReturningInserted => {
+                    let rows = self.fetch_all(sql, params).await?;
+                    if rows.is_empty() {
+                        result.skipped += 1;
+                    }
+
+                    for row in rows {
+                        if bool::from(row.get("inserted")?) {
+                            result.inserted += 1;
+                        } else {
+                            result.updated += 1;
+                        }
+                    }
+                }
+                UpsertReporting::AffectedRows => match self.execute_statement(sql, params).await? {
+                    0 => result.skipped += 1,
+                    1 => result.inserted += 1,
+                    _ => result.updated += 1,
+                },
+                UpsertReporting::WrittenRows => {
+                    let affected = self.execute_statement(sql, params).await?;
+                    if affected == 0 {
+                        result.skipped += 1;
+                    } else if merge.get_matched_actions().is_empty() {
+                        result.inserted += affected;
+                    } else if !merge.has_not_matched_insert() {
+                        result.updated += affected;
+                    } else {
+                        result.inserted_or_updated += affected;
+                    }
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Executes an SQL statement, returning a result set as a vector of Row objects.
+    /// If a result cache is configured, the rows of read-only queries fetched outside
+    /// a transaction are read from it and stored into it.
     pub async fn fetch_all<St: Into<String>>(
         &self,
         sql: St,
         params: Parameters<'static>,
     ) -> Result<Vec<Row>> {
-        let statement_result = self.query(sql, params).await?;
-        statement_result.fetch_all().await
+        let sql = sql.into();
+        let cache = self
+            .configuration
+            .get_result_cache()
+            .filter(|_| self.transaction_nesting_level.load(Ordering::SeqCst) == 0)
+            .filter(|_| is_cacheable(&sql));
+        let Some(cache) = cache else {
+            return self.query(sql, params).await?.fetch_all().await;
+        };
+
+        let key = ResultCacheKey::new(&sql, &params);
+        if let Some(rows) = cache.get(&key) {
+            return Ok(rows);
+        }
+
+        let rows = self.query(sql, params).await?.fetch_all().await?;
+        cache.put(key, rows.clone());
+
+        Ok(rows)
+    }
+
+    /// Notifies the result cache (if any) of the tables written by the given statement.
+    pub(crate) fn invalidate_result_cache(&self, sql: &str) {
+        if let Some(cache) = self.configuration.get_result_cache() {
+            for table in get_written_tables(sql) {
+                cache.invalidate(table);
+            }
+        }
     }
 
     /// Converts a value from database scalar format into runtime type format,
@@ -391,13 +1026,15 @@ impl Connection {
             .fetch_add(1, Ordering::SeqCst);
         if old_level == 0 {
             debug!(target: "creed::sql", r#""START TRANSACTION""#);
-            driver.begin_transaction().await?;
+            self.instrument("begin", "START TRANSACTION", driver.begin_transaction())
+                .await?;
         } else {
             debug!(target: "creed::sql", r#""SAVEPOINT""#);
-            self.create_savepoint(format!(
-                "CREED_SAVEPOINT_{}",
-                self.transaction_nesting_level.load(Ordering::SeqCst)
-            ))
+            self.create_savepoint(
+                self.get_nested_savepoint_name(
+                    self.transaction_nesting_level.load(Ordering::SeqCst),
+                ),
+            )
             .await?;
         }
 
@@ -415,9 +1052,9 @@ impl Connection {
         let driver = self.driver.as_ref().ok_or_else(Error::not_connected)?;
         if transaction_nesting_level == 1 {
             debug!(target: "creed::sql", r#""COMMIT""#);
-            driver.commit().await?;
+            self.instrument("commit", "COMMIT", driver.commit()).await?;
         } else {
-            self.release_savepoint(format!("CREED_SAVEPOINT_{}", transaction_nesting_level))
+            self.release_savepoint(self.get_nested_savepoint_name(transaction_nesting_level))
                 .await?;
         }
 
@@ -439,9 +1076,10 @@ impl Connection {
         let driver = self.driver.as_ref().ok_or_else(Error::not_connected)?;
         if transaction_nesting_level == 1 {
             debug!(target: "creed::sql", r#""ROLLBACK""#);
-            driver.roll_back().await?;
+            self.instrument("rollback", "ROLLBACK", driver.roll_back())
+                .await?;
         } else {
-            self.rollback_savepoint(format!("CREED_SAVEPOINT_{}", transaction_nesting_level))
+            self.rollback_savepoint(self.get_nested_savepoint_name(transaction_nesting_level))
                 .await?;
         }
 
@@ -453,6 +1091,15 @@ impl Connection {
         Ok(())
     }
 
+    /// Gets the name of the savepoint backing the transaction at the given nesting level.
+    fn get_nested_savepoint_name(&self, nesting_level: usize) -> String {
+        format!(
+            "{}{}",
+            self.configuration.get_savepoint_prefix(),
+            nesting_level
+        )
+    }
+
     /// Creates a new savepoint.
     pub async fn create_savepoint(&self, savepoint: impl AsRef<str>) -> Result<()> {
         let platform = self.get_platform()?;
@@ -511,14 +1158,89 @@ impl Connection {
 
         options
     }
+
+    /// Prepends the query tag configured in the connection options, if any, to the given SQL.
+    pub(crate) fn tag_sql(&self, sql: String) -> String {
+        if let Some(tag) = &self.connection_options.query_tag {
+            format!("/* {} */ {}", sanitize_comment(tag), sql)
+        } else {
+            sql
+        }
+    }
+
+    /// Runs the given driver operation, wrapped in a tracing span when enabled.
+    pub(crate) async fn instrument<T>(
+        &self,
+        operation: &'static str,
+        sql: &str,
+        future: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let platform = self.platform.as_ref().ok_or_else(Error::not_connected)?;
+        crate::instrument::instrument(
+            operation,
+            sql,
+            platform.as_ref().as_ref(),
+            self.configuration.get_redact_traced_sql(),
+            future,
+        )
+        .await
+    }
+}
+
+fn value_to_string(value: Value) -> Option<String> {
+    match value {
+        Value::String(res) => Some(res),
+        Value::Bytes(v) => {
+            let mut s = String::new();
+            v.as_slice().read_to_string(&mut s).ok().map(|_| s)
+        }
+        _ => None,
+    }
+}
+
+/// Breaks up comment delimiters, so that the given text cannot terminate
+/// (or open a nested) block comment it is embedded into.
+fn sanitize_comment(text: &str) -> String {
+    text.replace("*/", "* /").replace("/*", "/ *")
+}
+
+/// Computes the number of rows which can be inserted in a single statement
+/// without exceeding the maximum number of bound parameters.
+fn get_rows_per_statement(column_count: usize, max_parameters: usize) -> Result<usize> {
+    if column_count > max_parameters {
+        Err(Error::too_many_parameters(column_count, max_parameters))
+    } else {
+        Ok(max_parameters / column_count)
+    }
+}
+
+/// Generates a token identifying the connection as the holder of the emulated advisory locks,
+/// unique across the processes sharing the same database.
+fn generate_advisory_lock_owner() -> String {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos());
+
+    format!(
+        "{}-{:x}-{}",
+        std::process::id(),
+        nanos,
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::error::ErrorKind;
     use crate::event::ConnectionEvent;
     use crate::rows::ColumnIndex;
-    use crate::tests::get_database_dsn;
-    use crate::{params, r#type, Connection, EventDispatcher, Result, Row, Value};
+    use crate::tests::{get_database_dsn, MockPreparingConnection};
+    use crate::{
+        params, r#type, Configuration, Connection, ConnectionOptions, DuplicateColumnStrategy,
+        EventDispatcher, Result, Row, Value,
+    };
+    use creed_macros::value_map;
     use lazy_static::lazy_static;
     use serial_test::serial;
     use std::sync::atomic::{AtomicBool, Ordering};
@@ -534,6 +1256,21 @@ mod tests {
         assert!(format!("{:?}", connection).starts_with("Connection {"));
     }
 
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn server_now_is_close_to_client_clock() -> Result<()> {
+        let connection = Connection::create_from_dsn(&get_database_dsn(), None, None)?
+            .connect()
+            .await?;
+
+        let server_now = connection.server_now().await?;
+        let skew = server_now - chrono::Utc::now();
+        assert!(skew.num_seconds().abs() < 5, "clock skew: {}", skew);
+
+        Ok(())
+    }
+
     #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
     #[tokio::test]
     #[serial]
@@ -606,18 +1343,62 @@ mod tests {
     #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
     #[tokio::test]
     #[serial]
-    async fn can_convert_type_to_runtime() -> Result<()> {
-        let connection = Connection::create_from_dsn(&get_database_dsn(), None, None).unwrap();
-        let connection = connection.connect().await.expect("Connection failed");
-        assert!(connection.is_connected());
+    async fn can_iterate_results_with_small_fetch_size() -> Result<()> {
+        let connection = Connection::create_from_dsn(&get_database_dsn(), None, None)?
+            .connect()
+            .await?;
 
-        let mut result = connection
-            .query("SELECT 1", params![])
-            .await
-            .expect("Query failed");
-        let row = result.fetch_one().await?.expect("One row is expected");
+        connection.begin_transaction().await?;
+        let statement = connection.prepare(
+            "WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < 500) SELECT n FROM seq",
+        )?;
+        statement.set_fetch_size(Some(7));
+
+        let mut result = statement.query(params![]).await?;
+        let mut count = 0;
+        while let Some(row) = result.fetch_one().await? {
+            count += 1;
+            assert_eq!(row.get(0)?, &Value::Int(count));
+        }
 
-        let value = row.get(0).expect("At least one column is expected");
+        assert_eq!(count, 500);
+
+        // Dropping a partially iterated result releases the server resources.
+        let mut result = statement.query(params![]).await?;
+        assert_eq!(result.fetch_one().await?.unwrap().get(0)?, &Value::Int(1));
+        drop(result);
+        drop(statement);
+
+        // The cursor is closed by a spawned task.
+        tokio::task::yield_now().await;
+        if connection.get_platform()?.get_name() == "postgresql" {
+            let cursors = connection
+                .fetch_all("SELECT name FROM pg_cursors WHERE name <> ''", params![])
+                .await?;
+            assert!(cursors.is_empty());
+        } else {
+            let rows = connection.fetch_all("SELECT 1", params![]).await?;
+            assert_eq!(rows.len(), 1);
+        }
+
+        connection.commit().await
+    }
+
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn can_convert_type_to_runtime() -> Result<()> {
+        let connection = Connection::create_from_dsn(&get_database_dsn(), None, None).unwrap();
+        let connection = connection.connect().await.expect("Connection failed");
+        assert!(connection.is_connected());
+
+        let mut result = connection
+            .query("SELECT 1", params![])
+            .await
+            .expect("Query failed");
+        let row = result.fetch_one().await?.expect("One row is expected");
+
+        let value = row.get(0).expect("At least one column is expected");
         let v_int = connection
             .convert_value(value, crate::r#type::INTEGER)
             .expect("Failed integer conversion");
@@ -631,6 +1412,61 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn bit_flags_round_trip() -> Result<()> {
+        use crate::r#type::{BitFlag, BitFlagsType, TypeManager};
+        use crate::schema::{Column, Table};
+        use crate::BitFlags;
+
+        #[derive(BitFlags, Clone, Copy, Debug, PartialEq)]
+        #[bit_flags(name = "test_permissions")]
+        enum Permission {
+            Read = 0b001,
+            Write = 0b010,
+            Execute = 0b100,
+        }
+
+        TypeManager::get_instance().register::<BitFlagsType<Permission>>();
+
+        let connection = Connection::create_from_dsn(&get_database_dsn(), None, None)?
+            .connect()
+            .await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        let mut table = Table::new("bit_flags_test");
+        table.add_column(Column::builder("permissions", Permission::TYPE_NAME)?);
+        let _ = schema_manager.drop_table(&"bit_flags_test").await;
+        schema_manager.create_table(&table).await?;
+
+        let value = connection.convert_database_value(
+            BitFlagsType::to_value(&[Permission::Read, Permission::Execute]),
+            Permission::TYPE_NAME,
+        )?;
+        assert_eq!(value, Value::Int(5));
+        connection
+            .execute_statement(
+                "INSERT INTO bit_flags_test (permissions) VALUES (?)",
+                params![0 => value],
+            )
+            .await?;
+
+        let rows = connection
+            .fetch_all("SELECT permissions FROM bit_flags_test", params!())
+            .await?;
+        let value = connection.convert_value(rows[0].get(0)?, Permission::TYPE_NAME)?;
+        assert_eq!(
+            BitFlagsType::<Permission>::to_flags(&value)?,
+            vec![Permission::Read, Permission::Execute]
+        );
+        assert!(BitFlagsType::<Permission>::to_flags(&Value::Int(8)).is_err());
+
+        schema_manager.drop_table(&"bit_flags_test").await?;
+
+        Ok(())
+    }
+
     #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
     #[tokio::test]
     #[serial]
@@ -647,6 +1483,698 @@ mod tests {
         assert_eq!("dbal", &current_database);
     }
 
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn can_retrieve_replication_position() -> Result<()> {
+        let connection = Connection::create_from_dsn(&get_database_dsn(), None, None)?
+            .connect()
+            .await?;
+
+        let position = connection.replication_position().await?;
+        match connection.get_platform()?.get_name().as_str() {
+            "sqlite" => assert_eq!(position, None),
+            "postgresql" => assert!(!position.expect("no WAL position").is_empty()),
+            // The binary log could be disabled on the test server.
+            _ => assert!(position.is_none_or(|p| !p.is_empty())),
+        }
+
+        Ok(())
+    }
+
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn can_estimate_query_cost() -> Result<()> {
+        let connection = Connection::create_from_dsn(&get_database_dsn(), None, None)?
+            .connect()
+            .await?;
+
+        let platform = connection.get_platform()?;
+        if platform.get_name() == "sqlite" {
+            let cost = connection.estimate_cost("SELECT 1", params!()).await?;
+            assert_eq!(cost, None);
+            return Ok(());
+        }
+
+        let _ = connection
+            .execute_statement("DROP TABLE IF EXISTS estimate_cost_test", params!())
+            .await;
+        connection
+            .execute_statement(
+                "CREATE TABLE estimate_cost_test (id INT NOT NULL, val INT NOT NULL, PRIMARY KEY (id))",
+                params!(),
+            )
+            .await?;
+
+        if platform.get_name() == "postgresql" {
+            connection
+                .execute_statement(
+                    "INSERT INTO estimate_cost_test SELECT n, n FROM generate_series(1, 10000) n",
+                    params!(),
+                )
+                .await?;
+            connection
+                .execute_statement("ANALYZE estimate_cost_test", params!())
+                .await?;
+        } else {
+            connection
+                .execute_statement("SET SESSION cte_max_recursion_depth = 10000", params!())
+                .await?;
+            connection
+                .execute_statement(
+                    "INSERT INTO estimate_cost_test WITH RECURSIVE seq (n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < 10000) SELECT n, n FROM seq",
+                    params!(),
+                )
+                .await?;
+            connection
+                .execute_statement("ANALYZE TABLE estimate_cost_test", params!())
+                .await?;
+        }
+
+        let scan_cost = connection
+            .estimate_cost(
+                "SELECT * FROM estimate_cost_test WHERE val = ?",
+                params![0 => 42_i64],
+            )
+            .await?
+            .expect("no sequential scan cost");
+        let lookup_cost = connection
+            .estimate_cost(
+                "SELECT * FROM estimate_cost_test WHERE id = ?",
+                params![0 => 42_i64],
+            )
+            .await?
+            .expect("no indexed lookup cost");
+        assert!(scan_cost > lookup_cost);
+
+        connection
+            .execute_statement("DROP TABLE estimate_cost_test", params!())
+            .await?;
+
+        Ok(())
+    }
+
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn connect_honors_platform_option() -> Result<()> {
+        let options = ConnectionOptions::try_from(get_database_dsn().as_str())?;
+        let result = Connection::create(
+            options
+                .clone()
+                .with_platform_name(Some("unknown".to_string())),
+            None,
+            None,
+        )
+        .connect()
+        .await;
+        assert!(matches!(
+            result.err().unwrap().kind(),
+            ErrorKind::ConfigurationError
+        ));
+
+        let (platform_name, expected_name) = match options.scheme.as_deref() {
+            Some("sqlite") => ("sqlite", "sqlite"),
+            Some("mysql") => ("mariadb", "mysql"),
+            _ => ("postgresql", "postgresql"),
+        };
+
+        let connection = Connection::create(
+            options
+                .clone()
+                .with_platform_name(Some(platform_name.to_string())),
+            None,
+            None,
+        )
+        .connect()
+        .await?;
+        assert_eq!(connection.get_platform()?.get_name(), expected_name);
+
+        // Generic names still detect the server version.
+        let generic_name = match options.scheme.as_deref() {
+            Some("sqlite") => "sqlite",
+            Some("mysql") => "mysql",
+            _ => "postgresql",
+        };
+
+        let detected = Connection::create(options.clone(), None, None)
+            .connect()
+            .await?
+            .get_platform()?;
+        let connection = Connection::create(
+            options.with_platform_name(Some(generic_name.to_string())),
+            None,
+            None,
+        )
+        .connect()
+        .await?;
+        let platform = connection.get_platform()?;
+        assert_eq!(platform.get_name(), detected.get_name());
+        // MariaDB servers are handled as MySQL 5.7 by the generic mysql name.
+        let is_mariadb = generic_name == "mysql"
+            && connection
+                .server_version()
+                .await?
+                .split('.')
+                .next()
+                .and_then(|major| major.parse::<u32>().ok())
+                .is_some_and(|major| major >= 10);
+        if !is_mariadb {
+            assert_eq!(
+                platform.supports_column_compression(),
+                detected.supports_column_compression()
+            );
+            assert_eq!(
+                platform.get_replication_position_sql(),
+                detected.get_replication_position_sql()
+            );
+        }
+
+        Ok(())
+    }
+
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn advisory_locks_are_exclusive_between_connections() -> Result<()> {
+        // In-memory SQLite databases are not shared between connections.
+        let sqlite_file = std::env::temp_dir().join("creed_advisory_locks.sqlite");
+        let mut dsn = get_database_dsn();
+        if dsn.starts_with("sqlite") {
+            let _ = std::fs::remove_file(&sqlite_file);
+            dsn = format!("sqlite://{}", sqlite_file.display());
+
+            // Emulated advisory locks must be explicitly enabled.
+            let connection = Connection::create_from_dsn(&dsn, None, None)?
+                .connect()
+                .await?;
+            assert_eq!(
+                connection.try_advisory_lock(4242).await.unwrap_err().kind(),
+                ErrorKind::PlatformFeatureUnsupported
+            );
+        }
+
+        let configuration =
+            || Some(Configuration::default().set_advisory_locks_table(Some("app_locks")));
+        let first = Connection::create_from_dsn(&dsn, configuration(), None)?
+            .connect()
+            .await?;
+        let second = Connection::create_from_dsn(&dsn, configuration(), None)?
+            .connect()
+            .await?;
+
+        first.advisory_lock(4242).await?;
+        assert!(!second.try_advisory_lock(4242).await?);
+        assert!(second.try_advisory_lock(4243).await?);
+
+        assert!(first.advisory_unlock(4242).await?);
+        assert!(second.try_advisory_lock(4242).await?);
+        assert!(second.advisory_unlock(4242).await?);
+        assert!(second.advisory_unlock(4243).await?);
+        assert!(!second.advisory_unlock(4242).await?);
+
+        let _ = std::fs::remove_file(sqlite_file);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    #[serial]
+    async fn stale_emulated_advisory_locks_can_be_taken_over() -> Result<()> {
+        let sqlite_file = std::env::temp_dir().join("creed_stale_advisory_locks.sqlite");
+        let _ = std::fs::remove_file(&sqlite_file);
+        let dsn = format!("sqlite://{}", sqlite_file.display());
+        let configuration = || {
+            Some(
+                Configuration::default()
+                    .set_advisory_locks_table(Some("app_locks"))
+                    .set_advisory_lock_timeout(Some(std::time::Duration::from_secs(60))),
+            )
+        };
+
+        let first = Connection::create_from_dsn(&dsn, configuration(), None)?
+            .connect()
+            .await?;
+        let second = Connection::create_from_dsn(&dsn, configuration(), None)?
+            .connect()
+            .await?;
+
+        first.advisory_lock(4242).await?;
+        assert!(!second.try_advisory_lock(4242).await?);
+        assert!(!second.advisory_unlock(4242).await?);
+
+        first
+            .execute_statement(
+                "UPDATE app_locks SET acquired_at = acquired_at - 120",
+                params!(),
+            )
+            .await?;
+        assert!(second.try_advisory_lock(4242).await?);
+        assert!(!first.advisory_unlock(4242).await?);
+        assert!(second.advisory_unlock(4242).await?);
+
+        // The emulation table is part of the schema.
+        let schema_manager = first.create_schema_manager()?;
+        assert!(schema_manager
+            .list_table_names()
+            .await?
+            .iter()
+            .any(|t| t == "app_locks"));
+
+        let _ = std::fs::remove_file(sqlite_file);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    #[serial]
+    async fn can_stream_sqlite_blobs_from_and_to_files() -> Result<()> {
+        let connection = Connection::create_from_dsn("sqlite://:memory:", None, None)?
+            .connect()
+            .await?;
+        connection
+            .execute_statement("CREATE TABLE files (content BLOB)", params!())
+            .await?;
+        connection
+            .execute_statement("INSERT INTO files (content) VALUES (NULL)", params!())
+            .await?;
+
+        let directory = tempfile::tempdir()?;
+        let source = directory.path().join("source.bin");
+        let target = directory.path().join("target.bin");
+        let content = (0..5 * 1024 * 1024)
+            .map(|i: u32| (i % 251) as u8)
+            .collect::<Vec<_>>();
+        std::fs::write(&source, &content)?;
+
+        let read = connection.blob_from_file("files", "content", 1, &source)?;
+        assert_eq!(read, content.len() as u64);
+
+        let written = connection.blob_to_file("files", "content", 1, &target)?;
+        assert_eq!(written, content.len() as u64);
+        assert_eq!(std::fs::read(&target)?, content);
+
+        // A failed write is rolled back.
+        let missing = directory.path().join("missing.bin");
+        assert!(connection
+            .blob_from_file("files", "content", 1, &missing)
+            .is_err());
+        assert!(connection
+            .blob_from_file("files", "content", 2, &source)
+            .is_err());
+        connection.blob_to_file("files", "content", 1, &target)?;
+        assert_eq!(std::fs::read(&target)?, content);
+
+        Ok(())
+    }
+
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn duplicate_columns_can_be_disambiguated() -> Result<()> {
+        let sql = "SELECT * FROM (SELECT 1 AS id, 'a' AS name) u CROSS JOIN (SELECT 2 AS id) g";
+        for strategy in [
+            DuplicateColumnStrategy::PositionalSuffix,
+            DuplicateColumnStrategy::TablePrefix,
+        ] {
+            let configuration = Configuration::default().set_duplicate_column_strategy(strategy);
+            let connection =
+                Connection::create_from_dsn(&get_database_dsn(), Some(configuration), None)?
+                    .connect()
+                    .await?;
+
+            let rows = connection.fetch_all(sql, params!()).await?;
+            let row = rows.first().expect("no row returned");
+            let second_id = if strategy == DuplicateColumnStrategy::TablePrefix
+                && connection.get_platform()?.get_name() == "mysql"
+            {
+                assert_eq!(row.get("u.id")?, &Value::Int(1));
+                "g.id"
+            } else {
+                assert_eq!(row.get("id")?, &Value::Int(1));
+                "id_1"
+            };
+
+            assert_eq!(row.get(second_id)?, &Value::Int(2));
+            assert_eq!(row.get("name")?, &Value::from("a"));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn prepare_reusable_prepares_statement_once() -> Result<()> {
+        let driver = MockPreparingConnection::default();
+        let prepared = driver.prepared.clone();
+        let connection = Connection::create_with_connection(Box::new(driver), None, None).await?;
+
+        let statement = connection.prepare_reusable("UPDATE foo SET bar = ?")?;
+        for expected in 1..=3 {
+            assert_eq!(statement.execute(params![0 => 1_i64]).await?, expected);
+        }
+
+        assert_eq!(statement.get_sql(), "UPDATE foo SET bar = ?");
+        assert_eq!(prepared.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fetch_all_results_are_cached_until_expired() -> Result<()> {
+        use crate::InMemoryResultCache;
+        use std::time::Duration;
+
+        let driver = MockPreparingConnection::default();
+        let prepared = driver.prepared.clone();
+        let configuration = Configuration::default()
+            .set_result_cache(InMemoryResultCache::new(Duration::from_millis(200)));
+        let connection =
+            Connection::create_with_connection(Box::new(driver), Some(configuration), None).await?;
+
+        let sql = "SELECT * FROM countries WHERE id = ?";
+        connection.fetch_all(sql, params![0 => 1_i64]).await?;
+        connection.fetch_all(sql, params![0 => 1_i64]).await?;
+        assert_eq!(prepared.load(Ordering::SeqCst), 1);
+
+        connection.fetch_all(sql, params![0 => 2_i64]).await?;
+        assert_eq!(prepared.load(Ordering::SeqCst), 2);
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        connection.fetch_all(sql, params![0 => 1_i64]).await?;
+        assert_eq!(prepared.load(Ordering::SeqCst), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn result_cache_is_invalidated_on_write() -> Result<()> {
+        use crate::InMemoryResultCache;
+        use std::time::Duration;
+
+        let driver = MockPreparingConnection::default();
+        let prepared = driver.prepared.clone();
+        let configuration = Configuration::default().set_result_cache(
+            InMemoryResultCache::new(Duration::from_secs(60)).set_invalidate_on_write(true),
+        );
+        let connection =
+            Connection::create_with_connection(Box::new(driver), Some(configuration), None).await?;
+
+        let sql = "SELECT c.name FROM countries c";
+        connection.fetch_all(sql, params!()).await?;
+        connection
+            .execute_statement("UPDATE currencies SET rate = 1", params!())
+            .await?;
+        connection.fetch_all(sql, params!()).await?;
+        assert_eq!(prepared.load(Ordering::SeqCst), 2);
+
+        connection
+            .execute_statement("DELETE FROM countries WHERE id = 1", params!())
+            .await?;
+        connection.fetch_all(sql, params!()).await?;
+        assert_eq!(prepared.load(Ordering::SeqCst), 4);
+
+        let statement = connection.prepare_reusable("DELETE FROM countries WHERE id = ?")?;
+        statement.execute(params![0 => 2_i64]).await?;
+        connection.fetch_all(sql, params!()).await?;
+        assert_eq!(prepared.load(Ordering::SeqCst), 6);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn result_cache_skips_writes_and_transactions() -> Result<()> {
+        use crate::InMemoryResultCache;
+        use std::time::Duration;
+
+        let driver = MockPreparingConnection::default();
+        let prepared = driver.prepared.clone();
+        let configuration = Configuration::default().set_result_cache(
+            InMemoryResultCache::new(Duration::from_secs(60)).set_invalidate_on_write(true),
+        );
+        let connection =
+            Connection::create_with_connection(Box::new(driver), Some(configuration), None).await?;
+
+        let insert = "INSERT INTO countries (name) VALUES ('Italy') RETURNING id";
+        connection.fetch_all(insert, params!()).await?;
+        connection.fetch_all(insert, params!()).await?;
+        assert_eq!(prepared.load(Ordering::SeqCst), 2);
+
+        let sql = "SELECT c.name FROM countries c";
+        connection.fetch_all(sql, params!()).await?;
+        connection
+            .query(
+                "WITH d AS (DELETE FROM countries RETURNING id) SELECT * FROM d",
+                params!(),
+            )
+            .await?;
+        connection.fetch_all(sql, params!()).await?;
+        assert_eq!(prepared.load(Ordering::SeqCst), 5);
+
+        connection.begin_transaction().await?;
+        connection
+            .fetch_all("SELECT * FROM currencies", params!())
+            .await?;
+        connection.commit().await?;
+
+        let before = prepared.load(Ordering::SeqCst);
+        connection
+            .fetch_all("SELECT * FROM currencies", params!())
+            .await?;
+        assert_eq!(prepared.load(Ordering::SeqCst), before + 1);
+
+        // Cached rows could be stale inside a transaction.
+        connection.begin_transaction().await?;
+        let before = prepared.load(Ordering::SeqCst);
+        connection
+            .fetch_all("SELECT * FROM currencies", params!())
+            .await?;
+        assert_eq!(prepared.load(Ordering::SeqCst), before + 1);
+        connection.commit().await?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn cacheable_queries_are_detected_by_clause() {
+        assert!(super::is_cacheable("SELECT share, \"into\" FROM stocks"));
+        assert!(super::is_cacheable(
+            "SELECT id FROM notes WHERE body = 'select into, for update'"
+        ));
+        assert!(super::is_cacheable("WITH s AS (SELECT 1) SELECT * FROM s"));
+
+        assert!(!super::is_cacheable("SELECT id INTO tmp FROM notes"));
+        assert!(!super::is_cacheable("SELECT id FROM notes FOR UPDATE"));
+        assert!(!super::is_cacheable(
+            "SELECT id FROM notes FOR NO KEY UPDATE"
+        ));
+        assert!(!super::is_cacheable("SELECT id FROM notes for share"));
+        assert!(!super::is_cacheable(
+            "SELECT id FROM notes LOCK IN SHARE MODE"
+        ));
+        assert!(!super::is_cacheable(
+            "WITH d AS (DELETE FROM notes RETURNING id) SELECT * FROM d"
+        ));
+        assert!(!super::is_cacheable("UPDATE notes SET body = ''"));
+    }
+
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn nested_transactions_use_configured_savepoint_prefix() -> Result<()> {
+        let configuration = Configuration::default().set_savepoint_prefix("creed_sp_");
+        let connection =
+            Connection::create_from_dsn(&get_database_dsn(), Some(configuration), None)?
+                .connect()
+                .await?;
+
+        connection.begin_transaction().await?;
+        connection.begin_transaction().await?;
+
+        // Fails if the nested transaction savepoint has not been created with the given name.
+        connection.rollback_savepoint("creed_sp_2").await?;
+
+        connection.commit().await?;
+        connection.commit().await
+    }
+
+    #[tokio::test]
+    async fn features_are_probed_once() -> Result<()> {
+        let driver = MockPreparingConnection::default();
+        let prepared = driver.prepared.clone();
+        let connection = Connection::create_with_connection(Box::new(driver), None, None).await?;
+
+        assert!(!connection.feature("extension:unknown").await?);
+        assert!(!connection.feature("extension:unknown").await?);
+        assert_eq!(prepared.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn can_probe_server_features() -> Result<()> {
+        let connection = Connection::create_from_dsn(&get_database_dsn(), None, None)?
+            .connect()
+            .await?;
+
+        let feature = match connection.get_platform()?.get_name().as_str() {
+            "postgresql" => "extension:plpgsql",
+            "mysql" => "plugin:InnoDB",
+            _ => "function:abs",
+        };
+
+        assert!(connection.feature(feature).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn update_binds_set_values_before_criteria() -> Result<()> {
+        let driver = MockPreparingConnection::default();
+        let executed = driver.executed.clone();
+        let connection = Connection::create_with_connection(Box::new(driver), None, None).await?;
+
+        connection
+            .update(
+                "foo",
+                value_map! { "name" => "bar", "age" => 42, "email" => "bar@example.com" },
+                value_map! { "id" => 7, "deleted" => false },
+            )
+            .await?;
+        connection
+            .delete("foo", value_map! { "id" => 7, "deleted" => false })
+            .await?;
+
+        let executed = executed.lock().unwrap();
+        assert_eq!(
+            executed[0],
+            (
+                r#"UPDATE foo SET "age" = ?, "email" = ?, "name" = ? WHERE "deleted" = ? AND "id" = ?"#
+                    .to_string(),
+                vec![
+                    Value::Int(42),
+                    Value::from("bar@example.com"),
+                    Value::from("bar"),
+                    Value::Boolean(false),
+                    Value::Int(7),
+                ]
+            )
+        );
+        assert_eq!(
+            executed[1],
+            (
+                r#"DELETE FROM foo WHERE "deleted" = ? AND "id" = ?"#.to_string(),
+                vec![Value::Boolean(false), Value::Int(7)]
+            )
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn query_tag_is_prepended_to_statements() -> Result<()> {
+        let driver = MockPreparingConnection::default();
+        let executed = driver.executed.clone();
+        let mut connection =
+            Connection::create_with_connection(Box::new(driver), None, None).await?;
+
+        connection.connection_options = ConnectionOptions::default().with_query_tag("app:orders");
+        connection
+            .execute_statement("DELETE FROM foo", params!())
+            .await?;
+
+        connection.connection_options =
+            ConnectionOptions::default().with_query_tag("evil */ DROP TABLE foo; /*");
+        connection
+            .execute_statement("DELETE FROM foo", params!())
+            .await?;
+
+        let executed = executed.lock().unwrap();
+        assert_eq!(executed[0].0, "/* app:orders */ DELETE FROM foo");
+        assert_eq!(
+            executed[1].0,
+            "/* evil * / DROP TABLE foo; / * */ DELETE FROM foo"
+        );
+
+        Ok(())
+    }
+
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn tagged_statements_can_be_executed() -> Result<()> {
+        let options =
+            ConnectionOptions::try_from(get_database_dsn().as_str())?.with_query_tag("app:orders");
+        let connection = Connection::create(options, None, None).connect().await?;
+
+        let rows = connection
+            .query("SELECT ? + 1 AS val", params![0 => 1_i64])
+            .await?
+            .fetch_all()
+            .await?;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(i64::try_from(rows[0].get("val")?)?, 2);
+
+        // SQLite does not expose the text of the running statements.
+        let current_statement_sql = match connection.get_platform()?.get_name().as_str() {
+            "postgresql" => {
+                "SELECT query FROM pg_stat_activity WHERE pid = pg_backend_pid() AND ? = 1"
+            }
+            "mysql" => {
+                "SELECT info AS query FROM information_schema.processlist WHERE id = CONNECTION_ID() AND ? = 1"
+            }
+            _ => return Ok(()),
+        };
+
+        let row = connection
+            .query(current_statement_sql, params![0 => 1_i64])
+            .await?
+            .fetch_one()
+            .await?
+            .unwrap();
+        assert!(String::try_from(row.get("query")?)?.starts_with("/* app:orders */ SELECT"));
+
+        Ok(())
+    }
+
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn prepare_reusable_can_be_queried_many_times() -> Result<()> {
+        let connection = Connection::create_from_dsn(&get_database_dsn(), None, None)?
+            .connect()
+            .await?;
+
+        let statement = connection.prepare_reusable("SELECT ? + 1 AS val")?;
+        for i in 0_i64..3 {
+            let rows = statement.query(params![0 => i]).await?.fetch_all().await?;
+            assert_eq!(rows.len(), 1);
+            assert_eq!(rows[0].get("val")?, &Value::Int(i + 1));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn rows_per_statement_respects_bind_limits() -> Result<()> {
+        for max_parameters in [65535, 32766, 999] {
+            for column_count in [1, 3, 7, 999] {
+                let rows = super::get_rows_per_statement(column_count, max_parameters)?;
+                assert!(rows * column_count <= max_parameters);
+                assert!((rows + 1) * column_count > max_parameters);
+            }
+        }
+
+        let err = super::get_rows_per_statement(1000, 999).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TooManyParameters);
+
+        Ok(())
+    }
+
     #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
     #[tokio::test]
     #[serial]
@@ -705,4 +2233,83 @@ mod tests {
         }
         .unwrap();
     }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    async fn queries_are_traced_in_spans() -> Result<()> {
+        use std::collections::HashMap;
+        use std::sync::Arc;
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        type Spans = Arc<Mutex<Vec<(&'static str, HashMap<String, String>)>>>;
+
+        struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+        impl Visit for FieldVisitor<'_> {
+            fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+                self.0
+                    .insert(field.name().to_string(), format!("{:?}", value));
+            }
+
+            fn record_str(&mut self, field: &Field, value: &str) {
+                self.0.insert(field.name().to_string(), value.to_string());
+            }
+        }
+
+        struct CapturingSubscriber(Spans);
+        impl Subscriber for CapturingSubscriber {
+            fn enabled(&self, _: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut fields = HashMap::new();
+                span.record(&mut FieldVisitor(&mut fields));
+
+                let mut spans = self.0.lock().unwrap();
+                spans.push((span.metadata().name(), fields));
+                Id::from_u64(spans.len() as u64)
+            }
+
+            fn record(&self, span: &Id, values: &Record<'_>) {
+                let mut spans = self.0.lock().unwrap();
+                let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+                values.record(&mut FieldVisitor(fields));
+            }
+
+            fn record_follows_from(&self, _: &Id, _: &Id) {}
+            fn event(&self, _: &Event<'_>) {}
+            fn enter(&self, _: &Id) {}
+            fn exit(&self, _: &Id) {}
+        }
+
+        let spans = Spans::default();
+        let _guard = tracing::subscriber::set_default(CapturingSubscriber(spans.clone()));
+
+        let configuration = Configuration::default().set_redact_traced_sql(true);
+        let connection = Connection::create_with_connection(
+            Box::new(MockPreparingConnection::default()),
+            Some(configuration),
+            None,
+        )
+        .await?;
+
+        connection
+            .execute_statement("UPDATE foo SET bar = 'it''s secret'", params![])
+            .await?;
+
+        let spans = spans.lock().unwrap();
+        assert_eq!(spans.len(), 1);
+
+        let (name, fields) = &spans[0];
+        assert_eq!(*name, "creed.query");
+        assert_eq!(fields["db.system"], "mock");
+        assert_eq!(fields["db.operation"], "execute");
+        assert_eq!(fields["db.statement"], "UPDATE foo SET bar = ?");
+        assert!(fields.contains_key("elapsed_ms"));
+        assert!(!fields.contains_key("error"));
+
+        Ok(())
+    }
 }
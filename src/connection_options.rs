@@ -52,14 +52,16 @@ pub struct ConnectionOptions {
     pub database_name: Option<String>,
     pub database_name_suffix: Option<String>,
     pub platform: Option<PlatformBox>,
+    pub platform_name: Option<String>,
     pub ssl_mode: SslMode,
     pub ssl_cert: Option<String>,
     pub ssl_key: Option<String>,
     pub ssl_rootcert: Option<String>,
     pub ssl_crl: Option<String>,
-    pub application_name: Option<String>, // PostgreSQL
-
-                                          // TODO: replica/primary
+    pub application_name: Option<String>,         // PostgreSQL
+    pub prepared_statements_limit: Option<usize>, // PostgreSQL
+    pub query_tag: Option<String>,
+    // TODO: replica/primary
 }
 
 impl ConnectionOptions {
@@ -111,6 +113,15 @@ impl ConnectionOptions {
         self
     }
 
+    /// Selects the platform by name (ie: `mariadb`), overriding the server version detection.
+    /// Generic names (ie: `mysql`, `postgresql`) only select the platform family,
+    /// whose version is still detected from the server.
+    /// Ignored if a platform object is given through `with_platform`.
+    pub fn with_platform_name(mut self, platform_name: Option<String>) -> Self {
+        self.platform_name = platform_name;
+        self
+    }
+
     pub fn with_ssl_mode(mut self, ssl_mode: SslMode) -> Self {
         self.ssl_mode = ssl_mode;
         self
@@ -135,6 +146,19 @@ impl ConnectionOptions {
         self.application_name = application_name;
         self
     }
+
+    /// Sets the maximum number of server-side prepared statements kept for reuse.
+    /// The least recently used statements are deallocated when exceeding the limit.
+    pub fn with_prepared_statements_limit(mut self, limit: Option<usize>) -> Self {
+        self.prepared_statements_limit = limit;
+        self
+    }
+
+    /// Sets a tag prepended as a comment (ie: `/* app:orders */`) to every executed statement.
+    pub fn with_query_tag(mut self, query_tag: &str) -> Self {
+        self.query_tag = Some(query_tag.to_string());
+        self
+    }
 }
 
 impl TryFrom<&str> for ConnectionOptions {
@@ -197,9 +221,8 @@ impl TryFrom<&str> for ConnectionOptions {
                 .with_ssl_key(query_params.get("key").map(|s| s.to_string()))
                 .with_ssl_ca(query_params.get("ca").map(|s| s.to_string()))
                 .with_database_name(Some(db_name.to_string()))
-                .with_database_name_suffix(
-                    query_params.get("dbname_suffix").map(|s| s.to_string()),
-                )),
+                .with_database_name_suffix(query_params.get("dbname_suffix").map(|s| s.to_string()))
+                .with_platform_name(query_params.get("platform").map(|s| s.to_string()))),
             #[cfg(not(feature = "postgres"))]
             platform @ "pg"
             | platform @ "pgsql"
@@ -236,7 +259,13 @@ impl TryFrom<&str> for ConnectionOptions {
                     )
                     .with_application_name(
                         query_params.get("application_name").map(|s| s.to_string()),
-                    ))
+                    )
+                    .with_prepared_statements_limit(
+                        query_params
+                            .get("prepared_statements_limit")
+                            .and_then(|s| s.parse().ok()),
+                    )
+                    .with_platform_name(query_params.get("platform").map(|s| s.to_string())))
             }
             #[cfg(not(feature = "sqlite"))]
             platform @ "sqlite" => Err(Error::platform_not_compiled(platform)),
@@ -260,8 +289,11 @@ impl Debug for ConnectionOptions {
             .field("file_path", &self.file_path)
             .field("database_name", &self.database_name)
             .field("database_name_suffix", &self.database_name_suffix)
+            .field("platform_name", &self.platform_name)
             .field("ssl_mode", &self.ssl_mode)
             .field("application_name", &self.application_name)
+            .field("prepared_statements_limit", &self.prepared_statements_limit)
+            .field("query_tag", &self.query_tag)
             .finish()
     }
 }
@@ -285,6 +317,11 @@ mod tests {
             assert_eq!(opts.database_name.as_deref(), Some("my_db"));
             assert_eq!(opts.ssl_rootcert.as_deref(), Some("root.crt"));
             assert_eq!(opts.ssl_mode, super::SslMode::Require);
+            assert_eq!(opts.platform_name, None);
+
+            let opts =
+                ConnectionOptions::try_from("mysql://root@localhost/my_db?platform=mariadb")?;
+            assert_eq!(opts.platform_name.as_deref(), Some("mariadb"));
         }
 
         #[cfg(not(feature = "mysql"))]
@@ -315,6 +352,11 @@ mod tests {
             assert_eq!(opts.database_name.as_deref(), Some("my_db"));
             assert_eq!(opts.ssl_rootcert.as_deref(), Some("root.crt"));
             assert_eq!(opts.ssl_mode, super::SslMode::Require);
+            assert_eq!(opts.platform_name, None);
+
+            let opts =
+                ConnectionOptions::try_from("postgres://user@localhost/my_db?platform=postgresql")?;
+            assert_eq!(opts.platform_name.as_deref(), Some("postgresql"));
         }
 
         #[cfg(not(feature = "postgres"))]
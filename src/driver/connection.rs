@@ -2,9 +2,10 @@ use super::statement::Statement;
 use crate::driver::statement_result::StatementResult;
 use crate::parameter::NO_PARAMS;
 use crate::platform::DatabasePlatform;
-use crate::{Async, AsyncResult, EventDispatcher, Parameters, Result};
+use crate::{Async, AsyncResult, Error, EventDispatcher, Parameters, Result};
 use std::fmt::Debug;
 use std::future::Future;
+use std::path::Path;
 use std::sync::Arc;
 
 pub(in crate::driver) trait DriverConnection<T>: Sized {
@@ -60,4 +61,18 @@ pub trait Connection<'conn>: Debug + Send + Sync + 'conn {
             Ok(())
         })
     }
+
+    /// Writes the blob stored in `column` of the row identified by `rowid` into the file at `path`,
+    /// returning the number of bytes written.
+    #[allow(unused_variables)]
+    fn blob_to_file(&self, table: &str, column: &str, rowid: i64, path: &Path) -> Result<u64> {
+        Err(Error::platform_feature_unsupported("incremental blob I/O"))
+    }
+
+    /// Reads the file at `path` into the blob stored in `column` of the row identified by `rowid`,
+    /// returning the number of bytes read.
+    #[allow(unused_variables)]
+    fn blob_from_file(&self, table: &str, column: &str, rowid: i64, path: &Path) -> Result<u64> {
+        Err(Error::platform_feature_unsupported("incremental blob I/O"))
+    }
 }
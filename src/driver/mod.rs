@@ -4,6 +4,7 @@ use crate::platform::DatabasePlatform;
 use crate::{AsyncResult, ConnectionOptions, Error, EventDispatcher, Parameters, Result};
 use connection::{Connection, DriverConnection};
 use std::fmt::Debug;
+use std::path::Path;
 use std::sync::Arc;
 
 pub mod connection;
@@ -52,6 +53,41 @@ impl Driver {
         })
     }
 
+    /// Creates the platform with the given name (ie: `mariadb`) for the given scheme.
+    /// Versioned names (ie: `mysql8.0`) force the platform, while generic names
+    /// (ie: `mysql`, `postgresql`) only select the platform family: the version
+    /// is still detected from the server.
+    pub async fn create_platform_by_name(
+        &self,
+        scheme: &str,
+        name: &str,
+        ev: Arc<EventDispatcher>,
+    ) -> Result<Box<dyn DatabasePlatform + Send + Sync>> {
+        match (scheme, name.to_lowercase().as_str()) {
+            #[cfg(feature = "mysql")]
+            ("mysql", "mysql") => {
+                let version = self.inner_driver.server_version().await;
+                let variant = mysql::MySQLVariant::mysql_for_server_version(version.as_deref());
+                Ok(Box::new(mysql::MySQLPlatform::new(variant, ev)))
+            }
+            #[cfg(feature = "mysql")]
+            ("mysql", name) => Ok(Box::new(mysql::MySQLPlatform::new(
+                mysql::MySQLVariant::try_from(name)?,
+                ev,
+            ))),
+            #[cfg(feature = "postgres")]
+            ("psql" | "postgres" | "postgresql", "postgres" | "postgresql") => {
+                Ok(self.create_platform(ev).await)
+            }
+            #[cfg(feature = "sqlite")]
+            ("sqlite", "sqlite") => Ok(self.create_platform(ev).await),
+            _ => Err(Error::config(&format!(
+                "Unknown platform \"{}\" for {} connections",
+                name, scheme
+            ))),
+        }
+    }
+
     pub async fn create_platform(
         &self,
         ev: Arc<EventDispatcher>,
@@ -105,6 +141,22 @@ impl Driver {
     pub fn roll_back(&self) -> AsyncResult<()> {
         self.inner_driver.roll_back()
     }
+
+    /// Streams a blob into a file, if supported by the driver.
+    pub fn blob_to_file(&self, table: &str, column: &str, rowid: i64, path: &Path) -> Result<u64> {
+        self.inner_driver.blob_to_file(table, column, rowid, path)
+    }
+
+    /// Streams a file into a blob, if supported by the driver.
+    pub fn blob_from_file(
+        &self,
+        table: &str,
+        column: &str,
+        rowid: i64,
+        path: &Path,
+    ) -> Result<u64> {
+        self.inner_driver.blob_from_file(table, column, rowid, path)
+    }
 }
 
 #[cfg(test)]
@@ -13,10 +13,9 @@ use std::future::Future;
 use std::ops::{Deref, DerefMut};
 use std::sync::Arc;
 use url::Url;
-use version_compare::{compare_to, Cmp};
 
 pub struct Driver {
-    pub(super) connection: Mutex<Conn>,
+    pub(super) connection: Arc<Mutex<Conn>>,
 }
 
 impl Debug for Driver {
@@ -136,7 +135,7 @@ impl DriverConnection<ConnectionOptions> for Driver {
         let opts = Opts::from(opts_builder);
         async move {
             Ok(Self {
-                connection: Mutex::new(Conn::new(opts).await?),
+                connection: Arc::new(Mutex::new(Conn::new(opts).await?)),
             })
         }
     }
@@ -148,21 +147,8 @@ impl<'conn> Connection<'conn> for Driver {
         ev: Arc<EventDispatcher>,
     ) -> Async<Box<dyn DatabasePlatform + Send + Sync>> {
         Box::pin(async move {
-            let version = self
-                .server_version()
-                .await
-                .unwrap_or_else(|| "5.7.9".to_string());
-            let variant = if compare_to(&version, "10.5.2", Cmp::Ge).unwrap_or(false) {
-                platform::MySQLVariant::MariaDB
-            } else if compare_to(&version, "10", Cmp::Ge).unwrap_or(false) {
-                platform::MySQLVariant::MySQL5_6 // MariaDB 10
-            } else if compare_to(&version, "8", Cmp::Ge).unwrap_or(false) {
-                platform::MySQLVariant::MySQL8_0
-            } else if compare_to(&version, "5.7", Cmp::Ge).unwrap_or(false) {
-                platform::MySQLVariant::MySQL5_7
-            } else {
-                platform::MySQLVariant::MySQL5_6
-            };
+            let version = self.server_version().await;
+            let variant = platform::MySQLVariant::for_server_version(version.as_deref());
 
             Box::new(platform::MySQLPlatform::new(variant, ev))
                 as Box<dyn DatabasePlatform + Send + Sync>
@@ -1,14 +1,71 @@
 mod mysql_platform;
 mod schema_manager;
 
+use version_compare::{compare_to, Cmp};
+
 #[derive(Copy, Clone)]
 pub enum MySQLVariant {
     MySQL5_6,
     MySQL5_7,
     MySQL8_0,
+    MySQL8_4,
     MariaDB,
 }
 
+impl TryFrom<&str> for MySQLVariant {
+    type Error = crate::Error;
+
+    /// Gets the variant from its platform name, as accepted by the `platform` DSN option.
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        match name.to_lowercase().as_str() {
+            "mysql5.6" => Ok(Self::MySQL5_6),
+            // The variant used when the server version cannot be detected.
+            "mysql" | "mysql5.7" => Ok(Self::MySQL5_7),
+            "mysql8.0" | "mysql8" => Ok(Self::MySQL8_0),
+            "mysql8.4" => Ok(Self::MySQL8_4),
+            "mariadb" => Ok(Self::MariaDB),
+            _ => Err(crate::Error::config(&format!(
+                "Unknown platform \"{}\" for mysql connections",
+                name
+            ))),
+        }
+    }
+}
+
+impl MySQLVariant {
+    /// Gets the variant matching the given server version.
+    /// MySQL 5.7 is assumed if the version is unknown.
+    pub fn for_server_version(version: Option<&str>) -> Self {
+        let Some(version) = version else {
+            return Self::MySQL5_7;
+        };
+
+        if compare_to(version, "10.5.2", Cmp::Ge).unwrap_or(false) {
+            Self::MariaDB
+        } else if compare_to(version, "10", Cmp::Ge).unwrap_or(false) {
+            Self::MySQL5_6 // MariaDB 10
+        } else if compare_to(version, "8.4", Cmp::Ge).unwrap_or(false) {
+            Self::MySQL8_4
+        } else if compare_to(version, "8", Cmp::Ge).unwrap_or(false) {
+            Self::MySQL8_0
+        } else if compare_to(version, "5.7", Cmp::Ge).unwrap_or(false) {
+            Self::MySQL5_7
+        } else {
+            Self::MySQL5_6
+        }
+    }
+
+    /// Gets the MySQL variant matching the given server version, as selected
+    /// by the generic `mysql` platform name: MariaDB servers are handled as MySQL 5.7.
+    pub fn mysql_for_server_version(version: Option<&str>) -> Self {
+        if version.is_some_and(|v| compare_to(v, "10", Cmp::Ge).unwrap_or(false)) {
+            Self::MySQL5_7
+        } else {
+            Self::for_server_version(version)
+        }
+    }
+}
+
 pub mod mariadb;
 pub mod mysql;
 
@@ -5,15 +5,19 @@ use crate::driver::mysql::platform::mysql_platform::{
 };
 use crate::driver::mysql::platform::AbstractMySQLSchemaManager;
 use crate::driver::mysql::MySQLSchemaManager;
-use crate::platform::{default, DatabasePlatform, DateIntervalUnit};
-use crate::r#type::{IntoType, BLOB, STRING, TEXT};
+use crate::platform::{default, CreateFlags, DatabasePlatform, DateIntervalUnit};
+use crate::query::{Expression, MergeBuilder, QueryBuilder};
+use crate::r#type::{IntoType, BLOB, GUID, STRING, TEXT};
 use crate::schema::{
     extract_type_from_comment, remove_type_from_comment, Asset, Column, ColumnData,
-    FKConstraintList, ForeignKeyConstraint, Identifier, Index, TableDiff, TableOptions,
+    FKConstraintList, ForeignKeyConstraint, Identifier, Index, IntoIdentifier, Table, TableDiff,
+    TableGrant, TableOptions,
 };
 use crate::schema::{string_from_value, SchemaManager};
 use crate::util::strtr;
-use crate::{Error, Result, Row, SchemaDropTableEvent, TransactionIsolationLevel, Value};
+use crate::{
+    params, Error, Parameters, Result, Row, SchemaDropTableEvent, TransactionIsolationLevel, Value,
+};
 use core::option::Option::Some;
 use itertools::Itertools;
 use regex::Regex;
@@ -33,7 +37,15 @@ pub fn build_table_options(
 
     let mut opts = vec![];
 
-    let charset = options.charset.unwrap_or_else(|| "utf8".to_string());
+    // When only the collation is given, the charset is the collation prefix (ie: utf8mb4_bin).
+    let charset = options.charset.unwrap_or_else(|| {
+        options
+            .collation
+            .as_deref()
+            .and_then(|c| c.split('_').next())
+            .unwrap_or("utf8")
+            .to_string()
+    });
     opts.push(format!("DEFAULT CHARACTER SET {}", charset));
 
     let collation = options
@@ -132,6 +144,66 @@ pub fn get_regexp_expression() -> Result<String> {
     Ok("RLIKE".to_string())
 }
 
+/// Builds the inline table through the VALUES statement, available since MySQL 8.0.19.
+pub fn get_values_table_sql(
+    rows: &[Vec<Value>],
+    alias: &str,
+    columns: &[&str],
+) -> Result<Expression> {
+    let values = rows
+        .iter()
+        .map(|row| format!("ROW({})", row.iter().map(|_| "?").join(", ")))
+        .join(", ");
+
+    Ok(Expression::with_parameters(
+        format!("(VALUES {}) {} ({})", values, alias, columns.join(", ")),
+        rows.iter().flatten().cloned().collect(),
+    ))
+}
+
+pub fn get_merge_sql(this: &dyn DatabasePlatform, merge: &MergeBuilder) -> Result<String> {
+    merge.to_on_duplicate_key_sql(this)
+}
+
+pub fn get_update_from_sql(query: &QueryBuilder) -> Result<Expression> {
+    query.to_update_join_sql()
+}
+
+/// `SHOW MASTER STATUS` has been replaced by `SHOW BINARY LOG STATUS` in MySQL 8.4.
+pub fn get_replication_position_sql(binary_log_status: bool) -> String {
+    if binary_log_status {
+        "SHOW BINARY LOG STATUS".to_string()
+    } else {
+        "SHOW MASTER STATUS".to_string()
+    }
+}
+
+/// MySQL locks are identified by name and shared across all the databases of the server.
+pub fn get_advisory_lock_sql(key: i64, wait: bool) -> String {
+    format!(
+        "SELECT GET_LOCK('creed_advisory_lock_{}', {})",
+        key,
+        if wait { -1 } else { 0 }
+    )
+}
+
+pub fn get_advisory_unlock_sql(key: i64) -> String {
+    format!("SELECT RELEASE_LOCK('creed_advisory_lock_{}')", key)
+}
+
+pub fn get_feature_probe_sql(this: &dyn DatabasePlatform, feature: &str) -> Result<String> {
+    match feature.split_once(':') {
+        Some(("plugin", name)) => Ok(format!(
+            "SELECT 1 FROM information_schema.PLUGINS WHERE PLUGIN_NAME = {} AND PLUGIN_STATUS = 'ACTIVE'",
+            this.quote_string_literal(name)
+        )),
+        _ => Err(Error::platform_feature_unsupported(format!(
+            "feature probe \"{}\"",
+            feature
+        ))),
+    }
+}
+
 pub fn get_concat_expression(strings: Vec<&str>) -> Result<String> {
     Ok(format!("CONCAT({})", strings.join(", ")))
 }
@@ -166,6 +238,14 @@ pub fn get_length_expression(column: &str) -> Result<String> {
     Ok(format!("CHAR_LENGTH({})", column))
 }
 
+pub fn get_json_contains_expression(column: &str, value: &str) -> Result<String> {
+    Ok(format!("JSON_CONTAINS({}, {})", column, value))
+}
+
+pub fn get_json_array_length_expression(column: &str) -> Result<String> {
+    Ok(format!("JSON_LENGTH({})", column))
+}
+
 pub fn get_list_databases_sql() -> Result<String> {
     Ok("SHOW DATABASES".to_string())
 }
@@ -181,6 +261,30 @@ pub fn get_list_views_sql(this: &dyn SchemaManager, database: &str) -> Result<St
     ))
 }
 
+pub fn get_table_kind_sql(this: &dyn SchemaManager, table: &str, database: &str) -> Result<String> {
+    Ok(format!(
+        "SELECT CASE TABLE_TYPE WHEN 'VIEW' THEN 'VIEW' ELSE 'BASE TABLE' END AS kind \
+        FROM information_schema.TABLES \
+        WHERE TABLE_SCHEMA = {} AND TABLE_NAME = {}",
+        this.quote_string_literal(database),
+        this.quote_string_literal(table)
+    ))
+}
+
+pub fn get_table_timestamps_sql(
+    this: &dyn SchemaManager,
+    table: &str,
+    database: &str,
+) -> Result<String> {
+    Ok(format!(
+        "SELECT CREATE_TIME AS created, UPDATE_TIME AS updated \
+        FROM information_schema.TABLES \
+        WHERE TABLE_SCHEMA = {} AND TABLE_NAME = {}",
+        this.quote_string_literal(database),
+        this.quote_string_literal(table)
+    ))
+}
+
 pub fn get_varchar_type_declaration_sql_snippet(
     length: Option<usize>,
     fixed: bool,
@@ -201,12 +305,46 @@ pub fn get_binary_type_declaration_sql_snippet(
     Ok(format!("{}({})", c_type, length))
 }
 
+/// Returns the SQL snippet declaring an ENUM or SET column with the given values.
+fn get_values_declaration_sql(
+    this: &dyn DatabasePlatform,
+    kind: &str,
+    values: &[String],
+) -> String {
+    format!(
+        "{}({})",
+        kind,
+        values
+            .iter()
+            .map(|v| this.quote_string_literal(v))
+            .join(", ")
+    )
+}
+
+pub fn get_string_type_declaration_sql(
+    this: &dyn DatabasePlatform,
+    column: &ColumnData,
+) -> Result<String> {
+    if column.values.is_empty() {
+        default::get_string_type_declaration_sql(this, column)
+    } else {
+        Ok(get_values_declaration_sql(this, "ENUM", &column.values))
+    }
+}
+
 /// Gets the SQL snippet used to declare a CLOB column type.
 ///     TINYTEXT   : 2 ^  8 - 1 = 255
 ///     TEXT       : 2 ^ 16 - 1 = 65535
 ///     MEDIUMTEXT : 2 ^ 24 - 1 = 16777215
 ///     LONGTEXT   : 2 ^ 32 - 1 = 4294967295
-pub fn get_clob_type_declaration_sql(column: &ColumnData) -> Result<String> {
+pub fn get_clob_type_declaration_sql(
+    this: &dyn DatabasePlatform,
+    column: &ColumnData,
+) -> Result<String> {
+    if !column.values.is_empty() {
+        return Ok(get_values_declaration_sql(this, "SET", &column.values));
+    }
+
     Ok(if let Some(len) = column.length {
         if len <= LENGTH_LIMIT_TINYTEXT {
             "TINYTEXT"
@@ -308,7 +446,69 @@ pub fn get_default_value_declaration_sql(
     default::get_default_value_declaration_sql(this.as_dyn(), &column)
 }
 
+/// MySQL requires an index on the foreign key columns and implicitly creates one if missing:
+/// if enabled in the configuration, it is declared explicitly to keep the schema in sync.
+pub fn get_create_table_sql(
+    this: &dyn SchemaManager,
+    table: &Table,
+    create_flags: Option<CreateFlags>,
+) -> Result<Vec<String>> {
+    let create_flags = create_flags.or(Some(
+        CreateFlags::CREATE_INDEXES | CreateFlags::CREATE_FOREIGN_KEYS,
+    ));
+    let configuration = this.get_connection().get_configuration();
+    if !configuration.get_auto_index_foreign_keys() || table.foreign_keys_without_index().is_empty()
+    {
+        return default::get_create_table_sql(this, table, create_flags);
+    }
+
+    let mut table = table.clone();
+    table.add_missing_foreign_key_indexes()?;
+
+    default::get_create_table_sql(this, &table, create_flags)
+}
+
+/// Adds to the diff the indexes needed by the added foreign keys, if enabled in the configuration.
+fn add_missing_foreign_key_indexes(this: &dyn SchemaManager, diff: &mut TableDiff) -> Result<()> {
+    let configuration = this.get_connection().get_configuration();
+    let Some(from_table) = diff
+        .from_table
+        .filter(|_| configuration.get_auto_index_foreign_keys())
+    else {
+        return Ok(());
+    };
+
+    let mut table = from_table.clone();
+    for index in diff.removed_indexes.iter().chain(&diff.changed_indexes) {
+        table.drop_index(index.get_name());
+    }
+
+    table.add_columns(diff.added_columns.iter().cloned());
+    table.add_indices(
+        diff.added_indexes
+            .iter()
+            .chain(&diff.changed_indexes)
+            .cloned(),
+    );
+    table.add_foreign_keys_raw(diff.added_foreign_keys.iter().cloned());
+
+    for index in table.missing_foreign_key_indexes()? {
+        let columns = index.get_columns();
+        if diff
+            .added_foreign_keys
+            .iter()
+            .any(|fk| fk.get_unquoted_local_columns() == columns)
+        {
+            diff.added_indexes.push(index);
+        }
+    }
+
+    Ok(())
+}
+
 pub fn get_alter_table_sql(this: &dyn SchemaManager, diff: &mut TableDiff) -> Result<Vec<String>> {
+    add_missing_foreign_key_indexes(this, diff)?;
+
     let mut column_sql = vec![];
     let mut query_parts = vec![];
     let new_name = diff.get_new_name();
@@ -439,6 +639,34 @@ pub fn get_alter_table_sql(this: &dyn SchemaManager, diff: &mut TableDiff) -> Re
         }
     }
 
+    // Indexes changing their visibility only are altered in place.
+    if let Some(from_table) = diff.from_table {
+        diff.changed_indexes.retain(|index| {
+            let Some(from_index) = from_table.get_index(index.get_name()) else {
+                return true;
+            };
+
+            if from_index.is_visible() == index.is_visible()
+                || !from_index.is_fulfilled_by(index)
+                || !index.is_fulfilled_by(from_index)
+            {
+                return true;
+            }
+
+            query_parts.push(format!(
+                "ALTER INDEX {} {}",
+                index.get_quoted_name(&platform),
+                if index.is_visible() {
+                    "VISIBLE"
+                } else {
+                    "INVISIBLE"
+                }
+            ));
+
+            false
+        });
+    }
+
     let mut sql = vec![];
     let (res, mut table_sql) = this.on_schema_alter_table(diff, vec![])?;
     if !res {
@@ -462,6 +690,90 @@ pub fn get_alter_table_sql(this: &dyn SchemaManager, diff: &mut TableDiff) -> Re
     Ok(sql)
 }
 
+/// Gets the charset and collation of a textual column, inheriting the table defaults.
+fn get_column_charset_and_collation(
+    table: &Table,
+    column: &Column,
+) -> (Option<String>, Option<String>) {
+    if ![STRING, TEXT, GUID].contains(&column.get_type().get_name()) {
+        return (column.get_charset().clone(), column.get_collation().clone());
+    }
+
+    (
+        column.get_charset().clone().or_else(|| table.get_charset()),
+        column
+            .get_collation()
+            .clone()
+            .or_else(|| table.get_collation()),
+    )
+}
+
+/// Checks the local and the referenced columns of a foreign key have the same charset and collation,
+/// as MySQL refuses to create the constraint otherwise.
+pub fn validate_foreign_key(
+    local_table: &Table,
+    foreign_key: &ForeignKeyConstraint,
+    foreign_table: &Table,
+) -> Result<()> {
+    let local_columns = foreign_key.get_unquoted_local_columns();
+    let foreign_columns = foreign_key.get_unquoted_foreign_columns();
+
+    for (local_name, foreign_name) in local_columns.iter().zip(&foreign_columns) {
+        let (Some(local_column), Some(foreign_column)) = (
+            local_table.get_column(local_name.as_str()),
+            foreign_table.get_column(foreign_name.as_str()),
+        ) else {
+            continue;
+        };
+
+        let (local_charset, local_collation) =
+            get_column_charset_and_collation(local_table, local_column);
+        let (foreign_charset, foreign_collation) =
+            get_column_charset_and_collation(foreign_table, foreign_column);
+
+        for (local, foreign) in [
+            (local_collation, foreign_collation),
+            (local_charset, foreign_charset),
+        ] {
+            let (Some(local), Some(foreign)) = (local, foreign) else {
+                continue;
+            };
+
+            if !local.eq_ignore_ascii_case(&foreign) {
+                return Err(Error::foreign_key_collation_mismatch(
+                    &foreign_key.get_name(),
+                    &format!("{}.{}", local_table.get_name(), local_name),
+                    &local,
+                    &format!("{}.{}", foreign_table.get_name(), foreign_name),
+                    &foreign,
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn create_foreign_key(
+    this: &dyn SchemaManager,
+    foreign_key: ForeignKeyConstraint,
+    table_name: &str,
+) -> Result<()> {
+    let table = this.list_table_details(table_name).await?;
+    let foreign_table_name = foreign_key.get_foreign_table().get_name();
+    if foreign_table_name.eq_ignore_ascii_case(table_name) {
+        this.validate_foreign_key(&table, &foreign_key, &table)?;
+    } else {
+        let foreign_table = this.list_table_details(&foreign_table_name).await?;
+        this.validate_foreign_key(&table, &foreign_key, &foreign_table)?;
+    }
+
+    let mut table_diff = TableDiff::new(table_name, &table);
+    table_diff.added_foreign_keys.push(foreign_key);
+
+    this.alter_table(table_diff).await
+}
+
 fn get_pre_alter_table_alter_primary_key_sql(
     this: &dyn SchemaManager,
     diff: &TableDiff,
@@ -530,11 +842,12 @@ pub fn get_pre_alter_table_index_foreign_key_sql(
             };
 
             sql.push(format!(
-                "ALTER TABLE {} DROP INDEX {}, ADD {} ({})",
+                "ALTER TABLE {} DROP INDEX {}, ADD {} ({}){}",
                 table,
                 removed_index.get_name(),
                 index_clause,
-                this.get_index_field_declaration_list_sql(added_index)?
+                this.get_index_field_declaration_list_sql(added_index)?,
+                default::get_index_visibility_sql(platform.as_dyn(), added_index)
             ));
 
             indexes_to_be_removed.push(removed_index.get_name().into_owned());
@@ -654,6 +967,33 @@ pub fn get_column_collation_declaration_sql(
     ))
 }
 
+/// Grantees are account names (`user@host`, the host defaulting to `%`):
+/// both parts are quoted separately, as hosts can contain dots.
+pub fn get_grant_sql(
+    platform: &dyn DatabasePlatform,
+    table_name: &Identifier,
+    grant: &TableGrant,
+) -> Result<String> {
+    let (user, host) = grant
+        .get_grantee()
+        .rsplit_once('@')
+        .unwrap_or((grant.get_grantee(), "%"));
+    let unquote = |part: &str| part.trim_matches(['\'', '"', '`']).to_string();
+    let grantee = format!(
+        "{}@{}",
+        platform.quote_single_identifier(&unquote(user)),
+        platform.quote_single_identifier(&unquote(host))
+    );
+
+    default::get_grant_to_sql(platform, table_name, grant, &grantee)
+}
+
+/// Compressed columns (MariaDB only) support the default compression method:
+/// the given method name is ignored.
+pub fn get_column_compression_declaration_sql() -> Result<String> {
+    Ok("COMPRESSED".to_string())
+}
+
 pub fn get_advanced_foreign_key_options_sql(
     this: &dyn SchemaManager,
     foreign_key: &ForeignKeyConstraint,
@@ -815,6 +1155,47 @@ fn get_column_default(
     }
 }
 
+/// Extracts the values of an ENUM or SET column from its type (ie: `enum('a','b')`),
+/// preserving the declaration order.
+fn parse_enum_values(column_type: &str) -> Vec<String> {
+    let Some(list) = column_type
+        .split_once('(')
+        .and_then(|(_, list)| list.rsplit_once(')'))
+        .map(|(list, _)| list)
+    else {
+        return vec![];
+    };
+
+    let mut values = vec![];
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = list.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' if in_quotes && chars.peek() == Some(&'\'') => {
+                chars.next();
+                current.push('\'');
+            }
+            '\'' => {
+                if in_quotes {
+                    values.push(std::mem::take(&mut current));
+                }
+
+                in_quotes = !in_quotes;
+            }
+            '\\' if in_quotes => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            c if in_quotes => current.push(c),
+            _ => {}
+        }
+    }
+
+    values
+}
+
 pub fn get_portable_table_column_definition(
     this: &dyn SchemaManager,
     table_column: &Row,
@@ -842,6 +1223,7 @@ pub fn get_portable_table_column_definition(
     let mut fixed = false;
     let mut scale = None;
     let mut precision = None;
+    let mut values = vec![];
 
     let mut ty = platform.get_type_mapping(&db_type)?.into_type()?;
 
@@ -905,6 +1287,11 @@ pub fn get_portable_table_column_definition(
             length = None;
         }
 
+        "enum" | "set" => {
+            values = parse_enum_values(&col_type);
+            length = None;
+        }
+
         _ => { /* Do nothing */ }
     }
 
@@ -935,6 +1322,7 @@ pub fn get_portable_table_column_definition(
         column.set_precision(precision);
     }
 
+    column.set_values(values);
     column.set_autoincrement(
         table_column
             .get("extra")?
@@ -1035,12 +1423,186 @@ SELECT
     INDEX_TYPE  AS index_type,
     NULL        AS flags,
     NULL        AS `where`,
-    IF(INDEX_NAME = 'PRIMARY', 1, 0) AS `primary`
+    IF(INDEX_NAME = 'PRIMARY', 1, 0) AS `primary`,
+    {}          AS visible
 FROM information_schema.STATISTICS
 WHERE
     TABLE_SCHEMA = {} AND
     TABLE_NAME = {}
 ORDER BY SEQ_IN_INDEX"#,
+        if this.get_platform()?.supports_invisible_indexes() {
+            "IF(IS_VISIBLE = 'NO', 0, 1)"
+        } else {
+            "1"
+        },
+        this.quote_string_literal(database),
+        this.quote_string_literal(table)
+    ))
+}
+
+/// MySQL cannot truncate more than one table per statement, nor cascade the truncate:
+/// the referencing tables are collected by [`truncate_tables`], which cannot be expressed in SQL only.
+pub fn get_truncate_tables_sql(
+    this: &dyn SchemaManager,
+    tables: &[&str],
+    cascade: bool,
+) -> Result<Vec<String>> {
+    if cascade {
+        return Err(Error::platform_feature_unsupported(
+            "MySQL cannot cascade the truncate of tables in SQL: use truncate_tables instead.",
+        ));
+    }
+
+    tables
+        .iter()
+        .map(|t| this.get_truncate_table_sql(t, false))
+        .collect()
+}
+
+/// Truncates the given tables.
+///
+/// InnoDB refuses to truncate a table referenced by a foreign key, even if the
+/// referencing table is empty: if all the referencing tables are truncated as well
+/// (following the references if cascading), foreign key checks are disabled
+/// while truncating and restored afterwards, even on error.
+/// Otherwise, the truncate is executed as is and rejected by the server.
+pub async fn truncate_tables(
+    this: &dyn SchemaManager,
+    tables: Vec<String>,
+    cascade: bool,
+) -> Result<()> {
+    let connection = this.get_connection();
+    let mut tables = tables
+        .into_iter()
+        .unique_by(|t| t.to_lowercase())
+        .collect::<Vec<_>>();
+    if tables.is_empty() {
+        return Ok(());
+    }
+
+    let mut closed = false;
+    while !closed {
+        let referencing = list_referencing_tables(this, &tables).await?;
+        let missing = referencing
+            .into_iter()
+            .filter(|r| !tables.iter().any(|t| t.eq_ignore_ascii_case(r)))
+            .collect::<Vec<_>>();
+
+        closed = missing.is_empty();
+        if !closed && !cascade {
+            break;
+        }
+
+        tables.extend(missing);
+    }
+
+    let table_names = tables.iter().map(String::as_str).collect::<Vec<_>>();
+    let sql = get_truncate_tables_sql(this, &table_names, false)?;
+    if !closed {
+        for statement in sql {
+            connection.execute_statement(statement, params!()).await?;
+        }
+
+        return Ok(());
+    }
+
+    let previous = connection
+        .query("SELECT @@SESSION.foreign_key_checks", params!())
+        .await?
+        .fetch_one()
+        .await?
+        .map_or(Ok(true), |row| row.get(0).map(bool::from))?;
+
+    connection
+        .execute_statement("SET FOREIGN_KEY_CHECKS = 0", params!())
+        .await?;
+
+    let mut result = Ok(());
+    for statement in sql {
+        result = connection
+            .execute_statement(statement, params!())
+            .await
+            .map(|_| ());
+        if result.is_err() {
+            break;
+        }
+    }
+
+    let restored = connection
+        .execute_statement(
+            format!("SET FOREIGN_KEY_CHECKS = {}", i32::from(previous)),
+            params!(),
+        )
+        .await;
+
+    result.and(restored.map(|_| ()))
+}
+
+/// Lists the tables of the current database with a foreign key referencing one of the given tables.
+async fn list_referencing_tables(
+    this: &dyn SchemaManager,
+    tables: &[String],
+) -> Result<Vec<String>> {
+    let connection = this.get_connection();
+    let sql = format!(
+        "SELECT DISTINCT TABLE_NAME AS table_name FROM information_schema.KEY_COLUMN_USAGE WHERE TABLE_SCHEMA = DATABASE() AND REFERENCED_TABLE_SCHEMA = DATABASE() AND REFERENCED_TABLE_NAME IN ({})",
+        tables.iter().map(|_| "?").join(", ")
+    );
+
+    let rows = connection
+        .query(sql, Parameters::from(tables.to_vec()))
+        .await?
+        .fetch_all()
+        .await?;
+
+    rows.iter()
+        .map(|row| string_from_value(connection, row.get("table_name")))
+        .collect()
+}
+
+pub fn get_analyze_table_sql(
+    this: &dyn SchemaManager,
+    table_name: &dyn IntoIdentifier,
+) -> Result<Vec<String>> {
+    let platform = this.get_platform()?;
+    Ok(vec![format!(
+        "ANALYZE TABLE {}",
+        table_name
+            .into_identifier()
+            .get_quoted_name(platform.as_dyn())
+    )])
+}
+
+/// OPTIMIZE TABLE rebuilds the table (on InnoDB) reclaiming the unused space.
+pub fn get_optimize_table_sql(
+    this: &dyn SchemaManager,
+    table_name: &dyn IntoIdentifier,
+) -> Result<Vec<String>> {
+    let platform = this.get_platform()?;
+    Ok(vec![format!(
+        "OPTIMIZE TABLE {}",
+        table_name
+            .into_identifier()
+            .get_quoted_name(platform.as_dyn())
+    )])
+}
+
+pub fn get_index_statistics_sql(
+    this: &MySQLSchemaManager,
+    table: &str,
+    database: &str,
+) -> Result<String> {
+    Ok(format!(
+        r#"
+SELECT
+    INDEX_NAME       AS index_name,
+    MAX(CARDINALITY) AS cardinality,
+    NULL             AS scans
+FROM information_schema.STATISTICS
+WHERE
+    TABLE_SCHEMA = {} AND
+    TABLE_NAME = {}
+GROUP BY INDEX_NAME"#,
         this.quote_string_literal(database),
         this.quote_string_literal(table)
     ))
@@ -1054,12 +1616,22 @@ pub fn columns_equal(this: &dyn SchemaManager, column1: &Column, column2: &Colum
     let mut column1 = column1.clone();
     let mut column2 = column2.clone();
 
-    column1
-        .set_charset::<String, _>(None)
-        .set_collation::<String, _>(None);
-    column2
-        .set_charset::<String, _>(None)
-        .set_collation::<String, _>(None);
+    // Charset and collation are only compared when set on both columns:
+    // a column without them inherits the table defaults.
+    if column1.get_charset().is_none() || column2.get_charset().is_none() {
+        column1.set_charset::<String, _>(None);
+        column2.set_charset::<String, _>(None);
+    }
+
+    if column1.get_collation().is_none() || column2.get_collation().is_none() {
+        column1.set_collation::<String, _>(None);
+        column2.set_collation::<String, _>(None);
+    }
+
+    // The compression attribute is not exposed by information_schema, so it
+    // cannot be introspected and is not taken into account.
+    column1.set_compression::<String, _>(None);
+    column2.set_compression::<String, _>(None);
 
     default::columns_equal(this.as_dyn(), &column1, &column2)
 }
@@ -1,17 +1,21 @@
 use super::mysql;
 use crate::driver::mysql::platform::{mariadb, MySQLVariant};
 use crate::driver::mysql::MySQLSchemaManager;
-use crate::platform::{platform_debug, DatabasePlatform, DateIntervalUnit, KeywordList};
+use crate::platform::{
+    default, platform_debug, DatabasePlatform, DateIntervalUnit, KeywordList, UpsertReporting,
+};
+use crate::query::{Expression, MergeBuilder, QueryBuilder};
 use crate::r#type::{
     BigintType, BinaryType, BlobType, BooleanType, DateTimeType, DateType, DecimalType, FloatType,
     IntegerType, JsonType, SimpleArrayType, StringType, TextType, TimeType,
 };
-use crate::schema::{ColumnData, SchemaManager};
+use crate::schema::{ColumnData, ForeignKeyReferentialAction, SchemaManager};
 use crate::{Connection, Error};
-use crate::{EventDispatcher, Result, TransactionIsolationLevel};
+use crate::{EventDispatcher, Result, TransactionIsolationLevel, Value};
 use dashmap::DashMap;
 use std::any::TypeId;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub const LENGTH_LIMIT_TINYTEXT: usize = 255;
 pub const LENGTH_LIMIT_TEXT: usize = 65535;
@@ -64,7 +68,7 @@ impl DatabasePlatform for MySQLPlatform {
     fn get_json_type_declaration_sql(&self, column: &ColumnData) -> Result<String> {
         match self.variant {
             MySQLVariant::MariaDB => mariadb::get_json_type_declaration_sql(),
-            MySQLVariant::MySQL5_6 => mysql::get_clob_type_declaration_sql(column),
+            MySQLVariant::MySQL5_6 => mysql::get_clob_type_declaration_sql(self, column),
             _ => mysql::get_json_type_declaration_sql(),
         }
     }
@@ -104,7 +108,11 @@ impl DatabasePlatform for MySQLPlatform {
     ///     LONGTEXT   : 2 ^ 32 - 1 = 4294967295
 
     fn get_clob_type_declaration_sql(&self, column: &ColumnData) -> Result<String> {
-        mysql::get_clob_type_declaration_sql(column)
+        mysql::get_clob_type_declaration_sql(self, column)
+    }
+
+    fn get_string_type_declaration_sql(&self, column: &ColumnData) -> Result<String> {
+        mysql::get_string_type_declaration_sql(self, column)
     }
 
     fn get_blob_type_declaration_sql(&self, column: &ColumnData) -> Result<String> {
@@ -123,6 +131,14 @@ impl DatabasePlatform for MySQLPlatform {
         mysql::get_length_expression(column)
     }
 
+    fn get_json_contains_expression(&self, column: &str, value: &str) -> Result<String> {
+        mysql::get_json_contains_expression(column, value)
+    }
+
+    fn get_json_array_length_expression(&self, column: &str) -> Result<String> {
+        mysql::get_json_array_length_expression(column)
+    }
+
     fn get_concat_expression(&self, strings: Vec<&str>) -> Result<String> {
         mysql::get_concat_expression(strings)
     }
@@ -145,6 +161,14 @@ impl DatabasePlatform for MySQLPlatform {
         mysql::get_current_database_expression()
     }
 
+    fn get_max_identifier_length(&self) -> usize {
+        64
+    }
+
+    fn get_max_columns_per_table(&self) -> usize {
+        4096
+    }
+
     fn get_read_lock_sql(&self) -> Result<String> {
         mysql::get_read_lock_sql()
     }
@@ -201,6 +225,7 @@ impl DatabasePlatform for MySQLPlatform {
         self._add_type_mapping("datetime", TypeId::of::<DateTimeType>());
         self._add_type_mapping("decimal", TypeId::of::<DecimalType>());
         self._add_type_mapping("double", TypeId::of::<FloatType>());
+        self._add_type_mapping("enum", TypeId::of::<StringType>());
         self._add_type_mapping("float", TypeId::of::<FloatType>());
         self._add_type_mapping("json", TypeId::of::<JsonType>());
         self._add_type_mapping("int", TypeId::of::<IntegerType>());
@@ -228,7 +253,7 @@ impl DatabasePlatform for MySQLPlatform {
 
     fn create_reserved_keywords_list(&self) -> KeywordList {
         match self.variant {
-            MySQLVariant::MySQL8_0 => KeywordList::mysql80_keywords(),
+            MySQLVariant::MySQL8_0 | MySQLVariant::MySQL8_4 => KeywordList::mysql80_keywords(),
             MySQLVariant::MariaDB => KeywordList::mariadb_keywords(),
             _ => KeywordList::mysql_keywords(),
         }
@@ -242,6 +267,20 @@ impl DatabasePlatform for MySQLPlatform {
         true
     }
 
+    fn supports_invisible_indexes(&self) -> bool {
+        matches!(
+            self.variant,
+            MySQLVariant::MySQL8_0 | MySQLVariant::MySQL8_4
+        )
+    }
+
+    fn supports_named_windows(&self) -> bool {
+        matches!(
+            self.variant,
+            MySQLVariant::MySQL8_0 | MySQLVariant::MySQL8_4
+        )
+    }
+
     fn supports_inline_column_comments(&self) -> bool {
         true
     }
@@ -250,6 +289,29 @@ impl DatabasePlatform for MySQLPlatform {
         true
     }
 
+    /// MySQL only honours `COLUMN_FORMAT COMPRESSED` on NDB tables,
+    /// while MariaDB compresses columns of any storage engine.
+    fn supports_column_compression(&self) -> bool {
+        matches!(self.variant, MySQLVariant::MariaDB)
+    }
+
+    fn supports_grants(&self) -> bool {
+        true
+    }
+
+    /// InnoDB checks the constraints immediately: RESTRICT is the same as NO ACTION.
+    fn normalize_foreign_key_referential_action(
+        &self,
+        action: Option<ForeignKeyReferentialAction>,
+    ) -> ForeignKeyReferentialAction {
+        match action {
+            None | Some(ForeignKeyReferentialAction::Restrict) => {
+                ForeignKeyReferentialAction::NoAction
+            }
+            Some(action) => action,
+        }
+    }
+
     fn modify_limit_query(
         &self,
         query: &str,
@@ -271,6 +333,70 @@ impl DatabasePlatform for MySQLPlatform {
             .ok_or_else(|| Error::unknown_database_type(&db_type, &self.get_name()))
     }
 
+    fn get_merge_sql(&self, merge: &MergeBuilder) -> Result<String> {
+        mysql::get_merge_sql(self, merge)
+    }
+
+    fn get_upsert_reporting(&self) -> UpsertReporting {
+        UpsertReporting::AffectedRows
+    }
+
+    fn get_update_from_sql(&self, query: &QueryBuilder) -> Result<Expression> {
+        mysql::get_update_from_sql(query)
+    }
+
+    fn get_values_table_sql(
+        &self,
+        rows: &[Vec<Value>],
+        alias: &str,
+        columns: &[&str],
+    ) -> Result<Expression> {
+        match self.variant {
+            MySQLVariant::MySQL8_0 | MySQLVariant::MySQL8_4 => {
+                mysql::get_values_table_sql(rows, alias, columns)
+            }
+            _ => default::get_values_table_sql(rows, alias, columns),
+        }
+    }
+
+    fn get_replication_position_sql(&self) -> Option<String> {
+        Some(mysql::get_replication_position_sql(matches!(
+            self.variant,
+            MySQLVariant::MySQL8_4
+        )))
+    }
+
+    fn get_estimated_cost_sql(&self, sql: &str) -> Option<String> {
+        Some(format!("EXPLAIN FORMAT=JSON {}", sql))
+    }
+
+    fn get_current_utc_timestamp_sql(&self) -> Option<&str> {
+        Some("UTC_TIMESTAMP()")
+    }
+
+    fn get_last_insert_id_sql(&self) -> Result<String> {
+        Ok("SELECT LAST_INSERT_ID()".to_string())
+    }
+
+    fn get_advisory_lock_sql(
+        &self,
+        key: i64,
+        _: &str,
+        _: Option<&str>,
+        _: Option<Duration>,
+        wait: bool,
+    ) -> Result<String> {
+        Ok(mysql::get_advisory_lock_sql(key, wait))
+    }
+
+    fn get_advisory_unlock_sql(&self, key: i64, _: &str, _: Option<&str>) -> Result<String> {
+        Ok(mysql::get_advisory_unlock_sql(key))
+    }
+
+    fn get_feature_probe_sql(&self, feature: &str) -> Result<String> {
+        mysql::get_feature_probe_sql(self, feature)
+    }
+
     fn create_schema_manager<'a>(&self, connection: &'a Connection) -> Box<dyn SchemaManager + 'a> {
         Box::new(MySQLSchemaManager::new(connection, self.variant))
     }
@@ -280,7 +406,7 @@ impl DatabasePlatform for MySQLPlatform {
 mod tests {
     use crate::driver::mysql::MySQLPlatform;
     use crate::driver::mysql::MySQLVariant;
-    use crate::platform::DatabasePlatform;
+    use crate::platform::{DatabasePlatform, TrimMode};
     use crate::r#type::{BINARY, GUID, JSON};
     use crate::schema::Column;
     use crate::tests::common_platform_tests;
@@ -296,6 +422,10 @@ mod tests {
         MySQLPlatform::new(MySQLVariant::MySQL8_0, Arc::new(EventDispatcher::new()))
     }
 
+    pub fn create_mysql84_platform() -> MySQLPlatform {
+        MySQLPlatform::new(MySQLVariant::MySQL8_4, Arc::new(EventDispatcher::new()))
+    }
+
     pub fn create_mariadb_platform() -> MySQLPlatform {
         MySQLPlatform::new(MySQLVariant::MariaDB, Arc::new(EventDispatcher::new()))
     }
@@ -316,6 +446,27 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn generates_trim_expression_with_chars_set() -> Result<()> {
+        let platform = create_mysql_platform();
+        let chars = Some("'xy'".to_string());
+
+        assert_eq!(
+            platform.get_trim_expression("col", TrimMode::Leading, chars.clone())?,
+            "TRIM(LEADING 'xy' FROM col)"
+        );
+        assert_eq!(
+            platform.get_trim_expression("col", TrimMode::Trailing, chars.clone())?,
+            "TRIM(TRAILING 'xy' FROM col)"
+        );
+        assert_eq!(
+            platform.get_trim_expression("col", TrimMode::Both, chars)?,
+            "TRIM(BOTH 'xy' FROM col)"
+        );
+
+        Ok(())
+    }
+
     #[test]
     pub fn quote_single_identifier() {
         let platform = create_mysql_platform();
@@ -422,4 +573,262 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    pub fn generates_update_join_sql() -> Result<()> {
+        use crate::query::QueryBuilder;
+        use crate::Value;
+        let platform = create_mysql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.update("orders")
+            .set("orders.status", "shipped")
+            .update_join(
+                "shipments",
+                Some("s"),
+                expr.and_x([
+                    expr.eq("s.order_id", "orders.id"),
+                    expr.gt("s.weight", expr.param(10)),
+                ]),
+            )
+            .r#where(expr.eq("s.carrier", expr.param("ACME")));
+
+        assert_eq!(
+            qb.get_sql()?,
+            "UPDATE orders INNER JOIN shipments s ON (s.order_id = orders.id) AND (s.weight > ?) SET orders.status = ? WHERE s.carrier = ?"
+        );
+        assert_eq!(
+            qb.get_parameters()?,
+            vec![Value::Int(10), Value::from("shipped"), Value::from("ACME")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_row_lock_clauses() -> Result<()> {
+        use crate::query::QueryBuilder;
+        let platform = create_mysql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.select(["o.id"])
+            .from("orders", Some("o"))
+            .inner_join("customers", Some("c"), "c.id = o.customer_id")
+            .for_share();
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT o.id FROM orders o INNER JOIN customers c ON c.id = o.customer_id LOCK IN SHARE MODE"
+        );
+
+        qb.for_update_of(&["o"]);
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT o.id FROM orders o INNER JOIN customers c ON c.id = o.customer_id FOR UPDATE"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_values_table_sql() -> Result<()> {
+        use crate::query::QueryBuilder;
+        let platform = create_mysql80_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.select(["v.id", "v.name"])
+            .from_values([[1, 2], [3, 4]], "v", &["id", "name"])?;
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT v.id, v.name FROM (VALUES ROW(?, ?), ROW(?, ?)) v (id, name)"
+        );
+
+        let platform = create_mysql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.select(["v.id", "v.name"])
+            .from_values([[1, 2], [3, 4]], "v", &["id", "name"])?;
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT v.id, v.name FROM (SELECT ? AS id, ? AS name UNION ALL SELECT ?, ?) v"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn escapes_backslashes_in_default_values() -> Result<()> {
+        use crate::r#type::{IntoType, STRING};
+        let platform = create_mysql_platform();
+        let mut column = Column::new("foo", STRING.into_type()?);
+        column.set_default("C:\\path\\it's".into());
+
+        assert_eq!(
+            platform.get_default_value_declaration_sql(&column.generate_column_data(&platform))?,
+            " DEFAULT 'C:\\\\path\\\\it''s'"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_regex_conditions() -> Result<()> {
+        use crate::error::ErrorKind;
+        use crate::query::QueryBuilder;
+        let platform = create_mysql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.select(["u.id"])
+            .from("users", Some("u"))
+            .where_regex("u.name", "^a", false)?;
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT u.id FROM users u WHERE u.name RLIKE ?"
+        );
+        assert_eq!(
+            qb.where_regex("u.name", "^a", true).err().unwrap().kind(),
+            ErrorKind::PlatformFeatureUnsupported
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn json_expressions() -> Result<()> {
+        use crate::query::QueryBuilder;
+        use crate::Value;
+
+        let platform = create_mysql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select([expr.json_array_length("tags")?])
+            .from("posts", None)
+            .r#where(expr.json_contains("tags", expr.param("[\"rust\"]"))?);
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT JSON_LENGTH(tags) FROM posts WHERE JSON_CONTAINS(tags, ?)"
+        );
+        assert_eq!(qb.get_parameters()?, vec![Value::from("[\"rust\"]")]);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_overlaps_expression() -> Result<()> {
+        use crate::error::ErrorKind;
+        use crate::query::QueryBuilder;
+        use crate::Value;
+
+        let platform = create_mysql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select(["b.id"])
+            .from("bookings", Some("b"))
+            .r#where(expr.overlaps(
+                ("b.starts_at", "b.ends_at"),
+                (
+                    expr.param("2024-01-01 10:00:00"),
+                    expr.param("2024-01-01 11:00:00"),
+                ),
+            ));
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT b.id FROM bookings b WHERE ((b.starts_at < ?) AND (? < b.ends_at)) OR (b.starts_at = ?)"
+        );
+        assert_eq!(
+            qb.get_parameters()?,
+            vec![
+                Value::from("2024-01-01 11:00:00"),
+                Value::from("2024-01-01 10:00:00"),
+                Value::from("2024-01-01 10:00:00")
+            ]
+        );
+        assert_eq!(
+            expr.range_overlaps("b.slot", expr.param("[1,10)"))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::PlatformFeatureUnsupported
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn normalizes_foreign_key_referential_actions() {
+        use crate::schema::ForeignKeyReferentialAction;
+
+        for platform in [create_mysql_platform(), create_mariadb_platform()] {
+            for action in [
+                None,
+                Some(ForeignKeyReferentialAction::Restrict),
+                Some(ForeignKeyReferentialAction::NoAction),
+            ] {
+                assert_eq!(
+                    platform.normalize_foreign_key_referential_action(action),
+                    ForeignKeyReferentialAction::NoAction
+                );
+            }
+
+            assert_eq!(
+                platform.normalize_foreign_key_referential_action(Some(
+                    ForeignKeyReferentialAction::Cascade
+                )),
+                ForeignKeyReferentialAction::Cascade
+            );
+        }
+    }
+
+    #[test]
+    pub fn replication_position_sql() {
+        assert_eq!(
+            create_mysql80_platform().get_replication_position_sql(),
+            Some("SHOW MASTER STATUS".to_string())
+        );
+        assert_eq!(
+            create_mysql84_platform().get_replication_position_sql(),
+            Some("SHOW BINARY LOG STATUS".to_string())
+        );
+    }
+
+    #[test]
+    pub fn variant_from_platform_name() {
+        assert!(matches!(
+            MySQLVariant::try_from("mysql"),
+            Ok(MySQLVariant::MySQL5_7)
+        ));
+        assert!(matches!(
+            MySQLVariant::try_from("MySQL8.4"),
+            Ok(MySQLVariant::MySQL8_4)
+        ));
+        assert!(matches!(
+            MySQLVariant::try_from("mariadb"),
+            Ok(MySQLVariant::MariaDB)
+        ));
+        assert!(MySQLVariant::try_from("oracle").is_err());
+    }
+
+    #[test]
+    pub fn variant_from_server_version() {
+        assert!(matches!(
+            MySQLVariant::for_server_version(Some("8.0.36")),
+            MySQLVariant::MySQL8_0
+        ));
+        assert!(matches!(
+            MySQLVariant::for_server_version(Some("11.4.2")),
+            MySQLVariant::MariaDB
+        ));
+        assert!(matches!(
+            MySQLVariant::for_server_version(None),
+            MySQLVariant::MySQL5_7
+        ));
+        assert!(matches!(
+            MySQLVariant::mysql_for_server_version(Some("8.4.0")),
+            MySQLVariant::MySQL8_4
+        ));
+        assert!(matches!(
+            MySQLVariant::mysql_for_server_version(Some("11.4.2")),
+            MySQLVariant::MySQL5_7
+        ));
+    }
 }
@@ -1,9 +1,9 @@
 use super::mysql;
 use crate::driver::mysql::platform::MySQLVariant;
-use crate::platform::default;
+use crate::platform::{default, CreateFlags};
 use crate::schema::{
     Column, ColumnData, Comparator, FKConstraintList, ForeignKeyConstraint, GenericComparator,
-    Identifier, Index, SchemaManager, TableDiff, TableOptions,
+    Identifier, Index, IntoIdentifier, SchemaManager, Table, TableDiff, TableGrant, TableOptions,
 };
 use crate::{AsyncResult, Connection, Result, Row};
 use std::collections::HashMap;
@@ -51,6 +51,14 @@ impl<'a> SchemaManager for MySQLSchemaManager<'a> {
         self
     }
 
+    fn get_create_table_sql(
+        &self,
+        table: &Table,
+        create_flags: Option<CreateFlags>,
+    ) -> Result<Vec<String>> {
+        mysql::get_create_table_sql(self.as_dyn(), table, create_flags)
+    }
+
     #[inline]
     fn _get_create_table_sql(
         &self,
@@ -61,6 +69,29 @@ impl<'a> SchemaManager for MySQLSchemaManager<'a> {
         mysql::_get_create_table_sql(self.as_mysql_dyn(), name, columns, options)
     }
 
+    fn create_foreign_key(
+        &self,
+        foreign_key: &ForeignKeyConstraint,
+        table: &dyn IntoIdentifier,
+    ) -> AsyncResult<()> {
+        let table_name = table.into_identifier().get_name().to_string();
+        let foreign_key = foreign_key.clone();
+
+        Box::pin(
+            async move { mysql::create_foreign_key(self.as_dyn(), foreign_key, &table_name).await },
+        )
+    }
+
+    #[inline]
+    fn validate_foreign_key(
+        &self,
+        local_table: &Table,
+        foreign_key: &ForeignKeyConstraint,
+        foreign_table: &Table,
+    ) -> Result<()> {
+        mysql::validate_foreign_key(local_table, foreign_key, foreign_table)
+    }
+
     #[inline]
     fn get_create_index_sql_flags(&self, index: &Index) -> String {
         mysql::get_create_index_sql_flags(index)
@@ -89,6 +120,27 @@ impl<'a> SchemaManager for MySQLSchemaManager<'a> {
         mysql::get_list_table_indexes_sql(self, table, database)
     }
 
+    fn get_index_statistics_sql(&self, table: &str, database: &str) -> Result<String> {
+        mysql::get_index_statistics_sql(self, table, database)
+    }
+
+    fn get_truncate_tables_sql(&self, tables: &[&str], cascade: bool) -> Result<Vec<String>> {
+        mysql::get_truncate_tables_sql(self.as_dyn(), tables, cascade)
+    }
+
+    fn truncate_tables(&self, tables: &[&str], cascade: bool) -> AsyncResult<()> {
+        let tables = tables.iter().map(ToString::to_string).collect();
+        Box::pin(async move { mysql::truncate_tables(self.as_dyn(), tables, cascade).await })
+    }
+
+    fn get_analyze_table_sql(&self, table_name: &dyn IntoIdentifier) -> Result<Vec<String>> {
+        mysql::get_analyze_table_sql(self.as_dyn(), table_name)
+    }
+
+    fn get_optimize_table_sql(&self, table_name: &dyn IntoIdentifier) -> Result<Vec<String>> {
+        mysql::get_optimize_table_sql(self.as_dyn(), table_name)
+    }
+
     fn get_list_table_foreign_keys_sql(&self, table: &str, database: &str) -> Result<String> {
         mysql::get_list_table_foreign_keys_sql(self.as_dyn(), table, database)
     }
@@ -132,6 +184,15 @@ impl<'a> SchemaManager for MySQLSchemaManager<'a> {
         mysql::get_list_views_sql(self.as_dyn(), database)
     }
 
+    fn get_table_kind_sql(&self, table: &str, database: &str) -> Result<String> {
+        mysql::get_table_kind_sql(self.as_dyn(), table, database)
+    }
+
+    #[inline]
+    fn get_table_timestamps_sql(&self, table: &str, database: &str) -> Result<String> {
+        mysql::get_table_timestamps_sql(self.as_dyn(), table, database)
+    }
+
     #[inline]
     fn get_pre_alter_table_index_foreign_key_sql(&self, diff: &mut TableDiff) -> Result<Vec<String>>
     where
@@ -148,14 +209,15 @@ impl<'a> SchemaManager for MySQLSchemaManager<'a> {
         table_name: &Identifier,
     ) -> Result<Vec<String>> {
         match self.variant {
-            MySQLVariant::MySQL5_7 | MySQLVariant::MySQL8_0 | MySQLVariant::MariaDB => {
-                mysql::get_rename_index_sql(
-                    self.get_platform()?.as_dyn(),
-                    old_index_name,
-                    index,
-                    table_name,
-                )
-            }
+            MySQLVariant::MySQL5_7
+            | MySQLVariant::MySQL8_0
+            | MySQLVariant::MySQL8_4
+            | MySQLVariant::MariaDB => mysql::get_rename_index_sql(
+                self.get_platform()?.as_dyn(),
+                old_index_name,
+                index,
+                table_name,
+            ),
             _ => default::get_rename_index_sql(self.as_dyn(), old_index_name, index, table_name),
         }
     }
@@ -165,6 +227,16 @@ impl<'a> SchemaManager for MySQLSchemaManager<'a> {
         mysql::get_column_collation_declaration_sql(self.get_platform()?.as_dyn(), collation)
     }
 
+    #[inline]
+    fn get_grant_sql(&self, table_name: &Identifier, grant: &TableGrant) -> Result<String> {
+        mysql::get_grant_sql(self.get_platform()?.as_dyn(), table_name, grant)
+    }
+
+    #[inline]
+    fn get_column_compression_declaration_sql(&self, _: &str) -> Result<String> {
+        mysql::get_column_compression_declaration_sql()
+    }
+
     #[inline]
     fn get_advanced_foreign_key_options_sql(
         &self,
@@ -203,15 +275,16 @@ impl<'a> SchemaManager for MySQLSchemaManager<'a> {
 
 #[cfg(test)]
 mod tests {
+    use crate::error::ErrorKind;
     use crate::platform::CreateFlags;
     use crate::r#type::{IntoType, BOOLEAN};
     use crate::r#type::{INTEGER, SIMPLE_ARRAY, STRING};
     use crate::schema::{
-        Asset, ChangedProperty, Column, ColumnDiff, ForeignKeyConstraint, Index, Table, TableDiff,
-        UniqueConstraint,
+        Asset, ChangedProperty, Column, ColumnDiff, Comparator, ForeignKeyConstraint, Index,
+        Schema, Table, TableDiff, TableGrant, UniqueConstraint,
     };
-    use crate::tests::create_connection;
-    use crate::Result;
+    use crate::tests::{create_connection, get_database_dsn};
+    use crate::{Configuration, Connection, Result};
     use std::collections::HashMap;
     use version_compare::{compare_to, Cmp};
 
@@ -288,6 +361,38 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    pub async fn generates_invisible_index_sql() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        if !schema_manager.get_platform()?.supports_invisible_indexes() {
+            return Ok(());
+        }
+
+        let index: Index = Index::builder("my_idx")
+            .add_column("user_name")
+            .set_visible(false)
+            .into();
+
+        let sql = schema_manager.get_create_index_sql(&index, &"mytable")?;
+        assert_eq!(sql, "CREATE INDEX my_idx ON mytable (user_name) INVISIBLE");
+
+        let mut from_table = Table::new("mytable");
+        from_table.add_column(Column::new("user_name", STRING.into_type()?));
+        let mut to_table = from_table.clone();
+        from_table.add_index(Index::builder("my_idx").add_column("user_name"));
+        to_table.add_index(index);
+
+        let comparator = schema_manager.create_comparator();
+        let mut diff = comparator.diff_table(&from_table, &to_table)?.unwrap();
+        assert_eq!(diff.changed_indexes.len(), 1);
+
+        let sql = schema_manager.get_alter_table_sql(&mut diff)?;
+        assert_eq!(sql, &["ALTER TABLE mytable ALTER INDEX my_idx INVISIBLE"]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     pub async fn generates_unique_index_creation_sql() -> Result<()> {
         let connection = create_connection().await?;
@@ -331,6 +436,104 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    pub async fn auto_adds_foreign_key_indexes() -> Result<()> {
+        let mut table = Table::new("test");
+        table.add_column(Column::new("id", INTEGER.into_type()?));
+        table.add_column(Column::new("parent_id", INTEGER.into_type()?));
+        table.set_primary_key(&["id"], None)?;
+
+        let parent_fk = ForeignKeyConstraint::new(
+            &["parent_id"],
+            &["id"],
+            "parent",
+            HashMap::default(),
+            None,
+            None,
+        );
+        let base_table = table.clone();
+        table.add_foreign_keys_raw([parent_fk.clone()].into_iter());
+        assert_eq!(table.foreign_keys_without_index().len(), 1);
+
+        let connection = create_connection().await?;
+        let sql = connection
+            .create_schema_manager()?
+            .get_create_table_sql(&table, None)?;
+        assert!(!sql[0].contains("INDEX"));
+
+        let configuration = Configuration::default().set_auto_index_foreign_keys(true);
+        let connection =
+            Connection::create_from_dsn(&get_database_dsn(), Some(configuration), None)?
+                .connect()
+                .await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        let sql = schema_manager.get_create_table_sql(&table, None)?;
+        assert!(sql[0].contains(", INDEX IDX_"));
+        assert!(sql[0].contains(" (parent_id)"));
+
+        let mut diff = TableDiff::new("test", &base_table);
+        diff.added_foreign_keys.push(parent_fk);
+        diff.added_foreign_keys.push(ForeignKeyConstraint::new(
+            &["id"],
+            &["id"],
+            "other",
+            HashMap::default(),
+            None,
+            None,
+        ));
+        schema_manager.get_alter_table_sql(&mut diff)?;
+        assert_eq!(diff.added_indexes.len(), 1);
+        assert_eq!(diff.added_indexes[0].get_columns(), vec!["parent_id"]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn rejects_foreign_keys_with_mismatched_collations() -> Result<()> {
+        let mut parent = Table::new("parent");
+        parent.add_column(Column::new("code", STRING.into_type()?));
+        parent.set_collation("utf8mb4_bin");
+
+        let mut child = Table::new("child");
+        let mut parent_code = Column::new("parent_code", STRING.into_type()?);
+        parent_code.set_collation("utf8mb4_general_ci");
+        child.add_column(parent_code);
+        child.add_foreign_key_constraint(
+            &["parent_code"],
+            &["code"],
+            "parent",
+            HashMap::default(),
+            None,
+            None,
+            Some("fk_parent_code"),
+        )?;
+
+        let from_schema = Schema::new(vec![], vec![], vec![], vec![], Default::default());
+        let to_schema = Schema::new(
+            vec![parent, child],
+            vec![],
+            vec![],
+            vec![],
+            Default::default(),
+        );
+
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        let comparator = schema_manager.create_comparator();
+        let err = comparator
+            .compare_schemas(&from_schema, &to_schema)
+            .unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::ForeignKeyCollationMismatch);
+        assert_eq!(
+            err.to_string(),
+            "Foreign key fk_parent_code cannot reference column parent.code (utf8mb4_bin) from column child.parent_code (utf8mb4_general_ci): charset and collation must match"
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     pub async fn generates_constraint_creation_sql() -> Result<()> {
         let connection = create_connection().await?;
@@ -442,6 +645,30 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    pub async fn create_table_grants_ignoring_owner() -> Result<()> {
+        let mut table = Table::new("test");
+        table.add_column(Column::new("id", INTEGER.into_type()?));
+        table.set_primary_key(&["id"], None)?;
+        table.set_owner("app_owner");
+        table.add_grant(TableGrant::new(&["SELECT", "INSERT"], "'app'@'%'"));
+        table.add_grant(TableGrant::new(&["SELECT"], "reader@10.0.0.%"));
+
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        let sql = schema_manager.get_create_table_sql(&table, None)?;
+        assert_eq!(
+            sql,
+            &[
+                "CREATE TABLE test (id INT NOT NULL, PRIMARY KEY(id)) DEFAULT CHARACTER SET utf8 COLLATE `utf8_unicode_ci` ENGINE = InnoDB",
+                "GRANT SELECT, INSERT ON test TO `app`@`%`",
+                "GRANT SELECT ON test TO `reader`@`10.0.0.%`",
+            ]
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     pub async fn alter_table_column_comments() -> Result<()> {
         let mut table_diff = TableDiff::new("mytable", None);
@@ -673,6 +900,39 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    pub async fn get_maintenance_sql() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        assert_eq!(
+            schema_manager.get_analyze_table_sql(&"select")?,
+            vec!["ANALYZE TABLE `select`"]
+        );
+        assert_eq!(
+            schema_manager.get_optimize_table_sql(&"foo")?,
+            vec!["OPTIMIZE TABLE foo"]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn get_truncate_tables_sql() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        assert_eq!(
+            schema_manager.get_truncate_tables_sql(&["foo", "select"], false)?,
+            vec!["TRUNCATE foo", "TRUNCATE `select`"]
+        );
+        assert!(schema_manager
+            .get_truncate_tables_sql(&["foo", "select"], true)
+            .is_err());
+
+        Ok(())
+    }
+
     #[tokio::test]
     pub async fn quotes_reserved_keyword_in_index_declaration_sql() -> Result<()> {
         let connection = create_connection().await?;
@@ -1152,4 +1412,46 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    pub async fn generates_column_compression_sql() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        let mut table = Table::new("test");
+        table.add_column(
+            Column::builder("payload", STRING)?
+                .set_length(255)
+                .set_compression("zlib"),
+        );
+
+        let expected = if connection.get_platform()?.supports_column_compression() {
+            "CREATE TABLE test (payload VARCHAR(255) COMPRESSED NOT NULL) DEFAULT CHARACTER SET utf8 COLLATE `utf8_unicode_ci` ENGINE = InnoDB"
+        } else {
+            "CREATE TABLE test (payload VARCHAR(255) NOT NULL) DEFAULT CHARACTER SET utf8 COLLATE `utf8_unicode_ci` ENGINE = InnoDB"
+        };
+        assert_eq!(
+            schema_manager.get_create_table_sql(&table, None)?,
+            &[expected]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn uses_table_collation_as_default() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        let mut table = Table::new("test");
+        table.set_collation("utf8mb4_bin");
+        table.add_column(Column::builder("name", STRING)?.set_length(20));
+
+        assert_eq!(
+            schema_manager.get_create_table_sql(&table, None)?,
+            &["CREATE TABLE test (name VARCHAR(20) NOT NULL) DEFAULT CHARACTER SET utf8mb4 COLLATE `utf8mb4_bin` ENGINE = InnoDB"]
+        );
+
+        Ok(())
+    }
 }
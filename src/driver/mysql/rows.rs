@@ -1,5 +1,5 @@
 use crate::{Result, Row, Value};
-use futures::Stream;
+use futures::{ready, stream, Stream};
 use mysql_async::prelude::FromValue;
 use mysql_async::{BinaryProtocol, FromValueError, QueryResult};
 use std::pin::Pin;
@@ -70,10 +70,13 @@ impl FromValue for Value {
     type Intermediate = IrValue;
 }
 
+type RowStream =
+    dyn Stream<Item = std::result::Result<mysql_async::Row, mysql_async::Error>> + Send;
+
 pub struct MySQLRowsIterator {
     length: usize,
     columns: Vec<String>,
-    iter: Box<dyn Iterator<Item = mysql_async::Row> + Send>,
+    rows: Pin<Box<RowStream>>,
 }
 
 impl MySQLRowsIterator {
@@ -84,16 +87,31 @@ impl MySQLRowsIterator {
             vec![]
         };
 
-        let stream = rows.collect_and_drop().await?;
-        let length = stream.len();
+        let collected = rows.collect_and_drop().await?;
+        let length = collected.len();
 
         Ok(Self {
             length,
             columns,
-            iter: Box::new(stream.into_iter()),
+            rows: Box::pin(stream::iter(collected.into_iter().map(Ok))),
         })
     }
 
+    /// Creates an iterator over the rows streamed from the server.
+    /// The number of rows is unknown until the stream is exhausted.
+    pub fn streaming(
+        columns: Vec<String>,
+        rows: impl Stream<Item = std::result::Result<mysql_async::Row, mysql_async::Error>>
+            + Send
+            + 'static,
+    ) -> MySQLRowsIterator {
+        Self {
+            length: 0,
+            columns,
+            rows: Box::pin(rows),
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.length
     }
@@ -102,17 +120,19 @@ impl MySQLRowsIterator {
 impl Stream for MySQLRowsIterator {
     type Item = Result<Row>;
 
-    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        Poll::Ready(if let Some(row) = self.iter.next() {
-            let mut data_vector: Vec<Value> = Vec::new();
-            for i in 0..row.len() {
-                let value: Value = row.get(i).unwrap();
-                data_vector.push(value);
-            }
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let row = match ready!(self.rows.as_mut().poll_next(cx)) {
+            Some(Ok(row)) => row,
+            Some(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+            None => return Poll::Ready(None),
+        };
 
-            Some(Ok(Row::new(self.columns.clone(), data_vector)))
-        } else {
-            None
-        })
+        let mut data_vector: Vec<Value> = Vec::new();
+        for i in 0..row.len() {
+            let value: Value = row.get(i).unwrap();
+            data_vector.push(value);
+        }
+
+        Poll::Ready(Some(Ok(Row::new(self.columns.clone(), data_vector))))
     }
 }
@@ -3,7 +3,10 @@ use crate::driver::mysql::rows::MySQLRowsIterator;
 use crate::driver::statement_result::StatementResult;
 use crate::error::Error;
 use crate::parameter_type::ParameterType;
+use crate::sync::spawn;
 use crate::{AsyncResult, Parameter, ParameterIndex, Parameters, Result, Rows};
+use futures::channel::{mpsc, oneshot};
+use futures::SinkExt;
 use mysql_async::prelude::*;
 use mysql_async::{Params, Value};
 use std::collections::HashMap;
@@ -18,6 +21,7 @@ pub struct Statement<'conn> {
     pub(super) sql: String,
     parameters: Arc<Mutex<HashMap<ParameterIndex, Parameter>>>,
     row_count: AtomicUsize,
+    fetch_size: AtomicUsize,
     phantom_data: PhantomData<&'conn Self>,
 }
 
@@ -85,12 +89,18 @@ impl<'conn> Statement<'conn> {
             sql: sql.to_string(),
             parameters: Arc::new(Mutex::new(HashMap::new())),
             row_count: AtomicUsize::new(usize::MAX),
+            fetch_size: AtomicUsize::new(0),
             phantom_data: PhantomData,
         })
     }
 
     async fn internal_query(&'conn self, params: Vec<(ParameterIndex, Parameter)>) -> Result<Rows> {
         let params = self.parameters_to_params(params)?;
+        let fetch_size = self.fetch_size.load(Ordering::SeqCst);
+        if fetch_size > 0 {
+            return self.streaming_query(params, fetch_size).await;
+        }
+
         let mut connection = self.connection.connection.lock().await;
 
         let result = self
@@ -104,12 +114,84 @@ impl<'conn> Statement<'conn> {
             .columns()
             .map(|cols| cols.iter().map(|col| col.name_str().to_string()).collect())
             .unwrap_or_else(Vec::new);
+        let column_tables = result
+            .columns()
+            .map(|cols| {
+                cols.iter()
+                    .map(|col| Some(col.table_str().to_string()).filter(|t| !t.is_empty()))
+                    .collect()
+            })
+            .unwrap_or_else(Vec::new);
 
         let last_insert_id = result.last_insert_id().map(|id| id.to_string());
         let iterator = Box::pin(MySQLRowsIterator::new(result).await?);
         self.row_count.store(iterator.len(), Ordering::SeqCst);
 
-        Ok(Rows::new(columns, iterator.len(), last_insert_id, iterator))
+        Ok(Rows::new(columns, iterator.len(), last_insert_id, iterator)
+            .with_column_tables(column_tables))
+    }
+
+    /// Streams the rows of the query from a background task, buffering up to
+    /// `fetch_size` rows. The connection is locked until the rows are exhausted
+    /// or the result is dropped, in which case the remaining rows are discarded.
+    /// The number of rows is unknown in advance: the row count is reported as 0.
+    async fn streaming_query(&'conn self, params: Params, fetch_size: usize) -> Result<Rows> {
+        let mut connection = self.connection.connection.clone().lock_owned().await;
+        let sql = self.sql.clone();
+        let (header_tx, header_rx) = oneshot::channel();
+        let (mut row_tx, row_rx) = mpsc::channel(fetch_size);
+
+        spawn(async move {
+            let mut result = match sql.with(params).run(connection.deref_mut()).await {
+                Ok(result) => result,
+                Err(e) => {
+                    let _ = header_tx.send(Err(e));
+                    return;
+                }
+            };
+
+            let columns = result.columns().unwrap_or_else(|| Arc::from(vec![]));
+            let last_insert_id = result.last_insert_id();
+            if header_tx.send(Ok((columns, last_insert_id))).is_ok() {
+                loop {
+                    match result.next().await {
+                        Ok(Some(row)) => {
+                            if row_tx.send(Ok(row)).await.is_err() {
+                                break;
+                            }
+                        }
+                        Ok(None) => return,
+                        Err(e) => {
+                            let _ = row_tx.send(Err(e)).await;
+                            return;
+                        }
+                    }
+                }
+            }
+
+            let _ = result.drop_result().await;
+        });
+
+        let (columns, last_insert_id) = header_rx.await??;
+        let column_names = columns
+            .iter()
+            .map(|col| col.name_str().to_string())
+            .collect::<Vec<_>>();
+        let column_tables = columns
+            .iter()
+            .map(|col| Some(col.table_str().to_string()).filter(|t| !t.is_empty()))
+            .collect();
+
+        let iterator = Box::pin(MySQLRowsIterator::streaming(column_names.clone(), row_rx));
+        self.row_count.store(0, Ordering::SeqCst);
+
+        Ok(Rows::new(
+            column_names,
+            0,
+            last_insert_id.map(|id| id.to_string()),
+            iterator,
+        )
+        .with_column_tables(column_tables))
     }
 
     async fn internal_execute(
@@ -184,4 +266,11 @@ impl<'conn> crate::driver::statement::Statement<'conn> for Statement<'conn> {
     fn row_count(&self) -> usize {
         self.row_count.load(Ordering::SeqCst)
     }
+
+    /// Rows are streamed from the server, keeping the connection busy
+    /// until the result is exhausted or dropped.
+    fn set_fetch_size(&self, fetch_size: Option<usize>) {
+        self.fetch_size
+            .store(fetch_size.unwrap_or_default(), Ordering::SeqCst);
+    }
 }
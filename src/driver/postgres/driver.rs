@@ -8,10 +8,11 @@ use crate::sync::JoinHandle;
 use crate::tls::DbalTls;
 use crate::{Async, EventDispatcher, Result};
 use regex::Regex;
+use std::collections::VecDeque;
 use std::fmt::{Debug, Formatter};
 use std::future::Future;
 use std::ops::Deref;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio_postgres::{Client, Config, GenericClient};
 use url::Url;
 
@@ -27,6 +28,7 @@ pub struct ConnectionOptions {
     pub ssl_rootcert: Option<String>,
     pub ssl_crl: Option<String>,
     pub application_name: Option<String>,
+    pub prepared_statements_limit: Option<usize>,
 }
 
 impl From<&crate::ConnectionOptions> for ConnectionOptions {
@@ -47,6 +49,7 @@ impl From<&crate::ConnectionOptions> for ConnectionOptions {
             ssl_key: opts.ssl_key.clone(),
             ssl_rootcert: opts.ssl_rootcert.clone(),
             ssl_crl: opts.ssl_crl.clone(),
+            prepared_statements_limit: opts.prepared_statements_limit,
         }
     }
 }
@@ -64,6 +67,7 @@ impl ConnectionOptions {
         let mut ssl_key = None;
         let mut ssl_ca = None;
         let mut application_name = None;
+        let mut prepared_statements_limit = None;
 
         for (name, value) in url.query_pairs() {
             match name.deref() {
@@ -82,6 +86,9 @@ impl ConnectionOptions {
                 "ca" => {
                     ssl_ca = Some(value.to_string());
                 }
+                "prepared_statements_limit" => {
+                    prepared_statements_limit = value.parse().ok();
+                }
                 _ => (),
             }
         }
@@ -105,15 +112,46 @@ impl ConnectionOptions {
             ssl_rootcert: ssl_ca,
             ssl_crl: None,
             application_name,
+            prepared_statements_limit,
         }
     }
 }
 
 pub struct Driver {
-    pub(super) client: Client,
+    pub(super) client: Arc<Client>,
+    prepared_statements: Option<PreparedStatements>,
     handle: JoinHandle<()>,
 }
 
+/// Server-side prepared statements kept for reuse, in least-recently-used order.
+struct PreparedStatements {
+    limit: usize,
+    statements: Mutex<VecDeque<(String, tokio_postgres::Statement)>>,
+}
+
+impl PreparedStatements {
+    fn get(&self, sql: &str) -> Option<tokio_postgres::Statement> {
+        let mut statements = self.statements.lock().unwrap();
+        let position = statements.iter().position(|(s, _)| s == sql)?;
+        let entry = statements.remove(position)?;
+        let statement = entry.1.clone();
+        statements.push_back(entry);
+
+        Some(statement)
+    }
+
+    fn insert(&self, sql: &str, statement: tokio_postgres::Statement) {
+        let mut statements = self.statements.lock().unwrap();
+        statements.retain(|(s, _)| s != sql);
+        statements.push_back((sql.to_string(), statement));
+
+        // Dropping the last reference to a statement deallocates it on the server.
+        while statements.len() > self.limit {
+            statements.pop_front();
+        }
+    }
+}
+
 impl Debug for Driver {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Driver (PostgreSQL)")
@@ -122,6 +160,25 @@ impl Debug for Driver {
 }
 
 impl Driver {
+    /// Prepares the given SQL on the server.
+    ///
+    /// If a prepared statements limit has been configured, statements are kept on the
+    /// server and reused: the least recently used ones are deallocated when exceeding the limit.
+    pub(super) async fn prepare_statement(&self, sql: &str) -> Result<tokio_postgres::Statement> {
+        let Some(prepared_statements) = &self.prepared_statements else {
+            return Ok(self.client.prepare(sql).await?);
+        };
+
+        if let Some(statement) = prepared_statements.get(sql) {
+            return Ok(statement);
+        }
+
+        let statement = self.client.prepare(sql).await?;
+        prepared_statements.insert(sql, statement.clone());
+
+        Ok(statement)
+    }
+
     fn build_dsn(options: ConnectionOptions) -> (String, DbalTls) {
         let mut dsn = String::new();
         if let Some(host) = options.host {
@@ -177,6 +234,13 @@ impl DriverConnection<ConnectionOptions> for Driver {
     type Output = impl Future<Output = Result<Self>>;
 
     fn create(params: ConnectionOptions) -> Self::Output {
+        let prepared_statements = params
+            .prepared_statements_limit
+            .filter(|limit| *limit > 0)
+            .map(|limit| PreparedStatements {
+                limit,
+                statements: Mutex::new(VecDeque::with_capacity(limit + 1)),
+            });
         let (config, tls) = Self::build_dsn(params);
 
         async move {
@@ -192,7 +256,11 @@ impl DriverConnection<ConnectionOptions> for Driver {
                 }
             });
 
-            Ok(Self { client, handle })
+            Ok(Self {
+                client: Arc::new(client),
+                prepared_statements,
+                handle,
+            })
         }
     }
 }
@@ -209,7 +277,20 @@ impl<'conn> Connection<'conn> for Driver {
         ev: Arc<EventDispatcher>,
     ) -> Async<Box<dyn DatabasePlatform + Send + Sync>> {
         Box::pin(async move {
-            Box::new(PostgreSQLPlatform::new(ev)) as Box<(dyn DatabasePlatform + Send + Sync)>
+            let server_version_num = self
+                .client
+                .client()
+                .query_one("SHOW server_version_num", &[])
+                .await
+                .ok()
+                .and_then(|row| row.get::<_, String>(0).parse().ok());
+
+            let platform = match server_version_num {
+                Some(version) => PostgreSQLPlatform::for_server_version(version, ev),
+                None => PostgreSQLPlatform::new(ev),
+            };
+
+            Box::new(platform) as Box<(dyn DatabasePlatform + Send + Sync)>
         })
     }
 
@@ -296,4 +377,43 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn deallocates_least_recently_used_prepared_statements() -> Result<()> {
+        let mut options = ConnectionOptions::build_from_url(
+            &Url::parse(&std::env::var("DATABASE_DSN").unwrap()).unwrap(),
+        );
+        options.prepared_statements_limit = Some(2);
+        let connection = Driver::create(options).await?;
+
+        for i in 0..5 {
+            connection
+                .query(&format!("SELECT {}", i), params![])
+                .await?;
+        }
+
+        connection.query("SELECT 3", params![]).await?;
+        let mut statement = connection
+            .query(
+                "SELECT statement FROM pg_prepared_statements ORDER BY statement",
+                params![],
+            )
+            .await?;
+
+        let mut prepared = vec![];
+        while let Some(row) = statement.fetch_one().await? {
+            prepared.push(row.get(ColumnIndex::Position(0))?.to_string());
+        }
+
+        assert_eq!(
+            prepared,
+            [
+                "SELECT 3",
+                "SELECT statement FROM pg_prepared_statements ORDER BY statement",
+            ]
+        );
+
+        Ok(())
+    }
 }
@@ -3,11 +3,12 @@ use crate::parameter::NO_PARAMS;
 use crate::params;
 use crate::platform::DatabasePlatform;
 use crate::platform::{default, DateIntervalUnit};
-use crate::r#type::{IntoType, TypeManager, BINARY, BLOB, STRING};
+use crate::query::{Expression, MergeBuilder};
+use crate::r#type::{AsTypeId, IntoType, TypeManager, BINARY, BLOB, COMPOSITE, STRING};
 use crate::schema::{
     extract_type_from_comment, Asset, ChangedProperty, Column, ColumnData, ColumnDiff,
-    ForeignKeyConstraint, ForeignKeyReferentialAction, Identifier, Index, IndexList,
-    IntoIdentifier, Sequence, TableDiff, TableOptions,
+    CompositeTypeDefinition, ForeignKeyConstraint, ForeignKeyReferentialAction, Identifier, Index,
+    IndexList, IntoIdentifier, ReplicaIdentity, Sequence, TableDiff, TableOptions, View,
 };
 use crate::schema::{remove_type_from_comment, SchemaManager};
 use crate::{AsyncResult, Error, Result, Row, TransactionIsolationLevel, Value};
@@ -34,7 +35,107 @@ pub fn get_substring_expression(
 }
 
 pub fn get_regex_expression() -> Result<String> {
-    Ok("SIMILAR TO".to_string())
+    Ok("~".to_string())
+}
+
+pub fn get_case_insensitive_regex_expression() -> Result<String> {
+    Ok("~*".to_string())
+}
+
+pub fn get_json_contains_expression(column: &str, value: &str) -> Result<String> {
+    Ok(format!(
+        "CAST({} AS JSONB) @> CAST({} AS JSONB)",
+        column, value
+    ))
+}
+
+pub fn get_json_array_length_expression(column: &str) -> Result<String> {
+    Ok(format!("JSONB_ARRAY_LENGTH(CAST({} AS JSONB))", column))
+}
+
+pub fn get_merge_sql(this: &dyn DatabasePlatform, merge: &MergeBuilder) -> Result<String> {
+    merge.to_on_conflict_sql(this, "EXCLUDED")
+}
+
+/// Placeholders of the first row are cast to the type of the bound values,
+/// as parameters in a VALUES list would otherwise be resolved as text.
+pub fn get_values_table_sql(
+    rows: &[Vec<Value>],
+    alias: &str,
+    columns: &[&str],
+) -> Result<Expression> {
+    let casts = (0..columns.len())
+        .map(|i| {
+            rows.iter()
+                .map(|row| &row[i])
+                .find(|value| !matches!(value, Value::NULL))
+                .and_then(|value| match value {
+                    Value::Int(_) | Value::UInt(_) => Some("BIGINT"),
+                    Value::Float(_) => Some("DOUBLE PRECISION"),
+                    Value::Boolean(_) => Some("BOOLEAN"),
+                    _ => None,
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let values = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let placeholders = casts
+                .iter()
+                .take(row.len())
+                .map(|cast| match cast {
+                    Some(cast) if i == 0 => format!("CAST(? AS {})", cast),
+                    _ => "?".to_string(),
+                })
+                .join(", ");
+
+            format!("({})", placeholders)
+        })
+        .join(", ");
+
+    Ok(Expression::with_parameters(
+        format!("(VALUES {}) {} ({})", values, alias, columns.join(", ")),
+        rows.iter().flatten().cloned().collect(),
+    ))
+}
+
+/// The system column xmax is zero only for freshly inserted row versions.
+pub fn get_upsert_sql(this: &dyn DatabasePlatform, merge: &MergeBuilder) -> Result<String> {
+    Ok(format!(
+        "{} RETURNING (xmax = 0) AS inserted",
+        get_merge_sql(this, merge)?
+    ))
+}
+
+pub fn get_replication_position_sql() -> String {
+    "SELECT pg_current_wal_lsn()::text".to_string()
+}
+
+pub fn get_advisory_lock_sql(key: i64, wait: bool) -> String {
+    if wait {
+        format!("SELECT true FROM pg_advisory_lock({})", key)
+    } else {
+        format!("SELECT pg_try_advisory_lock({})", key)
+    }
+}
+
+pub fn get_advisory_unlock_sql(key: i64) -> String {
+    format!("SELECT pg_advisory_unlock({})", key)
+}
+
+pub fn get_feature_probe_sql(this: &dyn DatabasePlatform, feature: &str) -> Result<String> {
+    match feature.split_once(':') {
+        Some(("extension", name)) => Ok(format!(
+            "SELECT 1 FROM pg_catalog.pg_extension WHERE extname = {}",
+            this.quote_string_literal(name)
+        )),
+        _ => Err(Error::platform_feature_unsupported(format!(
+            "feature probe \"{}\"",
+            feature
+        ))),
+    }
 }
 
 pub fn get_locate_expression(
@@ -86,14 +187,19 @@ pub fn get_list_databases_sql() -> Result<String> {
 
 pub fn get_list_sequences_sql(this: &dyn SchemaManager, database: &str) -> Result<String> {
     Ok(format!(
-        "SELECT sequence_name AS relname,
-               sequence_schema AS schemaname,
-               minimum_value AS min_value,
-               increment AS increment_by
-        FROM   information_schema.sequences
-        WHERE  sequence_catalog = {}
-        AND    sequence_schema NOT LIKE 'pg\\_%'
-        AND    sequence_schema != 'information_schema'",
+        "SELECT sequencename AS relname,
+               schemaname,
+               data_type::text AS data_type,
+               start_value,
+               min_value,
+               max_value,
+               increment_by,
+               cycle,
+               cache_size
+        FROM   pg_sequences
+        WHERE  current_database() = {}
+        AND    schemaname NOT LIKE 'pg\\_%'
+        AND    schemaname != 'information_schema'",
         this.quote_string_literal(database)
     ))
 }
@@ -106,10 +212,84 @@ pub fn get_list_tables_sql() -> Result<String> {
                 AND    table_schema != 'information_schema'
                 AND    table_name != 'geometry_columns'
                 AND    table_name != 'spatial_ref_sys'
-                AND    table_type != 'VIEW'"
+                AND    table_type = 'BASE TABLE'"
         .to_string())
 }
 
+pub fn get_table_kind_sql(this: &dyn SchemaManager, table: &str) -> Result<String> {
+    Ok(format!(
+        "SELECT CASE c.relkind
+                    WHEN 'v' THEN 'VIEW'
+                    WHEN 'm' THEN 'MATERIALIZED VIEW'
+                    WHEN 'f' THEN 'FOREIGN'
+                    ELSE 'BASE TABLE'
+                END AS kind
+           FROM pg_catalog.pg_class c, pg_catalog.pg_namespace n
+          WHERE {} AND n.oid = c.relnamespace AND c.relkind IN ('r', 'p', 'v', 'm', 'f')",
+        get_table_where_clause(this, table, "c", "n")?
+    ))
+}
+
+/// PostgreSQL does not track the creation time of a table, nor the time it has been last modified:
+/// the update time is approximated with the last time the statistics of the table have been collected,
+/// as (auto)analyze runs after a relevant amount of data has changed.
+pub fn get_table_timestamps_sql(this: &dyn SchemaManager, table: &str) -> Result<String> {
+    Ok(format!(
+        "SELECT NULL::timestamptz AS created,
+                GREATEST(s.last_analyze, s.last_autoanalyze) AS updated
+           FROM pg_catalog.pg_class c
+           JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+           LEFT JOIN pg_catalog.pg_stat_user_tables s ON s.relid = c.oid
+          WHERE {} AND c.relkind IN ('r', 'p')",
+        get_table_where_clause(this, table, "c", "n")?
+    ))
+}
+
+pub fn get_create_materialized_view_sql(
+    platform: &dyn DatabasePlatform,
+    view: &View,
+) -> Result<String> {
+    Ok(format!(
+        "CREATE MATERIALIZED VIEW {} AS {}",
+        view.get_quoted_name(platform),
+        view.get_sql()
+    ))
+}
+
+pub fn get_create_table_as_sql(
+    platform: &dyn DatabasePlatform,
+    name: &Identifier,
+    select_sql: &str,
+    with_data: bool,
+) -> Result<String> {
+    Ok(format!(
+        "CREATE TABLE {} AS {}{}",
+        name.get_quoted_name(platform),
+        select_sql,
+        if with_data { "" } else { " WITH NO DATA" }
+    ))
+}
+
+pub fn get_refresh_materialized_view_sql(
+    platform: &dyn DatabasePlatform,
+    name: &Identifier,
+) -> Result<String> {
+    Ok(format!(
+        "REFRESH MATERIALIZED VIEW {}",
+        name.get_quoted_name(platform)
+    ))
+}
+
+pub fn get_optimize_table_sql(
+    platform: &dyn DatabasePlatform,
+    table_name: &dyn IntoIdentifier,
+) -> Result<Vec<String>> {
+    Ok(vec![format!(
+        "VACUUM {}",
+        table_name.into_identifier().get_quoted_name(platform)
+    )])
+}
+
 pub fn get_list_views_sql() -> Result<String> {
     Ok("SELECT quote_ident(table_name) AS viewname,
                table_schema AS schemaname,
@@ -154,11 +334,19 @@ WHERE oid IN (
     ))
 }
 
+/// `indnullsnotdistinct` is read through `to_jsonb`, as it is available on PostgreSQL 15+ only.
+/// Indexes backing a unique or primary key constraint are detected through `pg_constraint`.
 pub fn get_list_table_indexes_sql(this: &dyn SchemaManager, table: &str) -> Result<String> {
     Ok(format!(
         r#"SELECT quote_ident(relname) as relname, pg_index.indisunique, pg_index.indisprimary,
                   pg_index.indkey, pg_index.indrelid,
                   pg_get_expr(indpred, indrelid) AS where,
+                  COALESCE((to_jsonb(pg_index) ->> 'indnullsnotdistinct')::bool, false) AS nulls_not_distinct,
+                  EXISTS (
+                     SELECT 1
+                     FROM pg_constraint
+                     WHERE conindid = pg_index.indexrelid AND conrelid = pg_index.indrelid AND contype IN ('p', 'u')
+                  ) AS constraint_backed,
                   array(
                      SELECT attname
                      FROM pg_attribute
@@ -176,6 +364,29 @@ pub fn get_list_table_indexes_sql(this: &dyn SchemaManager, table: &str) -> Resu
     ))
 }
 
+pub fn get_index_statistics_sql(this: &dyn SchemaManager, table: &str) -> Result<String> {
+    Ok(format!(
+        r#"SELECT quote_ident(ic.relname) AS index_name,
+                  CASE WHEN c.reltuples < 0 THEN NULL
+                       WHEN i.indisunique THEN c.reltuples::bigint
+                       ELSE (
+                           SELECT CASE WHEN s.n_distinct >= 0 THEN s.n_distinct ELSE -s.n_distinct * c.reltuples END
+                           FROM pg_stats s
+                           JOIN pg_attribute a ON a.attrelid = c.oid AND a.attname = s.attname
+                           WHERE s.schemaname = n.nspname AND s.tablename = c.relname AND a.attnum = i.indkey[0]
+                       )::bigint
+                  END AS cardinality,
+                  psui.idx_scan AS scans
+            FROM pg_index i
+            JOIN pg_class c ON c.oid = i.indrelid
+            JOIN pg_class ic ON ic.oid = i.indexrelid
+            JOIN pg_namespace n ON n.oid = c.relnamespace
+            LEFT JOIN pg_stat_user_indexes psui ON psui.indexrelid = i.indexrelid
+            WHERE {}"#,
+        get_table_where_clause(this, table, "c", "n")?
+    ))
+}
+
 pub fn get_portable_table_indexes_list(
     this: &dyn SchemaManager,
     table_indexes: Vec<Row>,
@@ -193,6 +404,8 @@ pub fn get_portable_table_indexes_list(
                     "primary".into(),
                     "where".into(),
                     "flags".into(),
+                    "nulls_not_distinct".into(),
+                    "constraint_backed".into(),
                 ],
                 vec![
                     row.get("relname")?.clone(),
@@ -201,6 +414,8 @@ pub fn get_portable_table_indexes_list(
                     row.get("indisprimary")?.clone(),
                     row.get("where")?.clone(),
                     Value::NULL,
+                    row.get("nulls_not_distinct")?.clone(),
+                    row.get("constraint_backed")?.clone(),
                 ],
             );
 
@@ -314,6 +529,9 @@ fn get_table_where_clause(
     ))
 }
 
+/// Column compression (`attcompression`) is read through `to_jsonb` to keep
+/// the query working on servers older than PostgreSQL 14.
+/// Storage modes are reported only when different from the column type default.
 pub fn get_list_table_columns_sql(this: &dyn SchemaManager, table: &str) -> Result<String> {
     Ok(format!(
         r#"
@@ -325,10 +543,29 @@ SELECT
     t.typname AS type,
     format_type(a.atttypid, a.atttypmod) AS complete_type,
     (SELECT tc.collcollate FROM pg_catalog.pg_collation tc WHERE tc.oid = a.attcollation) AS collation,
+    CASE to_jsonb(a) ->> 'attcompression' WHEN 'p' THEN 'pglz' WHEN 'l' THEN 'lz4' END AS compression,
+    CASE WHEN a.attstorage <> t.typstorage THEN
+        CASE a.attstorage WHEN 'p' THEN 'PLAIN' WHEN 'e' THEN 'EXTERNAL' WHEN 'm' THEN 'MAIN' WHEN 'x' THEN 'EXTENDED' END
+    END AS storage,
+    NULLIF(a.attstattarget, -1)::int AS statistics_target,
     (SELECT t1.typname FROM pg_catalog.pg_type t1 WHERE t1.oid = t.typbasetype) AS domain_type,
     (SELECT format_type(t2.typbasetype, t2.typtypmod) FROM
        pg_catalog.pg_type t2 WHERE t2.typtype = 'd' AND t2.oid = a.atttypid) AS domain_complete_type,
     a.attnotnull AS isnotnull,
+    a.attidentity <> '' AS identity,
+    t.typtype = 'c' AS composite,
+    (SELECT json_agg(json_build_object(
+        'name', ca.attname,
+        'type', ct.typname,
+        'complete_type', format_type(ca.atttypid, ca.atttypmod),
+        'composite', ct.typtype = 'c'
+      ) ORDER BY ca.attnum)::text
+    FROM pg_catalog.pg_attribute ca
+    JOIN pg_catalog.pg_type ct ON ct.oid = ca.atttypid
+    WHERE ca.attrelid = t.typrelid
+       AND ca.attnum > 0
+       AND NOT ca.attisdropped
+    ) AS composite_attributes,
     (SELECT 't'
     FROM pg_index
     WHERE c.oid = pg_index.indrelid
@@ -340,6 +577,7 @@ SELECT
     WHERE c.oid = pg_attrdef.adrelid
        AND pg_attrdef.adnum=a.attnum
     ) AS default,
+    pg_get_serial_sequence(quote_ident(n.nspname) || '.' || quote_ident(c.relname), a.attname) AS serial_sequence,
     (SELECT pg_description.description
        FROM pg_description WHERE pg_description.objoid = c.oid AND a.attnum = pg_description.objsubid
     ) AS comment
@@ -418,6 +656,10 @@ pub fn get_alter_table_sql(this: &dyn SchemaManager, diff: &mut TableDiff) -> Re
             diff.get_name().get_quoted_name(&platform),
             query
         ));
+        sql.extend(get_column_storage_options_sql(
+            &diff.get_name().get_quoted_name(&platform),
+            &column.generate_column_data(&platform),
+        ));
 
         let comment = this.get_column_comment(column)?;
         if comment.is_empty() {
@@ -477,14 +719,15 @@ pub fn get_alter_table_sql(this: &dyn SchemaManager, diff: &mut TableDiff) -> Re
 
         if column_diff.has_changed(ChangedProperty::Default) {
             let column_data = column.generate_column_data(&platform);
-            let default_clause = if column_data.default == Value::NULL {
-                " DROP DEFAULT".to_string()
-            } else {
-                format!(
-                    " SET{}",
-                    platform.get_default_value_declaration_sql(&column_data)?
-                )
-            };
+            let default_clause =
+                if column_data.default == Value::NULL && column_data.default_expression.is_none() {
+                    " DROP DEFAULT".to_string()
+                } else {
+                    format!(
+                        " SET{}",
+                        platform.get_default_value_declaration_sql(&column_data)?
+                    )
+                };
             let query = format!("ALTER {}{}", old_column_name, default_clause);
             sql.push(format!(
                 "ALTER TABLE {} {}",
@@ -493,6 +736,50 @@ pub fn get_alter_table_sql(this: &dyn SchemaManager, diff: &mut TableDiff) -> Re
             ));
         }
 
+        if column_diff.has_changed(ChangedProperty::Compression)
+            && platform.supports_column_compression()
+        {
+            let query = format!(
+                "ALTER {} SET COMPRESSION {}",
+                old_column_name,
+                column.get_compression().unwrap_or("DEFAULT")
+            );
+            sql.push(format!(
+                "ALTER TABLE {} {}",
+                diff.get_name().get_quoted_name(&platform),
+                query
+            ));
+        }
+
+        if column_diff.has_changed(ChangedProperty::Storage) {
+            // Resetting the storage to the type default requires PostgreSQL 16.
+            let query = format!(
+                "ALTER {} SET STORAGE {}",
+                old_column_name,
+                column.get_storage().unwrap_or("DEFAULT")
+            );
+            sql.push(format!(
+                "ALTER TABLE {} {}",
+                diff.get_name().get_quoted_name(&platform),
+                query
+            ));
+        }
+
+        if column_diff.has_changed(ChangedProperty::StatisticsTarget) {
+            let query = format!(
+                "ALTER {} SET STATISTICS {}",
+                old_column_name,
+                column
+                    .get_statistics_target()
+                    .map_or_else(|| "-1".to_string(), |target| target.to_string())
+            );
+            sql.push(format!(
+                "ALTER TABLE {} {}",
+                diff.get_name().get_quoted_name(&platform),
+                query
+            ));
+        }
+
         if column_diff.has_changed(ChangedProperty::NotNull) {
             let query = format!(
                 "ALTER {} {} NOT NULL",
@@ -506,32 +793,59 @@ pub fn get_alter_table_sql(this: &dyn SchemaManager, diff: &mut TableDiff) -> Re
             ));
         }
 
-        if column_diff.has_changed(ChangedProperty::AutoIncrement) {
-            if column.is_autoincrement() {
+        if column_diff.has_changed(ChangedProperty::AutoIncrement)
+            || column_diff.has_changed(ChangedProperty::Identity)
+        {
+            let table_name = diff.get_name().get_quoted_name(&platform);
+            let was_identity = column_diff
+                .from_column
+                .as_ref()
+                .is_some_and(|c| c.is_autoincrement() && c.is_identity());
+
+            if was_identity {
+                sql.push(format!(
+                    "ALTER TABLE {} ALTER {} DROP IDENTITY IF EXISTS",
+                    table_name, old_column_name
+                ));
+            } else if !column.is_autoincrement() || column.is_identity() {
+                sql.push(format!(
+                    "ALTER TABLE {} ALTER {} DROP DEFAULT",
+                    table_name, old_column_name
+                ));
+            }
+
+            let was_serial = column_diff
+                .from_column
+                .as_ref()
+                .is_some_and(|c| c.is_autoincrement() && !c.is_identity());
+            if was_serial && column.is_autoincrement() && column.is_identity() {
+                // The sequence owned by the serial column would be left behind by the identity.
+                sql.push(format!(
+                    "DO $$ DECLARE seq text := pg_get_serial_sequence('{}', '{}'); BEGIN IF seq IS NOT NULL THEN EXECUTE 'DROP SEQUENCE ' || seq; END IF; END $$",
+                    table_name.replace('\'', "''"), old_column_name
+                ));
+            }
+
+            if column.is_autoincrement() && column.is_identity() {
+                sql.push(format!(
+                    "ALTER TABLE {} ALTER {} ADD GENERATED BY DEFAULT AS IDENTITY",
+                    table_name, old_column_name
+                ));
+                sql.push(format!(
+                    "SELECT setval(pg_get_serial_sequence('{}', '{}'), COALESCE((SELECT MAX({}) FROM {}), 0) + 1, false)",
+                    table_name.replace('\'', "''"), old_column_name, old_column_name, table_name
+                ));
+            } else if column.is_autoincrement() {
                 let seq_name = format!("{}_{}_seq", diff.name, old_column_name);
 
                 sql.push(format!("CREATE SEQUENCE {}", seq_name));
                 sql.push(format!(
                     "SELECT setval('{}', (SELECT MAX({}) FROM {}))",
-                    seq_name,
-                    old_column_name,
-                    diff.get_name().get_quoted_name(&platform)
+                    seq_name, old_column_name, table_name
                 ));
-                let query = format!(
-                    "ALTER {} SET DEFAULT nextval('{}')",
-                    old_column_name, seq_name
-                );
                 sql.push(format!(
-                    "ALTER TABLE {} {}",
-                    diff.get_name().get_quoted_name(&platform),
-                    query
-                ));
-            } else {
-                let query = format!("ALTER {} DROP DEFAULT", old_column_name);
-                sql.push(format!(
-                    "ALTER TABLE {} {}",
-                    diff.get_name().get_quoted_name(&platform),
-                    query
+                    "ALTER TABLE {} ALTER {} SET DEFAULT nextval('{}')",
+                    table_name, old_column_name, seq_name
                 ));
             }
         }
@@ -603,6 +917,12 @@ pub fn get_alter_table_sql(this: &dyn SchemaManager, diff: &mut TableDiff) -> Re
         new_sql.append(&mut sql);
         new_sql.append(&mut this.get_post_alter_table_index_foreign_key_sql(diff)?);
 
+        // The replica identity may use an index created above.
+        if let Some(replica_identity) = &diff.changed_replica_identity {
+            let table_name = diff.get_new_name().unwrap_or_else(|| diff.get_name());
+            new_sql.push(this.get_alter_table_replica_identity_sql(&table_name, replica_identity)?);
+        }
+
         sql = new_sql;
     }
 
@@ -665,6 +985,40 @@ pub fn get_rename_index_sql(
     )])
 }
 
+pub fn get_alter_table_owner_sql(
+    platform: &dyn DatabasePlatform,
+    table_name: &Identifier,
+    owner: &str,
+) -> Result<String> {
+    Ok(format!(
+        "ALTER TABLE {} OWNER TO {}",
+        table_name.get_quoted_name(platform),
+        Identifier::new(owner, true).get_quoted_name(platform)
+    ))
+}
+
+pub fn get_alter_table_replica_identity_sql(
+    platform: &dyn DatabasePlatform,
+    table_name: &Identifier,
+    replica_identity: &ReplicaIdentity,
+) -> Result<String> {
+    let identity = match replica_identity {
+        ReplicaIdentity::Default => "DEFAULT".to_string(),
+        ReplicaIdentity::Full => "FULL".to_string(),
+        ReplicaIdentity::Nothing => "NOTHING".to_string(),
+        ReplicaIdentity::UsingIndex(index) => format!(
+            "USING INDEX {}",
+            Identifier::new(index, false).get_quoted_name(platform)
+        ),
+    };
+
+    Ok(format!(
+        "ALTER TABLE {} REPLICA IDENTITY {}",
+        table_name.get_quoted_name(platform),
+        identity
+    ))
+}
+
 pub fn get_comment_on_column_sql(
     platform: &dyn DatabasePlatform,
     table_name: &dyn IntoIdentifier,
@@ -688,33 +1042,69 @@ pub fn get_create_sequence_sql(
     platform: &dyn DatabasePlatform,
     sequence: &Sequence,
 ) -> Result<String> {
-    Ok(format!(
-        "CREATE SEQUENCE {} INCREMENT BY {} MINVALUE {} START {} {}",
+    let mut sql = format!(
+        "CREATE SEQUENCE {} INCREMENT BY {} MINVALUE {}",
         sequence.get_quoted_name(platform),
         sequence.get_allocation_size(),
-        sequence.get_initial_value(),
-        sequence.get_initial_value(),
-        get_sequence_cache_sql(sequence)
-    ))
+        sequence
+            .get_min_value()
+            .unwrap_or(sequence.get_initial_value()),
+    );
+
+    if let Some(max_value) = sequence.get_max_value() {
+        sql += &format!(" MAXVALUE {}", max_value);
+    }
+
+    sql += &format!(" START {}", sequence.get_initial_value());
+    sql += &get_sequence_cache_sql(sequence);
+    if sequence.is_cycle() {
+        sql += " CYCLE";
+    }
+
+    Ok(sql)
 }
 
+/// An unset minimum value is reset to the default one, as the start value is not changed.
 pub fn get_alter_sequence_sql(
     platform: &dyn DatabasePlatform,
     sequence: &Sequence,
 ) -> Result<String> {
-    Ok(format!(
-        "ALTER SEQUENCE {} INCREMENT BY {} {}",
+    let mut sql = format!(
+        "ALTER SEQUENCE {} INCREMENT BY {}",
         sequence.get_quoted_name(platform),
         sequence.get_allocation_size(),
-        get_sequence_cache_sql(sequence)
-    ))
+    );
+
+    if let Some(min_value) = sequence.get_min_value() {
+        sql += &format!(" MINVALUE {}", min_value);
+    } else {
+        sql += " NO MINVALUE";
+    }
+
+    if let Some(max_value) = sequence.get_max_value() {
+        sql += &format!(" MAXVALUE {}", max_value);
+    } else {
+        sql += " NO MAXVALUE";
+    }
+
+    sql += &format!(
+        " CACHE {}",
+        sequence.get_cache().filter(|c| *c > 1).unwrap_or(1)
+    );
+    sql += if sequence.is_cycle() {
+        " CYCLE"
+    } else {
+        " NO CYCLE"
+    };
+
+    Ok(sql)
 }
 
 /// Cache definition for sequences
 pub fn get_sequence_cache_sql(sequence: &Sequence) -> String {
     let cache = sequence.get_cache().unwrap_or(0);
     if cache > 1 {
-        format!(" CACHE {} ", cache)
+        format!(" CACHE {}", cache)
     } else {
         String::new()
     }
@@ -750,11 +1140,12 @@ pub fn _get_create_table_sql(
         query_fields += &format!(", PRIMARY KEY({})", key_columns.join(", "));
     }
 
-    let mut sql = vec![format!(
-        "CREATE TABLE {} ({})",
-        name.get_quoted_name(&this.get_platform()?),
-        query_fields
-    )];
+    let table_name = name.get_quoted_name(&this.get_platform()?);
+    let mut sql = vec![format!("CREATE TABLE {} ({})", table_name, query_fields)];
+    for column in columns {
+        sql.extend(get_column_storage_options_sql(&table_name, column));
+    }
+
     for index in options.indexes.values() {
         sql.push(this.get_create_index_sql(index, name)?);
     }
@@ -770,6 +1161,35 @@ pub fn _get_create_table_sql(
     Ok(sql)
 }
 
+/// Storage modes and statistics targets are not part of the column declaration,
+/// so they are compared separately.
+pub fn columns_equal(this: &dyn SchemaManager, column1: &Column, column2: &Column) -> Result<bool> {
+    Ok(column1.get_storage() == column2.get_storage()
+        && column1.get_statistics_target() == column2.get_statistics_target()
+        && default::columns_equal(this, column1, column2)?)
+}
+
+/// Storage modes and statistics targets cannot be declared inline,
+/// so they are set altering the column once created.
+fn get_column_storage_options_sql(table_name: &str, column: &ColumnData) -> Vec<String> {
+    let mut sql = vec![];
+    if let Some(storage) = &column.storage {
+        sql.push(format!(
+            "ALTER TABLE {} ALTER {} SET STORAGE {}",
+            table_name, column.name, storage
+        ));
+    }
+
+    if let Some(target) = column.statistics_target {
+        sql.push(format!(
+            "ALTER TABLE {} ALTER {} SET STATISTICS {}",
+            table_name, column.name, target
+        ));
+    }
+
+    sql
+}
+
 /// Converts a single boolean value.
 ///
 /// First converts the value to its native boolean type
@@ -889,7 +1309,9 @@ pub fn get_boolean_type_declaration_sql() -> Result<String> {
 }
 
 pub fn get_integer_type_declaration_sql(column: &ColumnData) -> Result<String> {
-    if column.autoincrement {
+    if column.autoincrement && column.identity {
+        Ok("INT GENERATED BY DEFAULT AS IDENTITY".to_string())
+    } else if column.autoincrement {
         Ok("SERIAL".to_string())
     } else {
         Ok("INT".to_string())
@@ -897,7 +1319,9 @@ pub fn get_integer_type_declaration_sql(column: &ColumnData) -> Result<String> {
 }
 
 pub fn get_bigint_type_declaration_sql(column: &ColumnData) -> Result<String> {
-    if column.autoincrement {
+    if column.autoincrement && column.identity {
+        Ok("BIGINT GENERATED BY DEFAULT AS IDENTITY".to_string())
+    } else if column.autoincrement {
         Ok("BIGSERIAL".to_string())
     } else {
         Ok("BIGINT".to_string())
@@ -905,7 +1329,9 @@ pub fn get_bigint_type_declaration_sql(column: &ColumnData) -> Result<String> {
 }
 
 pub fn get_smallint_type_declaration_sql(column: &ColumnData) -> Result<String> {
-    if column.autoincrement {
+    if column.autoincrement && column.identity {
+        Ok("SMALLINT GENERATED BY DEFAULT AS IDENTITY".to_string())
+    } else if column.autoincrement {
         Ok("SMALLSERIAL".to_string())
     } else {
         Ok("SMALLINT".to_string())
@@ -986,14 +1412,49 @@ pub fn get_truncate_table_sql(
     Ok(sql)
 }
 
+pub fn get_truncate_tables_sql(
+    this: &dyn SchemaManager,
+    tables: &[&str],
+    cascade: bool,
+) -> Result<Vec<String>> {
+    let mut sql = default::get_truncate_tables_sql(this, tables)?;
+    if cascade {
+        for stmt in sql.iter_mut() {
+            *stmt += " CASCADE";
+        }
+    }
+
+    Ok(sql)
+}
+
 pub fn get_read_lock_sql() -> Result<String> {
     Ok("FOR SHARE".to_string())
 }
 
+pub fn get_write_lock_of_sql(tables: &[&str]) -> Result<String> {
+    Ok(if tables.is_empty() {
+        "FOR UPDATE".to_string()
+    } else {
+        format!("FOR UPDATE OF {}", tables.join(", "))
+    })
+}
+
 pub fn get_blob_type_declaration_sql() -> Result<String> {
     Ok("BYTEA".to_string())
 }
 
+pub fn get_duration_type_declaration_sql() -> Result<String> {
+    Ok("INTERVAL".to_string())
+}
+
+pub fn get_integer_range_type_declaration_sql() -> Result<String> {
+    Ok("INT4RANGE".to_string())
+}
+
+pub fn get_date_time_tz_range_type_declaration_sql() -> Result<String> {
+    Ok("TSTZRANGE".to_string())
+}
+
 pub fn get_default_value_declaration_sql(
     this: &dyn DatabasePlatform,
     column: &ColumnData,
@@ -1005,6 +1466,10 @@ pub fn get_default_value_declaration_sql(
     }
 }
 
+pub fn get_column_compression_declaration_sql(compression: &str) -> Result<String> {
+    Ok(format!("COMPRESSION {}", compression))
+}
+
 pub fn get_column_collation_declaration_sql(
     platform: &dyn DatabasePlatform,
     collation: &str,
@@ -1019,6 +1484,22 @@ pub fn get_json_type_declaration_sql(column: &ColumnData) -> Result<String> {
     Ok(if column.jsonb { "JSONB" } else { "JSON" }.to_string())
 }
 
+pub fn get_composite_type_declaration_sql(
+    this: &dyn DatabasePlatform,
+    column: &ColumnData,
+) -> Result<String> {
+    column
+        .composite_type
+        .as_ref()
+        .map(|t| t.get_quoted_name(this))
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::UnknownDatabaseType,
+                format!("Composite type is not defined for column {}", column.name),
+            )
+        })
+}
+
 fn get_old_column_comment(this: &dyn SchemaManager, column_diff: &ColumnDiff) -> Option<String> {
     column_diff
         .from_column
@@ -1026,6 +1507,16 @@ fn get_old_column_comment(this: &dyn SchemaManager, column_diff: &ColumnDiff) ->
         .map(|c| this.get_column_comment(c).unwrap_or_else(|_| String::new()))
 }
 
+/// Removes the casts added by the server to the literals of a default expression
+/// (ie: `nextval('seq'::regclass)` becomes `nextval('seq')`).
+fn normalize_default_expression(expression: &str) -> Result<String> {
+    let cast_re = Regex::new(
+        "('(?:[^']|'')*')::(?:character varying|double precision|timestamp with(?:out)? time zone|[A-Za-z_][A-Za-z0-9_]*)(?:\\[\\])?",
+    )?;
+
+    Ok(cast_re.replace_all(expression.trim(), "$1").into_owned())
+}
+
 pub fn get_portable_table_column_definition(
     this: &dyn SchemaManager,
     table_column: &Row,
@@ -1041,22 +1532,46 @@ pub fn get_portable_table_column_definition(
         col_length = Value::String(len.to_string());
     }
 
-    let mut autoincrement = false;
+    let identity = table_column.get("identity")?.to_string() == "true";
+    let mut autoincrement = identity;
 
     let mut col_default = table_column.get("default")?.clone();
+    let mut default_expression = None;
     if !col_default.is_null() {
         let def = col_default.to_string();
 
         let next_val_re = Regex::new("^nextval\\('(.*)'(::.*)?\\)$")?;
         let default_val_re = Regex::new("^['(](.*)[')]::")?;
         let null_val_re = Regex::new("^NULL::")?;
-        if next_val_re.captures(&def).is_some() {
+        let function_call_re = Regex::new("^[A-Za-z_][A-Za-z0-9_.]*\\(.*\\)$")?;
+        if let Some(matches) = next_val_re.captures(&def) {
+            // Only the sequence owned by the column (ie: created by a serial type) means autoincrement.
+            let serial_sequence = table_column.get("serial_sequence")?;
+            let unqualified =
+                |name: &str| name.rsplit('.').next().unwrap_or_default().replace('"', "");
+            if !serial_sequence.is_null()
+                && unqualified(matches.get(1).unwrap().as_str())
+                    == unqualified(&serial_sequence.to_string())
+            {
+                autoincrement = true;
+            } else {
+                default_expression = Some(normalize_default_expression(&def)?);
+            }
+
             col_default = Value::NULL;
-            autoincrement = true;
         } else if let Some(matches) = default_val_re.captures(&def) {
-            col_default = matches.get(1).unwrap().as_str().into();
+            let value = matches.get(1).unwrap().as_str();
+            col_default = if def.starts_with('\'') {
+                value.replace("''", "'")
+            } else {
+                value.to_string()
+            }
+            .into();
         } else if null_val_re.is_match(&def) {
             col_default = Value::NULL;
+        } else if function_call_re.is_match(&def) {
+            default_expression = Some(normalize_default_expression(&def)?);
+            col_default = Value::NULL;
         }
     }
 
@@ -1094,7 +1609,12 @@ pub fn get_portable_table_column_definition(
         complete_type = table_column.get("domain_complete_type")?.to_string();
     }
 
-    let ty = platform.get_type_mapping(&db_type)?;
+    let composite = table_column.get("composite")?.to_string() == "true";
+    let ty = if composite {
+        COMPOSITE.into_type()?.type_id()
+    } else {
+        platform.get_type_mapping(&db_type)?
+    };
     let comment = match table_column.get("comment")? {
         Value::NULL => None,
         v => Some(v.to_string()),
@@ -1120,10 +1640,6 @@ pub fn get_portable_table_column_definition(
         }
 
         "text" | "_varchar" | "varchar" => {
-            col_default = match col_default {
-                Value::NULL => col_default,
-                _ => Value::String(col_default.to_string().replace("''", "'")),
-            };
             fixed = Some(false);
         }
 
@@ -1131,6 +1647,16 @@ pub fn get_portable_table_column_definition(
             fixed = Some(false);
         }
 
+        "int4range" | "tstzrange" => {
+            if let Value::String(default) = &col_default {
+                if let Some(matches) = Regex::new("^'(.*)'::")?.captures(default) {
+                    col_default = Value::String(matches.get(1).unwrap().as_str().to_string());
+                }
+            }
+
+            col_default = ty.convert_to_value(&col_default, this.get_platform()?.as_dyn())?;
+        }
+
         "char" | "bpchar" => {
             fixed = Some(true);
         }
@@ -1150,6 +1676,8 @@ pub fn get_portable_table_column_definition(
             jsonb = Some(true);
         }
 
+        _ if composite => {}
+
         _ => {
             if !col_default.is_null() {
                 if let Some(matches) =
@@ -1172,6 +1700,21 @@ pub fn get_portable_table_column_definition(
     column.set_fixed(fixed);
     column.set_jsonb(jsonb);
     column.set_autoincrement(autoincrement);
+    column.set_identity(identity);
+    if let Some(expression) = default_expression {
+        column.set_default_expression(expression);
+    }
+
+    if composite {
+        let attributes = match table_column.get("composite_attributes")? {
+            Value::String(attributes) => {
+                get_portable_composite_attributes(platform.as_dyn(), attributes)?
+            }
+            _ => vec![],
+        };
+
+        column.set_composite_type(CompositeTypeDefinition::new(complete_type, attributes));
+    }
 
     let comment = comment.unwrap_or_default();
     if !comment.is_empty() {
@@ -1183,9 +1726,62 @@ pub fn get_portable_table_column_definition(
         column.set_collation(c);
     }
 
+    if let Value::String(compression) = table_column.get("compression")? {
+        column.set_compression(compression);
+    }
+
+    if let Value::String(storage) = table_column.get("storage")? {
+        column.set_storage(storage);
+    }
+
+    if let Value::Int(target) = table_column.get("statistics_target")? {
+        column.set_statistics_target(usize::try_from(*target)?);
+    }
+
     Ok(column)
 }
 
+/// Builds the attribute list of a composite type from its JSON description.
+/// Nested composite attributes are reported without their own attribute list.
+fn get_portable_composite_attributes(
+    platform: &dyn DatabasePlatform,
+    attributes: &str,
+) -> Result<Vec<Column>> {
+    let attributes: Vec<serde_json::Value> = serde_json::from_str(attributes)?;
+    let length_re = Regex::new("\\(([0-9]+)\\)$")?;
+    let precision_re = Regex::new("\\(([0-9]+),([0-9]+)\\)$")?;
+
+    attributes
+        .iter()
+        .map(|attribute| -> Result<Column> {
+            let field = |name: &str| {
+                attribute
+                    .get(name)
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string()
+            };
+
+            let complete_type = field("complete_type");
+            let ty = if attribute.get("composite").and_then(|v| v.as_bool()) == Some(true) {
+                COMPOSITE.into_type()?
+            } else {
+                platform.get_type_mapping(&field("type"))?.into_type()?
+            };
+
+            let mut column = Column::new(field("name"), ty);
+            if let Some(matches) = precision_re.captures(&complete_type) {
+                column.set_precision(matches.get(1).unwrap().as_str().parse::<usize>()?);
+                column.set_scale(matches.get(2).unwrap().as_str().parse::<usize>()?);
+            } else if let Some(matches) = length_re.captures(&complete_type) {
+                column.set_length(matches.get(1).unwrap().as_str().parse::<usize>()?);
+            }
+
+            Ok(column)
+        })
+        .collect()
+}
+
 pub fn get_portable_sequence_definition(row: &Row) -> Result<Sequence> {
     let sequence_name = if row.get("schemaname")? != &Value::from("public") {
         format!("{}.{}", row.get("schemaname")?, row.get("relname")?)
@@ -1201,15 +1797,30 @@ pub fn get_portable_sequence_definition(row: &Row) -> Result<Sequence> {
     }
     .parse()?;
 
-    let min_value = row.get("min_value")?;
-    let min_value: usize = if min_value.is_null() {
-        "1".to_string()
-    } else {
-        min_value.to_string()
-    }
-    .parse()?;
+    let start_value: i64 = row.get("start_value")?.to_string().parse()?;
+    let min_value: i64 = row.get("min_value")?.to_string().parse()?;
+    let max_value: i64 = row.get("max_value")?.to_string().parse()?;
+    let cache_size: usize = row.get("cache_size")?.to_string().parse()?;
+
+    // Default bounds (or the minimum value set to the start value on creation) are not reported,
+    // to be compared equal to a sequence without explicit bounds.
+    let type_max_value = match row.get("data_type")?.to_string().as_str() {
+        "smallint" => i16::MAX as i64,
+        "integer" => i32::MAX as i64,
+        _ => i64::MAX,
+    };
+
+    let mut sequence = Sequence::new(
+        sequence_name,
+        increment_by,
+        start_value,
+        (cache_size > 1).then_some(cache_size),
+    );
+    sequence.set_min_value((min_value != start_value && min_value != 1).then_some(min_value));
+    sequence.set_max_value((max_value != type_max_value).then_some(max_value));
+    sequence.set_cycle(bool::from(row.get("cycle")?));
 
-    Ok(Sequence::new(sequence_name, increment_by, min_value, None))
+    Ok(sequence)
 }
 
 pub fn list_schema_names(this: &dyn SchemaManager) -> AsyncResult<Vec<Identifier>> {
@@ -1273,7 +1884,17 @@ pub async fn fetch_table_options_by_table(
     let sql = format!(
         r#"
 SELECT c.relname,
-       obj_description(c.oid, 'pg_class') AS comment
+       obj_description(c.oid, 'pg_class') AS comment,
+       CASE c.relreplident
+           WHEN 'f' THEN 'full'
+           WHEN 'n' THEN 'nothing'
+           WHEN 'i' THEN 'index'
+           ELSE 'default'
+       END AS replica_identity,
+       (SELECT ic.relname
+        FROM pg_index i
+             INNER JOIN pg_class ic ON ic.oid = i.indexrelid
+        WHERE i.indrelid = c.oid AND i.indisreplident) AS replica_identity_index
 FROM pg_class c
      INNER JOIN pg_namespace n
          ON n.oid = c.relnamespace
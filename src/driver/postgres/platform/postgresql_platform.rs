@@ -1,15 +1,20 @@
 use super::postgresql;
 use crate::driver::postgres::platform::PostgreSQLSchemaManager;
-use crate::platform::{platform_debug, DatabasePlatform, DateIntervalUnit, KeywordList};
+use crate::platform::{
+    platform_debug, DatabasePlatform, DateIntervalUnit, KeywordList, UpsertReporting,
+};
+use crate::query::{Expression, MergeBuilder};
 use crate::r#type::{
-    BigintType, BlobType, BooleanType, DateTimeType, DateTimeTzType, DateType, DecimalType,
-    FloatType, GuidType, IntegerType, JsonType, StringType, TextType, TimeType,
+    BigintType, BlobType, BooleanType, DateTimeType, DateTimeTzRangeType, DateTimeTzType, DateType,
+    DecimalType, DurationType, FloatType, GuidType, IntegerRangeType, IntegerType, JsonType,
+    StringType, TextType, TimeType,
 };
 use crate::schema::{ColumnData, SchemaManager};
 use crate::{Connection, Error, EventDispatcher, Result, TransactionIsolationLevel, Value};
 use dashmap::DashMap;
 use std::any::TypeId;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub trait AbstractPostgreSQLPlatform: DatabasePlatform {}
 
@@ -17,13 +22,22 @@ platform_debug!(PostgreSQLPlatform);
 pub struct PostgreSQLPlatform {
     ev: Arc<EventDispatcher>,
     type_mappings: DashMap<String, TypeId>,
+    server_version_num: u32,
 }
 
 impl PostgreSQLPlatform {
+    /// Creates a platform supporting all the features of the latest server versions.
     pub fn new(ev: Arc<EventDispatcher>) -> Self {
+        Self::for_server_version(u32::MAX, ev)
+    }
+
+    /// Creates a platform for the server with the given version number
+    /// (as reported by `server_version_num`, ie: 150004 for 15.4).
+    pub fn for_server_version(server_version_num: u32, ev: Arc<EventDispatcher>) -> Self {
         let pl = Self {
             ev,
             type_mappings: DashMap::default(),
+            server_version_num,
         };
 
         pl.initialize_all_type_mappings()
@@ -56,6 +70,18 @@ impl DatabasePlatform for PostgreSQLPlatform {
         postgresql::get_regex_expression()
     }
 
+    fn get_case_insensitive_regexp_expression(&self) -> Result<String> {
+        postgresql::get_case_insensitive_regex_expression()
+    }
+
+    fn get_json_contains_expression(&self, column: &str, value: &str) -> Result<String> {
+        postgresql::get_json_contains_expression(column, value)
+    }
+
+    fn get_json_array_length_expression(&self, column: &str) -> Result<String> {
+        postgresql::get_json_array_length_expression(column)
+    }
+
     fn get_locate_expression(
         &self,
         str: &str,
@@ -95,14 +121,58 @@ impl DatabasePlatform for PostgreSQLPlatform {
         true
     }
 
+    fn supports_generated_identity_columns(&self) -> bool {
+        true
+    }
+
     fn supports_partial_indexes(&self) -> bool {
         true
     }
 
+    fn supports_insert_returning(&self) -> bool {
+        true
+    }
+
+    fn supports_nulls_not_distinct(&self) -> bool {
+        self.server_version_num >= 150000
+    }
+
+    fn supports_column_compression(&self) -> bool {
+        self.server_version_num >= 140000
+    }
+
     fn supports_comment_on_statement(&self) -> bool {
         true
     }
 
+    fn supports_table_owner(&self) -> bool {
+        true
+    }
+
+    fn supports_replica_identity(&self) -> bool {
+        true
+    }
+
+    fn supports_grants(&self) -> bool {
+        true
+    }
+
+    fn supports_ordered_set_aggregates(&self) -> bool {
+        true
+    }
+
+    fn supports_named_windows(&self) -> bool {
+        true
+    }
+
+    fn supports_overlaps_predicate(&self) -> bool {
+        true
+    }
+
+    fn supports_range_types(&self) -> bool {
+        true
+    }
+
     fn has_native_guid_type(&self) -> bool {
         true
     }
@@ -194,6 +264,10 @@ impl DatabasePlatform for PostgreSQLPlatform {
         postgresql::get_read_lock_sql()
     }
 
+    fn get_write_lock_of_sql(&self, tables: &[&str]) -> Result<String> {
+        postgresql::get_write_lock_of_sql(tables)
+    }
+
     fn _initialize_type_mappings(&self) {
         self._add_type_mapping("bigint", TypeId::of::<BigintType>());
         self._add_type_mapping("bigserial", TypeId::of::<BigintType>());
@@ -214,9 +288,10 @@ impl DatabasePlatform for PostgreSQLPlatform {
         self._add_type_mapping("int", TypeId::of::<IntegerType>());
         self._add_type_mapping("int2", TypeId::of::<IntegerType>());
         self._add_type_mapping("int4", TypeId::of::<IntegerType>());
+        self._add_type_mapping("int4range", TypeId::of::<IntegerRangeType>());
         self._add_type_mapping("int8", TypeId::of::<IntegerType>());
         self._add_type_mapping("integer", TypeId::of::<IntegerType>());
-        self._add_type_mapping("interval", TypeId::of::<StringType>());
+        self._add_type_mapping("interval", TypeId::of::<DurationType>());
         self._add_type_mapping("json", TypeId::of::<JsonType>());
         self._add_type_mapping("jsonb", TypeId::of::<JsonType>());
         self._add_type_mapping("money", TypeId::of::<DecimalType>());
@@ -231,6 +306,7 @@ impl DatabasePlatform for PostgreSQLPlatform {
         self._add_type_mapping("timestamp", TypeId::of::<DateTimeType>());
         self._add_type_mapping("timestamptz", TypeId::of::<DateTimeTzType>());
         self._add_type_mapping("timetz", TypeId::of::<TimeType>());
+        self._add_type_mapping("tstzrange", TypeId::of::<DateTimeTzRangeType>());
         self._add_type_mapping("tsvector", TypeId::of::<TextType>());
         self._add_type_mapping("uuid", TypeId::of::<GuidType>());
         self._add_type_mapping("varchar", TypeId::of::<StringType>());
@@ -242,6 +318,22 @@ impl DatabasePlatform for PostgreSQLPlatform {
         true
     }
 
+    fn has_native_interval_type(&self) -> bool {
+        true
+    }
+
+    fn get_duration_type_declaration_sql(&self, _: &ColumnData) -> Result<String> {
+        postgresql::get_duration_type_declaration_sql()
+    }
+
+    fn get_integer_range_type_declaration_sql(&self, _: &ColumnData) -> Result<String> {
+        postgresql::get_integer_range_type_declaration_sql()
+    }
+
+    fn get_date_time_tz_range_type_declaration_sql(&self, _: &ColumnData) -> Result<String> {
+        postgresql::get_date_time_tz_range_type_declaration_sql()
+    }
+
     fn get_blob_type_declaration_sql(&self, _: &ColumnData) -> Result<String> {
         postgresql::get_blob_type_declaration_sql()
     }
@@ -258,6 +350,10 @@ impl DatabasePlatform for PostgreSQLPlatform {
         postgresql::get_json_type_declaration_sql(column)
     }
 
+    fn get_composite_type_declaration_sql(&self, column: &ColumnData) -> Result<String> {
+        postgresql::get_composite_type_declaration_sql(self, column)
+    }
+
     fn _add_type_mapping(&self, db_type: &str, type_id: TypeId) {
         self.type_mappings.insert(db_type.to_string(), type_id);
     }
@@ -274,6 +370,58 @@ impl DatabasePlatform for PostgreSQLPlatform {
         KeywordList::postgres_keywords()
     }
 
+    fn get_merge_sql(&self, merge: &MergeBuilder) -> Result<String> {
+        postgresql::get_merge_sql(self, merge)
+    }
+
+    fn get_upsert_sql(&self, merge: &MergeBuilder) -> Result<String> {
+        postgresql::get_upsert_sql(self, merge)
+    }
+
+    fn get_values_table_sql(
+        &self,
+        rows: &[Vec<Value>],
+        alias: &str,
+        columns: &[&str],
+    ) -> Result<Expression> {
+        postgresql::get_values_table_sql(rows, alias, columns)
+    }
+
+    fn get_upsert_reporting(&self) -> UpsertReporting {
+        UpsertReporting::ReturningInserted
+    }
+
+    fn get_replication_position_sql(&self) -> Option<String> {
+        Some(postgresql::get_replication_position_sql())
+    }
+
+    fn get_estimated_cost_sql(&self, sql: &str) -> Option<String> {
+        Some(format!("EXPLAIN (FORMAT JSON) {}", sql))
+    }
+
+    fn get_last_insert_id_sql(&self) -> Result<String> {
+        Ok("SELECT lastval()".to_string())
+    }
+
+    fn get_advisory_lock_sql(
+        &self,
+        key: i64,
+        _: &str,
+        _: Option<&str>,
+        _: Option<Duration>,
+        wait: bool,
+    ) -> Result<String> {
+        Ok(postgresql::get_advisory_lock_sql(key, wait))
+    }
+
+    fn get_advisory_unlock_sql(&self, key: i64, _: &str, _: Option<&str>) -> Result<String> {
+        Ok(postgresql::get_advisory_unlock_sql(key))
+    }
+
+    fn get_feature_probe_sql(&self, feature: &str) -> Result<String> {
+        postgresql::get_feature_probe_sql(self, feature)
+    }
+
     fn create_schema_manager<'a>(&self, connection: &'a Connection) -> Box<dyn SchemaManager + 'a> {
         Box::new(PostgreSQLSchemaManager::new(connection))
     }
@@ -282,7 +430,7 @@ impl DatabasePlatform for PostgreSQLPlatform {
 #[cfg(test)]
 mod tests {
     use crate::driver::postgres::PostgreSQLPlatform;
-    use crate::platform::DatabasePlatform;
+    use crate::platform::{DatabasePlatform, TrimMode};
     use crate::r#type::{BINARY, GUID, JSON};
     use crate::schema::Column;
     use crate::tests::common_platform_tests;
@@ -310,6 +458,54 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn column_compression_depends_on_version() -> Result<()> {
+        use crate::r#type::TEXT;
+
+        let column = Column::builder("payload", TEXT)?
+            .set_compression("lz4")
+            .get_column();
+
+        let platform =
+            PostgreSQLPlatform::for_server_version(130011, Arc::new(EventDispatcher::new()));
+        assert!(!platform.supports_column_compression());
+        assert_eq!(column.generate_column_data(&platform).compression, None);
+
+        let platform =
+            PostgreSQLPlatform::for_server_version(140000, Arc::new(EventDispatcher::new()));
+        assert!(platform.supports_column_compression());
+        assert_eq!(
+            column
+                .generate_column_data(&platform)
+                .compression
+                .as_deref(),
+            Some("lz4")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_trim_expression_with_chars_set() -> Result<()> {
+        let platform = create_postgresql_platform();
+        let chars = Some("'xy'".to_string());
+
+        assert_eq!(
+            platform.get_trim_expression("col", TrimMode::Leading, chars.clone())?,
+            "TRIM(LEADING 'xy' FROM col)"
+        );
+        assert_eq!(
+            platform.get_trim_expression("col", TrimMode::Trailing, chars.clone())?,
+            "TRIM(TRAILING 'xy' FROM col)"
+        );
+        assert_eq!(
+            platform.get_trim_expression("col", TrimMode::Both, chars)?,
+            "TRIM(BOTH 'xy' FROM col)"
+        );
+
+        Ok(())
+    }
+
     #[test]
     pub fn quote_single_identifier() {
         let platform = create_postgresql_platform();
@@ -404,4 +600,280 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    pub fn translates_merge_to_on_conflict() -> Result<()> {
+        use crate::query::MergeBuilder;
+        let platform = create_postgresql_platform();
+        let merge = MergeBuilder::new("stock")
+            .using("id", 1)
+            .using("name", "foo")
+            .using("qty", 10)
+            .on(&["id"])
+            .when_matched_update_if("stock.qty < src.qty", &["qty"])
+            .when_not_matched_insert();
+
+        assert_eq!(
+            merge.get_sql(&platform)?,
+            r#"INSERT INTO "stock" ("id", "name", "qty") VALUES (?, ?, ?) ON CONFLICT ("id") DO UPDATE SET "qty" = EXCLUDED."qty" WHERE stock.qty < EXCLUDED.qty"#
+        );
+
+        let merge = MergeBuilder::new("stock")
+            .using("id", 1)
+            .using("name", "foo")
+            .on(&["id"])
+            .when_not_matched_insert();
+
+        assert_eq!(
+            merge.get_sql(&platform)?,
+            r#"INSERT INTO "stock" ("id", "name") VALUES (?, ?) ON CONFLICT ("id") DO NOTHING"#
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn cannot_translate_merge_with_delete_branch() {
+        use crate::error::ErrorKind;
+        use crate::query::MergeBuilder;
+        let platform = create_postgresql_platform();
+        let merge = MergeBuilder::new("stock")
+            .using("id", 1)
+            .on(&["id"])
+            .when_matched_delete()
+            .when_not_matched_insert();
+
+        let err = merge.get_sql(&platform).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PlatformFeatureUnsupported);
+    }
+
+    #[test]
+    pub fn generates_update_from_sql() -> Result<()> {
+        use crate::query::QueryBuilder;
+        use crate::Value;
+        let platform = create_postgresql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.update("orders")
+            .set("status", "shipped")
+            .update_join("shipments", Some("s"), "s.order_id = orders.id")
+            .r#where(expr.eq("s.carrier", expr.param("ACME")));
+
+        assert_eq!(
+            qb.get_sql()?,
+            "UPDATE orders SET status = ? FROM shipments s WHERE (s.order_id = orders.id) AND (s.carrier = ?)"
+        );
+        assert_eq!(
+            qb.get_parameters()?,
+            vec![Value::from("shipped"), Value::from("ACME")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_row_lock_clauses() -> Result<()> {
+        use crate::query::QueryBuilder;
+        let platform = create_postgresql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.select(["o.id"])
+            .from("orders", Some("o"))
+            .inner_join("customers", Some("c"), "c.id = o.customer_id")
+            .set_max_results(Some(1))
+            .for_share();
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT o.id FROM orders o INNER JOIN customers c ON c.id = o.customer_id LIMIT 1 FOR SHARE"
+        );
+
+        qb.for_update_of(&["o"]);
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT o.id FROM orders o INNER JOIN customers c ON c.id = o.customer_id LIMIT 1 FOR UPDATE OF o"
+        );
+
+        qb.for_update();
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT o.id FROM orders o INNER JOIN customers c ON c.id = o.customer_id LIMIT 1 FOR UPDATE"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_values_table_sql() -> Result<()> {
+        use crate::query::QueryBuilder;
+        use crate::Value;
+        let platform = create_postgresql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.select(["v.id", "v.name"]).from_values(
+            [
+                [Value::NULL, Value::from("foo")],
+                [Value::from(2), Value::from("bar")],
+            ],
+            "v",
+            &["id", "name"],
+        )?;
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT v.id, v.name FROM (VALUES (CAST(? AS BIGINT), ?), (?, ?)) v (id, name)"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_named_window_sql() -> Result<()> {
+        use crate::query::QueryBuilder;
+        let platform = create_postgresql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select([
+            expr.over("SUM(amount)", "w"),
+            expr.over("row_number()", "w"),
+        ])
+        .from("orders", None)
+        .r#where(expr.gt("amount", expr.param(10)))
+        .window("w", "PARTITION BY customer_id ORDER BY created_at")?
+        .order_by("customer_id", None);
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT SUM(amount) OVER w, row_number() OVER w FROM orders WHERE amount > ? WINDOW w AS (PARTITION BY customer_id ORDER BY created_at) ORDER BY customer_id"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_regex_conditions() -> Result<()> {
+        use crate::query::QueryBuilder;
+        let platform = create_postgresql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.select(["u.id"])
+            .from("users", Some("u"))
+            .where_regex("u.name", "^a", false)?
+            .where_regex("u.email", "@example\\.com$", true)?;
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT u.id FROM users u WHERE (u.name ~ ?) AND (u.email ~* ?)"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_percentile_expressions() -> Result<()> {
+        use crate::error::ErrorKind;
+        use crate::query::QueryBuilder;
+        let platform = create_postgresql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select([
+            expr.percentile_cont(0.5, "o.amount")?,
+            expr.percentile_disc(0.95, "o.amount DESC")?,
+        ])
+        .from("orders", Some("o"));
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT percentile_cont(0.5) WITHIN GROUP (ORDER BY o.amount), percentile_disc(0.95) WITHIN GROUP (ORDER BY o.amount DESC) FROM orders o"
+        );
+        assert_eq!(
+            expr.percentile_cont(1.5, "o.amount").err().unwrap().kind(),
+            ErrorKind::QueryBuilderInvalid
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn json_expressions() -> Result<()> {
+        use crate::query::QueryBuilder;
+        use crate::Value;
+
+        let platform = create_postgresql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select([expr.json_array_length("tags")?])
+            .from("posts", None)
+            .r#where(expr.json_contains("tags", expr.param("[\"rust\"]"))?);
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT JSONB_ARRAY_LENGTH(CAST(tags AS JSONB)) FROM posts WHERE CAST(tags AS JSONB) @> CAST(? AS JSONB)"
+        );
+        assert_eq!(qb.get_parameters()?, vec![Value::from("[\"rust\"]")]);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_overlaps_expression() -> Result<()> {
+        use crate::query::QueryBuilder;
+        use crate::Value;
+
+        let platform = create_postgresql_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select(["b.id"])
+            .from("bookings", Some("b"))
+            .r#where(expr.overlaps(
+                ("b.starts_at", "b.ends_at"),
+                (
+                    expr.param("2024-01-01 10:00:00"),
+                    expr.param("2024-01-01 11:00:00"),
+                ),
+            ));
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT b.id FROM bookings b WHERE (b.starts_at, b.ends_at) OVERLAPS (?, ?)"
+        );
+        assert_eq!(
+            qb.get_parameters()?,
+            vec![
+                Value::from("2024-01-01 10:00:00"),
+                Value::from("2024-01-01 11:00:00")
+            ]
+        );
+
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select(["b.id"])
+            .from("bookings", Some("b"))
+            .r#where(expr.range_contains("b.slot", expr.param(5))?)
+            .and_where(expr.range_contained_by("b.slot", expr.param("[0,100)"))?)
+            .and_where(expr.range_overlaps("b.slot", expr.param("[3,7)"))?);
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT b.id FROM bookings b WHERE ((b.slot @> ?) AND (b.slot <@ ?)) AND (b.slot && ?)"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn normalizes_foreign_key_referential_actions() {
+        use crate::schema::ForeignKeyReferentialAction;
+
+        let platform = create_postgresql_platform();
+
+        // NO ACTION checks can be deferred, while RESTRICT ones cannot.
+        assert_eq!(
+            platform.normalize_foreign_key_referential_action(Some(
+                ForeignKeyReferentialAction::Restrict
+            )),
+            ForeignKeyReferentialAction::Restrict
+        );
+        assert_eq!(
+            platform.normalize_foreign_key_referential_action(None),
+            ForeignKeyReferentialAction::NoAction
+        );
+    }
 }
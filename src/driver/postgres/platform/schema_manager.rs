@@ -1,7 +1,8 @@
 use super::postgresql;
 use crate::schema::{
     string_from_value, Column, ColumnData, Comparator, ForeignKeyConstraint, GenericComparator,
-    Identifier, Index, IndexList, IntoIdentifier, SchemaManager, Sequence, TableDiff, TableOptions,
+    Identifier, Index, IndexList, IntoIdentifier, ReplicaIdentity, SchemaManager, Sequence,
+    TableDiff, TableOptions, View,
 };
 use crate::{params, AsyncResult, Connection, Result, Row};
 use std::collections::HashMap;
@@ -152,6 +153,11 @@ impl<'a> SchemaManager for PostgreSQLSchemaManager<'a> {
         postgresql::get_list_table_indexes_sql(self.as_dyn(), table)
     }
 
+    #[inline]
+    fn get_index_statistics_sql(&self, table: &str, _: &str) -> Result<String> {
+        postgresql::get_index_statistics_sql(self.as_dyn(), table)
+    }
+
     #[inline]
     fn get_list_table_foreign_keys_sql(&self, table: &str, _: &str) -> Result<String> {
         postgresql::get_list_table_foreign_keys_sql(self.as_dyn(), table)
@@ -162,6 +168,23 @@ impl<'a> SchemaManager for PostgreSQLSchemaManager<'a> {
         postgresql::get_list_table_constraints_sql(self.as_dyn(), table)
     }
 
+    #[inline]
+    fn get_alter_table_owner_sql(&self, table_name: &Identifier, owner: &str) -> Result<String> {
+        postgresql::get_alter_table_owner_sql(self.get_platform()?.as_dyn(), table_name, owner)
+    }
+
+    fn get_alter_table_replica_identity_sql(
+        &self,
+        table_name: &Identifier,
+        replica_identity: &ReplicaIdentity,
+    ) -> Result<String> {
+        postgresql::get_alter_table_replica_identity_sql(
+            self.get_platform()?.as_dyn(),
+            table_name,
+            replica_identity,
+        )
+    }
+
     #[inline]
     fn get_comment_on_column_sql(
         &self,
@@ -198,6 +221,14 @@ impl<'a> SchemaManager for PostgreSQLSchemaManager<'a> {
         postgresql::get_truncate_table_sql(self, table_name, cascade)
     }
 
+    fn get_truncate_tables_sql(&self, tables: &[&str], cascade: bool) -> Result<Vec<String>> {
+        postgresql::get_truncate_tables_sql(self, tables, cascade)
+    }
+
+    fn get_optimize_table_sql(&self, table_name: &dyn IntoIdentifier) -> Result<Vec<String>> {
+        postgresql::get_optimize_table_sql(self.get_platform()?.as_dyn(), table_name)
+    }
+
     fn get_drop_foreign_key_sql(
         &self,
         foreign_key: &dyn IntoIdentifier,
@@ -220,6 +251,41 @@ impl<'a> SchemaManager for PostgreSQLSchemaManager<'a> {
         postgresql::get_list_views_sql()
     }
 
+    #[inline]
+    fn get_table_kind_sql(&self, table: &str, _: &str) -> Result<String> {
+        postgresql::get_table_kind_sql(self.as_dyn(), table)
+    }
+
+    #[inline]
+    fn get_table_timestamps_sql(&self, table: &str, _: &str) -> Result<String> {
+        postgresql::get_table_timestamps_sql(self.as_dyn(), table)
+    }
+
+    #[inline]
+    fn get_create_materialized_view_sql(&self, view: &View) -> Result<String> {
+        postgresql::get_create_materialized_view_sql(self.get_platform()?.as_dyn(), view)
+    }
+
+    #[inline]
+    fn get_refresh_materialized_view_sql(&self, name: &Identifier) -> Result<String> {
+        postgresql::get_refresh_materialized_view_sql(self.get_platform()?.as_dyn(), name)
+    }
+
+    #[inline]
+    fn get_create_table_as_sql(
+        &self,
+        name: &Identifier,
+        select_sql: &str,
+        with_data: bool,
+    ) -> Result<String> {
+        postgresql::get_create_table_as_sql(
+            self.get_platform()?.as_dyn(),
+            name,
+            select_sql,
+            with_data,
+        )
+    }
+
     #[inline]
     fn get_sequence_next_val_sql(&self, sequence: &str) -> Result<String> {
         postgresql::get_sequence_next_val_sql(sequence)
@@ -239,6 +305,16 @@ impl<'a> SchemaManager for PostgreSQLSchemaManager<'a> {
         postgresql::get_column_collation_declaration_sql(self.get_platform()?.as_dyn(), collation)
     }
 
+    #[inline]
+    fn get_column_compression_declaration_sql(&self, compression: &str) -> Result<String> {
+        postgresql::get_column_compression_declaration_sql(compression)
+    }
+
+    #[inline]
+    fn columns_equal(&self, column1: &Column, column2: &Column) -> Result<bool> {
+        postgresql::columns_equal(self.as_dyn(), column1, column2)
+    }
+
     #[inline]
     fn get_advanced_foreign_key_options_sql(
         &self,
@@ -295,12 +371,14 @@ impl<'a> SchemaManager for PostgreSQLSchemaManager<'a> {
 
 #[cfg(test)]
 mod tests {
+    use crate::error::ErrorKind;
+    use crate::params;
     use crate::platform::CreateFlags;
-    use crate::r#type::{IntoType, BOOLEAN, INTEGER, SIMPLE_ARRAY, STRING};
+    use crate::r#type::{IntoType, BOOLEAN, INTEGER, SIMPLE_ARRAY, STRING, TEXT};
     use crate::result::Result;
     use crate::schema::{
-        Asset, ChangedProperty, Column, ColumnDiff, ForeignKeyConstraint, Index, Table, TableDiff,
-        UniqueConstraint,
+        Asset, ChangedProperty, Column, ColumnDiff, ForeignKeyConstraint, Index, IntoIdentifier,
+        ReplicaIdentity, Schema, Sequence, Table, TableDiff, TableGrant, UniqueConstraint,
     };
     use crate::tests::create_connection;
     use serial_test::serial;
@@ -560,6 +638,83 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial]
+    pub async fn create_table_owner_and_grants() -> Result<()> {
+        let mut table = Table::new("test");
+        table.add_column(Column::new("id", INTEGER.into_type()?));
+        table.set_primary_key(&["id"], None)?;
+        table.set_owner("app_owner");
+        table.add_grant(TableGrant::new(&["select"], "PUBLIC"));
+        table.add_grant(TableGrant::new(
+            &["SELECT", "INSERT", "UPDATE"],
+            "app_writer",
+        ));
+
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        let sql = schema_manager.get_create_table_sql(&table, None)?;
+        assert_eq!(
+            sql,
+            &[
+                "CREATE TABLE test (id INT NOT NULL, PRIMARY KEY(id))",
+                "ALTER TABLE test OWNER TO \"app_owner\"",
+                "GRANT SELECT ON test TO PUBLIC",
+                "GRANT SELECT, INSERT, UPDATE ON test TO \"app_writer\"",
+            ]
+        );
+
+        let mut table = Table::new("test");
+        table.add_column(Column::new("id", INTEGER.into_type()?));
+        table.set_owner("evil\"; DROP TABLE test; --");
+        table.add_grant(TableGrant::new::<&str, _>(&[], "app_writer"));
+        assert_eq!(
+            schema_manager
+                .get_alter_table_owner_sql(table.get_table_name(), "evil\"; DROP TABLE test; --")?,
+            "ALTER TABLE test OWNER TO \"evil; DROP TABLE test; --\""
+        );
+        assert_eq!(
+            schema_manager
+                .get_create_table_sql(&table, None)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::GrantDefinitionInvalid
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn generates_replica_identity_sql() -> Result<()> {
+        let mut table = Table::new("test");
+        table.add_column(Column::new("id", INTEGER.into_type()?));
+        table.set_primary_key(&["id"], None)?;
+        table.set_replica_identity(ReplicaIdentity::Full);
+
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        let sql = schema_manager.get_create_table_sql(&table, None)?;
+        assert_eq!(
+            sql,
+            &[
+                "CREATE TABLE test (id INT NOT NULL, PRIMARY KEY(id))",
+                "ALTER TABLE test REPLICA IDENTITY FULL",
+            ]
+        );
+
+        let mut table_diff = TableDiff::new("test", None);
+        table_diff.changed_replica_identity =
+            Some(ReplicaIdentity::UsingIndex("uniq_id".to_string()));
+        let sql = schema_manager.get_alter_table_sql(&mut table_diff)?;
+        assert_eq!(
+            sql,
+            &["ALTER TABLE test REPLICA IDENTITY USING INDEX uniq_id"]
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial]
     pub async fn alter_table_column_comments() -> Result<()> {
@@ -817,6 +972,130 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial]
+    pub async fn get_sequence_sql() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        let mut sequence = Sequence::new("foo_seq", 5, 10, None);
+        assert_eq!(
+            schema_manager.get_create_sequence_sql(&sequence)?,
+            "CREATE SEQUENCE foo_seq INCREMENT BY 5 MINVALUE 10 START 10"
+        );
+
+        sequence.set_cache(Some(20));
+        sequence.set_cycle(true);
+        sequence.set_min_value(Some(1));
+        sequence.set_max_value(Some(1000));
+        assert_eq!(
+            schema_manager.get_create_sequence_sql(&sequence)?,
+            "CREATE SEQUENCE foo_seq INCREMENT BY 5 MINVALUE 1 MAXVALUE 1000 START 10 CACHE 20 CYCLE"
+        );
+        assert_eq!(
+            schema_manager.get_alter_sequence_sql(&sequence)?,
+            "ALTER SEQUENCE foo_seq INCREMENT BY 5 MINVALUE 1 MAXVALUE 1000 CACHE 20 CYCLE"
+        );
+
+        let sequence = Sequence::new("foo_seq", 5, 10, None);
+        assert_eq!(
+            schema_manager.get_alter_sequence_sql(&sequence)?,
+            "ALTER SEQUENCE foo_seq INCREMENT BY 5 NO MINVALUE NO MAXVALUE CACHE 1 NO CYCLE"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn sequence_bounds_are_introspected_and_reset() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        let _ = schema_manager.drop_sequence(&"sequence_bounds_seq").await;
+        connection
+            .execute_statement(
+                "CREATE SEQUENCE sequence_bounds_seq AS integer MINVALUE -10 START -5",
+                params!(),
+            )
+            .await?;
+
+        let online = schema_manager
+            .list_sequences()
+            .await?
+            .into_iter()
+            .find(|s| s.get_name() == "sequence_bounds_seq")
+            .expect("sequence not found");
+        assert_eq!(online.get_initial_value(), -5);
+        assert_eq!(online.get_min_value(), Some(-10));
+        assert_eq!(online.get_max_value(), None);
+
+        // Removing the minimum value must converge.
+        let mut sequence = Sequence::new("sequence_bounds_seq", 1, -5, None);
+        sequence.set_min_value(Some(-10));
+        schema_manager.drop_sequence(&"sequence_bounds_seq").await?;
+        sequence.set_initial_value(5);
+        schema_manager.create_sequence(&sequence).await?;
+
+        sequence.set_min_value(None);
+        connection
+            .execute_statement(schema_manager.get_alter_sequence_sql(&sequence)?, params!())
+            .await?;
+
+        let online = schema_manager
+            .list_sequences()
+            .await?
+            .into_iter()
+            .find(|s| s.get_name() == "sequence_bounds_seq")
+            .expect("sequence not found");
+        assert_eq!(online.get_min_value(), None);
+
+        let comparator = schema_manager.create_comparator();
+        let from_schema = Schema::new(vec![], vec![], vec![online], vec![], Default::default());
+        let to_schema = Schema::new(vec![], vec![], vec![sequence], vec![], Default::default());
+        let diff = comparator.compare_schemas(&from_schema, &to_schema)?;
+        assert!(diff.changed_sequences.is_empty());
+
+        schema_manager.drop_sequence(&"sequence_bounds_seq").await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn get_maintenance_sql() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        assert_eq!(
+            schema_manager.get_analyze_table_sql(&"select")?,
+            vec![r#"ANALYZE "select""#]
+        );
+        assert_eq!(
+            schema_manager.get_optimize_table_sql(&"foo")?,
+            vec!["VACUUM foo"]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn get_truncate_tables_sql() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        assert_eq!(
+            schema_manager.get_truncate_tables_sql(&["foo", "select"], false)?,
+            vec![r#"TRUNCATE foo, "select""#]
+        );
+        assert_eq!(
+            schema_manager.get_truncate_tables_sql(&["foo", "select"], true)?,
+            vec![r#"TRUNCATE foo, "select" CASCADE"#]
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial]
     pub async fn quotes_reserved_keyword_in_index_declaration_sql() -> Result<()> {
@@ -1153,6 +1432,99 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial]
+    pub async fn generates_serial_to_identity_conversion_sql() -> Result<()> {
+        let mut from_table = Table::new("mytable");
+        let mut column = Column::new("id", INTEGER.into_type()?);
+        column.set_autoincrement(true);
+        from_table.add_column(column);
+
+        let mut to_table = Table::new("mytable");
+        let mut column = Column::new("id", INTEGER.into_type()?);
+        column.set_autoincrement(true);
+        column.set_identity(true);
+        to_table.add_column(column);
+
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        let comparator = schema_manager.create_comparator();
+
+        let mut diff = comparator.diff_table(&from_table, &to_table)?;
+        assert!(diff.is_some());
+        assert_eq!(
+            schema_manager.get_alter_table_sql(diff.as_mut().unwrap())?,
+            &[
+                "ALTER TABLE mytable ALTER id DROP DEFAULT",
+                "DO $$ DECLARE seq text := pg_get_serial_sequence('mytable', 'id'); BEGIN IF seq IS NOT NULL THEN EXECUTE 'DROP SEQUENCE ' || seq; END IF; END $$",
+                "ALTER TABLE mytable ALTER id ADD GENERATED BY DEFAULT AS IDENTITY",
+                "SELECT setval(pg_get_serial_sequence('mytable', 'id'), COALESCE((SELECT MAX(id) FROM mytable), 0) + 1, false)",
+            ]
+        );
+
+        let mut diff = comparator.diff_table(&to_table, &from_table)?;
+        assert!(diff.is_some());
+        assert_eq!(
+            schema_manager.get_alter_table_sql(diff.as_mut().unwrap())?,
+            &[
+                "ALTER TABLE mytable ALTER id DROP IDENTITY IF EXISTS",
+                "CREATE SEQUENCE mytable_id_seq",
+                "SELECT setval('mytable_id_seq', (SELECT MAX(id) FROM mytable))",
+                "ALTER TABLE mytable ALTER id SET DEFAULT nextval('mytable_id_seq')",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn serial_to_identity_conversion_drops_serial_sequence() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        let _ = schema_manager.drop_table(&"serial_to_identity").await;
+
+        let mut from_table = Table::new("serial_to_identity");
+        from_table.add_column(Column::builder("id", INTEGER)?.set_autoincrement(true));
+        from_table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&from_table).await?;
+        for _ in 0..2 {
+            connection
+                .execute_statement("INSERT INTO serial_to_identity DEFAULT VALUES", params!())
+                .await?;
+        }
+
+        let mut to_table = from_table.clone();
+        to_table.get_column_mut("id").unwrap().set_identity(true);
+
+        let comparator = schema_manager.create_comparator();
+        let diff = comparator.diff_table(&from_table, &to_table)?.unwrap();
+        schema_manager.alter_table(diff).await?;
+
+        let owned_sequences = connection
+            .fetch_all(
+                "SELECT s.relname FROM pg_depend d JOIN pg_class s ON s.oid = d.objid WHERE s.relkind = 'S' AND d.refobjid = 'serial_to_identity'::regclass",
+                params!(),
+            )
+            .await?;
+        assert_eq!(owned_sequences.len(), 1);
+
+        let id = connection
+            .query(
+                "INSERT INTO serial_to_identity DEFAULT VALUES RETURNING id",
+                params!(),
+            )
+            .await?
+            .fetch_one()
+            .await?
+            .unwrap();
+        assert_eq!(i64::try_from(id.get(0)?)?, 3);
+
+        schema_manager.drop_table(&"serial_to_identity").await?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial]
     pub async fn alter_table_rename_index_in_schema() -> Result<()> {
@@ -1246,4 +1618,135 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn generates_column_compression_sql() -> Result<()> {
+        let mut table = Table::new("test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(
+            Column::builder("payload", STRING)?
+                .set_length(255)
+                .set_compression("lz4"),
+        );
+
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        assert_eq!(
+            schema_manager.get_create_table_sql(&table, None)?,
+            &["CREATE TABLE test (id INT NOT NULL, payload VARCHAR(255) COMPRESSION lz4 NOT NULL)"]
+        );
+
+        let mut table_diff = TableDiff::new("test", Some(&table));
+        table_diff.changed_columns.push(ColumnDiff::new(
+            "payload",
+            &Column::builder("payload", STRING)?
+                .set_length(255)
+                .get_column(),
+            &[ChangedProperty::Compression],
+            None,
+        ));
+
+        assert_eq!(
+            schema_manager.get_alter_table_sql(&mut table_diff)?,
+            &["ALTER TABLE test ALTER payload SET COMPRESSION DEFAULT"]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn generates_create_table_as_sql() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        let name = "derived".into_identifier();
+
+        assert_eq!(
+            schema_manager.get_create_table_as_sql(&name, "SELECT 1 AS id", true)?,
+            "CREATE TABLE derived AS SELECT 1 AS id"
+        );
+        assert_eq!(
+            schema_manager.get_create_table_as_sql(&name, "SELECT 1 AS id", false)?,
+            "CREATE TABLE derived AS SELECT 1 AS id WITH NO DATA"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn generates_column_storage_options_sql() -> Result<()> {
+        let mut table = Table::new("test");
+        table.add_column(Column::builder("id", INTEGER)?.set_statistics_target(500));
+        table.add_column(Column::builder("payload", TEXT)?.set_storage("external"));
+
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        assert_eq!(
+            schema_manager.get_create_table_sql(&table, None)?,
+            &[
+                "CREATE TABLE test (id INT NOT NULL, payload TEXT NOT NULL)",
+                "ALTER TABLE test ALTER id SET STATISTICS 500",
+                "ALTER TABLE test ALTER payload SET STORAGE EXTERNAL",
+            ]
+        );
+
+        let mut table_diff = TableDiff::new("test", Some(&table));
+        table_diff.changed_columns.push(ColumnDiff::new(
+            "id",
+            &Column::builder("id", INTEGER)?.get_column(),
+            &[ChangedProperty::StatisticsTarget],
+            None,
+        ));
+        table_diff.changed_columns.push(ColumnDiff::new(
+            "payload",
+            &Column::builder("payload", TEXT)?
+                .set_storage("main")
+                .get_column(),
+            &[ChangedProperty::Storage],
+            None,
+        ));
+
+        assert_eq!(
+            schema_manager.get_alter_table_sql(&mut table_diff)?,
+            &[
+                "ALTER TABLE test ALTER id SET STATISTICS -1",
+                "ALTER TABLE test ALTER payload SET STORAGE MAIN",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn generates_nulls_not_distinct_sql() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        let index: Index = Index::builder("uniq_email")
+            .add_column("email")
+            .set_unique(true)
+            .set_nulls_not_distinct(true)
+            .into();
+        assert_eq!(
+            schema_manager.get_create_index_sql(&index, &"users")?,
+            "CREATE UNIQUE INDEX uniq_email ON users (email) NULLS NOT DISTINCT"
+        );
+
+        let mut constraint =
+            UniqueConstraint::new("uniq_email", &["email"], &[], HashMap::default());
+        constraint.set_nulls_not_distinct(true);
+        assert_eq!(
+            schema_manager.get_create_unique_constraint_sql(&constraint, &"users")?,
+            "ALTER TABLE users ADD CONSTRAINT uniq_email UNIQUE NULLS NOT DISTINCT (email)"
+        );
+        assert_eq!(
+            schema_manager.get_unique_constraint_declaration_sql("uniq_email", &constraint)?,
+            "CONSTRAINT uniq_email UNIQUE NULLS NOT DISTINCT (email)"
+        );
+
+        Ok(())
+    }
 }
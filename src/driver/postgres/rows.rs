@@ -1,13 +1,14 @@
-use crate::{Error, Result, Row, Value};
+use crate::{Error, Range, Result, Row, Value};
 use chrono::{Local, NaiveDate, NaiveDateTime, NaiveTime};
 use fallible_iterator::FallibleIterator;
 use futures::{Stream, StreamExt};
 use postgres_protocol::types;
 use std::io::Read;
+use std::ops::Bound;
 use std::pin::Pin;
 use std::task::{Context, Poll};
-use tokio_postgres::types::{FromSql, Kind, Type};
-use tokio_postgres::{RowStream, Statement};
+use tokio_postgres::types::{Field, FromSql, Kind, Type};
+use tokio_postgres::Statement;
 
 fn simple_type_from_sql(
     ty: &Type,
@@ -64,13 +65,14 @@ fn simple_type_from_sql(
                 Value::NULL
             }
         }
+        Type::INTERVAL => Value::String(interval_from_sql(raw)?),
+        Type::UUID => Value::Uuid(uuid::Uuid::from_slice(raw)?),
         Type::CSTRING
         | Type::NAME
         | Type::VARCHAR
         | Type::TIMETZ
         | Type::INET
         | Type::TEXT
-        | Type::UUID
         | Type::XML
         | Type::REGCLASS
         | Type::REGPROC
@@ -92,6 +94,121 @@ fn simple_type_from_sql(
     })
 }
 
+/// Decodes an interval, formatting it as PostgreSQL does with the default interval style
+/// (ie: `1 year 2 mons 3 days 04:05:06.5`).
+fn interval_from_sql(
+    raw: &[u8],
+) -> core::result::Result<String, Box<dyn std::error::Error + Sync + Send>> {
+    if raw.len() != 16 {
+        return Err("invalid interval value buffer".into());
+    }
+
+    let micros = i64::from_be_bytes(raw[0..8].try_into()?);
+    let days = i32::from_be_bytes(raw[8..12].try_into()?);
+    let months = i32::from_be_bytes(raw[12..16].try_into()?);
+
+    let mut parts = vec![];
+    for (amount, unit) in [(months / 12, "year"), (months % 12, "mon"), (days, "day")] {
+        if amount != 0 {
+            let plural = if amount.abs() == 1 { "" } else { "s" };
+            parts.push(format!("{} {}{}", amount, unit, plural));
+        }
+    }
+
+    if micros != 0 || parts.is_empty() {
+        let sign = if micros < 0 { "-" } else { "" };
+        let micros = micros.unsigned_abs();
+        let seconds = micros / 1_000_000;
+        let mut time = format!(
+            "{}{:02}:{:02}:{:02}",
+            sign,
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60
+        );
+
+        let fraction = micros % 1_000_000;
+        if fraction != 0 {
+            time += format!(".{:06}", fraction).trim_end_matches('0');
+        }
+
+        parts.push(time);
+    }
+
+    Ok(parts.join(" "))
+}
+
+/// Decodes a range value, converting its bounds as values of the range subtype.
+fn range_from_sql(
+    ty: &Type,
+    raw: &[u8],
+) -> core::result::Result<Range, Box<dyn std::error::Error + Sync + Send>> {
+    let bound = |bound: types::RangeBound<Option<&[u8]>>| -> core::result::Result<
+        Bound<Value>,
+        Box<dyn std::error::Error + Sync + Send>,
+    > {
+        Ok(match bound {
+            types::RangeBound::Inclusive(v) => Bound::Included(match v {
+                Some(v) => simple_type_from_sql(ty, v)?,
+                None => Value::NULL,
+            }),
+            types::RangeBound::Exclusive(v) => Bound::Excluded(match v {
+                Some(v) => simple_type_from_sql(ty, v)?,
+                None => Value::NULL,
+            }),
+            types::RangeBound::Unbounded => Bound::Unbounded,
+        })
+    };
+
+    Ok(match types::range_from_sql(raw)? {
+        types::Range::Empty => Range::Empty,
+        types::Range::Nonempty(lower, upper) => Range::new(bound(lower)?, bound(upper)?),
+    })
+}
+
+/// Decodes a composite value as the list of its attribute values.
+fn composite_from_sql(
+    fields: &[Field],
+    mut raw: &[u8],
+) -> core::result::Result<Vec<Value>, Box<dyn std::error::Error + Sync + Send>> {
+    fn read_i32(
+        raw: &mut &[u8],
+    ) -> core::result::Result<i32, Box<dyn std::error::Error + Sync + Send>> {
+        let (bytes, rest) = raw
+            .split_first_chunk::<4>()
+            .ok_or("invalid composite value buffer")?;
+        *raw = rest;
+
+        Ok(i32::from_be_bytes(*bytes))
+    }
+
+    let count = read_i32(&mut raw)?;
+    if usize::try_from(count)? != fields.len() {
+        return Err("composite value attribute count mismatch".into());
+    }
+
+    fields
+        .iter()
+        .map(|field| {
+            let _oid = read_i32(&mut raw)?;
+            let len = read_i32(&mut raw)?;
+            if len < 0 {
+                return Ok(Value::NULL);
+            }
+
+            let len = len as usize;
+            if raw.len() < len {
+                return Err("invalid composite value buffer".into());
+            }
+
+            let (value, rest) = raw.split_at(len);
+            raw = rest;
+
+            Value::from_sql(field.type_(), value)
+        })
+        .collect()
+}
+
 impl<'a> FromSql<'a> for Value {
     fn from_sql(
         ty: &Type,
@@ -118,6 +235,8 @@ impl<'a> FromSql<'a> for Value {
 
                 Value::Array(out)
             }
+            Kind::Composite(fields) => Value::Array(composite_from_sql(fields, raw)?),
+            Kind::Range(ty) => Value::Range(Box::new(range_from_sql(ty, raw)?)),
             _ => {
                 println!("{:?}", ty);
                 todo!()
@@ -139,12 +258,22 @@ impl<'a> FromSql<'a> for Value {
 }
 
 pub struct PostgreSQLRowsIterator {
-    row_stream: Pin<Box<RowStream>>,
+    row_stream: Pin<
+        Box<
+            dyn Stream<Item = std::result::Result<tokio_postgres::Row, tokio_postgres::Error>>
+                + Send,
+        >,
+    >,
     columns: Vec<String>,
 }
 
 impl PostgreSQLRowsIterator {
-    pub fn new(row_stream: RowStream, statement: &Statement) -> Result<Self> {
+    pub fn new(
+        row_stream: impl Stream<Item = std::result::Result<tokio_postgres::Row, tokio_postgres::Error>>
+            + Send
+            + 'static,
+        statement: &Statement,
+    ) -> Result<Self> {
         let mut columns = vec![];
         for column in statement.columns() {
             columns.push(column.name().to_string());
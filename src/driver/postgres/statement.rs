@@ -5,22 +5,25 @@ use crate::error::{Error, StdError};
 use crate::parameter_type::ParameterType;
 use crate::{AsyncResult, Parameter, ParameterIndex, Parameters, Result, Rows, Value};
 use dashmap::DashMap;
-use sqlparser::ast::{visit_expressions_mut, Expr};
+use futures::{stream, Stream, TryStreamExt};
 use sqlparser::dialect::PostgreSqlDialect;
-use sqlparser::parser::Parser;
+use sqlparser::tokenizer::{Token, Tokenizer};
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter, Write};
 use std::marker::PhantomData;
-use std::ops::ControlFlow;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio_postgres::error::SqlState;
 use tokio_postgres::types::private::BytesMut;
 use tokio_postgres::types::{to_sql_checked, Format, IsNull, ToSql, Type};
+use tokio_postgres::Client;
 
 pub struct Statement<'conn> {
     pub(super) connection: &'conn Driver,
     pub(super) sql: String,
     parameters: DashMap<ParameterIndex, Parameter>,
     row_count: AtomicUsize,
+    fetch_size: AtomicUsize,
     phantom_data: PhantomData<&'conn Self>,
 }
 
@@ -66,6 +69,7 @@ impl ToSql for Parameter {
                 Value::DateTime(val) => val.to_sql(ty, out),
                 Value::Json(val) => <String as ToSql>::to_sql(&val.to_string(), ty, out),
                 Value::Uuid(val) => <String as ToSql>::to_sql(&val.to_string(), ty, out),
+                Value::Range(val) => <String as ToSql>::to_sql(&val.to_string(), ty, out),
                 _ => Err(Box::new(StdError::from(Error::postgres_type_mismatch()))),
             },
             ParameterType::LargeObject => bytes_to_binary(&self.value, ty, out),
@@ -102,6 +106,7 @@ impl<'conn> Statement<'conn> {
             sql: sql.to_string(),
             parameters: DashMap::new(),
             row_count: AtomicUsize::new(usize::MAX),
+            fetch_size: AtomicUsize::new(0),
             phantom_data: PhantomData,
         }
     }
@@ -109,7 +114,7 @@ impl<'conn> Statement<'conn> {
     async fn prepare_statement(
         &'conn self,
         params: Vec<(ParameterIndex, Parameter)>,
-    ) -> Result<(tokio_postgres::Statement, Vec<Parameter>)> {
+    ) -> Result<(tokio_postgres::Statement, String, Vec<Parameter>)> {
         let mut raw_params = Vec::with_capacity(params.len());
         let mut sql = self.sql.clone();
 
@@ -133,71 +138,77 @@ impl<'conn> Statement<'conn> {
                 raw_params.push(p);
             }
 
-            // We should parse the SQL query in order to replace the "?" or named parameters
-            // with the postgresql indexed parameters ($1, $2, ...)
-            let mut statement = Parser::new(&PostgreSqlDialect {})
-                .try_with_sql(&self.sql)?
-                .parse_statement()?;
-            let mut last_pos: usize = 0;
-
-            let flow = visit_expressions_mut(&mut statement, |expr| {
-                if let Expr::Value(sqlparser::ast::Value::Placeholder(placeholder)) = expr {
-                    let s: String = placeholder.to_string();
-                    let new_value = if s.starts_with(':') {
-                        // query has named parameters
-                        let name = s.get(1..).unwrap(); // remove the starting colon
-
-                        // Search for parameter in the named parameters map
-                        let Some(pos) = named_map.get(name).copied() else {
-                            return ControlFlow::Break(Error::cannot_find_named_parameter(name));
-                        };
-
-                        format!("${}", pos)
-                    } else if s.starts_with('$') {
-                        s.clone()
-                    } else {
-                        last_pos += 1;
-                        format!("${}", last_pos)
-                    };
-
-                    *expr = Expr::Value(sqlparser::ast::Value::Placeholder(new_value));
-                }
-
-                ControlFlow::Continue(())
-            });
-
-            if let ControlFlow::Break(e) = flow {
-                return Err(e);
-            }
-
-            sql = statement.to_string();
+            // Replace the "?" or named parameters with the postgresql indexed parameters ($1, $2, ...).
+            // The SQL is tokenized rather than parsed, so that the statement text is kept verbatim
+            // (including comments and syntax not supported by the parser, ie: OVERLAPS).
+            sql = replace_placeholders(&self.sql, &named_map)?;
         }
 
-        let statement = self.connection.client.prepare(&sql).await?;
+        let statement = self.connection.prepare_statement(&sql).await?;
 
-        Ok((statement, raw_params))
+        Ok((statement, sql, raw_params))
     }
 
     async fn internal_query(&'conn self, params: Vec<(ParameterIndex, Parameter)>) -> Result<Rows> {
-        let (statement, raw_params) = self.prepare_statement(params).await?;
-        let row_stream = self
-            .connection
-            .client
-            .query_raw(&statement, raw_params)
-            .await?;
+        let (statement, sql, raw_params) = self.prepare_statement(params).await?;
+        let fetch_size = self.fetch_size.load(Ordering::SeqCst);
+
+        let iterator = if fetch_size > 0 {
+            match self.declare_cursor(&sql, &raw_params).await {
+                Ok(name) => PostgreSQLRowsIterator::new(
+                    cursor_stream(self.connection.client.clone(), name, fetch_size),
+                    &statement,
+                )?,
+                // Cursors can only be declared in transaction blocks: fetch all the rows otherwise.
+                Err(e) if e.code() == Some(&SqlState::NO_ACTIVE_SQL_TRANSACTION) => {
+                    let row_stream = self
+                        .connection
+                        .client
+                        .query_raw(&statement, raw_params)
+                        .await?;
+                    PostgreSQLRowsIterator::new(row_stream, &statement)?
+                }
+                Err(e) => return Err(e.into()),
+            }
+        } else {
+            let row_stream = self
+                .connection
+                .client
+                .query_raw(&statement, raw_params)
+                .await?;
+            PostgreSQLRowsIterator::new(row_stream, &statement)?
+        };
 
-        let iterator = PostgreSQLRowsIterator::new(row_stream, &statement)?;
         let rows = Rows::new(iterator.columns().clone(), 0, None, Box::pin(iterator));
         self.row_count.store(rows.len(), Ordering::SeqCst);
 
         Ok(rows)
     }
 
+    /// Declares a cursor for the given query, returning its name.
+    async fn declare_cursor(
+        &self,
+        sql: &str,
+        raw_params: &[Parameter],
+    ) -> std::result::Result<String, tokio_postgres::Error> {
+        let name = format!(
+            "creed_cursor_{}",
+            CURSOR_COUNTER.fetch_add(1, Ordering::SeqCst)
+        );
+        let declare_sql = format!("DECLARE {} NO SCROLL CURSOR FOR {}", name, sql);
+        self.connection
+            .client
+            .execute_raw(&declare_sql, raw_params)
+            .await?;
+
+        Ok(name)
+    }
+
     async fn internal_execute(
         &'conn self,
         params: Vec<(ParameterIndex, Parameter)>,
     ) -> Result<usize> {
-        let (statement, raw_params) = self.prepare_statement(params).await?;
+        let (statement, _, raw_params) = self.prepare_statement(params).await?;
         let affected_rows = self
             .connection
             .client
@@ -209,6 +220,147 @@ impl<'conn> Statement<'conn> {
     }
 }
 
+static CURSOR_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// An open cursor, closed when dropped before being exhausted.
+struct Cursor {
+    client: Arc<Client>,
+    name: String,
+    open: bool,
+}
+
+impl Cursor {
+    async fn close(mut self) -> std::result::Result<(), tokio_postgres::Error> {
+        self.open = false;
+        self.client
+            .batch_execute(&format!("CLOSE {}", self.name))
+            .await
+    }
+}
+
+impl Drop for Cursor {
+    fn drop(&mut self) {
+        if !self.open {
+            return;
+        }
+
+        // Cursors are closed anyway at the end of the transaction.
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+
+        // The close cannot be awaited here, so it is spawned on the runtime. The transaction
+        // could have ended in the meantime: the cursor is closed only if it still exists,
+        // as a failing statement would abort the transaction it is executed in.
+        let client = self.client.clone();
+        let sql = format!(
+            "DO $$ BEGIN IF EXISTS (SELECT 1 FROM pg_cursors WHERE name = '{0}') THEN EXECUTE 'CLOSE {0}'; END IF; END $$",
+            self.name
+        );
+        handle.spawn(async move {
+            let _ = client.batch_execute(&sql).await;
+        });
+    }
+}
+
+/// Fetches the rows of the given cursor in batches of `fetch_size` rows,
+/// closing the cursor once exhausted or when the stream is dropped.
+fn cursor_stream(
+    client: Arc<Client>,
+    name: String,
+    fetch_size: usize,
+) -> impl Stream<Item = std::result::Result<tokio_postgres::Row, tokio_postgres::Error>> + Send {
+    let fetch_sql = format!("FETCH {} FROM {}", fetch_size, name);
+    let cursor = Cursor {
+        client,
+        name,
+        open: true,
+    };
+
+    stream::try_unfold(Some(cursor), move |cursor| {
+        let fetch_sql = fetch_sql.clone();
+        async move {
+            let Some(cursor) = cursor else {
+                return Ok(None);
+            };
+
+            let rows = cursor.client.query(&fetch_sql, &[]).await?;
+            if rows.len() < fetch_size {
+                cursor.close().await?;
+                Ok(Some((rows, None)))
+            } else {
+                Ok(Some((rows, Some(cursor))))
+            }
+        }
+    })
+    .map_ok(|rows| stream::iter(rows.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+/// Replaces the positional ("?") and named (":name") placeholders of the given SQL
+/// with the postgresql indexed parameters, leaving the rest of the statement untouched.
+fn replace_placeholders(sql: &str, named_map: &HashMap<String, usize>) -> Result<String> {
+    let tokens = Tokenizer::new(&PostgreSqlDialect {}, sql).tokenize_with_location()?;
+
+    // Maps the (line, column) location reported by the tokenizer to the byte offset in the SQL.
+    let mut offsets = HashMap::new();
+    let (mut line, mut column) = (1, 1);
+    for (offset, ch) in sql.char_indices() {
+        offsets.insert((line, column), offset);
+        if ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+
+    let mut result = String::with_capacity(sql.len());
+    let mut last_offset = 0;
+    let mut last_pos: usize = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        let location = (token.location.line, token.location.column);
+        let (len, replacement) = match &token.token {
+            Token::Placeholder(s) if s.starts_with('$') => continue,
+            Token::Placeholder(s) => {
+                last_pos += 1;
+                (s.len(), format!("${}", last_pos))
+            }
+            Token::Colon => {
+                let Some(Token::Word(word)) = tokens
+                    .get(i + 1)
+                    .filter(|next| {
+                        next.location.line == location.0 && next.location.column == location.1 + 1
+                    })
+                    .map(|next| &next.token)
+                else {
+                    continue;
+                };
+
+                if word.quote_style.is_some() {
+                    continue;
+                }
+
+                let Some(pos) = named_map.get(&word.value).copied() else {
+                    return Err(Error::cannot_find_named_parameter(&word.value));
+                };
+
+                (word.value.len() + 1, format!("${}", pos))
+            }
+            _ => continue,
+        };
+
+        let offset = offsets[&location];
+        result.push_str(&sql[last_offset..offset]);
+        result.push_str(&replacement);
+        last_offset = offset + len;
+    }
+
+    result.push_str(&sql[last_offset..]);
+
+    Ok(result)
+}
+
 impl<'conn> Debug for Statement<'conn> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("PostgreSQL Statement")
@@ -253,4 +405,11 @@ impl<'conn> crate::driver::statement::Statement<'conn> for Statement<'conn> {
     fn row_count(&self) -> usize {
         self.row_count.load(Ordering::SeqCst)
     }
+
+    /// Rows are fetched in batches through a cursor, which requires an active transaction.
+    /// Outside of transactions, all the rows are fetched at once.
+    fn set_fetch_size(&self, fetch_size: Option<usize>) {
+        self.fetch_size
+            .store(fetch_size.unwrap_or_default(), Ordering::SeqCst);
+    }
 }
@@ -2,15 +2,19 @@ use crate::driver::connection::{Connection as DbalConnection, DriverConnection};
 use crate::driver::sqlite;
 use crate::driver::sqlite::platform::SQLitePlatform;
 use crate::driver::statement::Statement;
+use crate::error::StdError;
 use crate::platform::DatabasePlatform;
-use crate::{Async, EventDispatcher, Parameter, Result, Value};
+use crate::{Async, Error, EventDispatcher, Parameter, Result, Value};
 use itertools::Itertools;
 use rusqlite::functions::{Context, FunctionFlags};
 use rusqlite::types::ToSqlOutput;
-use rusqlite::ToSql;
+use rusqlite::{DatabaseName, ToSql};
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::fs::File;
 use std::future::Future;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
 use std::sync::Arc;
 use url::Url;
 
@@ -210,6 +214,51 @@ impl<'conn> DbalConnection<'conn> for Driver {
     fn prepare(&'conn self, sql: &str) -> Result<Box<dyn Statement + 'conn>> {
         Ok(Box::new(sqlite::statement::Statement::new(self, sql)?))
     }
+
+    /// The blob is streamed through incremental I/O, without loading it entirely in memory.
+    fn blob_to_file(&self, table: &str, column: &str, rowid: i64, path: &Path) -> Result<u64> {
+        let mut blob =
+            self.connection
+                .0
+                .blob_open(DatabaseName::Main, table, column, rowid, true)?;
+        let mut file = BufWriter::new(File::create(path)?);
+        let written = std::io::copy(&mut blob, &mut file)?;
+        file.flush()?;
+
+        Ok(written)
+    }
+
+    /// As incremental I/O cannot resize a blob, the column is first set to a zero-filled blob
+    /// of the file length, then the file content is streamed into it.
+    /// The write is performed within a savepoint, rolled back on failure.
+    fn blob_from_file(&self, table: &str, column: &str, rowid: i64, path: &Path) -> Result<u64> {
+        let file = File::open(path)?;
+        let length = i64::try_from(file.metadata()?.len())?;
+
+        let connection = &self.connection.0;
+        connection.execute_batch("SAVEPOINT creed_blob_from_file")?;
+        let result = (|| {
+            let quote = |identifier: &str| format!("\"{}\"", identifier.replace('"', "\"\""));
+            connection.execute(
+                &format!(
+                    "UPDATE {} SET {} = zeroblob(?) WHERE rowid = ?",
+                    quote(table),
+                    quote(column)
+                ),
+                rusqlite::params![length, rowid],
+            )?;
+
+            let mut blob = connection.blob_open(DatabaseName::Main, table, column, rowid, false)?;
+            Ok(std::io::copy(&mut BufReader::new(file), &mut blob)?)
+        })();
+
+        if result.is_err() {
+            connection.execute_batch("ROLLBACK TO creed_blob_from_file")?;
+        }
+
+        connection.execute_batch("RELEASE creed_blob_from_file")?;
+        result
+    }
 }
 
 impl ToSql for Parameter {
@@ -239,6 +288,12 @@ impl ToSql for Value {
                 ToSqlOutput::Owned(rusqlite::types::Value::Text(value.to_string()))
             }
             Value::Uuid(value) => ToSqlOutput::from(value.to_string()),
+            Value::Range(value) => ToSqlOutput::from(value.to_string()),
+            Value::Default => {
+                return Err(rusqlite::Error::ToSqlConversionFailure(Box::new(
+                    StdError::from(Error::type_mismatch()),
+                )))
+            }
         })
     }
 }
@@ -401,4 +456,42 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn regexp_can_be_provided_by_udf() -> Result<()> {
+        use crate::Parameters;
+        use regex::Regex;
+        use rusqlite::functions::Context;
+
+        let mut options = ConnectionOptions::new_from_memory();
+        options.add_user_defined_function(
+            "regexp",
+            2,
+            Box::new(|context: &Context| {
+                let pattern = context.get::<String>(0)?;
+                let text = context.get::<Option<String>>(1)?;
+                let regex = Regex::new(&pattern)
+                    .map_err(|e| rusqlite::Error::UserFunctionError(Box::new(e)))?;
+
+                Ok(Box::new(text.is_some_and(|t| regex.is_match(&t))))
+            }),
+        );
+
+        let driver = Driver::create(options).await?;
+        let connection =
+            crate::Connection::create_with_connection(Box::new(driver), None, None).await?;
+
+        let mut qb = connection.create_query_builder()?;
+        qb.select(["v.name"])
+            .from_values([["apple"], ["banana"], ["avocado"]], "v", &["name"])?
+            .where_regex("v.name", "^a.*o$", false)?;
+
+        let rows = connection
+            .fetch_all(qb.get_sql()?, Parameters::from(qb.get_parameters()?))
+            .await?;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get("name")?, &Value::from("avocado"));
+
+        Ok(())
+    }
 }
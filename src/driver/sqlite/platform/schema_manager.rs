@@ -4,7 +4,7 @@ use crate::platform::{default, CreateFlags};
 use crate::schema::{
     extract_type_from_comment, remove_type_from_comment, Asset, Column, ColumnData, ColumnList,
     Comparator, FKConstraintList, ForeignKeyConstraint, GenericComparator, Identifier, Index,
-    IndexList, IntoIdentifier, SchemaManager, Table, TableDiff, TableOptions,
+    IndexList, IndexStatistics, IntoIdentifier, SchemaManager, Table, TableDiff, TableOptions,
 };
 use crate::{params, AsyncResult, Connection, Error, Parameters, Result, Row, Value};
 use regex::Regex;
@@ -280,6 +280,16 @@ impl<'a> SchemaManager for SQLiteSchemaManager<'a> {
         sqlite::get_list_views_sql()
     }
 
+    #[inline(always)]
+    fn get_table_kind_sql(&self, table: &str, _: &str) -> Result<String> {
+        sqlite::get_table_kind_sql(self.as_dyn(), table)
+    }
+
+    #[inline(always)]
+    fn get_table_timestamps_sql(&self, table: &str, _: &str) -> Result<String> {
+        sqlite::get_table_timestamps_sql(self.as_dyn(), table)
+    }
+
     fn get_pre_alter_table_index_foreign_key_sql(&self, _: &mut TableDiff) -> Result<Vec<String>> {
         sqlite::get_pre_alter_table_index_foreign_key_sql()
     }
@@ -299,6 +309,20 @@ impl<'a> SchemaManager for SQLiteSchemaManager<'a> {
         sqlite::get_truncate_table_sql(self, table_name)
     }
 
+    fn get_truncate_tables_sql(&self, tables: &[&str], _: bool) -> Result<Vec<String>> {
+        sqlite::get_truncate_tables_sql(self, tables)
+    }
+
+    fn get_optimize_table_sql(&self, _: &dyn IntoIdentifier) -> Result<Vec<String>> {
+        // SQLite cannot vacuum a single table: the whole database file is rebuilt.
+        Ok(vec!["VACUUM".to_string()])
+    }
+
+    fn truncate_tables(&self, tables: &[&str], _: bool) -> AsyncResult<()> {
+        let tables = tables.iter().map(ToString::to_string).collect();
+        Box::pin(async move { sqlite::truncate_tables(self, tables).await })
+    }
+
     fn get_portable_table_column_definition(&self, table_column: &Row) -> Result<Column> {
         sqlite::get_portable_table_column_definition(self.as_dyn(), table_column)
     }
@@ -422,6 +446,15 @@ AND name = ?
         })
     }
 
+    fn get_index_statistics(&self, table: &str) -> AsyncResult<Vec<IndexStatistics>> {
+        let table = self.normalize_name(table);
+        Box::pin(async move { sqlite::get_index_statistics(self.as_dyn(), table).await })
+    }
+
+    fn get_portable_index_statistics_list(&self, rows: Vec<Row>) -> Result<Vec<IndexStatistics>> {
+        sqlite::get_portable_index_statistics_list(self.as_dyn(), rows)
+    }
+
     fn create_comparator(&self) -> Box<dyn Comparator + Send + '_> {
         Box::new(GenericComparator::new(self))
     }
@@ -497,12 +530,12 @@ JOIN pragma_index_list(t.name) i
 #[cfg(test)]
 mod tests {
     use crate::platform::CreateFlags;
-    use crate::r#type::{IntoType, BOOLEAN, INTEGER, STRING};
+    use crate::r#type::{IntoType, BOOLEAN, DATETIME, INTEGER, STRING};
     use crate::schema::{
         ChangedProperty, Column, ColumnDiff, Index, Table, TableDiff, UniqueConstraint,
     };
     use crate::tests::create_connection;
-    use crate::Result;
+    use crate::{params, Result, Value};
     use std::collections::HashMap;
 
     #[tokio::test]
@@ -679,7 +712,7 @@ mod tests {
             "CREATE TEMPORARY TABLE __temp__mytable AS SELECT id, bar, bloo FROM mytable",
             "DROP TABLE mytable",
             "CREATE TABLE mytable (id INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, baz VARCHAR(255) DEFAULT 'def' NOT NULL, bloo BOOLEAN DEFAULT 0 NOT NULL, quota INTEGER DEFAULT NULL)",
-            "INSERT INTO mytable (id, baz, bloo) SELECT id, bar, bloo FROM __temp__mytable",
+            "INSERT INTO mytable (id, baz, bloo) SELECT id, CAST(bar AS VARCHAR(255)), bloo FROM __temp__mytable",
             "DROP TABLE __temp__mytable",
             "ALTER TABLE mytable RENAME TO userlist",
         ]);
@@ -687,6 +720,131 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    pub async fn rename_and_retype_column_keeps_copied_data() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        let mut table = Table::new("stock");
+        table.add_column(Column::new("id", INTEGER.into_type()?));
+        table.add_column(Column::new("amount", STRING.into_type()?));
+        schema_manager.create_table(&table).await?;
+        connection
+            .execute_statement(
+                "INSERT INTO stock (id, amount) VALUES (1, '12 apples'), (2, '7')",
+                params!(),
+            )
+            .await?;
+
+        let table_diff = || -> Result<TableDiff> {
+            let mut table_diff = TableDiff::new("stock", &table);
+            table_diff.changed_columns.push(ColumnDiff::new(
+                "amount",
+                &Column::new("quantity", INTEGER.into_type()?),
+                &[ChangedProperty::Type],
+                table.get_column("amount").cloned(),
+            ));
+
+            Ok(table_diff)
+        };
+
+        // Casting to INTEGER would truncate '12 apples' to 12: values are copied as-is.
+        let sql = schema_manager.get_alter_table_sql(&mut table_diff()?)?;
+        assert!(sql.contains(
+            &"INSERT INTO stock (id, quantity) SELECT id, amount FROM __temp__stock".to_string()
+        ));
+
+        schema_manager.alter_table(table_diff()?).await?;
+        let rows = connection
+            .fetch_all(
+                "SELECT quantity, typeof(quantity) AS type FROM stock ORDER BY id",
+                params!(),
+            )
+            .await?;
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get("quantity")?, &Value::from("12 apples"));
+        assert_eq!(rows[0].get("type")?, &Value::from("text"));
+        assert_eq!(rows[1].get("quantity")?, &Value::Int(7));
+        assert_eq!(rows[1].get("type")?, &Value::from("integer"));
+
+        // Casting back to a TEXT affinity type is lossless.
+        let table = schema_manager.introspect_table("stock").await?;
+        let mut table_diff = TableDiff::new("stock", &table);
+        table_diff.changed_columns.push(ColumnDiff::new(
+            "quantity",
+            &Column::new("quantity", STRING.into_type()?),
+            &[ChangedProperty::Type],
+            table.get_column("quantity").cloned(),
+        ));
+
+        let sql = schema_manager.get_alter_table_sql(&mut table_diff)?;
+        assert!(sql.contains(
+            &"INSERT INTO stock (id, quantity) SELECT id, CAST(quantity AS VARCHAR(255)) FROM __temp__stock"
+                .to_string()
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn retype_to_numeric_affinity_keeps_copied_data() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        let mut table = Table::new("events");
+        table.add_column(Column::new("id", INTEGER.into_type()?));
+        table.add_column(Column::new("happened_at", STRING.into_type()?));
+        schema_manager.create_table(&table).await?;
+        connection
+            .execute_statement(
+                "INSERT INTO events (id, happened_at) VALUES (1, '2024-05-01 10:00:00')",
+                params!(),
+            )
+            .await?;
+
+        let table_diff = |force_cast: bool| -> Result<TableDiff> {
+            let mut column_diff = ColumnDiff::new(
+                "happened_at",
+                &Column::new("happened_at", DATETIME.into_type()?),
+                &[ChangedProperty::Type],
+                table.get_column("happened_at").cloned(),
+            );
+            column_diff.set_force_cast(force_cast);
+
+            let mut table_diff = TableDiff::new("events", &table);
+            table_diff.changed_columns.push(column_diff);
+
+            Ok(table_diff)
+        };
+
+        // Casting to DATETIME (NUMERIC affinity) would truncate the value to 2024.
+        let sql = schema_manager.get_alter_table_sql(&mut table_diff(false)?)?;
+        assert!(sql.contains(
+            &"INSERT INTO events (id, happened_at) SELECT id, happened_at FROM __temp__events"
+                .to_string()
+        ));
+
+        let sql = schema_manager.get_alter_table_sql(&mut table_diff(true)?)?;
+        assert!(sql.contains(
+            &"INSERT INTO events (id, happened_at) SELECT id, CAST(happened_at AS DATETIME) FROM __temp__events"
+                .to_string()
+        ));
+
+        schema_manager.alter_table(table_diff(false)?).await?;
+        let rows = connection
+            .fetch_all("SELECT happened_at FROM events", params!())
+            .await?;
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(
+            rows[0].get("happened_at")?,
+            &Value::from("2024-05-01 10:00:00")
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     pub async fn quoted_column_in_primary_key_propagation() -> Result<()> {
         let connection = create_connection().await?;
@@ -887,6 +1045,56 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    pub async fn get_truncate_tables_sql() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+
+        assert_eq!(
+            schema_manager.get_truncate_tables_sql(&["foo", "select"], true)?,
+            vec!["DELETE FROM foo", r#"DELETE FROM "select""#]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    pub async fn truncate_tables_deletes_referencing_tables_first() -> Result<()> {
+        let connection = create_connection().await?;
+        connection
+            .execute_statement("PRAGMA foreign_keys = ON", params!())
+            .await?;
+        connection
+            .execute_statement("CREATE TABLE parent (id INTEGER PRIMARY KEY)", params!())
+            .await?;
+        connection
+            .execute_statement(
+                "CREATE TABLE child (id INTEGER PRIMARY KEY, parent_id INTEGER REFERENCES parent (id))",
+                params!(),
+            )
+            .await?;
+        connection
+            .execute_statement("INSERT INTO parent (id) VALUES (1)", params!())
+            .await?;
+        connection
+            .execute_statement("INSERT INTO child (id, parent_id) VALUES (1, 1)", params!())
+            .await?;
+
+        let schema_manager = connection.create_schema_manager()?;
+        schema_manager
+            .truncate_tables(&["parent", "child"], false)
+            .await?;
+
+        for table in ["parent", "child"] {
+            let rows = connection
+                .fetch_all(format!("SELECT * FROM {}", table), params!())
+                .await?;
+            assert!(rows.is_empty());
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     pub async fn quotes_reserved_keyword_in_index_declaration_sql() -> Result<()> {
         let connection = create_connection().await?;
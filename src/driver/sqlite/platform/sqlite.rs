@@ -3,12 +3,13 @@ use crate::driver::sqlite::platform::AbstractSQLiteSchemaManager;
 use crate::driver::statement_result::StatementResult;
 use crate::error::ErrorKind;
 use crate::platform::{default, CreateFlags, DatabasePlatform, DateIntervalUnit, TrimMode};
+use crate::query::{Expression, MergeBuilder, QueryBuilder};
 use crate::r#type::{IntoType, BIGINT, DATE, DATETIME, INTEGER, STRING, TIME};
+use crate::schema::{string_from_value, ColumnList, IntoIdentifier};
 use crate::schema::{
-    Asset, Column, ColumnData, ForeignKeyConstraint, Identifier, Index, SchemaManager, Table,
-    TableDiff, TableOptions,
+    Asset, Column, ColumnData, ForeignKeyConstraint, Identifier, Index, IndexStatistics,
+    SchemaManager, Table, TableDiff, TableOptions,
 };
-use crate::schema::{ColumnList, IntoIdentifier};
 use crate::{params, Error, Parameters, Result, Row, TransactionIsolationLevel, Value};
 use creed::schema::IndexList;
 use itertools::Itertools;
@@ -16,14 +17,34 @@ use regex::{escape, Regex};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::time::Duration;
 
+/// SQLite parses the REGEXP operator, but does not ship an implementation of it:
+/// a `regexp(pattern, text)` function must be registered on the connection
+/// (ie: through a [`Udf`](crate::driver::sqlite::Udf)) for the expression to be evaluated.
 pub fn get_regexp_expression() -> Result<String> {
     Ok("REGEXP".to_string())
 }
 
-pub fn get_trim_expression(str: &str, mode: TrimMode, char: Option<String>) -> Result<String> {
-    let trim_char = if let Some(char) = char {
-        format!(", {}", char)
+/// Containment is checked on the top-level elements of the document: each element
+/// of the given value must be found (with the same key, for objects) in the column.
+/// The column is referenced more than once, thus it should not contain placeholders.
+pub fn get_json_contains_expression(column: &str, value: &str) -> Result<String> {
+    Ok(format!(
+        "NOT EXISTS (SELECT 1 FROM json_each({value}) AS v WHERE NOT EXISTS (\
+SELECT 1 FROM json_each({column}) AS c WHERE c.type = v.type AND c.value = v.value \
+AND (c.key = v.key OR (json_type({column}) = 'array' AND typeof(v.key) != 'text'))))"
+    ))
+}
+
+pub fn get_json_array_length_expression(column: &str) -> Result<String> {
+    Ok(format!("json_array_length({})", column))
+}
+
+/// SQLite trim functions remove any of the given chars, not the whole string.
+pub fn get_trim_expression(str: &str, mode: TrimMode, chars: Option<String>) -> Result<String> {
+    let trim_chars = if let Some(chars) = chars {
+        format!(", {}", chars)
     } else {
         "".to_string()
     };
@@ -33,7 +54,7 @@ pub fn get_trim_expression(str: &str, mode: TrimMode, char: Option<String>) -> R
         _ => "TRIM",
     };
 
-    Ok(format!("{}({}{})", trim_fn, str, trim_char))
+    Ok(format!("{}({}{})", trim_fn, str, trim_chars))
 }
 
 pub fn get_substring_expression(
@@ -310,6 +331,25 @@ pub fn get_list_tables_sql() -> Result<String> {
         .to_string())
 }
 
+pub fn get_table_kind_sql(this: &dyn SchemaManager, table: &str) -> Result<String> {
+    Ok(format!(
+        "SELECT CASE type WHEN 'view' THEN 'VIEW' ELSE 'BASE TABLE' END AS kind \
+        FROM (SELECT type, name FROM sqlite_master UNION ALL SELECT type, name FROM sqlite_temp_master) \
+        WHERE type IN ('table', 'view') AND name = {}",
+        this.quote_string_literal(table)
+    ))
+}
+
+/// SQLite does not track table timestamps: the query only checks the table exists.
+pub fn get_table_timestamps_sql(this: &dyn SchemaManager, table: &str) -> Result<String> {
+    Ok(format!(
+        "SELECT NULL AS created, NULL AS updated \
+        FROM (SELECT type, name FROM sqlite_master UNION ALL SELECT type, name FROM sqlite_temp_master) \
+        WHERE type = 'table' AND name = {}",
+        this.quote_string_literal(table)
+    ))
+}
+
 pub fn get_list_views_sql() -> Result<String> {
     Ok("SELECT name AS viewname, NULL AS schemaname, sql AS definition FROM sqlite_master WHERE type='view' AND sql NOT NULL".to_string())
 }
@@ -333,6 +373,77 @@ pub fn get_advanced_foreign_key_options_sql(
     Ok(query)
 }
 
+/// SQLite has no advisory locks: they are emulated inserting the lock key into the
+/// configured table, shared by all the connections to the same database file, along with
+/// the owner token of the holding connection and the acquisition time (in seconds since the epoch).
+pub fn get_advisory_locks_table_sql(this: &dyn DatabasePlatform, table: &str) -> String {
+    format!(
+        "CREATE TABLE IF NOT EXISTS {} (lock_key INTEGER PRIMARY KEY NOT NULL, owner TEXT NOT NULL, acquired_at INTEGER NOT NULL)",
+        this.quote_identifier(table)
+    )
+}
+
+/// A lock held for longer than the given timeout is considered stale and taken over.
+/// Returns an error if no emulation table is configured.
+pub fn get_advisory_lock_sql(
+    this: &dyn DatabasePlatform,
+    key: i64,
+    owner: &str,
+    table: Option<&str>,
+    timeout: Option<Duration>,
+) -> Result<String> {
+    let table = table.ok_or_else(advisory_locks_unsupported)?;
+    let on_conflict = match timeout {
+        Some(timeout) => format!(
+            "(lock_key) DO UPDATE SET owner = excluded.owner, acquired_at = excluded.acquired_at WHERE acquired_at < excluded.acquired_at - {}",
+            timeout.as_secs()
+        ),
+        None => "DO NOTHING".to_string(),
+    };
+
+    Ok(format!(
+        "INSERT INTO {} (lock_key, owner, acquired_at) VALUES ({}, {}, CAST(strftime('%s', 'now') AS INTEGER)) ON CONFLICT {} RETURNING 1",
+        this.quote_identifier(table),
+        key,
+        this.quote_string_literal(owner),
+        on_conflict
+    ))
+}
+
+pub fn get_advisory_unlock_sql(
+    this: &dyn DatabasePlatform,
+    key: i64,
+    owner: &str,
+    table: Option<&str>,
+) -> Result<String> {
+    let table = table.ok_or_else(advisory_locks_unsupported)?;
+    Ok(format!(
+        "DELETE FROM {} WHERE lock_key = {} AND owner = {} RETURNING 1",
+        this.quote_identifier(table),
+        key,
+        this.quote_string_literal(owner)
+    ))
+}
+
+fn advisory_locks_unsupported() -> Error {
+    Error::platform_feature_unsupported(
+        "advisory locks without an emulation table (see Configuration::set_advisory_locks_table)",
+    )
+}
+
+pub fn get_feature_probe_sql(this: &dyn DatabasePlatform, feature: &str) -> Result<String> {
+    match feature.split_once(':') {
+        Some(("function", name)) => Ok(format!(
+            "SELECT 1 FROM pragma_function_list WHERE name = {}",
+            this.quote_string_literal(name)
+        )),
+        _ => Err(Error::platform_feature_unsupported(format!(
+            "feature probe \"{}\"",
+            feature
+        ))),
+    }
+}
+
 pub fn get_truncate_table_sql(
     this: &dyn SchemaManager,
     table_name: &dyn IntoIdentifier,
@@ -346,6 +457,98 @@ pub fn get_truncate_table_sql(
     ))
 }
 
+pub fn get_truncate_tables_sql(this: &dyn SchemaManager, tables: &[&str]) -> Result<Vec<String>> {
+    tables
+        .iter()
+        .map(|t| get_truncate_table_sql(this, t))
+        .collect()
+}
+
+/// Deletes all the rows from the given tables, emptying the referencing tables
+/// before the tables they reference.
+pub async fn truncate_tables(this: &dyn SchemaManager, tables: Vec<String>) -> Result<()> {
+    let mut references = HashMap::new();
+    for table in &tables {
+        let foreign_tables = this
+            .list_table_foreign_keys(table)
+            .await?
+            .iter()
+            .map(|fk| fk.get_foreign_table().get_name().to_lowercase())
+            .collect::<Vec<_>>();
+        references.insert(table.to_lowercase(), foreign_tables);
+    }
+
+    let mut remaining = tables;
+    let mut ordered = vec![];
+    while !remaining.is_empty() {
+        let position = remaining
+            .iter()
+            .position(|table| {
+                let name = table.to_lowercase();
+                !remaining.iter().any(|other| {
+                    let other = other.to_lowercase();
+                    other != name && references[&other].contains(&name)
+                })
+            })
+            .unwrap_or(0);
+
+        ordered.push(remaining.remove(position));
+    }
+
+    let tables = ordered.iter().map(String::as_str).collect::<Vec<_>>();
+    for sql in get_truncate_tables_sql(this, &tables)? {
+        this.get_connection()
+            .execute_statement(sql, params!())
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub fn get_merge_sql(this: &dyn DatabasePlatform, merge: &MergeBuilder) -> Result<String> {
+    merge.to_on_conflict_sql(this, "excluded")
+}
+
+pub fn get_update_from_sql(query: &QueryBuilder) -> Result<Expression> {
+    get_update_from_sql_for_version(query, rusqlite::version_number())
+}
+
+/// UPDATE ... FROM is only supported since SQLite 3.33.0.
+pub(super) fn get_update_from_sql_for_version(
+    query: &QueryBuilder,
+    version: i32,
+) -> Result<Expression> {
+    if version < 3_033_000 {
+        Err(Error::platform_feature_unsupported(
+            "UPDATE ... FROM requires SQLite 3.33.0 or later",
+        ))
+    } else {
+        query.to_update_from_sql()
+    }
+}
+
+pub fn supports_insert_returning() -> bool {
+    supports_insert_returning_for_version(rusqlite::version_number())
+}
+
+/// The RETURNING clause is only supported since SQLite 3.35.0.
+pub(super) fn supports_insert_returning_for_version(version: i32) -> bool {
+    version >= 3_035_000
+}
+
+pub fn get_max_bind_parameters() -> usize {
+    get_max_bind_parameters_for_version(rusqlite::version_number())
+}
+
+/// The default limit of bound parameters has been raised in SQLite 3.32.0.
+pub(super) fn get_max_bind_parameters_for_version(version: i32) -> usize {
+    if version < 3_032_000 {
+        999
+    } else {
+        32766
+    }
+}
+
 pub fn get_for_update_sql() -> Result<String> {
     Ok("".to_string())
 }
@@ -766,6 +969,28 @@ fn replace_column(
     }
 }
 
+/// Whether casting a value to the declared type of the given column keeps its content.
+/// Only types with TEXT or BLOB affinity qualify: casting to INTEGER, REAL or NUMERIC
+/// affinity truncates text values to their numeric prefix (`'12 apples'` becomes `12`).
+/// Other values are copied as-is, letting the column affinity convert them when possible.
+fn has_lossless_cast(platform: &dyn DatabasePlatform, column: &Column) -> Result<bool> {
+    let column_data = column.generate_column_data(platform);
+    let declaration = column
+        .get_type()
+        .get_sql_declaration(&column_data, platform)?
+        .to_uppercase();
+
+    // Affinity rules are evaluated in order: a declaration containing "INT" has INTEGER affinity.
+    if declaration.contains("INT") {
+        return Ok(false);
+    }
+
+    Ok(declaration.is_empty()
+        || ["CHAR", "CLOB", "TEXT", "BLOB"]
+            .iter()
+            .any(|affinity| declaration.contains(affinity)))
+}
+
 pub fn get_alter_table_sql<T: AbstractSQLiteSchemaManager + Sync + ?Sized>(
     this: &T,
     diff: &mut TableDiff,
@@ -777,6 +1002,7 @@ pub fn get_alter_table_sql<T: AbstractSQLiteSchemaManager + Sync + ?Sized>(
         let mut column_sql = vec![];
         let mut columns = ColumnList::default();
         let mut old_column_names = vec![];
+        let mut cast_expressions = HashMap::new();
 
         for column in from_table.columns() {
             columns.push(column.clone());
@@ -828,6 +1054,12 @@ pub fn get_alter_table_sql<T: AbstractSQLiteSchemaManager + Sync + ?Sized>(
             let old_column_identifier = column_diff.get_old_column_name();
             let old_column_name = old_column_identifier.get_name().to_lowercase();
             columns = replace_column(&diff.name, columns, &old_column_name, &column_diff.column)?;
+            if column_diff.is_cast_forced() || has_lossless_cast(&platform, &column_diff.column)? {
+                cast_expressions.insert(
+                    old_column_identifier.get_quoted_name(&platform),
+                    column_diff.get_cast_expression(&platform)?,
+                );
+            }
         }
 
         for column in &diff.added_columns {
@@ -845,6 +1077,10 @@ pub fn get_alter_table_sql<T: AbstractSQLiteSchemaManager + Sync + ?Sized>(
         let (res, mut table_sql) = this.on_schema_alter_table(diff, vec![])?;
         if !res {
             let old_columns = old_column_names.join(", ");
+            let copied_columns = old_column_names
+                .iter()
+                .map(|c| cast_expressions.get(c).unwrap_or(c))
+                .join(", ");
             let added_columns = diff
                 .added_columns
                 .iter()
@@ -886,7 +1122,7 @@ pub fn get_alter_table_sql<T: AbstractSQLiteSchemaManager + Sync + ?Sized>(
                 "INSERT INTO {} ({}) SELECT {} FROM {}",
                 new_table.get_quoted_name(&platform),
                 new_columns,
-                old_columns,
+                copied_columns,
                 data_table_quoted
             ));
             sql.push(this.get_drop_table_sql(&data_table)?);
@@ -999,6 +1235,70 @@ pub fn get_portable_table_column_definition(
     Ok(column)
 }
 
+pub async fn get_index_statistics(
+    this: &dyn SchemaManager,
+    table: String,
+) -> Result<Vec<IndexStatistics>> {
+    let connection = this.get_connection();
+    let analyzed = !connection
+        .fetch_all(
+            "SELECT name FROM sqlite_master WHERE type = 'table' AND name = 'sqlite_stat1'",
+            params!(),
+        )
+        .await?
+        .is_empty();
+
+    let table = this.quote_string_literal(&table);
+    let sql = if analyzed {
+        format!(
+            "SELECT i.name AS index_name, s.stat FROM pragma_index_list({0}) i LEFT JOIN sqlite_stat1 s ON s.tbl = {0} AND s.idx = i.name",
+            table
+        )
+    } else {
+        format!(
+            "SELECT name AS index_name, NULL AS stat FROM pragma_index_list({})",
+            table
+        )
+    };
+
+    let rows = connection.fetch_all(sql, params!()).await?;
+    this.get_portable_index_statistics_list(rows)
+}
+
+/// The stat column of sqlite_stat1 contains the number of rows in the index,
+/// followed by the average number of rows selected by each prefix of the index columns.
+pub fn get_portable_index_statistics_list(
+    this: &dyn SchemaManager,
+    rows: Vec<Row>,
+) -> Result<Vec<IndexStatistics>> {
+    let connection = this.get_connection();
+    rows.into_iter()
+        .map(|row| {
+            let cardinality = match row.get("stat")? {
+                Value::String(stat) => {
+                    let stat = stat
+                        .split_whitespace()
+                        .map_while(|n| n.parse::<u64>().ok())
+                        .collect::<Vec<_>>();
+                    match (stat.first(), stat.last()) {
+                        (Some(rows), Some(per_key)) if stat.len() > 1 => {
+                            Some(rows / (*per_key).max(1))
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+
+            Ok(IndexStatistics::new(
+                string_from_value(connection, row.get("index_name"))?,
+                cardinality,
+                None,
+            ))
+        })
+        .collect()
+}
+
 pub async fn get_portable_table_indexes_list(
     this: &dyn SchemaManager,
     table_indexes: Vec<Row>,
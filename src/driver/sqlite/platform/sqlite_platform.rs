@@ -1,6 +1,7 @@
 use super::sqlite;
 use crate::driver::sqlite::SQLiteSchemaManager;
 use crate::platform::{platform_debug, DatabasePlatform, DateIntervalUnit, KeywordList, TrimMode};
+use crate::query::{Expression, MergeBuilder, QueryBuilder};
 use crate::r#type::{
     BigintType, BinaryType, BlobType, BooleanType, DateTimeType, DateType, DecimalType, FloatType,
     IntegerType, StringType, TextType, TimeType,
@@ -10,6 +11,7 @@ use crate::{Connection, Error, EventDispatcher, Result, TransactionIsolationLeve
 use dashmap::DashMap;
 use std::any::TypeId;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub trait AbstractSQLitePlatform: DatabasePlatform {}
 impl AbstractSQLitePlatform for SQLitePlatform {}
@@ -46,13 +48,21 @@ impl DatabasePlatform for SQLitePlatform {
         sqlite::get_regexp_expression()
     }
 
+    fn get_json_contains_expression(&self, column: &str, value: &str) -> Result<String> {
+        sqlite::get_json_contains_expression(column, value)
+    }
+
+    fn get_json_array_length_expression(&self, column: &str) -> Result<String> {
+        sqlite::get_json_array_length_expression(column)
+    }
+
     fn get_trim_expression(
         &self,
         str: &str,
         mode: TrimMode,
-        char: Option<String>,
+        chars: Option<String>,
     ) -> Result<String> {
-        sqlite::get_trim_expression(str, mode, char)
+        sqlite::get_trim_expression(str, mode, chars)
     }
 
     fn get_substring_expression(
@@ -156,10 +166,22 @@ impl DatabasePlatform for SQLitePlatform {
         true
     }
 
+    fn supports_default_in_insert_values(&self) -> bool {
+        false
+    }
+
+    fn supports_insert_returning(&self) -> bool {
+        sqlite::supports_insert_returning()
+    }
+
     fn supports_column_collation(&self) -> bool {
         true
     }
 
+    fn supports_named_windows(&self) -> bool {
+        true
+    }
+
     fn get_name(&self) -> String {
         "sqlite".to_string()
     }
@@ -236,6 +258,59 @@ impl DatabasePlatform for SQLitePlatform {
             .ok_or_else(|| Error::unknown_database_type(&db_type, &self.get_name()))
     }
 
+    fn get_merge_sql(&self, merge: &MergeBuilder) -> Result<String> {
+        sqlite::get_merge_sql(self, merge)
+    }
+
+    fn get_update_from_sql(&self, query: &QueryBuilder) -> Result<Expression> {
+        sqlite::get_update_from_sql(query)
+    }
+
+    fn get_max_columns_per_table(&self) -> usize {
+        2000
+    }
+
+    fn get_max_bind_parameters(&self) -> usize {
+        sqlite::get_max_bind_parameters()
+    }
+
+    fn get_advisory_lock_sql(
+        &self,
+        key: i64,
+        owner: &str,
+        table: Option<&str>,
+        timeout: Option<Duration>,
+        _: bool,
+    ) -> Result<String> {
+        sqlite::get_advisory_lock_sql(self, key, owner, table, timeout)
+    }
+
+    fn get_advisory_unlock_sql(
+        &self,
+        key: i64,
+        owner: &str,
+        table: Option<&str>,
+    ) -> Result<String> {
+        sqlite::get_advisory_unlock_sql(self, key, owner, table)
+    }
+
+    fn get_advisory_locks_table_sql(&self, table: &str) -> Option<String> {
+        Some(sqlite::get_advisory_locks_table_sql(self, table))
+    }
+
+    fn get_feature_probe_sql(&self, feature: &str) -> Result<String> {
+        sqlite::get_feature_probe_sql(self, feature)
+    }
+
+    /// SQLite current timestamp is always in UTC.
+    fn get_current_utc_timestamp_sql(&self) -> Option<&str> {
+        Some(self.get_current_timestamp_sql())
+    }
+
+    fn get_last_insert_id_sql(&self) -> Result<String> {
+        Ok("SELECT last_insert_rowid()".to_string())
+    }
+
     fn create_schema_manager<'a>(&self, connection: &'a Connection) -> Box<dyn SchemaManager + 'a> {
         Box::new(SQLiteSchemaManager::new(connection))
     }
@@ -244,7 +319,7 @@ impl DatabasePlatform for SQLitePlatform {
 #[cfg(test)]
 mod tests {
     use crate::driver::sqlite::SQLitePlatform;
-    use crate::platform::DatabasePlatform;
+    use crate::platform::{DatabasePlatform, TrimMode};
     use crate::r#type::{BINARY, GUID, JSON};
     use crate::schema::Column;
     use crate::tests::common_platform_tests;
@@ -272,6 +347,27 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn generates_trim_expression_with_chars_set() -> Result<()> {
+        let platform = create_sqlite_platform();
+        let chars = Some("'xy'".to_string());
+
+        assert_eq!(
+            platform.get_trim_expression("col", TrimMode::Leading, chars.clone())?,
+            "LTRIM(col, 'xy')"
+        );
+        assert_eq!(
+            platform.get_trim_expression("col", TrimMode::Trailing, chars.clone())?,
+            "RTRIM(col, 'xy')"
+        );
+        assert_eq!(
+            platform.get_trim_expression("col", TrimMode::Both, chars)?,
+            "TRIM(col, 'xy')"
+        );
+
+        Ok(())
+    }
+
     #[test]
     pub fn quote_single_identifier() {
         let platform = create_sqlite_platform();
@@ -367,4 +463,152 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    pub fn generates_update_from_sql() -> Result<()> {
+        use crate::driver::sqlite::platform::sqlite;
+        use crate::error::ErrorKind;
+        use crate::query::QueryBuilder;
+        use crate::Value;
+        let platform = create_sqlite_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.update("orders")
+            .set("status", "shipped")
+            .update_from("shipments", None)
+            .r#where(expr.eq("shipments.order_id", "orders.id"))
+            .and_where(expr.eq("shipments.carrier", expr.param("ACME")));
+
+        assert_eq!(
+            qb.get_sql()?,
+            "UPDATE orders SET status = ? FROM shipments WHERE (shipments.order_id = orders.id) AND (shipments.carrier = ?)"
+        );
+        assert_eq!(
+            qb.get_parameters()?,
+            vec![Value::from("shipped"), Value::from("ACME")]
+        );
+
+        let err = sqlite::get_update_from_sql_for_version(&qb, 3_032_003).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PlatformFeatureUnsupported);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn max_bind_parameters_depends_on_version() {
+        use crate::driver::sqlite::platform::sqlite;
+        assert_eq!(sqlite::get_max_bind_parameters_for_version(3_031_001), 999);
+        assert_eq!(
+            sqlite::get_max_bind_parameters_for_version(3_032_000),
+            32766
+        );
+    }
+
+    #[test]
+    pub fn insert_returning_depends_on_version() {
+        use crate::driver::sqlite::platform::sqlite;
+        assert!(!sqlite::supports_insert_returning_for_version(3_034_001));
+        assert!(sqlite::supports_insert_returning_for_version(3_035_000));
+    }
+
+    #[test]
+    pub fn omits_row_lock_clauses() -> Result<()> {
+        use crate::query::QueryBuilder;
+        let platform = create_sqlite_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.select(["id"]).from("orders", None).for_share();
+        assert_eq!(qb.get_sql()?, "SELECT id FROM orders");
+
+        qb.for_update_of(&["orders"]);
+        assert_eq!(qb.get_sql()?, "SELECT id FROM orders");
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn json_expressions() -> Result<()> {
+        use crate::error::ErrorKind;
+        use crate::query::QueryBuilder;
+        use crate::Value;
+
+        let platform = create_sqlite_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select([expr.json_array_length("tags")?])
+            .from("posts", None)
+            .r#where(expr.json_contains("tags", expr.param("[\"rust\"]"))?);
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT json_array_length(tags) FROM posts WHERE NOT EXISTS (SELECT 1 FROM json_each(?) AS v WHERE NOT EXISTS (SELECT 1 FROM json_each(tags) AS c WHERE c.type = v.type AND c.value = v.value AND (c.key = v.key OR (json_type(tags) = 'array' AND typeof(v.key) != 'text'))))"
+        );
+        assert_eq!(qb.get_parameters()?, vec![Value::from("[\"rust\"]")]);
+
+        let err = expr
+            .json_contains(expr.param("[1]"), expr.param("1"))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::QueryBuilderInvalid);
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn generates_overlaps_expression() -> Result<()> {
+        use crate::error::ErrorKind;
+        use crate::query::QueryBuilder;
+        use crate::Value;
+
+        let platform = create_sqlite_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select(["b.id"])
+            .from("bookings", Some("b"))
+            .r#where(expr.overlaps(
+                ("b.starts_at", "b.ends_at"),
+                (
+                    expr.param("2024-01-01 10:00:00"),
+                    expr.param("2024-01-01 11:00:00"),
+                ),
+            ));
+
+        assert_eq!(
+            qb.get_sql()?,
+            "SELECT b.id FROM bookings b WHERE ((b.starts_at < ?) AND (? < b.ends_at)) OR (b.starts_at = ?)"
+        );
+        assert_eq!(
+            qb.get_parameters()?,
+            vec![
+                Value::from("2024-01-01 11:00:00"),
+                Value::from("2024-01-01 10:00:00"),
+                Value::from("2024-01-01 10:00:00")
+            ]
+        );
+        assert_eq!(
+            expr.range_overlaps("b.slot", expr.param("[1,10)"))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::PlatformFeatureUnsupported
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn normalizes_foreign_key_referential_actions() {
+        use crate::schema::ForeignKeyReferentialAction;
+
+        let platform = create_sqlite_platform();
+
+        // RESTRICT is checked immediately, NO ACTION at the end of the statement.
+        assert_eq!(
+            platform.normalize_foreign_key_referential_action(Some(
+                ForeignKeyReferentialAction::Restrict
+            )),
+            ForeignKeyReferentialAction::Restrict
+        );
+        assert_eq!(
+            platform.normalize_foreign_key_referential_action(None),
+            ForeignKeyReferentialAction::NoAction
+        );
+    }
 }
@@ -53,6 +53,20 @@ pub trait Statement<'conn>: Debug + Send + Sync {
     /// this behaviour is not guaranteed for all databases and should not be
     /// relied on for portable applications.
     fn row_count(&self) -> usize;
+
+    /// Sets how many rows the driver should fetch from the server per round-trip
+    /// when iterating the results of a query. `None` restores the driver default.
+    ///
+    /// - PostgreSQL fetches the rows through a cursor, which can only be declared
+    ///   inside a transaction: outside of transactions all the rows are fetched at once.
+    /// - MySQL streams the rows, keeping the connection busy until the result
+    ///   is exhausted or dropped: other statements wait for it in the meantime.
+    /// - SQLite ignores this setting.
+    ///
+    /// The number of rows of a streamed result is unknown until it is exhausted:
+    /// both [`Rows::len`](crate::Rows::len) and [`row_count`](Statement::row_count) report 0.
+    #[allow(unused_variables)]
+    fn set_fetch_size(&self, fetch_size: Option<usize>) {}
 }
 
 impl<'conn, T: Statement<'conn> + ?Sized> Statement<'conn> for Box<T> {
@@ -62,6 +76,7 @@ impl<'conn, T: Statement<'conn> + ?Sized> Statement<'conn> for Box<T> {
             fn query(&self, params: Parameters) -> AsyncResult<StatementResult>;
             fn execute(&self, params: Parameters) -> AsyncResult<usize>;
             fn row_count(&self) -> usize;
+            fn set_fetch_size(&self, fetch_size: Option<usize>);
         }
 
         to (*self) {
@@ -1,5 +1,5 @@
 use crate::rows::RowsIterator;
-use crate::{Result, Row, Rows};
+use crate::{DuplicateColumnStrategy, Result, Row, Rows};
 use std::fmt::{Debug, Formatter};
 use std::future::Future;
 
@@ -27,6 +27,12 @@ impl StatementResult {
         }
     }
 
+    /// Renames the duplicate columns of the result set according to the given strategy.
+    pub(crate) fn deduplicate_columns(mut self, strategy: DuplicateColumnStrategy) -> Self {
+        self.rows = self.rows.deduplicate_columns(strategy);
+        self
+    }
+
     /// Returns the *NEXT* row of the statement if any.
     /// If the iterator has been consumed fully, [None] is returned.
     pub fn fetch_one(&mut self) -> impl Future<Output = Result<Option<Row>>> + '_ {
@@ -28,6 +28,8 @@ pub enum ErrorKind {
     ConfigurationError = 101,
     SkipMigration = 102,
     EmptyCriteria = 103,
+    DuplicateMigrationVersions = 104,
+    FixtureInvalid = 105,
 
     NoActiveTransaction = 500,
 
@@ -38,6 +40,14 @@ pub enum ErrorKind {
     IndexDefinitionInvalid = 2003,
     ColumnDoesNotExist = 2004,
     TableDoesNotExist = 2005,
+    MergeDefinitionInvalid = 2006,
+    QueryBuilderInvalid = 2007,
+    TooManyColumnsForTable = 2008,
+    TooManyParameters = 2009,
+    RowColumnsMismatch = 2010,
+    ForeignKeyCollationMismatch = 2011,
+    ForeignKeyReferenceInvalid = 2012,
+    GrantDefinitionInvalid = 2013,
     NotConnected = 5000,
     DatabaseRequired = 5001,
 
@@ -118,6 +128,27 @@ impl Error {
         Self::new(ErrorKind::EmptyCriteria, "Empty criteria")
     }
 
+    pub fn duplicate_migration_versions(versions: &[i64]) -> Self {
+        Self::new(
+            ErrorKind::DuplicateMigrationVersions,
+            format!(
+                "Multiple migrations share the same version: {}",
+                versions
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        )
+    }
+
+    pub fn fixture_invalid<T: ToString>(reason: T) -> Self {
+        Self::new(
+            ErrorKind::FixtureInvalid,
+            format!("Invalid fixtures: {}", reason.to_string()),
+        )
+    }
+
     pub fn no_active_transaction() -> Self {
         Self::new(ErrorKind::NoActiveTransaction, "No active transaction")
     }
@@ -212,6 +243,75 @@ impl Error {
         )
     }
 
+    pub fn too_many_columns_for_table(table_name: &Identifier, count: usize, max: usize) -> Self {
+        Self::new(
+            ErrorKind::TooManyColumnsForTable,
+            format!(
+                "Table {} has {} columns, but the platform supports at most {}",
+                table_name.get_name(),
+                count,
+                max
+            ),
+        )
+    }
+
+    pub fn too_many_parameters(count: usize, max: usize) -> Self {
+        Self::new(
+            ErrorKind::TooManyParameters,
+            format!(
+                "Statement binds {} parameters, but the platform supports at most {}",
+                count, max
+            ),
+        )
+    }
+
+    pub fn row_columns_mismatch(table_name: &str) -> Self {
+        Self::new(
+            ErrorKind::RowColumnsMismatch,
+            format!(
+                "All the rows inserted into table {} must have the same columns",
+                table_name
+            ),
+        )
+    }
+
+    pub fn foreign_key_collation_mismatch(
+        foreign_key_name: &str,
+        local_column: &str,
+        local_collation: &str,
+        foreign_column: &str,
+        foreign_collation: &str,
+    ) -> Self {
+        Self::new(
+            ErrorKind::ForeignKeyCollationMismatch,
+            format!(
+                "Foreign key {} cannot reference column {} ({}) from column {} ({}): charset and collation must match",
+                foreign_key_name, foreign_column, foreign_collation, local_column, local_collation
+            ),
+        )
+    }
+
+    pub fn foreign_key_reference_invalid(
+        foreign_key_name: &str,
+        table_name: &str,
+        reason: &str,
+    ) -> Self {
+        Self::new(
+            ErrorKind::ForeignKeyReferenceInvalid,
+            format!(
+                "Foreign key {} of table {} is invalid: {}",
+                foreign_key_name, table_name, reason
+            ),
+        )
+    }
+
+    pub fn grant_definition_invalid(grantee: &str, reason: &str) -> Self {
+        Self::new(
+            ErrorKind::GrantDefinitionInvalid,
+            format!("Grant to {} is invalid: {}", grantee, reason),
+        )
+    }
+
     pub fn foreign_key_definition_invalid(invalid_component: &str) -> Self {
         Self::new(
             ErrorKind::ForeignKeyDefinitionInvalid,
@@ -226,9 +326,23 @@ impl Error {
         )
     }
 
+    pub fn merge_definition_invalid(invalid_component: &str) -> Self {
+        Self::new(
+            ErrorKind::MergeDefinitionInvalid,
+            format!(
+                "Incomplete merge definition. '{}' required.",
+                invalid_component
+            ),
+        )
+    }
+
+    pub fn query_builder_invalid<S: AsRef<str>>(message: S) -> Self {
+        Self::new(ErrorKind::QueryBuilderInvalid, message.as_ref())
+    }
+
     pub fn table_does_not_exist(invalid_table: &dyn IntoIdentifier) -> Self {
         Self::new(
-            ErrorKind::ColumnDoesNotExist,
+            ErrorKind::TableDoesNotExist,
             format!("Table '{}' does not exist.", invalid_table),
         )
     }
@@ -0,0 +1,371 @@
+use crate::{params, Connection, Error, Result, TypedValue, TypedValueMap, Value};
+use std::collections::{HashMap, HashSet};
+
+/// The value of a fixture row column.
+#[derive(Clone, Debug)]
+pub enum FixtureValue {
+    Value(Value),
+    /// The identifier of the fixture row with the given reference name.
+    Reference(String),
+}
+
+/// A row inserted by a fixture.
+#[derive(Clone, Debug, Default)]
+pub struct FixtureRow {
+    reference: Option<String>,
+    values: Vec<(String, FixtureValue)>,
+}
+
+impl FixtureRow {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a row which can be referenced by the other fixture rows with the given name.
+    pub fn named<S: Into<String>>(reference: S) -> Self {
+        Self {
+            reference: Some(reference.into()),
+            values: vec![],
+        }
+    }
+
+    pub fn set<S: Into<String>, V: Into<Value>>(mut self, column: S, value: V) -> Self {
+        self.values
+            .push((column.into(), FixtureValue::Value(value.into())));
+        self
+    }
+
+    /// Sets the column to the identifier of the fixture row with the given reference name.
+    pub fn set_reference<S: Into<String>, R: Into<String>>(
+        mut self,
+        column: S,
+        reference: R,
+    ) -> Self {
+        self.values
+            .push((column.into(), FixtureValue::Reference(reference.into())));
+        self
+    }
+
+    pub fn get_reference(&self) -> Option<&str> {
+        self.reference.as_deref()
+    }
+
+    pub fn get_values(&self) -> &[(String, FixtureValue)] {
+        &self.values
+    }
+
+    fn referenced_rows(&self) -> impl Iterator<Item = &str> {
+        self.values.iter().filter_map(|(_, value)| match value {
+            FixtureValue::Reference(reference) => Some(reference.as_str()),
+            FixtureValue::Value(_) => None,
+        })
+    }
+}
+
+/// Describes the rows to be inserted into a table.
+#[derive(Clone, Debug)]
+pub struct Fixture {
+    table: String,
+    id_column: String,
+    rows: Vec<FixtureRow>,
+}
+
+impl Fixture {
+    pub fn new<S: Into<String>>(table: S) -> Self {
+        Self {
+            table: table.into(),
+            id_column: "id".to_string(),
+            rows: vec![],
+        }
+    }
+
+    /// Sets the column identifying the rows (defaults to `id`).
+    /// If not set on a named row, the value generated by the database is used.
+    pub fn set_id_column<S: Into<String>>(mut self, column: S) -> Self {
+        self.id_column = column.into();
+        self
+    }
+
+    pub fn add_row(mut self, row: FixtureRow) -> Self {
+        self.rows.push(row);
+        self
+    }
+
+    pub fn get_table(&self) -> &str {
+        &self.table
+    }
+
+    pub fn get_id_column(&self) -> &str {
+        &self.id_column
+    }
+
+    pub fn get_rows(&self) -> &[FixtureRow] {
+        &self.rows
+    }
+}
+
+/// Inserts the rows of the given fixtures within a transaction.
+///
+/// Fixtures are inserted after the ones holding the rows they reference
+/// and the ones whose table is referenced by a foreign key of their table.
+/// References are resolved to the identifier of the referenced row,
+/// which must be inserted before the referencing one.
+pub async fn apply_fixtures(connection: &Connection, fixtures: &[Fixture]) -> Result<()> {
+    let fixtures = sort_fixtures(connection, fixtures).await?;
+
+    connection.begin_transaction().await?;
+    match insert_fixtures(connection, &fixtures).await {
+        Ok(()) => connection.commit().await,
+        Err(e) => {
+            connection.roll_back().await?;
+            Err(e)
+        }
+    }
+}
+
+/// Sorts the given fixtures in dependency order, keeping the given order where possible.
+async fn sort_fixtures<'a>(
+    connection: &Connection,
+    fixtures: &'a [Fixture],
+) -> Result<Vec<&'a Fixture>> {
+    let mut defined_by = HashMap::new();
+    for (idx, fixture) in fixtures.iter().enumerate() {
+        for reference in fixture.rows.iter().filter_map(FixtureRow::get_reference) {
+            if defined_by.insert(reference, idx).is_some() {
+                return Err(Error::fixture_invalid(format!(
+                    "reference \"{}\" is defined more than once",
+                    reference
+                )));
+            }
+        }
+    }
+
+    let schema_manager = connection.create_schema_manager()?;
+    let mut foreign_tables: HashMap<String, Vec<String>> = HashMap::new();
+    let mut dependencies = Vec::with_capacity(fixtures.len());
+    for (idx, fixture) in fixtures.iter().enumerate() {
+        let table = unqualified_name(&fixture.table);
+        if !foreign_tables.contains_key(&table) {
+            let foreign_keys = schema_manager
+                .list_table_foreign_keys(&fixture.table)
+                .await?;
+            let referenced = foreign_keys
+                .iter()
+                .map(|fk| fk.get_unqualified_foreign_table_name())
+                .collect();
+            foreign_tables.insert(table.clone(), referenced);
+        }
+
+        let mut depends_on = HashSet::new();
+        for (other_idx, other) in fixtures.iter().enumerate() {
+            let other_table = unqualified_name(&other.table);
+            if other_table != table && foreign_tables[&table].contains(&other_table) {
+                depends_on.insert(other_idx);
+            }
+        }
+
+        for reference in fixture.rows.iter().flat_map(FixtureRow::referenced_rows) {
+            let Some(&other_idx) = defined_by.get(reference) else {
+                return Err(Error::fixture_invalid(format!(
+                    "reference \"{}\" is not defined",
+                    reference
+                )));
+            };
+
+            if other_idx != idx {
+                depends_on.insert(other_idx);
+            }
+        }
+
+        dependencies.push(depends_on);
+    }
+
+    let mut sorted = Vec::with_capacity(fixtures.len());
+    let mut inserted = vec![false; fixtures.len()];
+    while sorted.len() < fixtures.len() {
+        let Some(next) = (0..fixtures.len())
+            .find(|&idx| !inserted[idx] && dependencies[idx].iter().all(|&dep| inserted[dep]))
+        else {
+            return Err(Error::fixture_invalid(
+                "fixtures have circular dependencies",
+            ));
+        };
+
+        inserted[next] = true;
+        sorted.push(&fixtures[next]);
+    }
+
+    Ok(sorted)
+}
+
+async fn insert_fixtures(connection: &Connection, fixtures: &[&Fixture]) -> Result<()> {
+    let platform = connection.get_platform()?;
+    let mut identifiers: HashMap<&str, Value> = HashMap::new();
+    for fixture in fixtures {
+        for row in &fixture.rows {
+            let mut values = HashMap::with_capacity(row.values.len());
+            for (column, value) in &row.values {
+                let value = match value {
+                    FixtureValue::Value(value) => value.clone(),
+                    FixtureValue::Reference(reference) => identifiers
+                        .get(reference.as_str())
+                        .cloned()
+                        .ok_or_else(|| {
+                            Error::fixture_invalid(format!(
+                                "reference \"{}\" is used before the referenced row is inserted",
+                                reference
+                            ))
+                        })?,
+                };
+
+                values.insert(
+                    column.as_str(),
+                    TypedValue {
+                        value,
+                        r#type: None,
+                    },
+                );
+            }
+
+            let id = values
+                .get(fixture.id_column.as_str())
+                .map(|id| id.value.clone());
+
+            let Some(reference) = row.get_reference() else {
+                connection
+                    .insert(&fixture.table, TypedValueMap(values))
+                    .await?;
+                continue;
+            };
+
+            let id = match id {
+                Some(id) => {
+                    connection
+                        .insert(&fixture.table, TypedValueMap(values))
+                        .await?;
+                    Some(id)
+                }
+                None if platform.supports_insert_returning() => {
+                    connection
+                        .insert_returning(&fixture.table, TypedValueMap(values), &fixture.id_column)
+                        .await?
+                }
+                None => {
+                    connection
+                        .insert(&fixture.table, TypedValueMap(values))
+                        .await?;
+                    connection
+                        .query(platform.get_last_insert_id_sql()?, params!())
+                        .await?
+                        .fetch_one()
+                        .await?
+                        .map(|row| row.get(0).cloned())
+                        .transpose()?
+                }
+            };
+
+            let id = id.ok_or_else(|| {
+                Error::fixture_invalid(format!(
+                    "cannot retrieve the identifier of row \"{}\"",
+                    reference
+                ))
+            })?;
+
+            identifiers.insert(reference, id);
+        }
+    }
+
+    Ok(())
+}
+
+fn unqualified_name(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.rfind('.') {
+        Some(pos) => name[pos + 1..].to_string(),
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_fixtures, Fixture, FixtureRow};
+    use crate::error::ErrorKind;
+    use crate::r#type::{INTEGER, STRING};
+    use crate::schema::{Column, ForeignKeyConstraint, Table};
+    use crate::tests::create_connection;
+    use crate::{params, Result};
+    use serial_test::serial;
+
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[tokio::test]
+    #[serial]
+    async fn fixtures_are_applied_in_dependency_order() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        let _ = schema_manager.drop_table(&"fixture_books").await;
+        let _ = schema_manager.drop_table(&"fixture_authors").await;
+
+        let mut authors = Table::new("fixture_authors");
+        authors.add_column(Column::builder("id", INTEGER)?.set_autoincrement(true));
+        authors.add_column(Column::builder("name", STRING)?.set_length(255));
+        authors.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&authors).await?;
+
+        let mut books = Table::new("fixture_books");
+        books.add_column(Column::builder("id", INTEGER)?.set_autoincrement(true));
+        books.add_column(Column::builder("author_id", INTEGER)?);
+        books.add_column(Column::builder("title", STRING)?.set_length(255));
+        books.set_primary_key(&["id"], None)?;
+        books.add_foreign_key(
+            ForeignKeyConstraint::builder("fixture_authors")
+                .set_local_columns(vec!["author_id"])
+                .set_foreign_columns(vec!["id"])
+                .set_name("fk_fixture_books_author"),
+        )?;
+        schema_manager.create_table(&books).await?;
+
+        // The child fixture comes first: it must be inserted after its parent.
+        let fixtures = [
+            Fixture::new("fixture_books")
+                .add_row(
+                    FixtureRow::new()
+                        .set("title", "Dune")
+                        .set_reference("author_id", "herbert"),
+                )
+                .add_row(
+                    FixtureRow::new()
+                        .set("title", "Foundation")
+                        .set_reference("author_id", "asimov"),
+                ),
+            Fixture::new("fixture_authors")
+                .add_row(FixtureRow::named("asimov").set("name", "Isaac Asimov"))
+                .add_row(FixtureRow::named("herbert").set("name", "Frank Herbert")),
+        ];
+        apply_fixtures(&connection, &fixtures).await?;
+
+        let rows = connection
+            .fetch_all(
+                "SELECT b.title, a.name FROM fixture_books b JOIN fixture_authors a ON a.id = b.author_id ORDER BY b.title",
+                params!(),
+            )
+            .await?;
+        assert_eq!(rows.len(), 2);
+        assert_eq!(String::try_from(rows[0].get("title")?)?, "Dune");
+        assert_eq!(String::try_from(rows[0].get("name")?)?, "Frank Herbert");
+        assert_eq!(String::try_from(rows[1].get("title")?)?, "Foundation");
+        assert_eq!(String::try_from(rows[1].get("name")?)?, "Isaac Asimov");
+
+        let undefined = [Fixture::new("fixture_books").add_row(
+            FixtureRow::new()
+                .set("title", "Emma")
+                .set_reference("author_id", "austen"),
+        )];
+        let err = apply_fixtures(&connection, &undefined).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::FixtureInvalid);
+
+        schema_manager.drop_table(&"fixture_books").await?;
+        schema_manager.drop_table(&"fixture_authors").await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use crate::platform::DatabasePlatform;
+use crate::Result;
+use std::future::Future;
+
+/// Runs the given database operation inside a `creed.query` span.
+///
+/// The span carries the executed SQL, the platform name and the elapsed time;
+/// errors returned by the operation are recorded on the span.
+/// When the `tracing` feature is disabled, the operation is simply awaited.
+#[cfg(feature = "tracing")]
+pub(crate) async fn instrument<T, F>(
+    operation: &'static str,
+    sql: &str,
+    platform: &(dyn DatabasePlatform + Sync + Send),
+    redact: bool,
+    future: F,
+) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    use tracing::field::{display, Empty};
+    use tracing::Instrument;
+
+    let statement = if redact {
+        redact_sql(sql)
+    } else {
+        sql.to_string()
+    };
+
+    let system = platform.get_name();
+    let span = tracing::info_span!(
+        "creed.query",
+        db.system = system.as_str(),
+        db.operation = operation,
+        db.statement = statement.as_str(),
+        elapsed_ms = Empty,
+        error = Empty,
+    );
+
+    let start = std::time::Instant::now();
+    let result = future.instrument(span.clone()).await;
+    span.record("elapsed_ms", start.elapsed().as_millis() as u64);
+    if let Err(e) = &result {
+        span.record("error", display(e));
+    }
+
+    result
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) async fn instrument<T, F>(
+    _: &'static str,
+    _: &str,
+    _: &(dyn DatabasePlatform + Sync + Send),
+    _: bool,
+    future: F,
+) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    future.await
+}
+
+/// Replaces the string literals of the given SQL with a `?` placeholder.
+#[cfg(feature = "tracing")]
+fn redact_sql(sql: &str) -> String {
+    let mut result = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            result.push(c);
+            continue;
+        }
+
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+        }
+
+        result.push('?');
+    }
+
+    result
+}
@@ -14,9 +14,12 @@ mod configuration;
 mod connection;
 mod connection_options;
 mod event;
+mod instrument;
 mod parameter;
 mod parameter_type;
 mod result;
+mod result_cache;
+mod reusable_statement;
 mod rows;
 mod transaction_isolation_level;
 mod value;
@@ -26,14 +29,16 @@ pub(crate) mod util;
 
 pub mod driver;
 pub mod error;
+pub mod fixtures;
 pub mod migrate;
 pub mod platform;
+pub mod query;
 pub mod schema;
 pub mod sync;
 pub mod tls;
 pub mod r#type;
 
-pub use configuration::Configuration;
+pub use configuration::{Configuration, DuplicateColumnStrategy};
 pub use connection::Connection;
 pub use connection_options::ConnectionOptions;
 pub use error::Error;
@@ -44,12 +49,14 @@ pub use parameter::ParameterIndex;
 pub use parameter::Parameters;
 pub use parameter_type::ParameterType;
 pub use result::{Async, AsyncResult, Result};
+pub use result_cache::{InMemoryResultCache, ResultCache, ResultCacheKey};
+pub use reusable_statement::ReusableStatement;
 pub use rows::{Row, Rows};
 pub use transaction_isolation_level::TransactionIsolationLevel;
 pub use util::const_expr_count;
-pub use value::{TypedValue, TypedValueMap, UntypedValueMap, Value, ValueMap};
+pub use value::{Range, TypedValue, TypedValueMap, UntypedValueMap, Value, ValueMap};
 
-pub use creed_macros::{migrator, value_map, IntoIdentifier};
+pub use creed_macros::{migrator, value_map, BitFlags, IntoIdentifier};
 
 #[cfg(test)]
 pub mod tests;
@@ -1,7 +1,7 @@
 use crate::error::ErrorKind;
 use crate::migrate::execution_result::ExecutionResult;
 use crate::migrate::migration_plan::MigrationPlan;
-use crate::migrate::Direction;
+use crate::migrate::{Direction, Progress, ProgressCallback, ProgressEvent};
 use crate::parameter::NO_PARAMS;
 use crate::schema::{Schema, SchemaManager};
 use crate::util::PlatformBox;
@@ -34,6 +34,8 @@ impl<'conn> Executor<'conn> {
         &mut self,
         migration: &mut MigrationPlan,
         from_schema: Option<Schema>,
+        progress: &ProgressCallback<'_>,
+        started_at: chrono::DateTime<chrono::Utc>,
     ) -> Result<usize> {
         let schema_manager = self.connection.create_schema_manager()?;
         let comparator = schema_manager.create_comparator();
@@ -108,7 +110,19 @@ impl<'conn> Executor<'conn> {
 
         let start = chrono::Utc::now();
         if error.is_none() && !self.sql.is_empty() {
-            for q in self.sql.iter() {
+            let total = self.sql.len();
+            let notify = |event, current| {
+                progress(&Progress {
+                    event,
+                    version: migration.version,
+                    current,
+                    total,
+                    elapsed: chrono::Utc::now() - started_at,
+                })
+            };
+
+            for (idx, q) in self.sql.iter().enumerate() {
+                notify(ProgressEvent::StatementStarted, idx + 1);
                 if let Err(e) = self
                     .connection
                     .execute_statement(q.as_str(), NO_PARAMS)
@@ -117,6 +131,8 @@ impl<'conn> Executor<'conn> {
                     let _ = error.insert(e);
                     break;
                 }
+
+                notify(ProgressEvent::StatementFinished, idx + 1);
             }
         }
 
@@ -4,6 +4,7 @@ pub struct ExecutedMigration {
     pub execution_time: Option<u64>,
 }
 
+#[derive(Default)]
 pub struct ExecutedMigrationList {
     pub(super) items: Vec<ExecutedMigration>,
 }
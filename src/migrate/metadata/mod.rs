@@ -3,7 +3,7 @@ mod executed_migration;
 use crate::migrate::execution_result::ExecutionResult;
 use crate::migrate::Direction;
 use crate::r#type::{IntoType, BIGINT, DATETIME, INTEGER};
-use crate::schema::{Column, Table};
+use crate::schema::{Asset, Column, Identifier, Table};
 use crate::{params, AsyncResult, Connection};
 use chrono::TimeZone;
 use creed::Value;
@@ -20,6 +20,7 @@ pub struct TableMetadataStorage<'conn> {
     connection: &'conn Connection,
     is_initialized: AtomicBool,
     schema_up_to_date: AtomicBool,
+    schema_name: Option<String>,
     table_name: String,
     version_column_name: String,
     executed_at_column_name: String,
@@ -32,6 +33,7 @@ impl<'conn> TableMetadataStorage<'conn> {
             connection,
             is_initialized: AtomicBool::new(false),
             schema_up_to_date: AtomicBool::new(false),
+            schema_name: None,
             table_name: "migration_versions".to_string(),
             version_column_name: "version".to_string(),
             executed_at_column_name: "executed_at".to_string(),
@@ -39,6 +41,15 @@ impl<'conn> TableMetadataStorage<'conn> {
         }
     }
 
+    /// Stores the metadata table in the given schema, which is created if
+    /// it does not exist yet (on platforms supporting schemas).
+    /// On other platforms the name qualifies the table as is (ie: the database
+    /// name on MySQL), and the referenced database must already exist.
+    pub fn with_schema_name(mut self, schema_name: &str) -> Self {
+        self.schema_name = Some(schema_name.to_string());
+        self
+    }
+
     pub fn with_table_name(mut self, table_name: &str) -> Self {
         self.table_name = table_name.to_string();
         self
@@ -59,9 +70,47 @@ impl<'conn> TableMetadataStorage<'conn> {
         self
     }
 
+    /// Gets the metadata table identifier, qualified with the schema name if set.
+    fn get_table_identifier(&self) -> Identifier {
+        let name = if let Some(schema_name) = &self.schema_name {
+            format!("{}.{}", schema_name, self.table_name)
+        } else {
+            self.table_name.clone()
+        };
+
+        Identifier::new(name, false)
+    }
+
+    /// Gets the metadata table name, quoted to be used in SQL statements.
+    fn get_quoted_table_name(&self) -> crate::Result<String> {
+        let platform = self.connection.get_platform()?;
+        Ok(self.get_table_identifier().get_quoted_name(&platform))
+    }
+
     async fn is_initialized(&self, connection: &Connection) -> crate::Result<bool> {
         let schema_manager = connection.create_schema_manager()?;
-        schema_manager.tables_exist(&[&self.table_name]).await
+        schema_manager
+            .tables_exist(&[&self.get_table_identifier().get_name()])
+            .await
+    }
+
+    async fn ensure_schema_exists(&self, connection: &Connection) -> crate::Result<()> {
+        let Some(schema_name) = &self.schema_name else {
+            return Ok(());
+        };
+
+        if !connection.get_platform()?.supports_schemas() {
+            return Ok(());
+        }
+
+        let schema_manager = connection.create_schema_manager()?;
+        let schema_names = schema_manager.list_schema_names().await?;
+        if !schema_names.iter().any(|s| s.get_name() == *schema_name) {
+            let sql = schema_manager.get_create_schema_sql(schema_name)?;
+            connection.execute_statement(sql, params!()).await?;
+        }
+
+        Ok(())
     }
 
     async fn ensure_initialized(&self, connection: &Connection) -> crate::Result<()> {
@@ -69,6 +118,7 @@ impl<'conn> TableMetadataStorage<'conn> {
         let schema_manager = connection.create_schema_manager()?;
 
         if !self.is_initialized(connection).await? {
+            self.ensure_schema_exists(connection).await?;
             schema_manager.create_table(&table).await?;
             self.is_initialized.store(true, Ordering::SeqCst);
             self.schema_up_to_date.store(true, Ordering::SeqCst);
@@ -76,7 +126,9 @@ impl<'conn> TableMetadataStorage<'conn> {
             self.is_initialized.store(true, Ordering::SeqCst);
             if !self.schema_up_to_date.load(Ordering::SeqCst) {
                 let comparator = schema_manager.create_comparator();
-                let online_table = schema_manager.introspect_table(&self.table_name).await?;
+                let online_table = schema_manager
+                    .introspect_table(&self.get_table_identifier().get_name())
+                    .await?;
                 let diff = comparator.diff_table(&online_table, &table)?;
                 if let Some(table_diff) = diff {
                     schema_manager.alter_table(table_diff).await?;
@@ -90,7 +142,7 @@ impl<'conn> TableMetadataStorage<'conn> {
     }
 
     fn get_expected_table(&self) -> Table {
-        let mut table = Table::new(&self.table_name);
+        let mut table = Table::new(self.get_table_identifier().get_name());
         table.add_column(
             Column::builder(&self.version_column_name, BIGINT)
                 .expect("unable to create version column")
@@ -126,7 +178,10 @@ impl MetadataStorage for TableMetadataStorage<'_> {
 
                 let rows = self
                     .connection
-                    .fetch_all(format!("SELECT * FROM {}", self.table_name), params!())
+                    .fetch_all(
+                        format!("SELECT * FROM {}", self.get_quoted_table_name()?),
+                        params!(),
+                    )
                     .await?;
                 let mut migrations = vec![];
                 for row in rows {
@@ -173,7 +228,7 @@ impl MetadataStorage for TableMetadataStorage<'_> {
             if execution_result.direction == Direction::Up {
                 self.connection
                     .insert(
-                        &self.table_name,
+                        &self.get_table_identifier().get_name(),
                         value_map! {
                             version_column_name.as_str() => execution_result.version typeof BIGINT,
                             execution_time_column_name.as_str() => execution_result.execution_time typeof INTEGER,
@@ -184,7 +239,7 @@ impl MetadataStorage for TableMetadataStorage<'_> {
             } else {
                 self.connection
                     .delete(
-                        &self.table_name,
+                        &self.get_quoted_table_name()?,
                         value_map! {
                             version_column_name.as_str() => execution_result.version typeof BIGINT,
                         },
@@ -196,3 +251,121 @@ impl MetadataStorage for TableMetadataStorage<'_> {
         })
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "functional-tests")]
+mod tests {
+    use super::{MetadataStorage, TableMetadataStorage};
+    use crate::migrate::execution_result::ExecutionResult;
+    use crate::migrate::Direction;
+    use crate::params;
+    use crate::schema::{Asset, Identifier};
+    use crate::tests::FunctionalTestsHelper;
+    use serial_test::serial;
+
+    #[tokio::test]
+    #[serial]
+    async fn can_store_metadata_in_custom_schema() -> crate::Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        if !helper.platform.supports_schemas() {
+            return Ok(());
+        }
+
+        let connection = &helper.connection;
+        connection
+            .execute_statement("DROP SCHEMA IF EXISTS creed_metadata CASCADE", params!())
+            .await?;
+
+        let storage = TableMetadataStorage::new(connection).with_schema_name("creed_metadata");
+        assert!(storage.get_executed_migration().await?.is_empty());
+
+        storage
+            .complete(ExecutionResult {
+                sql: vec![],
+                version: 20240101000000,
+                direction: Direction::Up,
+                executed_at: chrono::Utc::now(),
+                execution_time: 42,
+                skipped: false,
+                error: None,
+                to_schema: None,
+            })
+            .await?;
+
+        let schema_manager = helper.get_schema_manager();
+        assert!(
+            schema_manager
+                .tables_exist(&["creed_metadata.migration_versions"])
+                .await?
+        );
+        assert!(!TableMetadataStorage::new(connection)
+            .get_executed_migration()
+            .await?
+            .has_migration(20240101000000));
+
+        let storage = TableMetadataStorage::new(connection).with_schema_name("creed_metadata");
+        let executed = storage.get_executed_migration().await?;
+        assert_eq!(executed.len(), 1);
+        assert_eq!(
+            executed
+                .get_migration(20240101000000)
+                .and_then(|m| m.execution_time),
+            Some(42)
+        );
+
+        connection
+            .execute_statement("DROP SCHEMA creed_metadata CASCADE", params!())
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn can_store_metadata_in_keyword_named_table() -> crate::Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let connection = &helper.connection;
+        let platform = connection.get_platform()?;
+        helper
+            .drop_table_if_exists(&Identifier::new("order", false).get_quoted_name(&platform))
+            .await;
+
+        let storage = TableMetadataStorage::new(connection).with_table_name("order");
+        storage
+            .complete(ExecutionResult {
+                sql: vec![],
+                version: 20240101000000,
+                direction: Direction::Up,
+                executed_at: chrono::Utc::now(),
+                execution_time: 42,
+                skipped: false,
+                error: None,
+                to_schema: None,
+            })
+            .await?;
+
+        let executed = storage.get_executed_migration().await?;
+        assert!(executed.has_migration(20240101000000));
+
+        storage
+            .complete(ExecutionResult {
+                sql: vec![],
+                version: 20240101000000,
+                direction: Direction::Down,
+                executed_at: chrono::Utc::now(),
+                execution_time: 0,
+                skipped: false,
+                error: None,
+                to_schema: None,
+            })
+            .await?;
+
+        assert!(storage.get_executed_migration().await?.is_empty());
+
+        helper
+            .drop_table_if_exists(&Identifier::new("order", false).get_quoted_name(&platform))
+            .await;
+
+        Ok(())
+    }
+}
@@ -2,14 +2,18 @@ mod execution_result;
 mod executor;
 pub mod metadata;
 mod migration_plan;
+mod progress;
 
 pub use crate::migrate::executor::Executor;
 use crate::migrate::metadata::{ExecutedMigrationList, MetadataStorage, TableMetadataStorage};
+pub use crate::migrate::progress::{Progress, ProgressCallback, ProgressEvent};
 use crate::schema::Schema;
 use crate::sync::Mutex;
-use crate::{Connection, Result};
-use log::{error, info};
+use crate::{Connection, Error, Result};
+use log::{error, info, warn};
 use std::borrow::Cow;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 
@@ -32,6 +36,18 @@ impl Display for Direction {
     }
 }
 
+/// How migrations sharing the same version are handled.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum DuplicateVersionStrategy {
+    /// Migrating fails with an error listing the duplicate versions.
+    #[default]
+    Error,
+    /// The first declared migration of each version is executed.
+    First,
+    /// The last declared migration of each version is executed.
+    Last,
+}
+
 pub type OpClosure = dyn (Fn(&mut Executor, &Schema) -> Result<()>) + Send + Sync;
 pub type PreOpClosure = dyn (Fn(&Schema) -> Result<Schema>) + Send + Sync;
 pub type PostOpClosure = dyn (Fn(&Schema) -> Result<()>) + Send + Sync;
@@ -54,6 +70,7 @@ pub struct Migrator {
     migrations: Cow<'static, [Migration]>,
     ignore_missing: bool,
     locking: bool,
+    duplicate_version_strategy: DuplicateVersionStrategy,
     metadata_storage: Mutex<Option<Box<dyn MetadataStorage>>>,
 }
 
@@ -67,10 +84,17 @@ impl Migrator {
             migrations,
             ignore_missing,
             locking,
+            duplicate_version_strategy: DuplicateVersionStrategy::Error,
             metadata_storage: Mutex::const_new(None),
         }
     }
 
+    /// Sets how migrations sharing the same version are handled.
+    pub fn with_duplicate_version_strategy(mut self, strategy: DuplicateVersionStrategy) -> Self {
+        self.duplicate_version_strategy = strategy;
+        self
+    }
+
     pub async fn with_metadata_storage<M: MetadataStorage + 'static>(
         self,
         metadata_storage: Box<M>,
@@ -84,12 +108,23 @@ impl Migrator {
     }
 
     pub async fn migrate(&self, connection: &Connection) -> Result<()> {
-        let Some(last_migration) = self.migrations.last() else {
+        self.migrate_with_progress(connection, &|_| {}).await
+    }
+
+    /// Executes the pending migrations, notifying the given callback before and
+    /// after each migration and each SQL statement is executed.
+    pub async fn migrate_with_progress(
+        &self,
+        connection: &Connection,
+        progress: &ProgressCallback<'_>,
+    ) -> Result<()> {
+        let migrations = self.get_migrations()?;
+        let Some(last_migration) = migrations.last() else {
             return Ok(());
         };
 
         let (plans, direction) = self
-            .get_plan_until_version(last_migration.version, connection)
+            .get_plan_until_version(&migrations, last_migration.version, connection)
             .await?;
 
         info!(target: "creed::migrate", "Migrating {} to {}", direction, last_migration.version);
@@ -109,8 +144,22 @@ impl Migrator {
 
         // todo: dispatch event
         let mut to_schema = None;
-        for mut plan in plans {
-            sql_count += executor.execute(&mut plan, to_schema).await?;
+        for (idx, mut plan) in plans.into_iter().enumerate() {
+            let version = plan.version;
+            let notify = |event| {
+                progress(&Progress {
+                    event,
+                    version,
+                    current: idx + 1,
+                    total: plans_count,
+                    elapsed: chrono::Utc::now() - global_start,
+                })
+            };
+
+            notify(ProgressEvent::MigrationStarted);
+            sql_count += executor
+                .execute(&mut plan, to_schema, progress, global_start)
+                .await?;
 
             if let Some(execution_result) = plan.execution_result {
                 if let Some(error) = execution_result.error {
@@ -130,6 +179,8 @@ impl Migrator {
             } else {
                 to_schema = None;
             }
+
+            notify(ProgressEvent::MigrationFinished);
         }
 
         connection.commit().await?;
@@ -140,6 +191,45 @@ impl Migrator {
         Ok(())
     }
 
+    /// Gets the migrations to be considered, resolving the duplicate versions
+    /// according to the configured strategy.
+    fn get_migrations(&self) -> Result<Vec<Migration>> {
+        let mut selected = HashMap::new();
+        let mut duplicates = vec![];
+        for (idx, migration) in self.migrations.iter().enumerate() {
+            match selected.entry(migration.version) {
+                Entry::Vacant(e) => {
+                    e.insert(idx);
+                }
+                Entry::Occupied(mut e) => {
+                    if !duplicates.contains(&migration.version) {
+                        duplicates.push(migration.version);
+                    }
+
+                    if self.duplicate_version_strategy == DuplicateVersionStrategy::Last {
+                        e.insert(idx);
+                    }
+                }
+            }
+        }
+
+        if !duplicates.is_empty() {
+            if self.duplicate_version_strategy == DuplicateVersionStrategy::Error {
+                return Err(Error::duplicate_migration_versions(&duplicates));
+            }
+
+            warn!(target: "creed::migrate", "Multiple migrations share the same version: {:?}", duplicates);
+        }
+
+        Ok(self
+            .migrations
+            .iter()
+            .enumerate()
+            .filter(|(idx, migration)| selected[&migration.version] == *idx)
+            .map(|(_, migration)| migration.clone())
+            .collect())
+    }
+
     async fn get_executed_migrations(
         &self,
         connection: &Connection,
@@ -157,13 +247,14 @@ impl Migrator {
 
     async fn get_plan_until_version(
         &self,
+        migrations: &[Migration],
         version: i64,
         connection: &Connection,
     ) -> Result<(Vec<migration_plan::MigrationPlan>, Direction)> {
         let executed_migrations = self.get_executed_migrations(connection).await?;
-        let direction = self.find_direction(version, &executed_migrations);
+        let direction = self.find_direction(migrations, version, &executed_migrations);
 
-        let mut sorted_migrations = self.migrations.to_vec();
+        let mut sorted_migrations = migrations.to_vec();
         sorted_migrations.sort_by_key(|m| m.version);
         if direction == Direction::Down {
             sorted_migrations.reverse()
@@ -189,13 +280,14 @@ impl Migrator {
 
     fn find_direction(
         &self,
+        migrations: &[Migration],
         version: i64,
         executed_migration_list: &ExecutedMigrationList,
     ) -> Direction {
         if version == 0 {
             Direction::Down
         } else {
-            for migration in self.migrations.iter() {
+            for migration in migrations.iter() {
                 if migration.version == version {
                     break;
                 }
@@ -240,4 +332,177 @@ mod tests {
         let result = MIGRATOR.migrate(&connection).await;
         assert!(result.is_ok(), "error: {:#?}", result.unwrap_err());
     }
+
+    #[cfg(feature = "functional-tests")]
+    mod progress {
+        use crate::error::ErrorKind;
+        use crate::migrate::execution_result::ExecutionResult;
+        use crate::migrate::metadata::{ExecutedMigrationList, MetadataStorage};
+        use crate::migrate::{
+            DuplicateVersionStrategy, Executor, Migration, Migrator, ProgressEvent,
+        };
+        use crate::schema::Schema;
+        use crate::tests::FunctionalTestsHelper;
+        use crate::AsyncResult;
+        use serial_test::serial;
+        use std::borrow::Cow;
+        use std::sync::Mutex;
+
+        struct NullMetadataStorage;
+
+        impl MetadataStorage for NullMetadataStorage {
+            fn get_executed_migration(&self) -> AsyncResult<ExecutedMigrationList> {
+                Box::pin(async { Ok(ExecutedMigrationList::default()) })
+            }
+
+            fn complete(&self, _: ExecutionResult) -> AsyncResult<()> {
+                Box::pin(async { Ok(()) })
+            }
+        }
+
+        fn description() -> &'static str {
+            "progress test migration"
+        }
+
+        fn two_statements(executor: &mut Executor, _: &Schema) -> crate::Result<()> {
+            executor.add_sql("SELECT 1");
+            executor.add_sql("SELECT 2");
+            Ok(())
+        }
+
+        fn one_statement(executor: &mut Executor, _: &Schema) -> crate::Result<()> {
+            executor.add_sql("SELECT 1");
+            Ok(())
+        }
+
+        fn noop(_: &mut Executor, _: &Schema) -> crate::Result<()> {
+            Ok(())
+        }
+
+        static MIGRATIONS: [Migration; 2] = [
+            Migration {
+                version: 1,
+                description: &description,
+                up: &two_statements,
+                down: &noop,
+                pre_up: None,
+                post_up: None,
+                pre_down: None,
+                post_down: None,
+                checksum: Cow::Borrowed(&[]),
+            },
+            Migration {
+                version: 2,
+                description: &description,
+                up: &one_statement,
+                down: &noop,
+                pre_up: None,
+                post_up: None,
+                pre_down: None,
+                post_down: None,
+                checksum: Cow::Borrowed(&[]),
+            },
+        ];
+
+        static DUPLICATE_MIGRATIONS: [Migration; 2] = [
+            Migration {
+                version: 1,
+                description: &description,
+                up: &two_statements,
+                down: &noop,
+                pre_up: None,
+                post_up: None,
+                pre_down: None,
+                post_down: None,
+                checksum: Cow::Borrowed(&[]),
+            },
+            Migration {
+                version: 1,
+                description: &description,
+                up: &one_statement,
+                down: &noop,
+                pre_up: None,
+                post_up: None,
+                pre_down: None,
+                post_down: None,
+                checksum: Cow::Borrowed(&[]),
+            },
+        ];
+
+        #[tokio::test]
+        #[serial]
+        async fn duplicate_versions_are_detected() -> crate::Result<()> {
+            let helper = FunctionalTestsHelper::default().await;
+            let migrator = Migrator::new(Cow::Borrowed(&DUPLICATE_MIGRATIONS), false, false)
+                .with_metadata_storage(Box::new(NullMetadataStorage))
+                .await;
+
+            let err = migrator.migrate(&helper.connection).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::DuplicateMigrationVersions);
+            assert!(err.to_string().ends_with("same version: 1"));
+
+            for (strategy, expected_statements) in [
+                (DuplicateVersionStrategy::First, 2),
+                (DuplicateVersionStrategy::Last, 1),
+            ] {
+                let migrator = Migrator::new(Cow::Borrowed(&DUPLICATE_MIGRATIONS), false, false)
+                    .with_duplicate_version_strategy(strategy)
+                    .with_metadata_storage(Box::new(NullMetadataStorage))
+                    .await;
+
+                let events = Mutex::new(vec![]);
+                migrator
+                    .migrate_with_progress(&helper.connection, &|p| {
+                        events.lock().unwrap().push((p.event, p.version))
+                    })
+                    .await?;
+
+                let events = events.into_inner().unwrap();
+                let count = |event| events.iter().filter(|e| e.0 == event).count();
+                assert!(events.iter().all(|e| e.1 == 1));
+                assert_eq!(count(ProgressEvent::MigrationStarted), 1);
+                assert_eq!(count(ProgressEvent::StatementStarted), expected_statements);
+            }
+
+            Ok(())
+        }
+
+        #[tokio::test]
+        #[serial]
+        async fn migrate_reports_progress() -> crate::Result<()> {
+            let helper = FunctionalTestsHelper::default().await;
+            let migrator = Migrator::new(Cow::Borrowed(&MIGRATIONS), false, false)
+                .with_metadata_storage(Box::new(NullMetadataStorage))
+                .await;
+
+            let events = Mutex::new(vec![]);
+            migrator
+                .migrate_with_progress(&helper.connection, &|p| {
+                    events
+                        .lock()
+                        .unwrap()
+                        .push((p.event, p.version, p.current, p.total))
+                })
+                .await?;
+
+            use ProgressEvent::*;
+            assert_eq!(
+                events.into_inner().unwrap(),
+                vec![
+                    (MigrationStarted, 1, 1, 2),
+                    (StatementStarted, 1, 1, 2),
+                    (StatementFinished, 1, 1, 2),
+                    (StatementStarted, 1, 2, 2),
+                    (StatementFinished, 1, 2, 2),
+                    (MigrationFinished, 1, 1, 2),
+                    (MigrationStarted, 2, 2, 2),
+                    (StatementStarted, 2, 1, 1),
+                    (StatementFinished, 2, 1, 1),
+                    (MigrationFinished, 2, 2, 2),
+                ]
+            );
+
+            Ok(())
+        }
+    }
 }
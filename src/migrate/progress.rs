@@ -0,0 +1,26 @@
+/// Step of the migration process a [`Progress`] notification refers to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ProgressEvent {
+    MigrationStarted,
+    MigrationFinished,
+    StatementStarted,
+    StatementFinished,
+}
+
+/// Progress notification passed to the callback given to
+/// [`Migrator::migrate_with_progress`](crate::migrate::Migrator::migrate_with_progress).
+#[derive(Clone, Debug)]
+pub struct Progress {
+    pub event: ProgressEvent,
+    /// Version of the migration being executed.
+    pub version: i64,
+    /// Position (1-based) of the current migration in the plan, or of the
+    /// current statement in the migration being executed.
+    pub current: usize,
+    /// Number of migrations in the plan, or of statements in the migration being executed.
+    pub total: usize,
+    /// Time elapsed since the beginning of the migration process.
+    pub elapsed: chrono::Duration,
+}
+
+pub type ProgressCallback<'a> = dyn Fn(&Progress) + Send + Sync + 'a;
@@ -183,6 +183,7 @@ impl TryFrom<Parameter> for Vec<u8> {
             Value::DateTime(dt) => Ok(dt.to_rfc3339().into_bytes()),
             Value::Json(json) => Ok(json.to_string().into_bytes()),
             Value::Uuid(uuid) => Ok(uuid.to_string().into_bytes()),
+            Value::Range(range) => Ok(range.to_string().into_bytes()),
             _ => Err(Error::type_mismatch()),
         }
     }
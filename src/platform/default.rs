@@ -4,12 +4,14 @@ use crate::event::{
     SchemaAlterTableAddColumnEvent, SchemaAlterTableRemoveColumnEvent, SchemaCreateTableEvent,
     SchemaDropTableEvent,
 };
-use crate::r#type::{TypeManager, TypePtr};
+use crate::query::{Expression, MergeBuilder};
+use crate::r#type::{TypeManager, TypePtr, DATETIMETZ};
 use crate::schema::{
     get_database, string_from_value, Asset, CheckConstraint, Column, ColumnData, ColumnDiff,
     ColumnList, FKConstraintList, ForeignKeyConstraint, ForeignKeyReferentialAction, Identifier,
-    Index, IndexOptions, IntoIdentifier, SchemaManager, Sequence, Table, TableDiff, TableList,
-    TableOptions, UniqueConstraint, View,
+    Index, IndexOptions, IndexStatistics, IntoIdentifier, ReplicaIdentity, SchemaManager, Sequence,
+    Table, TableDiff, TableGrant, TableKind, TableList, TableOptions, TableTimestamps,
+    UniqueConstraint, View,
 };
 use crate::util::{filter_asset_names, function_name};
 use crate::{
@@ -17,6 +19,7 @@ use crate::{
     SchemaAlterTableEvent, SchemaAlterTableRenameColumnEvent, SchemaColumnDefinitionEvent,
     SchemaCreateTableColumnEvent, SchemaIndexDefinitionEvent, TransactionIsolationLevel, Value,
 };
+use chrono::{DateTime, Utc};
 use creed::schema::IndexList;
 use itertools::Itertools;
 use regex::Regex;
@@ -78,7 +81,7 @@ pub fn get_mod_expression(expression1: &str, expression2: &str) -> Result<String
     Ok(format!("MOD({}, {})", expression1, expression2))
 }
 
-pub fn get_trim_expression(str: &str, mode: TrimMode, char: Option<String>) -> Result<String> {
+pub fn get_trim_expression(str: &str, mode: TrimMode, chars: Option<String>) -> Result<String> {
     let mut expression = "".to_string();
 
     match mode {
@@ -88,11 +91,11 @@ pub fn get_trim_expression(str: &str, mode: TrimMode, char: Option<String>) -> R
         _ => {}
     }
 
-    if let Some(ref char) = char {
-        expression += &format!("{} ", char.clone());
+    if let Some(ref chars) = chars {
+        expression += &format!("{} ", chars);
     }
 
-    if mode != TrimMode::Unspecified || char.is_some() {
+    if mode != TrimMode::Unspecified || chars.is_some() {
         expression += "FROM ";
     }
 
@@ -270,6 +273,37 @@ pub fn append_lock_hint(from_clause: &str, lock_mode: LockMode) -> Result<String
     }
 }
 
+pub fn get_merge_sql(this: &dyn DatabasePlatform, merge: &MergeBuilder) -> Result<String> {
+    merge.to_merge_sql(this)
+}
+
+/// Builds the inline table as a chain of SELECT statements joined by UNION ALL,
+/// which is understood by every platform.
+pub fn get_values_table_sql(
+    rows: &[Vec<Value>],
+    alias: &str,
+    columns: &[&str],
+) -> Result<Expression> {
+    let selects = rows
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let placeholders = if i == 0 {
+                columns.iter().map(|c| format!("? AS {}", c)).join(", ")
+            } else {
+                row.iter().map(|_| "?").join(", ")
+            };
+
+            format!("SELECT {}", placeholders)
+        })
+        .join(" UNION ALL ");
+
+    Ok(Expression::with_parameters(
+        format!("({}) {}", selects, alias),
+        rows.iter().flatten().cloned().collect(),
+    ))
+}
+
 pub fn get_read_lock_sql(this: &dyn DatabasePlatform) -> Result<String> {
     this.get_for_update_sql()
 }
@@ -278,6 +312,11 @@ pub fn get_write_lock_sql(this: &dyn DatabasePlatform) -> Result<String> {
     this.get_for_update_sql()
 }
 
+#[allow(unused_variables)]
+pub fn get_write_lock_of_sql(this: &dyn DatabasePlatform, tables: &[&str]) -> Result<String> {
+    this.get_write_lock_sql()
+}
+
 pub fn get_drop_table_sql(
     this: &dyn SchemaManager,
     table_name: &dyn IntoIdentifier,
@@ -361,8 +400,22 @@ pub fn get_create_table_sql(
         ));
     }
 
+    let max_columns = platform.get_max_columns_per_table();
+    if table.columns().len() > max_columns {
+        return Err(Error::too_many_columns_for_table(
+            table.get_table_name(),
+            table.columns().len(),
+            max_columns,
+        ));
+    }
+
     let mut options = TableOptions {
+        charset: table.get_charset(),
+        collation: table.get_collation(),
         comment: table.get_comment().map(|c| c.to_string()),
+        owner: table.get_owner().map(|o| o.to_string()),
+        grants: table.get_grants().to_vec(),
+        replica_identity: table.get_replica_identity().cloned(),
         ..Default::default()
     };
 
@@ -459,6 +512,29 @@ pub fn get_create_table_sql(
         }
     }
 
+    if platform.supports_table_owner() {
+        if let Some(owner) = options.owner.as_deref() {
+            sql.push(this.get_alter_table_owner_sql(table.get_table_name(), owner)?);
+        }
+    }
+
+    if platform.supports_grants() {
+        for grant in &options.grants {
+            sql.push(this.get_grant_sql(table.get_table_name(), grant)?);
+        }
+    }
+
+    if platform.supports_replica_identity() {
+        if let Some(replica_identity) = &options.replica_identity {
+            sql.push(
+                this.get_alter_table_replica_identity_sql(
+                    table.get_table_name(),
+                    replica_identity,
+                )?,
+            );
+        }
+    }
+
     Ok(sql)
 }
 
@@ -506,6 +582,43 @@ pub fn get_comment_on_table_sql(
     ))
 }
 
+pub fn get_grant_sql(
+    platform: &dyn DatabasePlatform,
+    table_name: &Identifier,
+    grant: &TableGrant,
+) -> Result<String> {
+    let grantee = grant.get_grantee();
+    let grantee = if grantee.eq_ignore_ascii_case("PUBLIC") {
+        "PUBLIC".to_string()
+    } else {
+        Identifier::new(grantee, true).get_quoted_name(platform)
+    };
+
+    get_grant_to_sql(platform, table_name, grant, &grantee)
+}
+
+/// Returns the GRANT statement for the given (already quoted) grantee.
+pub fn get_grant_to_sql(
+    platform: &dyn DatabasePlatform,
+    table_name: &Identifier,
+    grant: &TableGrant,
+    quoted_grantee: &str,
+) -> Result<String> {
+    if grant.get_privileges().is_empty() {
+        return Err(Error::grant_definition_invalid(
+            grant.get_grantee(),
+            "at least one privilege is required",
+        ));
+    }
+
+    Ok(format!(
+        "GRANT {} ON {} TO {}",
+        grant.get_privileges().join(", "),
+        table_name.get_quoted_name(platform),
+        quoted_grantee
+    ))
+}
+
 pub fn get_comment_on_column_sql(
     platform: &dyn DatabasePlatform,
     table_name: &dyn IntoIdentifier,
@@ -614,12 +727,17 @@ pub fn get_create_index_sql(
         let name = index.get_quoted_name(platform.as_dyn());
 
         Ok(format!(
-            "CREATE {}INDEX {} ON {} ({}){}",
+            "CREATE {}INDEX {} ON {} ({}){}{}{}",
             this.get_create_index_sql_flags(index),
             name,
             table,
             this.get_index_field_declaration_list_sql(index)?,
-            this.get_partial_index_sql(index)?
+            get_nulls_not_distinct_sql(
+                platform.as_dyn(),
+                index.is_unique() && index.is_nulls_not_distinct()
+            )?,
+            this.get_partial_index_sql(index)?,
+            get_index_visibility_sql(platform.as_dyn(), index)
         ))
     }
 }
@@ -634,6 +752,30 @@ pub fn get_partial_index_sql(platform: &dyn DatabasePlatform, index: &Index) ->
     )
 }
 
+/// Gets the clause making a unique index or constraint treat null values as equal.
+/// Errors if requested on a platform not supporting it.
+pub fn get_nulls_not_distinct_sql(
+    platform: &dyn DatabasePlatform,
+    nulls_not_distinct: bool,
+) -> Result<&'static str> {
+    if !nulls_not_distinct {
+        Ok("")
+    } else if platform.supports_nulls_not_distinct() {
+        Ok(" NULLS NOT DISTINCT")
+    } else {
+        Err(Error::platform_feature_unsupported("NULLS NOT DISTINCT"))
+    }
+}
+
+/// Gets the suffix marking an index as invisible, on platforms supporting invisible indexes.
+pub fn get_index_visibility_sql(platform: &dyn DatabasePlatform, index: &Index) -> &'static str {
+    if platform.supports_invisible_indexes() && !index.is_visible() && !index.is_primary() {
+        " INVISIBLE"
+    } else {
+        ""
+    }
+}
+
 pub fn get_create_index_sql_flags(index: &Index) -> String {
     if index.is_unique() {
         "UNIQUE ".to_string()
@@ -677,9 +819,10 @@ pub fn get_create_unique_constraint_sql(
 ) -> Result<String> {
     let table = table_name.into_identifier().get_quoted_name(platform);
     let query = format!(
-        "ALTER TABLE {} ADD CONSTRAINT {} UNIQUE ({})",
+        "ALTER TABLE {} ADD CONSTRAINT {} UNIQUE{} ({})",
         table,
         constraint.get_quoted_name(platform),
+        get_nulls_not_distinct_sql(platform, constraint.is_nulls_not_distinct())?,
         constraint.get_quoted_columns(platform).join(", ")
     );
 
@@ -953,6 +1096,14 @@ pub fn get_column_declaration_sql(
             .as_ref()
             .map(|v| format!(" {}", this.get_column_charset_declaration_sql(v)))
             .unwrap_or_default();
+        let compression = column
+            .compression
+            .as_ref()
+            .map(|c| this.get_column_compression_declaration_sql(c))
+            .transpose()?
+            .filter(|c| !c.is_empty())
+            .map(|c| format!(" {}", c))
+            .unwrap_or_default();
         let collation = if let Some(collation) = column.collation.as_ref() {
             if !collation.is_empty() {
                 format!(" {}", this.get_column_collation_declaration_sql(collation)?)
@@ -984,8 +1135,16 @@ pub fn get_column_declaration_sql(
         }
 
         format!(
-            "{}{}{}{}{}{}{}{}",
-            type_decl, charset, default, not_null, unique, check, collation, comment_decl
+            "{}{}{}{}{}{}{}{}{}",
+            type_decl,
+            charset,
+            compression,
+            default,
+            not_null,
+            unique,
+            check,
+            collation,
+            comment_decl
         )
     };
 
@@ -1004,7 +1163,9 @@ pub fn get_default_value_declaration_sql(
     column: &ColumnData,
 ) -> Result<String> {
     let default = &column.default;
-    if matches!(default, &Value::NULL) {
+    if let Some(expression) = &column.default_expression {
+        Ok(format!(" DEFAULT {}", expression))
+    } else if matches!(default, &Value::NULL) {
         Ok((if column.notnull { "" } else { " DEFAULT NULL" }).to_string())
     } else {
         let t = column.r#type.clone();
@@ -1083,9 +1244,10 @@ pub fn get_unique_constraint_declaration_sql(
         let column_list_names = this.get_columns_field_declaration_list_sql(columns.as_slice())?;
 
         Ok(format!(
-            "CONSTRAINT {} {} ({})",
+            "CONSTRAINT {} {}{} ({})",
             constraint_name,
             constraint_flags.join(" "),
+            get_nulls_not_distinct_sql(platform.as_dyn(), constraint.is_nulls_not_distinct())?,
             column_list_names
         ))
     }
@@ -1104,11 +1266,16 @@ pub fn get_index_declaration_sql(
         Err(Error::index_definition_invalid("columns"))
     } else {
         Ok(format!(
-            "{}INDEX {} ({}){}",
+            "{}INDEX {} ({}){}{}{}",
             this.get_create_index_sql_flags(index),
             name.get_quoted_name(platform.as_dyn()),
             this.get_index_field_declaration_list_sql(index)?,
-            this.get_partial_index_sql(index)?
+            get_nulls_not_distinct_sql(
+                platform.as_dyn(),
+                index.is_unique() && index.is_nulls_not_distinct()
+            )?,
+            this.get_partial_index_sql(index)?,
+            get_index_visibility_sql(platform.as_dyn(), index)
         ))
     }
 }
@@ -1219,6 +1386,10 @@ pub fn get_column_charset_declaration_sql() -> String {
     "".to_string()
 }
 
+pub fn get_column_compression_declaration_sql() -> Result<String> {
+    Ok("".to_string())
+}
+
 pub fn get_column_collation_declaration_sql(
     platform: &dyn DatabasePlatform,
     collation: &str,
@@ -1284,6 +1455,25 @@ pub fn get_create_view_sql(platform: &dyn DatabasePlatform, view: &View) -> Resu
     ))
 }
 
+pub fn get_create_table_as_sql(
+    platform: &dyn DatabasePlatform,
+    name: &Identifier,
+    select_sql: &str,
+    with_data: bool,
+) -> Result<String> {
+    if !with_data {
+        return Err(Error::platform_feature_unsupported(
+            "create table as select with no data",
+        ));
+    }
+
+    Ok(format!(
+        "CREATE TABLE {} AS {}",
+        name.get_quoted_name(platform),
+        select_sql
+    ))
+}
+
 pub fn get_drop_view_sql(
     platform: &dyn DatabasePlatform,
     sequence: &dyn IntoIdentifier,
@@ -1365,6 +1555,14 @@ pub fn get_max_identifier_length() -> usize {
     63
 }
 
+pub fn get_max_columns_per_table() -> usize {
+    1600
+}
+
+pub fn get_max_bind_parameters() -> usize {
+    65535
+}
+
 pub fn get_empty_identity_insert_sql(
     quoted_table_name: &str,
     quoted_identifier_column_name: &str,
@@ -1375,6 +1573,19 @@ pub fn get_empty_identity_insert_sql(
     )
 }
 
+pub fn get_analyze_table_sql(
+    this: &dyn SchemaManager,
+    table_name: &dyn IntoIdentifier,
+) -> Result<Vec<String>> {
+    let platform = this.get_platform()?;
+    Ok(vec![format!(
+        "ANALYZE {}",
+        table_name
+            .into_identifier()
+            .get_quoted_name(platform.as_dyn())
+    )])
+}
+
 pub fn get_truncate_table_sql(
     this: &dyn SchemaManager,
     table_name: &dyn IntoIdentifier,
@@ -1388,6 +1599,17 @@ pub fn get_truncate_table_sql(
     ))
 }
 
+pub fn get_truncate_tables_sql(this: &dyn SchemaManager, tables: &[&str]) -> Result<Vec<String>> {
+    let platform = this.get_platform()?;
+    Ok(vec![format!(
+        "TRUNCATE {}",
+        tables
+            .iter()
+            .map(|t| t.into_identifier().get_quoted_name(platform.as_dyn()))
+            .join(", ")
+    )])
+}
+
 pub fn create_save_point(savepoint: &str) -> String {
     format!("SAVEPOINT {}", savepoint)
 }
@@ -1490,6 +1712,68 @@ pub async fn list_table_indexes(this: &dyn SchemaManager, table: String) -> Resu
         .await
 }
 
+/// Retrieves the usage and cardinality statistics of the indexes of the given table.
+pub async fn get_index_statistics(
+    this: &dyn SchemaManager,
+    table: String,
+) -> Result<Vec<IndexStatistics>> {
+    let database = get_database(this.get_connection(), function_name!()).await?;
+    let sql = this.get_index_statistics_sql(&table, &database)?;
+    let rows = this.get_connection().fetch_all(sql, params!()).await?;
+
+    this.get_portable_index_statistics_list(rows)
+}
+
+pub async fn get_table_kind(this: &dyn SchemaManager, name: String) -> Result<TableKind> {
+    let database = get_database(this.get_connection(), function_name!()).await?;
+    let sql = this.get_table_kind_sql(&name, &database)?;
+    let Some(row) = this
+        .get_connection()
+        .query(sql, params!())
+        .await?
+        .fetch_one()
+        .await?
+    else {
+        return Err(Error::table_does_not_exist(&name));
+    };
+
+    match string_from_value(this.get_connection(), row.get("kind"))?.as_str() {
+        "VIEW" => Ok(TableKind::View),
+        "MATERIALIZED VIEW" => Ok(TableKind::MaterializedView),
+        "FOREIGN" => Ok(TableKind::Foreign),
+        _ => Ok(TableKind::Base),
+    }
+}
+
+pub async fn get_table_timestamps(
+    this: &dyn SchemaManager,
+    name: String,
+) -> Result<TableTimestamps> {
+    let database = get_database(this.get_connection(), function_name!()).await?;
+    let sql = this.get_table_timestamps_sql(&name, &database)?;
+    let connection = this.get_connection();
+    let Some(row) = connection.query(sql, params!()).await?.fetch_one().await? else {
+        return Err(Error::table_does_not_exist(&name));
+    };
+
+    let timestamp = |column: &str| -> Result<Option<DateTime<Utc>>> {
+        match connection.convert_value(row.get(column)?, DATETIMETZ)? {
+            Value::NULL => Ok(None),
+            Value::DateTime(dt) => Ok(Some(dt.with_timezone(&Utc))),
+            value => Err(Error::conversion_failed_invalid_type(
+                &value,
+                DATETIMETZ,
+                &["DateTime"],
+            )),
+        }
+    };
+
+    Ok(TableTimestamps::new(
+        timestamp("created")?,
+        timestamp("updated")?,
+    ))
+}
+
 /// Whether all the given tables exist.
 pub async fn tables_exist(this: &dyn SchemaManager, names: Vec<String>) -> Result<bool> {
     let table_names = this
@@ -1580,6 +1864,18 @@ pub async fn list_table_details(this: &dyn SchemaManager, name: String) -> Resul
                 table.set_comment(str);
             }
         }
+
+        if let Ok(Value::String(replica_identity)) = opt.get("replica_identity") {
+            table.set_replica_identity(match replica_identity.as_str() {
+                "full" => Some(ReplicaIdentity::Full),
+                "nothing" => Some(ReplicaIdentity::Nothing),
+                "index" => match opt.get("replica_identity_index") {
+                    Ok(Value::String(index)) => Some(ReplicaIdentity::UsingIndex(index.clone())),
+                    _ => None,
+                },
+                _ => Some(ReplicaIdentity::Default),
+            });
+        }
     }
 
     Ok(table)
@@ -1671,6 +1967,7 @@ pub async fn list_views(this: &dyn SchemaManager) -> Result<Vec<View>> {
     let views = this.get_connection().fetch_all(sql, params!()).await?;
 
     this.get_portable_views_list(views)
+        .map(|views| filter_asset_names(this.get_connection(), views))
 }
 
 /// Lists the foreign keys for the given table.
@@ -1685,6 +1982,22 @@ pub async fn list_table_foreign_keys(
     this.get_portable_table_foreign_keys_list(table_foreign_keys)
 }
 
+pub fn get_portable_index_statistics_list(
+    this: &dyn SchemaManager,
+    rows: Vec<Row>,
+) -> Result<Vec<IndexStatistics>> {
+    let connection = this.get_connection();
+    rows.into_iter()
+        .map(|row| {
+            Ok(IndexStatistics::new(
+                string_from_value(connection, row.get("index_name"))?,
+                row.get("cardinality").and_then(u64::try_from).ok(),
+                row.get("scans").and_then(u64::try_from).ok(),
+            ))
+        })
+        .collect()
+}
+
 pub fn get_portable_table_indexes_list(
     this: &dyn SchemaManager,
     table_indexes: Vec<Row>,
@@ -1740,6 +2053,9 @@ pub fn get_portable_table_indexes_list(
                             _ => Err("invalid".into()),
                         })
                         .ok(),
+                    visible: table_index.get("visible").map_or(true, bool::from),
+                    nulls_not_distinct: table_index.get("nulls_not_distinct").is_ok_and(bool::from),
+                    constraint_backed: table_index.get("constraint_backed").is_ok_and(bool::from),
                 });
             }
             Occupied(mut e) => {
@@ -4,10 +4,12 @@ pub mod default;
 mod keyword;
 mod lock_mode;
 mod trim_mode;
+mod upsert_reporting;
 
+use crate::query::{Expression, MergeBuilder, QueryBuilder};
 use crate::r#type::{TypeManager, TypePtr};
 use crate::schema::ColumnData;
-use crate::schema::SchemaManager;
+use crate::schema::{ForeignKeyReferentialAction, SchemaManager};
 use crate::{Connection, Error, EventDispatcher, Result, TransactionIsolationLevel, Value};
 pub use create_flags::CreateFlags;
 pub use date_interval_unit::DateIntervalUnit;
@@ -16,7 +18,9 @@ pub use lock_mode::LockMode;
 use std::any::TypeId;
 use std::fmt::{Debug, Display};
 use std::sync::Arc;
+use std::time::Duration;
 pub use trim_mode::TrimMode;
+pub use upsert_reporting::UpsertReporting;
 
 pub(crate) macro platform_debug($platform:ident) {
     impl std::fmt::Debug for $platform {
@@ -89,6 +93,31 @@ pub trait DatabasePlatform: Debug {
         default::get_json_type_declaration_sql(self.as_dyn(), column)
     }
 
+    /// Returns the SQL snippet to declare a column backed by a user-defined composite type.
+    #[allow(unused_variables)]
+    fn get_composite_type_declaration_sql(&self, column: &ColumnData) -> Result<String> {
+        Err(Error::platform_feature_unsupported("composite types"))
+    }
+
+    /// Returns the SQL snippet to declare a column storing a range of integers.
+    #[allow(unused_variables)]
+    fn get_integer_range_type_declaration_sql(&self, column: &ColumnData) -> Result<String> {
+        Err(Error::platform_feature_unsupported("range types"))
+    }
+
+    /// Returns the SQL snippet to declare a column storing a range of timestamps with time zone.
+    #[allow(unused_variables)]
+    fn get_date_time_tz_range_type_declaration_sql(&self, column: &ColumnData) -> Result<String> {
+        Err(Error::platform_feature_unsupported("range types"))
+    }
+
+    /// Returns the SQL snippet to declare a column storing a duration.
+    ///
+    /// Platforms without a native interval type store the number of seconds.
+    fn get_duration_type_declaration_sql(&self, column: &ColumnData) -> Result<String> {
+        self.get_bigint_type_declaration_sql(column)
+    }
+
     #[allow(unused_variables)]
     fn get_varchar_type_declaration_sql_snippet(
         &self,
@@ -173,11 +202,35 @@ pub trait DatabasePlatform: Debug {
         ))
     }
 
+    /// Returns the case-insensitive regular expression operator.
+    fn get_case_insensitive_regexp_expression(&self) -> Result<String> {
+        Err(Error::platform_feature_unsupported(
+            "Case-insensitive REGEXP expressions are not supported by this platform.",
+        ))
+    }
+
     /// Returns the SQL snippet to get the length of a text column in characters.
     fn get_length_expression(&self, column: &str) -> Result<String> {
         default::get_length_expression(column)
     }
 
+    /// Returns the SQL snippet to check whether the JSON document in column
+    /// contains the given JSON value.
+    #[allow(unused_variables)]
+    fn get_json_contains_expression(&self, column: &str, value: &str) -> Result<String> {
+        Err(Error::platform_feature_unsupported(
+            "JSON contains expressions are not supported by this platform.",
+        ))
+    }
+
+    /// Returns the SQL snippet to get the length of the JSON array in column.
+    #[allow(unused_variables)]
+    fn get_json_array_length_expression(&self, column: &str) -> Result<String> {
+        Err(Error::platform_feature_unsupported(
+            "JSON array length expressions are not supported by this platform.",
+        ))
+    }
+
     /// Returns the SQL snippet to get the remainder of the division operation $expression1 / $expression2.
     fn get_mod_expression(&self, expression1: &str, expression2: &str) -> Result<String> {
         default::get_mod_expression(expression1, expression2)
@@ -189,14 +242,17 @@ pub trait DatabasePlatform: Debug {
     ///
     /// * `str` - The expression to apply the trim to.
     /// * `mode` - The position of the trim (leading/trailing/both).
-    /// * `char` - The char to trim, has to be quoted already. Defaults to space.
+    /// * `chars` - The set of chars to trim, has to be quoted already. Defaults to space.
+    ///
+    /// PostgreSQL and SQLite remove any of the given chars from the string ends,
+    /// while MySQL removes the repeated occurrences of the whole given string.
     fn get_trim_expression(
         &self,
         str: &str,
         mode: TrimMode,
-        char: Option<String>,
+        chars: Option<String>,
     ) -> Result<String> {
-        default::get_trim_expression(str, mode, char)
+        default::get_trim_expression(str, mode, chars)
     }
 
     /// Returns the SQL snippet to get the position of the first occurrence of substring $substr in string `str`.
@@ -406,6 +462,15 @@ pub trait DatabasePlatform: Debug {
         default::get_write_lock_sql(self.as_dyn())
     }
 
+    /// Returns the SQL snippet to append to any SELECT statement which obtains an exclusive lock
+    /// on the rows of the given tables only.
+    ///
+    /// Platforms which cannot restrict the lock to some of the tables in the query
+    /// fall back to the write lock on all the selected rows.
+    fn get_write_lock_of_sql(&self, tables: &[&str]) -> Result<String> {
+        default::get_write_lock_of_sql(self.as_dyn(), tables)
+    }
+
     /// Gets the comment to append to a column comment that helps parsing this type in reverse engineering.
     fn get_creed_type_comment(&self, creed_type: &TypePtr) -> String {
         default::get_creed_type_comment(creed_type)
@@ -506,6 +571,12 @@ pub trait DatabasePlatform: Debug {
         default::get_current_timestamp_sql()
     }
 
+    /// Returns the SQL to get the current UTC timestamp, without time zone information,
+    /// on platforms whose current timestamp is not time zone aware.
+    fn get_current_utc_timestamp_sql(&self) -> Option<&str> {
+        None
+    }
+
     /// Returns the SQL for a given transaction isolation level Connection constant.
     /// # Protected
     fn get_transaction_isolation_level_sql(&self, level: TransactionIsolationLevel) -> String {
@@ -584,6 +655,12 @@ pub trait DatabasePlatform: Debug {
         false
     }
 
+    /// Whether the platform distinguishes standard identity columns
+    /// (`GENERATED ... AS IDENTITY`) from other auto-increment columns.
+    fn supports_generated_identity_columns(&self) -> bool {
+        false
+    }
+
     /// Whether the platform supports partial indexes.
     fn supports_partial_indexes(&self) -> bool {
         false
@@ -594,6 +671,27 @@ pub trait DatabasePlatform: Debug {
         false
     }
 
+    /// Whether the platform supports indexes invisible to the query optimizer.
+    fn supports_invisible_indexes(&self) -> bool {
+        false
+    }
+
+    /// Whether the platform supports unique indexes and constraints treating nulls as not distinct.
+    fn supports_nulls_not_distinct(&self) -> bool {
+        false
+    }
+
+    /// Whether the platform supports the DEFAULT keyword in INSERT value lists
+    /// and UPDATE assignments.
+    fn supports_default_in_insert_values(&self) -> bool {
+        true
+    }
+
+    /// Whether the platform supports the RETURNING clause in INSERT statements.
+    fn supports_insert_returning(&self) -> bool {
+        false
+    }
+
     /// Whether the platform supports savepoints.
     fn supports_savepoints(&self) -> bool {
         true
@@ -631,6 +729,52 @@ pub trait DatabasePlatform: Debug {
         false
     }
 
+    /// Whether this platform supports changing the owner of a table.
+    fn supports_table_owner(&self) -> bool {
+        false
+    }
+
+    /// Whether this platform supports granting privileges on a table.
+    fn supports_grants(&self) -> bool {
+        false
+    }
+
+    /// Whether this platform supports setting the replica identity of a table.
+    fn supports_replica_identity(&self) -> bool {
+        false
+    }
+
+    /// Normalizes a foreign key referential action for comparison, so that the actions
+    /// behaving identically on this platform are reported as the same action.
+    /// An unspecified action is normalized to the default (NO ACTION).
+    fn normalize_foreign_key_referential_action(
+        &self,
+        action: Option<ForeignKeyReferentialAction>,
+    ) -> ForeignKeyReferentialAction {
+        action.unwrap_or(ForeignKeyReferentialAction::NoAction)
+    }
+
+    /// Whether this platform supports ordered-set aggregates
+    /// (ie: `percentile_cont(0.5) WITHIN GROUP (ORDER BY x)`).
+    fn supports_ordered_set_aggregates(&self) -> bool {
+        false
+    }
+
+    /// Whether this platform supports named windows declared in the `WINDOW` clause.
+    fn supports_named_windows(&self) -> bool {
+        false
+    }
+
+    /// Whether this platform supports the SQL `OVERLAPS` predicate between two periods.
+    fn supports_overlaps_predicate(&self) -> bool {
+        false
+    }
+
+    /// Whether this platform supports range types and their operators (ie: `@>`, `<@` and `&&`).
+    fn supports_range_types(&self) -> bool {
+        false
+    }
+
     /// Does this platform have native guid type.
     fn has_native_guid_type(&self) -> bool {
         false
@@ -641,11 +785,21 @@ pub trait DatabasePlatform: Debug {
         false
     }
 
+    /// Does this platform have native interval type.
+    fn has_native_interval_type(&self) -> bool {
+        false
+    }
+
     /// Does this platform support column collation?
     fn supports_column_collation(&self) -> bool {
         false
     }
 
+    /// Does this platform support setting the compression method of a column?
+    fn supports_column_compression(&self) -> bool {
+        false
+    }
+
     /// Gets the format string, as accepted by the date() function, that describes
     /// the format of a stored datetime value of this platform.
     fn get_date_time_format_string(&self) -> &str {
@@ -685,6 +839,16 @@ pub trait DatabasePlatform: Debug {
         default::get_max_identifier_length()
     }
 
+    /// Maximum number of columns a table can have.
+    fn get_max_columns_per_table(&self) -> usize {
+        default::get_max_columns_per_table()
+    }
+
+    /// Maximum number of parameters which can be bound to a single statement.
+    fn get_max_bind_parameters(&self) -> usize {
+        default::get_max_bind_parameters()
+    }
+
     /// Returns the insert SQL for an empty insert statement.
     fn get_empty_identity_insert_sql(
         &self,
@@ -723,6 +887,115 @@ pub trait DatabasePlatform: Debug {
         default::get_like_wildcard_characters()
     }
 
+    /// Returns the SQL for the given merge statement.
+    /// Platforms without MERGE support should translate it to their upsert form.
+    fn get_merge_sql(&self, merge: &MergeBuilder) -> Result<String> {
+        default::get_merge_sql(self.as_dyn(), merge)
+    }
+
+    /// Returns the SQL of the merge statement executed as an upsert, whose outcome
+    /// is reported as described by [`DatabasePlatform::get_upsert_reporting`].
+    fn get_upsert_sql(&self, merge: &MergeBuilder) -> Result<String> {
+        self.get_merge_sql(merge)
+    }
+
+    /// Returns how the outcome of each row of an upsert statement is reported.
+    fn get_upsert_reporting(&self) -> UpsertReporting {
+        UpsertReporting::WrittenRows
+    }
+
+    /// Returns the SQL for an UPDATE query correlated with other tables
+    /// (added through [`QueryBuilder::update_from`] or [`QueryBuilder::update_join`]).
+    fn get_update_from_sql(&self, query: &QueryBuilder) -> Result<Expression> {
+        query.to_update_from_sql()
+    }
+
+    /// Returns the SQL for an inline table built from a list of rows
+    /// (added through [`QueryBuilder::from_values`]), binding the values as parameters.
+    fn get_values_table_sql(
+        &self,
+        rows: &[Vec<Value>],
+        alias: &str,
+        columns: &[&str],
+    ) -> Result<Expression> {
+        default::get_values_table_sql(rows, alias, columns)
+    }
+
+    /// Returns the SQL to retrieve the current replication log position
+    /// (e.g. binlog coordinates or WAL LSN), if supported by the platform.
+    fn get_replication_position_sql(&self) -> Option<String> {
+        None
+    }
+
+    /// Returns the SQL retrieving the execution plan of the given query in JSON format,
+    /// including the planner estimated cost, if supported by the platform.
+    #[allow(unused_variables)]
+    fn get_estimated_cost_sql(&self, sql: &str) -> Option<String> {
+        None
+    }
+
+    /// Returns the SQL retrieving the value generated for the auto-increment column
+    /// (or the sequence) by the last INSERT statement executed in the current session.
+    fn get_last_insert_id_sql(&self) -> Result<String> {
+        Err(Error::platform_feature_unsupported(
+            "Last insert id retrieval is not supported by this platform.",
+        ))
+    }
+
+    /// Returns the SQL acquiring the advisory lock identified by the given key.
+    /// The query must return a single truthy value if the lock has been acquired.
+    ///
+    /// If `wait` is false, the query must return immediately (with a falsy value
+    /// or no row) if the lock is held by another session. Platforms unable to wait
+    /// for a lock can return immediately anyway: acquisition is retried until successful.
+    ///
+    /// Platforms emulating advisory locks record the `owner` token of the acquiring
+    /// connection into the given `table`, and can take over the locks held for longer
+    /// than `timeout`. They return an error if no table is given.
+    #[allow(unused_variables)]
+    fn get_advisory_lock_sql(
+        &self,
+        key: i64,
+        owner: &str,
+        table: Option<&str>,
+        timeout: Option<Duration>,
+        wait: bool,
+    ) -> Result<String> {
+        Err(Error::platform_feature_unsupported("advisory locks"))
+    }
+
+    /// Returns the SQL releasing the advisory lock identified by the given key.
+    /// The query must return a single truthy value if the lock was held by the current session
+    /// (identified by the `owner` token on platforms emulating advisory locks).
+    #[allow(unused_variables)]
+    fn get_advisory_unlock_sql(
+        &self,
+        key: i64,
+        owner: &str,
+        table: Option<&str>,
+    ) -> Result<String> {
+        Err(Error::platform_feature_unsupported("advisory locks"))
+    }
+
+    /// Returns the SQL probing whether the given server feature is available.
+    /// The query must return at least one row if the feature is available.
+    ///
+    /// Features are identified by their kind and name, separated by a colon:
+    /// * `extension:<name>` an installed extension (PostgreSQL);
+    /// * `plugin:<name>` an active plugin (MySQL);
+    /// * `function:<name>` an available SQL function (SQLite).
+    #[allow(unused_variables)]
+    fn get_feature_probe_sql(&self, feature: &str) -> Result<String> {
+        Err(Error::platform_feature_unsupported("feature probes"))
+    }
+
+    /// Returns the SQL creating the given table used to emulate advisory locks,
+    /// on platforms which do not support them natively.
+    #[allow(unused_variables)]
+    fn get_advisory_locks_table_sql(&self, table: &str) -> Option<String> {
+        None
+    }
+
     fn create_schema_manager<'a>(&self, connection: &'a Connection) -> Box<dyn SchemaManager + 'a>;
 }
 
@@ -742,6 +1015,10 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for &mut P {
             fn get_binary_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
             fn get_guid_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
             fn get_json_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_composite_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_integer_range_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_date_time_tz_range_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_duration_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
             fn get_varchar_type_declaration_sql_snippet(&self, length: Option<usize>, fixed: bool) -> Result<String>;
             fn get_binary_type_declaration_sql_snippet(&self, length: Option<usize>, fixed: bool) -> Result<String>;
             fn get_clob_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
@@ -750,9 +1027,12 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for &mut P {
             fn get_type_mapping(&self, db_type: &str) -> Result<TypeId>;
             fn has_type_mapping_for(&self, db_type: &str) -> bool;
             fn get_regexp_expression(&self) -> Result<String>;
+            fn get_case_insensitive_regexp_expression(&self) -> Result<String>;
+            fn get_json_contains_expression(&self, column: &str, value: &str) -> Result<String>;
+            fn get_json_array_length_expression(&self, column: &str) -> Result<String>;
             fn get_length_expression(&self, column: &str) -> Result<String>;
             fn get_mod_expression(&self, expression1: &str, expression2: &str) -> Result<String>;
-            fn get_trim_expression(&self, str: &str, mode: TrimMode, char: Option<String>) -> Result<String>;
+            fn get_trim_expression(&self, str: &str, mode: TrimMode, chars: Option<String>) -> Result<String>;
             fn get_locate_expression(&self, str: &str, substr: &str, start_pos: Option<usize>) -> Result<String>;
             fn get_substring_expression(&self, string: &str, start: usize, length: Option<usize>) -> Result<String>;
             fn get_concat_expression(&self, strings: Vec<&str>) -> Result<String>;
@@ -781,6 +1061,7 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for &mut P {
             fn append_lock_hint(&self, from_clause: &str, lock_mode: LockMode) -> Result<String>;
             fn get_read_lock_sql(&self) -> Result<String>;
             fn get_write_lock_sql(&self) -> Result<String>;
+            fn get_write_lock_of_sql(&self, tables: &[&str]) -> Result<String>;
             fn get_creed_type_comment(&self, creed_type: &TypePtr) -> String;
             fn quote_identifier(&self, identifier: &str) -> String;
             fn quote_single_identifier(&self, str: &str) -> String;
@@ -796,6 +1077,7 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for &mut P {
             fn get_current_date_sql(&self) -> &str;
             fn get_current_time_sql(&self) -> &str;
             fn get_current_timestamp_sql(&self) -> &str;
+            fn get_current_utc_timestamp_sql(&self) -> Option<&str>;
             fn get_transaction_isolation_level_sql(&self, level: TransactionIsolationLevel) -> String;
             fn get_set_transaction_isolation_sql(&self, level: TransactionIsolationLevel) -> Result<String>;
             fn get_date_time_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
@@ -808,8 +1090,13 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for &mut P {
             fn supports_sequences(&self) -> bool;
             fn supports_identity_columns(&self) -> bool;
             fn uses_sequence_emulated_identity_columns(&self) -> bool;
+            fn supports_generated_identity_columns(&self) -> bool;
             fn supports_partial_indexes(&self) -> bool;
             fn supports_column_length_indexes(&self) -> bool;
+            fn supports_invisible_indexes(&self) -> bool;
+            fn supports_nulls_not_distinct(&self) -> bool;
+            fn supports_default_in_insert_values(&self) -> bool;
+            fn supports_insert_returning(&self) -> bool;
             fn supports_savepoints(&self) -> bool;
             fn supports_release_savepoints(&self) -> bool;
             fn supports_foreign_key_constraints(&self) -> bool;
@@ -817,15 +1104,27 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for &mut P {
             fn supports_create_drop_database(&self) -> bool;
             fn supports_inline_column_comments(&self) -> bool;
             fn supports_comment_on_statement(&self) -> bool;
+            fn supports_table_owner(&self) -> bool;
+            fn supports_grants(&self) -> bool;
+            fn supports_replica_identity(&self) -> bool;
+            fn normalize_foreign_key_referential_action(&self, action: Option<ForeignKeyReferentialAction>) -> ForeignKeyReferentialAction;
+            fn supports_ordered_set_aggregates(&self) -> bool;
+            fn supports_named_windows(&self) -> bool;
+            fn supports_overlaps_predicate(&self) -> bool;
+            fn supports_range_types(&self) -> bool;
             fn has_native_guid_type(&self) -> bool;
             fn has_native_json_type(&self) -> bool;
+            fn has_native_interval_type(&self) -> bool;
             fn supports_column_collation(&self) -> bool;
+            fn supports_column_compression(&self) -> bool;
             fn get_date_time_format_string(&self) -> &str;
             fn get_date_time_tz_format_string(&self) -> &str;
             fn get_date_format_string(&self) -> &str;
             fn get_time_format_string(&self) -> &str;
             fn modify_limit_query(&self, query: &str, limit: Option<usize>, offset: Option<usize>) -> String;
             fn get_max_identifier_length(&self) -> usize;
+            fn get_max_columns_per_table(&self) -> usize;
+            fn get_max_bind_parameters(&self) -> usize;
             fn get_empty_identity_insert_sql(&self, quoted_table_name: &str, quoted_identifier_column_name: &str) -> String;
             fn get_dummy_select_sql(&self, expression: Option<&str>) -> String;
             fn create_save_point(&self, savepoint: &str) -> String;
@@ -833,6 +1132,18 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for &mut P {
             fn rollback_save_point(&self, savepoint: &str) -> String;
             fn escape_string_for_like(&self, input_string: &str, escape_char: &str) -> Result<String>;
             fn get_like_wildcard_characters(&self) -> &'static str;
+            fn get_merge_sql(&self, merge: &MergeBuilder) -> Result<String>;
+            fn get_upsert_sql(&self, merge: &MergeBuilder) -> Result<String>;
+            fn get_upsert_reporting(&self) -> UpsertReporting;
+            fn get_update_from_sql(&self, query: &QueryBuilder) -> Result<Expression>;
+            fn get_values_table_sql(&self, rows: &[Vec<Value>], alias: &str, columns: &[&str]) -> Result<Expression>;
+            fn get_replication_position_sql(&self) -> Option<String>;
+            fn get_estimated_cost_sql(&self, sql: &str) -> Option<String>;
+            fn get_last_insert_id_sql(&self) -> Result<String>;
+            fn get_advisory_lock_sql(&self, key: i64, owner: &str, table: Option<&str>, timeout: Option<Duration>, wait: bool) -> Result<String>;
+            fn get_advisory_unlock_sql(&self, key: i64, owner: &str, table: Option<&str>) -> Result<String>;
+            fn get_feature_probe_sql(&self, feature: &str) -> Result<String>;
+            fn get_advisory_locks_table_sql(&self, table: &str) -> Option<String>;
             fn create_schema_manager<'a>(&self, connection: &'a Connection) -> Box<dyn SchemaManager + 'a>;
         }
     }
@@ -854,6 +1165,10 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Box<P> {
             fn get_binary_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
             fn get_guid_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
             fn get_json_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_composite_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_integer_range_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_date_time_tz_range_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_duration_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
             fn get_varchar_type_declaration_sql_snippet(&self, length: Option<usize>, fixed: bool) -> Result<String>;
             fn get_binary_type_declaration_sql_snippet(&self, length: Option<usize>, fixed: bool) -> Result<String>;
             fn get_clob_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
@@ -862,9 +1177,12 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Box<P> {
             fn get_type_mapping(&self, db_type: &str) -> Result<TypeId>;
             fn has_type_mapping_for(&self, db_type: &str) -> bool;
             fn get_regexp_expression(&self) -> Result<String>;
+            fn get_case_insensitive_regexp_expression(&self) -> Result<String>;
+            fn get_json_contains_expression(&self, column: &str, value: &str) -> Result<String>;
+            fn get_json_array_length_expression(&self, column: &str) -> Result<String>;
             fn get_length_expression(&self, column: &str) -> Result<String>;
             fn get_mod_expression(&self, expression1: &str, expression2: &str) -> Result<String>;
-            fn get_trim_expression(&self, str: &str, mode: TrimMode, char: Option<String>) -> Result<String>;
+            fn get_trim_expression(&self, str: &str, mode: TrimMode, chars: Option<String>) -> Result<String>;
             fn get_locate_expression(&self, str: &str, substr: &str, start_pos: Option<usize>) -> Result<String>;
             fn get_substring_expression(&self, string: &str, start: usize, length: Option<usize>) -> Result<String>;
             fn get_concat_expression(&self, strings: Vec<&str>) -> Result<String>;
@@ -893,6 +1211,7 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Box<P> {
             fn append_lock_hint(&self, from_clause: &str, lock_mode: LockMode) -> Result<String>;
             fn get_read_lock_sql(&self) -> Result<String>;
             fn get_write_lock_sql(&self) -> Result<String>;
+            fn get_write_lock_of_sql(&self, tables: &[&str]) -> Result<String>;
             fn get_creed_type_comment(&self, creed_type: &TypePtr) -> String;
             fn quote_identifier(&self, identifier: &str) -> String;
             fn quote_single_identifier(&self, str: &str) -> String;
@@ -908,6 +1227,7 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Box<P> {
             fn get_current_date_sql(&self) -> &str;
             fn get_current_time_sql(&self) -> &str;
             fn get_current_timestamp_sql(&self) -> &str;
+            fn get_current_utc_timestamp_sql(&self) -> Option<&str>;
             fn get_transaction_isolation_level_sql(&self, level: TransactionIsolationLevel) -> String;
             fn get_set_transaction_isolation_sql(&self, level: TransactionIsolationLevel) -> Result<String>;
             fn get_date_time_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
@@ -920,8 +1240,13 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Box<P> {
             fn supports_sequences(&self) -> bool;
             fn supports_identity_columns(&self) -> bool;
             fn uses_sequence_emulated_identity_columns(&self) -> bool;
+            fn supports_generated_identity_columns(&self) -> bool;
             fn supports_partial_indexes(&self) -> bool;
             fn supports_column_length_indexes(&self) -> bool;
+            fn supports_invisible_indexes(&self) -> bool;
+            fn supports_nulls_not_distinct(&self) -> bool;
+            fn supports_default_in_insert_values(&self) -> bool;
+            fn supports_insert_returning(&self) -> bool;
             fn supports_savepoints(&self) -> bool;
             fn supports_release_savepoints(&self) -> bool;
             fn supports_foreign_key_constraints(&self) -> bool;
@@ -929,15 +1254,27 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Box<P> {
             fn supports_create_drop_database(&self) -> bool;
             fn supports_inline_column_comments(&self) -> bool;
             fn supports_comment_on_statement(&self) -> bool;
+            fn supports_table_owner(&self) -> bool;
+            fn supports_grants(&self) -> bool;
+            fn supports_replica_identity(&self) -> bool;
+            fn normalize_foreign_key_referential_action(&self, action: Option<ForeignKeyReferentialAction>) -> ForeignKeyReferentialAction;
+            fn supports_ordered_set_aggregates(&self) -> bool;
+            fn supports_named_windows(&self) -> bool;
+            fn supports_overlaps_predicate(&self) -> bool;
+            fn supports_range_types(&self) -> bool;
             fn has_native_guid_type(&self) -> bool;
             fn has_native_json_type(&self) -> bool;
+            fn has_native_interval_type(&self) -> bool;
             fn supports_column_collation(&self) -> bool;
+            fn supports_column_compression(&self) -> bool;
             fn get_date_time_format_string(&self) -> &str;
             fn get_date_time_tz_format_string(&self) -> &str;
             fn get_date_format_string(&self) -> &str;
             fn get_time_format_string(&self) -> &str;
             fn modify_limit_query(&self, query: &str, limit: Option<usize>, offset: Option<usize>) -> String;
             fn get_max_identifier_length(&self) -> usize;
+            fn get_max_columns_per_table(&self) -> usize;
+            fn get_max_bind_parameters(&self) -> usize;
             fn get_empty_identity_insert_sql(&self, quoted_table_name: &str, quoted_identifier_column_name: &str) -> String;
             fn get_dummy_select_sql(&self, expression: Option<&str>) -> String;
             fn create_save_point(&self, savepoint: &str) -> String;
@@ -945,6 +1282,18 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Box<P> {
             fn rollback_save_point(&self, savepoint: &str) -> String;
             fn escape_string_for_like(&self, input_string: &str, escape_char: &str) -> Result<String>;
             fn get_like_wildcard_characters(&self) -> &'static str;
+            fn get_merge_sql(&self, merge: &MergeBuilder) -> Result<String>;
+            fn get_upsert_sql(&self, merge: &MergeBuilder) -> Result<String>;
+            fn get_upsert_reporting(&self) -> UpsertReporting;
+            fn get_update_from_sql(&self, query: &QueryBuilder) -> Result<Expression>;
+            fn get_values_table_sql(&self, rows: &[Vec<Value>], alias: &str, columns: &[&str]) -> Result<Expression>;
+            fn get_replication_position_sql(&self) -> Option<String>;
+            fn get_estimated_cost_sql(&self, sql: &str) -> Option<String>;
+            fn get_last_insert_id_sql(&self) -> Result<String>;
+            fn get_advisory_lock_sql(&self, key: i64, owner: &str, table: Option<&str>, timeout: Option<Duration>, wait: bool) -> Result<String>;
+            fn get_advisory_unlock_sql(&self, key: i64, owner: &str, table: Option<&str>) -> Result<String>;
+            fn get_feature_probe_sql(&self, feature: &str) -> Result<String>;
+            fn get_advisory_locks_table_sql(&self, table: &str) -> Option<String>;
             fn create_schema_manager<'a>(&self, connection: &'a Connection) -> Box<dyn SchemaManager + 'a>;
         }
     }
@@ -966,6 +1315,10 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Arc<Box<P>> {
             fn get_binary_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
             fn get_guid_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
             fn get_json_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_composite_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_integer_range_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_date_time_tz_range_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
+            fn get_duration_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
             fn get_varchar_type_declaration_sql_snippet(&self, length: Option<usize>, fixed: bool) -> Result<String>;
             fn get_binary_type_declaration_sql_snippet(&self, length: Option<usize>, fixed: bool) -> Result<String>;
             fn get_clob_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
@@ -974,9 +1327,12 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Arc<Box<P>> {
             fn get_type_mapping(&self, db_type: &str) -> Result<TypeId>;
             fn has_type_mapping_for(&self, db_type: &str) -> bool;
             fn get_regexp_expression(&self) -> Result<String>;
+            fn get_case_insensitive_regexp_expression(&self) -> Result<String>;
+            fn get_json_contains_expression(&self, column: &str, value: &str) -> Result<String>;
+            fn get_json_array_length_expression(&self, column: &str) -> Result<String>;
             fn get_length_expression(&self, column: &str) -> Result<String>;
             fn get_mod_expression(&self, expression1: &str, expression2: &str) -> Result<String>;
-            fn get_trim_expression(&self, str: &str, mode: TrimMode, char: Option<String>) -> Result<String>;
+            fn get_trim_expression(&self, str: &str, mode: TrimMode, chars: Option<String>) -> Result<String>;
             fn get_locate_expression(&self, str: &str, substr: &str, start_pos: Option<usize>) -> Result<String>;
             fn get_substring_expression(&self, string: &str, start: usize, length: Option<usize>) -> Result<String>;
             fn get_concat_expression(&self, strings: Vec<&str>) -> Result<String>;
@@ -1005,6 +1361,7 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Arc<Box<P>> {
             fn append_lock_hint(&self, from_clause: &str, lock_mode: LockMode) -> Result<String>;
             fn get_read_lock_sql(&self) -> Result<String>;
             fn get_write_lock_sql(&self) -> Result<String>;
+            fn get_write_lock_of_sql(&self, tables: &[&str]) -> Result<String>;
             fn get_creed_type_comment(&self, creed_type: &TypePtr) -> String;
             fn quote_identifier(&self, identifier: &str) -> String;
             fn quote_single_identifier(&self, str: &str) -> String;
@@ -1020,6 +1377,7 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Arc<Box<P>> {
             fn get_current_date_sql(&self) -> &str;
             fn get_current_time_sql(&self) -> &str;
             fn get_current_timestamp_sql(&self) -> &str;
+            fn get_current_utc_timestamp_sql(&self) -> Option<&str>;
             fn get_transaction_isolation_level_sql(&self, level: TransactionIsolationLevel) -> String;
             fn get_set_transaction_isolation_sql(&self, level: TransactionIsolationLevel) -> Result<String>;
             fn get_date_time_type_declaration_sql(&self, column: &ColumnData) -> Result<String>;
@@ -1032,8 +1390,13 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Arc<Box<P>> {
             fn supports_sequences(&self) -> bool;
             fn supports_identity_columns(&self) -> bool;
             fn uses_sequence_emulated_identity_columns(&self) -> bool;
+            fn supports_generated_identity_columns(&self) -> bool;
             fn supports_partial_indexes(&self) -> bool;
             fn supports_column_length_indexes(&self) -> bool;
+            fn supports_invisible_indexes(&self) -> bool;
+            fn supports_nulls_not_distinct(&self) -> bool;
+            fn supports_default_in_insert_values(&self) -> bool;
+            fn supports_insert_returning(&self) -> bool;
             fn supports_savepoints(&self) -> bool;
             fn supports_release_savepoints(&self) -> bool;
             fn supports_foreign_key_constraints(&self) -> bool;
@@ -1041,15 +1404,27 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Arc<Box<P>> {
             fn supports_create_drop_database(&self) -> bool;
             fn supports_inline_column_comments(&self) -> bool;
             fn supports_comment_on_statement(&self) -> bool;
+            fn supports_table_owner(&self) -> bool;
+            fn supports_grants(&self) -> bool;
+            fn supports_replica_identity(&self) -> bool;
+            fn normalize_foreign_key_referential_action(&self, action: Option<ForeignKeyReferentialAction>) -> ForeignKeyReferentialAction;
+            fn supports_ordered_set_aggregates(&self) -> bool;
+            fn supports_named_windows(&self) -> bool;
+            fn supports_overlaps_predicate(&self) -> bool;
+            fn supports_range_types(&self) -> bool;
             fn has_native_guid_type(&self) -> bool;
             fn has_native_json_type(&self) -> bool;
+            fn has_native_interval_type(&self) -> bool;
             fn supports_column_collation(&self) -> bool;
+            fn supports_column_compression(&self) -> bool;
             fn get_date_time_format_string(&self) -> &str;
             fn get_date_time_tz_format_string(&self) -> &str;
             fn get_date_format_string(&self) -> &str;
             fn get_time_format_string(&self) -> &str;
             fn modify_limit_query(&self, query: &str, limit: Option<usize>, offset: Option<usize>) -> String;
             fn get_max_identifier_length(&self) -> usize;
+            fn get_max_columns_per_table(&self) -> usize;
+            fn get_max_bind_parameters(&self) -> usize;
             fn get_empty_identity_insert_sql(&self, quoted_table_name: &str, quoted_identifier_column_name: &str) -> String;
             fn get_dummy_select_sql(&self, expression: Option<&str>) -> String;
             fn create_save_point(&self, savepoint: &str) -> String;
@@ -1057,6 +1432,18 @@ impl<P: DatabasePlatform + ?Sized> DatabasePlatform for Arc<Box<P>> {
             fn rollback_save_point(&self, savepoint: &str) -> String;
             fn escape_string_for_like(&self, input_string: &str, escape_char: &str) -> Result<String>;
             fn get_like_wildcard_characters(&self) -> &'static str;
+            fn get_merge_sql(&self, merge: &MergeBuilder) -> Result<String>;
+            fn get_upsert_sql(&self, merge: &MergeBuilder) -> Result<String>;
+            fn get_upsert_reporting(&self) -> UpsertReporting;
+            fn get_update_from_sql(&self, query: &QueryBuilder) -> Result<Expression>;
+            fn get_values_table_sql(&self, rows: &[Vec<Value>], alias: &str, columns: &[&str]) -> Result<Expression>;
+            fn get_replication_position_sql(&self) -> Option<String>;
+            fn get_estimated_cost_sql(&self, sql: &str) -> Option<String>;
+            fn get_last_insert_id_sql(&self) -> Result<String>;
+            fn get_advisory_lock_sql(&self, key: i64, owner: &str, table: Option<&str>, timeout: Option<Duration>, wait: bool) -> Result<String>;
+            fn get_advisory_unlock_sql(&self, key: i64, owner: &str, table: Option<&str>) -> Result<String>;
+            fn get_feature_probe_sql(&self, feature: &str) -> Result<String>;
+            fn get_advisory_locks_table_sql(&self, table: &str) -> Option<String>;
             fn create_schema_manager<'a>(&self, connection: &'a Connection) -> Box<dyn SchemaManager + 'a>;
         }
     }
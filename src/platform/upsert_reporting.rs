@@ -0,0 +1,15 @@
+/// How the outcome of each row of an upsert statement is reported by the platform.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UpsertReporting {
+    /// The statement returns a row with a boolean `inserted` column for each
+    /// inserted or updated row, and no row for skipped ones.
+    ReturningInserted,
+
+    /// The affected row count is 1 for an inserted row, 2 for an updated row
+    /// and 0 for a skipped (or unchanged) row.
+    AffectedRows,
+
+    /// The affected row count is 1 for an inserted or updated row and 0 for a skipped row:
+    /// inserted and updated rows cannot be told apart.
+    WrittenRows,
+}
@@ -0,0 +1,471 @@
+use crate::platform::DatabasePlatform;
+use crate::{Error, Result, Value};
+use itertools::Itertools;
+use std::fmt::{Display, Formatter};
+
+/// A SQL fragment along with the values bound to its positional placeholders.
+///
+/// Expressions can be freely composed: the parameters of the combined
+/// expression always follow the order of the placeholders in the SQL.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Expression {
+    sql: String,
+    parameters: Vec<Value>,
+}
+
+impl Expression {
+    pub fn new<S: AsRef<str>>(sql: S) -> Self {
+        Self {
+            sql: sql.as_ref().to_string(),
+            parameters: vec![],
+        }
+    }
+
+    pub fn with_parameters<S: AsRef<str>>(sql: S, parameters: Vec<Value>) -> Self {
+        Self {
+            sql: sql.as_ref().to_string(),
+            parameters,
+        }
+    }
+
+    pub fn get_sql(&self) -> &str {
+        &self.sql
+    }
+
+    pub fn get_parameters(&self) -> &[Value] {
+        &self.parameters
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sql.is_empty()
+    }
+
+    /// Joins the given expressions with the separator, concatenating their parameters.
+    pub fn join<I: IntoIterator<Item = Expression>>(expressions: I, separator: &str) -> Self {
+        let mut parameters = vec![];
+        let sql = expressions
+            .into_iter()
+            .map(|e| {
+                parameters.extend(e.parameters);
+                e.sql
+            })
+            .join(separator);
+
+        Self { sql, parameters }
+    }
+
+    pub(crate) fn into_parts(self) -> (String, Vec<Value>) {
+        (self.sql, self.parameters)
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.sql)
+    }
+}
+
+impl From<&str> for Expression {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for Expression {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<&String> for Expression {
+    fn from(value: &String) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Builds SQL expressions for the query builder.
+#[derive(Clone, Copy)]
+pub struct ExpressionBuilder<'a> {
+    platform: &'a dyn DatabasePlatform,
+}
+
+impl<'a> ExpressionBuilder<'a> {
+    pub fn new(platform: &'a dyn DatabasePlatform) -> Self {
+        Self { platform }
+    }
+
+    pub fn get_platform(&self) -> &'a dyn DatabasePlatform {
+        self.platform
+    }
+
+    /// Creates a positional placeholder bound to the given value.
+    pub fn param<V: Into<Value>>(&self, value: V) -> Expression {
+        Expression::with_parameters("?", vec![value.into()])
+    }
+
+    /// Creates a conjunction of the given expressions.
+    pub fn and_x<E: Into<Expression>, I: IntoIterator<Item = E>>(&self, parts: I) -> Expression {
+        composite(parts, "AND")
+    }
+
+    /// Creates a disjunction of the given expressions.
+    pub fn or_x<E: Into<Expression>, I: IntoIterator<Item = E>>(&self, parts: I) -> Expression {
+        composite(parts, "OR")
+    }
+
+    pub fn comparison<X: Into<Expression>, Y: Into<Expression>>(
+        &self,
+        x: X,
+        operator: &str,
+        y: Y,
+    ) -> Expression {
+        Expression::join([x.into(), y.into()], &format!(" {} ", operator))
+    }
+
+    pub fn eq<X: Into<Expression>, Y: Into<Expression>>(&self, x: X, y: Y) -> Expression {
+        self.comparison(x, "=", y)
+    }
+
+    pub fn neq<X: Into<Expression>, Y: Into<Expression>>(&self, x: X, y: Y) -> Expression {
+        self.comparison(x, "<>", y)
+    }
+
+    pub fn lt<X: Into<Expression>, Y: Into<Expression>>(&self, x: X, y: Y) -> Expression {
+        self.comparison(x, "<", y)
+    }
+
+    pub fn lte<X: Into<Expression>, Y: Into<Expression>>(&self, x: X, y: Y) -> Expression {
+        self.comparison(x, "<=", y)
+    }
+
+    pub fn gt<X: Into<Expression>, Y: Into<Expression>>(&self, x: X, y: Y) -> Expression {
+        self.comparison(x, ">", y)
+    }
+
+    pub fn gte<X: Into<Expression>, Y: Into<Expression>>(&self, x: X, y: Y) -> Expression {
+        self.comparison(x, ">=", y)
+    }
+
+    pub fn is_null<X: Into<Expression>>(&self, x: X) -> Expression {
+        Expression::join([x.into(), "IS NULL".into()], " ")
+    }
+
+    pub fn is_not_null<X: Into<Expression>>(&self, x: X) -> Expression {
+        Expression::join([x.into(), "IS NOT NULL".into()], " ")
+    }
+
+    pub fn like<X: Into<Expression>, Y: Into<Expression>>(&self, x: X, y: Y) -> Expression {
+        self.comparison(x, "LIKE", y)
+    }
+
+    pub fn not_like<X: Into<Expression>, Y: Into<Expression>>(&self, x: X, y: Y) -> Expression {
+        self.comparison(x, "NOT LIKE", y)
+    }
+
+    /// Creates a regular expression match through the platform REGEXP operator.
+    pub fn regexp<X: Into<Expression>, Y: Into<Expression>>(
+        &self,
+        x: X,
+        y: Y,
+    ) -> Result<Expression> {
+        Ok(self.comparison(x, &self.platform.get_regexp_expression()?, y))
+    }
+
+    /// Creates a case-insensitive regular expression match.
+    pub fn iregexp<X: Into<Expression>, Y: Into<Expression>>(
+        &self,
+        x: X,
+        y: Y,
+    ) -> Result<Expression> {
+        Ok(self.comparison(
+            x,
+            &self.platform.get_case_insensitive_regexp_expression()?,
+            y,
+        ))
+    }
+
+    /// Creates an expression checking whether the JSON document in the given column
+    /// contains the given JSON value (ie: a parameter holding the encoded JSON).
+    /// The column expression cannot bind parameters, as some platforms reference it more than once.
+    pub fn json_contains<X: Into<Expression>, Y: Into<Expression>>(
+        &self,
+        column: X,
+        value: Y,
+    ) -> Result<Expression> {
+        let column = column.into();
+        if !column.get_parameters().is_empty() {
+            return Err(Error::query_builder_invalid(
+                "JSON contains column expression cannot bind parameters",
+            ));
+        }
+
+        let (value, parameters) = value.into().into_parts();
+        Ok(Expression::with_parameters(
+            self.platform
+                .get_json_contains_expression(column.get_sql(), &value)?,
+            parameters,
+        ))
+    }
+
+    /// Creates an expression returning the length of the JSON array in the given column.
+    pub fn json_array_length<X: Into<Expression>>(&self, column: X) -> Result<Expression> {
+        let (column, parameters) = column.into().into_parts();
+        Ok(Expression::with_parameters(
+            self.platform.get_json_array_length_expression(&column)?,
+            parameters,
+        ))
+    }
+
+    /// Creates an IN expression binding each of the given values.
+    pub fn in_values<X: Into<Expression>, V: Into<Value>, I: IntoIterator<Item = V>>(
+        &self,
+        x: X,
+        values: I,
+    ) -> Expression {
+        self.in_list(x, "IN", values)
+    }
+
+    /// Creates a NOT IN expression binding each of the given values.
+    pub fn not_in_values<X: Into<Expression>, V: Into<Value>, I: IntoIterator<Item = V>>(
+        &self,
+        x: X,
+        values: I,
+    ) -> Expression {
+        self.in_list(x, "NOT IN", values)
+    }
+
+    /// Creates a BETWEEN expression.
+    pub fn between<X: Into<Expression>, L: Into<Expression>, H: Into<Expression>>(
+        &self,
+        x: X,
+        low: L,
+        high: H,
+    ) -> Expression {
+        Expression::join(
+            [
+                x.into(),
+                "BETWEEN".into(),
+                low.into(),
+                "AND".into(),
+                high.into(),
+            ],
+            " ",
+        )
+    }
+
+    /// Creates a NOT BETWEEN expression.
+    pub fn not_between<X: Into<Expression>, L: Into<Expression>, H: Into<Expression>>(
+        &self,
+        x: X,
+        low: L,
+        high: H,
+    ) -> Expression {
+        Expression::join(
+            [
+                x.into(),
+                "NOT BETWEEN".into(),
+                low.into(),
+                "AND".into(),
+                high.into(),
+            ],
+            " ",
+        )
+    }
+
+    /// Creates an expression checking whether the two given periods (start, end) overlap.
+    ///
+    /// Renders the SQL standard `OVERLAPS` predicate where supported (ie: PostgreSQL).
+    /// Other platforms emulate it comparing the bounds: periods are half-open
+    /// (a period ending when the other starts does not overlap it), while a zero-length
+    /// period is a single instant, as in the standard predicate. Unlike `OVERLAPS`,
+    /// the emulation does not swap reversed bounds: start must not follow end.
+    /// Start bounds are referenced twice by the emulation, so their parameters are bound twice.
+    pub fn overlaps<S1, E1, S2, E2>(&self, period1: (S1, E1), period2: (S2, E2)) -> Expression
+    where
+        S1: Into<Expression>,
+        E1: Into<Expression>,
+        S2: Into<Expression>,
+        E2: Into<Expression>,
+    {
+        let (start1, end1) = (period1.0.into(), period1.1.into());
+        let (start2, end2) = (period2.0.into(), period2.1.into());
+
+        if self.platform.supports_overlaps_predicate() {
+            Expression::join(
+                [
+                    "(".into(),
+                    start1,
+                    ", ".into(),
+                    end1,
+                    ") OVERLAPS (".into(),
+                    start2,
+                    ", ".into(),
+                    end2,
+                    ")".into(),
+                ],
+                "",
+            )
+        } else {
+            composite(
+                [
+                    composite(
+                        [self.lt(start1.clone(), end2), self.lt(start2.clone(), end1)],
+                        "AND",
+                    ),
+                    self.eq(start1, start2),
+                ],
+                "OR",
+            )
+        }
+    }
+
+    /// Creates an expression checking whether the given range contains
+    /// the other range or element (`@>` operator).
+    pub fn range_contains<X: Into<Expression>, Y: Into<Expression>>(
+        &self,
+        range: X,
+        value: Y,
+    ) -> Result<Expression> {
+        self.range_comparison(range, "@>", value)
+    }
+
+    /// Creates an expression checking whether the given range or element
+    /// is contained by the other range (`<@` operator).
+    pub fn range_contained_by<X: Into<Expression>, Y: Into<Expression>>(
+        &self,
+        value: X,
+        range: Y,
+    ) -> Result<Expression> {
+        self.range_comparison(value, "<@", range)
+    }
+
+    /// Creates an expression checking whether the given ranges have points in common
+    /// (`&&` operator).
+    pub fn range_overlaps<X: Into<Expression>, Y: Into<Expression>>(
+        &self,
+        range1: X,
+        range2: Y,
+    ) -> Result<Expression> {
+        self.range_comparison(range1, "&&", range2)
+    }
+
+    fn range_comparison<X: Into<Expression>, Y: Into<Expression>>(
+        &self,
+        x: X,
+        operator: &str,
+        y: Y,
+    ) -> Result<Expression> {
+        if !self.platform.supports_range_types() {
+            return Err(Error::platform_feature_unsupported("range types"));
+        }
+
+        Ok(self.comparison(x, operator, y))
+    }
+
+    /// Creates a `percentile_cont` ordered-set aggregate, computing the value at the
+    /// given fraction (between 0 and 1) interpolating between the ordered values if needed.
+    pub fn percentile_cont<X: Into<Expression>>(
+        &self,
+        fraction: f64,
+        order_by: X,
+    ) -> Result<Expression> {
+        self.ordered_set_aggregate("percentile_cont", fraction, order_by)
+    }
+
+    /// Creates a `percentile_disc` ordered-set aggregate, returning the first ordered
+    /// value whose position is greater than or equal to the given fraction (between 0 and 1).
+    pub fn percentile_disc<X: Into<Expression>>(
+        &self,
+        fraction: f64,
+        order_by: X,
+    ) -> Result<Expression> {
+        self.ordered_set_aggregate("percentile_disc", fraction, order_by)
+    }
+
+    /// Applies the given aggregate or window function over a window,
+    /// either named (see [`QueryBuilder::window`](super::QueryBuilder::window))
+    /// or defined inline (ie: `(PARTITION BY x)`).
+    pub fn over<X: Into<Expression>>(&self, function: X, window: &str) -> Expression {
+        Expression::join([function.into(), "OVER".into(), window.into()], " ")
+    }
+
+    fn ordered_set_aggregate<X: Into<Expression>>(
+        &self,
+        function: &str,
+        fraction: f64,
+        order_by: X,
+    ) -> Result<Expression> {
+        if !self.platform.supports_ordered_set_aggregates() {
+            return Err(Error::platform_feature_unsupported(format!(
+                "{} is not supported by this platform.",
+                function
+            )));
+        }
+
+        if !(0.0..=1.0).contains(&fraction) {
+            return Err(Error::query_builder_invalid(format!(
+                "{} fraction must be between 0 and 1, {} given",
+                function, fraction
+            )));
+        }
+
+        Ok(Expression::join(
+            [
+                format!("{}({}) WITHIN GROUP (ORDER BY ", function, fraction).into(),
+                order_by.into(),
+                ")".into(),
+            ],
+            "",
+        ))
+    }
+
+    fn in_list<X: Into<Expression>, V: Into<Value>, I: IntoIterator<Item = V>>(
+        &self,
+        x: X,
+        operator: &str,
+        values: I,
+    ) -> Expression {
+        let values = Expression::join(values.into_iter().map(|v| self.param(v)), ", ");
+        Expression::join(
+            [
+                x.into(),
+                operator.into(),
+                Expression::join(["(".into(), values, ")".into()], ""),
+            ],
+            " ",
+        )
+    }
+}
+
+/// Combines the given parts with the given logical operator.
+/// Each part is wrapped in parentheses when more than one is given.
+/// Without parts, the neutral condition of the operator is returned
+/// (always true for AND, always false for OR).
+pub(super) fn composite<E: Into<Expression>, I: IntoIterator<Item = E>>(
+    parts: I,
+    operator: &str,
+) -> Expression {
+    let parts = parts
+        .into_iter()
+        .map(Into::into)
+        .filter(|e| !e.is_empty())
+        .collect::<Vec<_>>();
+
+    if parts.is_empty() {
+        match operator {
+            "OR" => "1 = 0".into(),
+            _ => "1 = 1".into(),
+        }
+    } else if parts.len() == 1 {
+        parts.into_iter().next().unwrap()
+    } else {
+        Expression::join(
+            [
+                "(".into(),
+                Expression::join(parts, &format!(") {} (", operator)),
+                ")".into(),
+            ],
+            "",
+        )
+    }
+}
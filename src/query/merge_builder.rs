@@ -0,0 +1,466 @@
+use crate::platform::DatabasePlatform;
+use crate::{Error, Result, Value};
+use itertools::Itertools;
+use regex::Regex;
+
+/// An action executed when a source row matches a row of the target table.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MergeMatchedAction {
+    /// Updates the given columns from the source row.
+    /// An empty column list means every source column not used in the ON condition.
+    Update {
+        condition: Option<String>,
+        columns: Vec<String>,
+    },
+    /// Deletes the matched target row.
+    Delete { condition: Option<String> },
+}
+
+/// Builds a SQL standard MERGE statement.
+///
+/// The source is a single row of values bound as positional parameters
+/// and aliased as `src` (see [`MergeBuilder::source_alias`]).
+/// Platforms without MERGE support translate the statement into their
+/// upsert form, given that the definition can be expressed that way.
+#[derive(Clone, Debug)]
+pub struct MergeBuilder {
+    target: String,
+    source_alias: String,
+    values: Vec<(String, Value)>,
+    on: Vec<String>,
+    when_matched: Vec<MergeMatchedAction>,
+    when_not_matched_insert: bool,
+}
+
+impl MergeBuilder {
+    pub fn new<S: AsRef<str>>(target: S) -> Self {
+        Self {
+            target: target.as_ref().to_string(),
+            source_alias: "src".to_string(),
+            values: vec![],
+            on: vec![],
+            when_matched: vec![],
+            when_not_matched_insert: false,
+        }
+    }
+
+    /// Sets the alias of the source row, usable in the branch conditions.
+    pub fn source_alias<S: AsRef<str>>(mut self, alias: S) -> Self {
+        self.source_alias = alias.as_ref().to_string();
+        self
+    }
+
+    /// Adds a column to the source row.
+    pub fn using<S: AsRef<str>, V: Into<Value>>(mut self, column: S, value: V) -> Self {
+        self.values
+            .push((column.as_ref().to_string(), value.into()));
+        self
+    }
+
+    /// Adds a set of columns to the source row.
+    pub fn using_values<S: AsRef<str>, V: Into<Value>, I: IntoIterator<Item = (S, V)>>(
+        mut self,
+        values: I,
+    ) -> Self {
+        for (column, value) in values {
+            self = self.using(column, value);
+        }
+
+        self
+    }
+
+    /// Sets the columns compared for equality between source and target rows.
+    pub fn on<S: AsRef<str>>(mut self, columns: &[S]) -> Self {
+        self.on = columns.iter().map(|c| c.as_ref().to_string()).collect();
+        self
+    }
+
+    pub fn when_matched_update<S: AsRef<str>>(self, columns: &[S]) -> Self {
+        self.add_matched_update(None, columns)
+    }
+
+    pub fn when_matched_update_if<C: AsRef<str>, S: AsRef<str>>(
+        self,
+        condition: C,
+        columns: &[S],
+    ) -> Self {
+        self.add_matched_update(Some(condition.as_ref().to_string()), columns)
+    }
+
+    pub fn when_matched_delete(mut self) -> Self {
+        self.when_matched
+            .push(MergeMatchedAction::Delete { condition: None });
+        self
+    }
+
+    pub fn when_matched_delete_if<C: AsRef<str>>(mut self, condition: C) -> Self {
+        self.when_matched.push(MergeMatchedAction::Delete {
+            condition: Some(condition.as_ref().to_string()),
+        });
+        self
+    }
+
+    /// Inserts the source row when no target row matches.
+    pub fn when_not_matched_insert(mut self) -> Self {
+        self.when_not_matched_insert = true;
+        self
+    }
+
+    pub fn get_target(&self) -> &str {
+        &self.target
+    }
+
+    pub fn get_source_alias(&self) -> &str {
+        &self.source_alias
+    }
+
+    pub fn get_columns(&self) -> Vec<&str> {
+        self.values.iter().map(|(c, _)| c.as_str()).collect()
+    }
+
+    pub fn get_on_columns(&self) -> &[String] {
+        &self.on
+    }
+
+    pub fn get_matched_actions(&self) -> &[MergeMatchedAction] {
+        &self.when_matched
+    }
+
+    pub fn has_not_matched_insert(&self) -> bool {
+        self.when_not_matched_insert
+    }
+
+    /// Gets the positional parameters to be bound to the generated statement.
+    pub fn get_parameters(&self) -> Vec<Value> {
+        self.values.iter().map(|(_, v)| v.clone()).collect()
+    }
+
+    /// Generates the statement for the given platform.
+    pub fn get_sql(&self, platform: &dyn DatabasePlatform) -> Result<String> {
+        platform.get_merge_sql(self)
+    }
+
+    fn add_matched_update<S: AsRef<str>>(
+        mut self,
+        condition: Option<String>,
+        columns: &[S],
+    ) -> Self {
+        self.when_matched.push(MergeMatchedAction::Update {
+            condition,
+            columns: columns.iter().map(|c| c.as_ref().to_string()).collect(),
+        });
+        self
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.values.is_empty() {
+            return Err(Error::merge_definition_invalid("source values"));
+        }
+
+        if self.on.is_empty() {
+            return Err(Error::merge_definition_invalid("on columns"));
+        }
+
+        if let Some(column) = self
+            .on
+            .iter()
+            .find(|c| !self.values.iter().any(|(v, _)| v == *c))
+        {
+            return Err(Error::merge_definition_invalid(&format!(
+                "source value for column {}",
+                column
+            )));
+        }
+
+        if self.when_matched.is_empty() && !self.when_not_matched_insert {
+            return Err(Error::merge_definition_invalid("when clause"));
+        }
+
+        Ok(())
+    }
+
+    fn update_columns<'a>(&'a self, columns: &'a [String]) -> Vec<&'a str> {
+        if columns.is_empty() {
+            self.values
+                .iter()
+                .map(|(c, _)| c.as_str())
+                .filter(|c| !self.on.iter().any(|o| o == c))
+                .collect()
+        } else {
+            columns.iter().map(String::as_str).collect()
+        }
+    }
+
+    fn quoted_columns(&self, platform: &dyn DatabasePlatform) -> String {
+        self.values
+            .iter()
+            .map(|(c, _)| platform.quote_identifier(c))
+            .join(", ")
+    }
+
+    fn placeholders(&self) -> String {
+        vec!["?"; self.values.len()].join(", ")
+    }
+
+    /// Generates the SQL standard MERGE statement.
+    pub fn to_merge_sql(&self, platform: &dyn DatabasePlatform) -> Result<String> {
+        self.validate()?;
+
+        let target = platform.quote_identifier(&self.target);
+        let source = &self.source_alias;
+        let on = self
+            .on
+            .iter()
+            .map(|c| {
+                let c = platform.quote_identifier(c);
+                format!("{}.{} = {}.{}", target, c, source, c)
+            })
+            .join(" AND ");
+
+        let mut sql = format!(
+            "MERGE INTO {} USING (VALUES ({})) AS {} ({}) ON ({})",
+            target,
+            self.placeholders(),
+            source,
+            self.quoted_columns(platform),
+            on
+        );
+
+        for action in &self.when_matched {
+            let (condition, then) = match action {
+                // Nothing to update when every column is an ON column.
+                MergeMatchedAction::Update { columns, .. }
+                    if self.update_columns(columns).is_empty() =>
+                {
+                    continue;
+                }
+                MergeMatchedAction::Update { condition, columns } => {
+                    let set = self
+                        .update_columns(columns)
+                        .into_iter()
+                        .map(|c| {
+                            let c = platform.quote_identifier(c);
+                            format!("{} = {}.{}", c, source, c)
+                        })
+                        .join(", ");
+
+                    (condition, format!("UPDATE SET {}", set))
+                }
+                MergeMatchedAction::Delete { condition } => (condition, "DELETE".to_string()),
+            };
+
+            sql += " WHEN MATCHED";
+            if let Some(condition) = condition {
+                sql += &format!(" AND ({})", condition);
+            }
+
+            sql += &format!(" THEN {}", then);
+        }
+
+        if self.when_not_matched_insert {
+            let values = self
+                .values
+                .iter()
+                .map(|(c, _)| format!("{}.{}", source, platform.quote_identifier(c)))
+                .join(", ");
+
+            sql += &format!(
+                " WHEN NOT MATCHED THEN INSERT ({}) VALUES ({})",
+                self.quoted_columns(platform),
+                values
+            );
+        }
+
+        Ok(sql)
+    }
+
+    /// Returns the only update branch, if the merge can be expressed as an upsert.
+    fn upsert_update_action(&self) -> Result<Option<(&Option<String>, Vec<&str>)>> {
+        self.validate()?;
+        if !self.when_not_matched_insert {
+            return Err(Error::platform_feature_unsupported(
+                "Merge statements without a WHEN NOT MATCHED INSERT branch are not supported by the platform",
+            ));
+        }
+
+        if self
+            .when_matched
+            .iter()
+            .any(|a| matches!(a, MergeMatchedAction::Delete { .. }))
+        {
+            return Err(Error::platform_feature_unsupported(
+                "WHEN MATCHED THEN DELETE branches are not supported by the platform",
+            ));
+        }
+
+        match self.when_matched.as_slice() {
+            [] => Ok(None),
+            [MergeMatchedAction::Update { condition, columns }] => {
+                Ok(Some((condition, self.update_columns(columns))))
+            }
+            _ => Err(Error::platform_feature_unsupported(
+                "Multiple WHEN MATCHED branches are not supported by the platform",
+            )),
+        }
+    }
+
+    /// Translates the merge to an INSERT ... ON CONFLICT statement.
+    /// References to the source alias in the update condition are
+    /// replaced with the given excluded table name.
+    pub fn to_on_conflict_sql(
+        &self,
+        platform: &dyn DatabasePlatform,
+        excluded: &str,
+    ) -> Result<String> {
+        let action = self.upsert_update_action()?;
+        let on = self
+            .on
+            .iter()
+            .map(|c| platform.quote_identifier(c))
+            .join(", ");
+
+        let mut sql = format!(
+            "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT ({})",
+            platform.quote_identifier(&self.target),
+            self.quoted_columns(platform),
+            self.placeholders(),
+            on
+        );
+
+        // Nothing to update when every column is an ON column.
+        let Some((condition, columns)) = action.filter(|(_, columns)| !columns.is_empty()) else {
+            return Ok(sql + " DO NOTHING");
+        };
+
+        let set = columns
+            .into_iter()
+            .map(|c| {
+                let c = platform.quote_identifier(c);
+                format!("{} = {}.{}", c, excluded, c)
+            })
+            .join(", ");
+
+        sql += &format!(" DO UPDATE SET {}", set);
+        if let Some(condition) = condition {
+            let source =
+                Regex::new(&format!(r"\b{}\.", regex::escape(&self.source_alias))).unwrap();
+            sql += &format!(
+                " WHERE {}",
+                source.replace_all(condition, format!("{}.", excluded))
+            );
+        }
+
+        Ok(sql)
+    }
+
+    /// Translates the merge to an INSERT ... ON DUPLICATE KEY UPDATE statement.
+    /// ON columns are expected to be covered by a primary key or unique index.
+    pub fn to_on_duplicate_key_sql(&self, platform: &dyn DatabasePlatform) -> Result<String> {
+        let action = self.upsert_update_action()?;
+        let table = platform.quote_identifier(&self.target);
+        let columns = self.quoted_columns(platform);
+        let placeholders = self.placeholders();
+
+        let set = match action.filter(|(_, columns)| !columns.is_empty()) {
+            Some((Some(_), _)) => {
+                return Err(Error::platform_feature_unsupported(
+                    "Conditional WHEN MATCHED branches are not supported by the platform",
+                ));
+            }
+            Some((None, update_columns)) => update_columns
+                .into_iter()
+                .map(|c| {
+                    let c = platform.quote_identifier(c);
+                    format!("{} = VALUES({})", c, c)
+                })
+                .join(", "),
+            // A no-op update skips the duplicate rows, without ignoring
+            // the other errors as INSERT IGNORE would do.
+            None => {
+                let c = platform.quote_identifier(&self.on[0]);
+                format!("{} = {}", c, c)
+            }
+        };
+
+        Ok(format!(
+            "INSERT INTO {} ({}) VALUES ({}) ON DUPLICATE KEY UPDATE {}",
+            table, columns, placeholders, set
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MergeBuilder;
+    use crate::error::ErrorKind;
+    use crate::tests::MockPlatform;
+    use crate::{EventDispatcher, Value};
+    use std::sync::Arc;
+
+    fn create_platform() -> MockPlatform {
+        MockPlatform {
+            ev: Arc::new(EventDispatcher::new()),
+        }
+    }
+
+    #[test]
+    pub fn generates_merge_sql() {
+        let platform = create_platform();
+        let merge = MergeBuilder::new("stock")
+            .using("id", 1)
+            .using("name", "foo")
+            .using("qty", 10)
+            .on(&["id"])
+            .when_matched_delete_if("src.qty = 0")
+            .when_matched_update::<&str>(&[])
+            .when_not_matched_insert();
+
+        assert_eq!(
+            merge.get_sql(&platform).unwrap(),
+            r#"MERGE INTO "stock" USING (VALUES (?, ?, ?)) AS src ("id", "name", "qty") ON ("stock"."id" = src."id") WHEN MATCHED AND (src.qty = 0) THEN DELETE WHEN MATCHED THEN UPDATE SET "name" = src."name", "qty" = src."qty" WHEN NOT MATCHED THEN INSERT ("id", "name", "qty") VALUES (src."id", src."name", src."qty")"#
+        );
+        assert_eq!(
+            merge.get_parameters(),
+            vec![
+                Value::Int(1),
+                Value::String("foo".to_string()),
+                Value::Int(10)
+            ]
+        );
+    }
+
+    #[test]
+    pub fn merge_requires_on_columns_in_source() {
+        let platform = create_platform();
+        let merge = MergeBuilder::new("stock")
+            .using("name", "foo")
+            .on(&["id"])
+            .when_not_matched_insert();
+
+        let err = merge.get_sql(&platform).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::MergeDefinitionInvalid);
+    }
+
+    #[test]
+    pub fn skips_update_when_every_column_is_an_on_column() {
+        let platform = create_platform();
+        let merge = MergeBuilder::new("tags")
+            .using("id", 1)
+            .on(&["id"])
+            .when_matched_update::<&str>(&[])
+            .when_not_matched_insert();
+
+        assert_eq!(
+            merge.to_merge_sql(&platform).unwrap(),
+            r#"MERGE INTO "tags" USING (VALUES (?)) AS src ("id") ON ("tags"."id" = src."id") WHEN NOT MATCHED THEN INSERT ("id") VALUES (src."id")"#
+        );
+        assert_eq!(
+            merge.to_on_conflict_sql(&platform, "EXCLUDED").unwrap(),
+            r#"INSERT INTO "tags" ("id") VALUES (?) ON CONFLICT ("id") DO NOTHING"#
+        );
+        assert_eq!(
+            merge.to_on_duplicate_key_sql(&platform).unwrap(),
+            r#"INSERT INTO "tags" ("id") VALUES (?) ON DUPLICATE KEY UPDATE "id" = "id""#
+        );
+    }
+}
@@ -0,0 +1,9 @@
+mod expression;
+mod merge_builder;
+mod query_builder;
+mod upsert_result;
+
+pub use expression::{Expression, ExpressionBuilder};
+pub use merge_builder::{MergeBuilder, MergeMatchedAction};
+pub use query_builder::{QueryBuilder, QueryType};
+pub use upsert_result::UpsertResult;
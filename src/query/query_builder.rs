@@ -0,0 +1,906 @@
+use super::expression::{composite, Expression, ExpressionBuilder};
+use crate::platform::DatabasePlatform;
+use crate::{Error, Result, Value};
+use itertools::Itertools;
+
+/// The kind of statement built by a [`QueryBuilder`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueryType {
+    #[default]
+    Select,
+    Insert,
+    Update,
+    Delete,
+}
+
+/// The row lock acquired by a SELECT query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum RowLock {
+    Share,
+    Update(Vec<String>),
+}
+
+/// Builds SELECT, INSERT, UPDATE and DELETE statements programmatically.
+///
+/// Values are bound as positional parameters: each clause keeps its own
+/// parameters, which are emitted following the order of the placeholders
+/// in the generated SQL, regardless of the order in which the clauses were added.
+/// Table and column names are not escaped.
+pub struct QueryBuilder<'a> {
+    platform: &'a dyn DatabasePlatform,
+    query_type: QueryType,
+    distinct: bool,
+    select: Vec<Expression>,
+    from: Vec<Expression>,
+    joins: Vec<Expression>,
+    table: Option<String>,
+    update_sources: Vec<(Expression, Option<Expression>)>,
+    set: Vec<Expression>,
+    values: Vec<(String, Expression)>,
+    r#where: Option<Expression>,
+    group_by: Vec<Expression>,
+    having: Option<Expression>,
+    windows: Vec<(String, Expression)>,
+    order_by: Vec<Expression>,
+    max_results: Option<usize>,
+    first_result: Option<usize>,
+    lock: Option<RowLock>,
+}
+
+impl<'a> QueryBuilder<'a> {
+    pub fn new(platform: &'a dyn DatabasePlatform) -> Self {
+        Self {
+            platform,
+            query_type: QueryType::Select,
+            distinct: false,
+            select: vec![],
+            from: vec![],
+            joins: vec![],
+            table: None,
+            update_sources: vec![],
+            set: vec![],
+            values: vec![],
+            r#where: None,
+            group_by: vec![],
+            having: None,
+            windows: vec![],
+            order_by: vec![],
+            max_results: None,
+            first_result: None,
+            lock: None,
+        }
+    }
+
+    /// Gets an expression builder bound to the same platform.
+    pub fn expr(&self) -> ExpressionBuilder<'a> {
+        ExpressionBuilder::new(self.platform)
+    }
+
+    pub fn get_type(&self) -> QueryType {
+        self.query_type
+    }
+
+    /// Replaces the selected expressions, turning this into a SELECT query.
+    pub fn select<E: Into<Expression>, I: IntoIterator<Item = E>>(
+        &mut self,
+        expressions: I,
+    ) -> &mut Self {
+        self.query_type = QueryType::Select;
+        self.select = expressions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Adds the given expressions to the selected ones.
+    pub fn add_select<E: Into<Expression>, I: IntoIterator<Item = E>>(
+        &mut self,
+        expressions: I,
+    ) -> &mut Self {
+        self.query_type = QueryType::Select;
+        self.select.extend(expressions.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn distinct(&mut self, distinct: bool) -> &mut Self {
+        self.distinct = distinct;
+        self
+    }
+
+    /// Adds a table (or any other FROM item) to the FROM clause.
+    pub fn from<E: Into<Expression>>(&mut self, from: E, alias: Option<&str>) -> &mut Self {
+        self.from.push(aliased(from.into(), alias));
+        self
+    }
+
+    /// Adds an inline table built from a list of rows to the FROM clause,
+    /// naming it with the given alias and columns.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if no row is given or if the rows
+    /// do not have the same number of values as the columns.
+    pub fn from_values<S, V, R, I>(
+        &mut self,
+        rows: I,
+        alias: &str,
+        columns: &[S],
+    ) -> Result<&mut Self>
+    where
+        S: AsRef<str>,
+        V: Into<Value>,
+        R: IntoIterator<Item = V>,
+        I: IntoIterator<Item = R>,
+    {
+        let columns = columns.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().map(Into::into).collect::<Vec<Value>>())
+            .collect::<Vec<_>>();
+
+        if rows.is_empty() {
+            return Err(Error::query_builder_invalid(
+                "VALUES table requires at least one row",
+            ));
+        }
+
+        if let Some(row) = rows.iter().find(|row| row.len() != columns.len()) {
+            return Err(Error::query_builder_invalid(format!(
+                "VALUES table row has {} values, {} columns expected",
+                row.len(),
+                columns.len()
+            )));
+        }
+
+        let table = self.platform.get_values_table_sql(&rows, alias, &columns)?;
+        self.from.push(table);
+
+        Ok(self)
+    }
+
+    /// Turns this into an INSERT query on the given table.
+    pub fn insert<S: AsRef<str>>(&mut self, table: S) -> &mut Self {
+        self.query_type = QueryType::Insert;
+        self.table = Some(table.as_ref().to_string());
+        self
+    }
+
+    /// Turns this into an UPDATE query on the given table.
+    pub fn update<S: AsRef<str>>(&mut self, table: S) -> &mut Self {
+        self.query_type = QueryType::Update;
+        self.table = Some(table.as_ref().to_string());
+        self
+    }
+
+    /// Adds a table the UPDATE query is correlated with.
+    /// The conditions linking the tables must be added to the WHERE clause.
+    pub fn update_from<E: Into<Expression>>(&mut self, from: E, alias: Option<&str>) -> &mut Self {
+        self.update_sources
+            .push((aliased(from.into(), alias), None));
+        self
+    }
+
+    /// Adds a table joined to the target of the UPDATE query on the given condition.
+    pub fn update_join<E: Into<Expression>, C: Into<Expression>>(
+        &mut self,
+        join: E,
+        alias: Option<&str>,
+        condition: C,
+    ) -> &mut Self {
+        self.update_sources
+            .push((aliased(join.into(), alias), Some(condition.into())));
+        self
+    }
+
+    /// Turns this into a DELETE query on the given table.
+    pub fn delete<S: AsRef<str>>(&mut self, table: S) -> &mut Self {
+        self.query_type = QueryType::Delete;
+        self.table = Some(table.as_ref().to_string());
+        self
+    }
+
+    pub fn inner_join<E: Into<Expression>, C: Into<Expression>>(
+        &mut self,
+        join: E,
+        alias: Option<&str>,
+        condition: C,
+    ) -> &mut Self {
+        self.add_join("INNER JOIN", join.into(), alias, condition.into())
+    }
+
+    pub fn left_join<E: Into<Expression>, C: Into<Expression>>(
+        &mut self,
+        join: E,
+        alias: Option<&str>,
+        condition: C,
+    ) -> &mut Self {
+        self.add_join("LEFT JOIN", join.into(), alias, condition.into())
+    }
+
+    pub fn right_join<E: Into<Expression>, C: Into<Expression>>(
+        &mut self,
+        join: E,
+        alias: Option<&str>,
+        condition: C,
+    ) -> &mut Self {
+        self.add_join("RIGHT JOIN", join.into(), alias, condition.into())
+    }
+
+    /// Sets a column to the given value in an UPDATE query.
+    pub fn set<S: AsRef<str>, V: Into<Value>>(&mut self, column: S, value: V) -> &mut Self {
+        let value = self.expr().param(value);
+        self.set_expr(column, value)
+    }
+
+    /// Sets a column to the given (unescaped) expression in an UPDATE query.
+    pub fn set_expr<S: AsRef<str>, E: Into<Expression>>(
+        &mut self,
+        column: S,
+        expression: E,
+    ) -> &mut Self {
+        let expression = self.expr().eq(column.as_ref(), expression);
+        self.set.push(expression);
+        self
+    }
+
+    /// Sets the value of a column in an INSERT query.
+    pub fn value<S: AsRef<str>, V: Into<Value>>(&mut self, column: S, value: V) -> &mut Self {
+        let value = self.expr().param(value);
+        self.value_expr(column, value)
+    }
+
+    /// Sets the value of a column to the given (unescaped) expression in an INSERT query.
+    pub fn value_expr<S: AsRef<str>, E: Into<Expression>>(
+        &mut self,
+        column: S,
+        expression: E,
+    ) -> &mut Self {
+        let column = column.as_ref().to_string();
+        let expression = expression.into();
+        if let Some(existing) = self.values.iter_mut().find(|(c, _)| *c == column) {
+            existing.1 = expression;
+        } else {
+            self.values.push((column, expression));
+        }
+
+        self
+    }
+
+    /// Replaces the WHERE clause.
+    pub fn r#where<E: Into<Expression>>(&mut self, condition: E) -> &mut Self {
+        self.r#where = Some(condition.into());
+        self
+    }
+
+    /// Adds a condition to the WHERE clause, in conjunction with the existing ones.
+    pub fn and_where<E: Into<Expression>>(&mut self, condition: E) -> &mut Self {
+        self.r#where = Some(combine(self.r#where.take(), condition.into(), "AND"));
+        self
+    }
+
+    /// Adds a condition to the WHERE clause, in disjunction with the existing ones.
+    pub fn or_where<E: Into<Expression>>(&mut self, condition: E) -> &mut Self {
+        self.r#where = Some(combine(self.r#where.take(), condition.into(), "OR"));
+        self
+    }
+
+    /// Adds a `column BETWEEN ? AND ?` condition to the WHERE clause,
+    /// in conjunction with the existing ones.
+    pub fn where_between<S: AsRef<str>, L: Into<Value>, H: Into<Value>>(
+        &mut self,
+        column: S,
+        low: L,
+        high: H,
+    ) -> &mut Self {
+        let expr = self.expr();
+        let condition = expr.between(column.as_ref(), expr.param(low), expr.param(high));
+        self.and_where(condition)
+    }
+
+    /// Adds a `column NOT BETWEEN ? AND ?` condition to the WHERE clause,
+    /// in conjunction with the existing ones.
+    pub fn where_not_between<S: AsRef<str>, L: Into<Value>, H: Into<Value>>(
+        &mut self,
+        column: S,
+        low: L,
+        high: H,
+    ) -> &mut Self {
+        let expr = self.expr();
+        let condition = expr.not_between(column.as_ref(), expr.param(low), expr.param(high));
+        self.and_where(condition)
+    }
+
+    /// Adds a condition matching the column against the given regular expression
+    /// to the WHERE clause, in conjunction with the existing ones.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the platform does not support regular expressions,
+    /// or case-insensitive ones if requested.
+    pub fn where_regex<S: AsRef<str>, P: Into<Value>>(
+        &mut self,
+        column: S,
+        pattern: P,
+        case_insensitive: bool,
+    ) -> Result<&mut Self> {
+        let expr = self.expr();
+        let condition = if case_insensitive {
+            expr.iregexp(column.as_ref(), expr.param(pattern))
+        } else {
+            expr.regexp(column.as_ref(), expr.param(pattern))
+        }?;
+
+        Ok(self.and_where(condition))
+    }
+
+    pub fn group_by<E: Into<Expression>, I: IntoIterator<Item = E>>(
+        &mut self,
+        expressions: I,
+    ) -> &mut Self {
+        self.group_by = expressions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn add_group_by<E: Into<Expression>, I: IntoIterator<Item = E>>(
+        &mut self,
+        expressions: I,
+    ) -> &mut Self {
+        self.group_by
+            .extend(expressions.into_iter().map(Into::into));
+        self
+    }
+
+    /// Replaces the HAVING clause.
+    pub fn having<E: Into<Expression>>(&mut self, condition: E) -> &mut Self {
+        self.having = Some(condition.into());
+        self
+    }
+
+    pub fn and_having<E: Into<Expression>>(&mut self, condition: E) -> &mut Self {
+        self.having = Some(combine(self.having.take(), condition.into(), "AND"));
+        self
+    }
+
+    pub fn or_having<E: Into<Expression>>(&mut self, condition: E) -> &mut Self {
+        self.having = Some(combine(self.having.take(), condition.into(), "OR"));
+        self
+    }
+
+    /// Declares a named window in the WINDOW clause, to be referenced
+    /// by window functions (see [`ExpressionBuilder::over`]).
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the platform does not support named windows.
+    pub fn window<S: AsRef<str>, E: Into<Expression>>(
+        &mut self,
+        name: S,
+        definition: E,
+    ) -> Result<&mut Self> {
+        if !self.platform.supports_named_windows() {
+            return Err(Error::platform_feature_unsupported(
+                "Named windows are not supported by this platform.",
+            ));
+        }
+
+        self.windows
+            .push((name.as_ref().to_string(), definition.into()));
+        Ok(self)
+    }
+
+    /// Replaces the ordering of the query.
+    pub fn order_by<E: Into<Expression>>(&mut self, sort: E, order: Option<&str>) -> &mut Self {
+        self.order_by.clear();
+        self.add_order_by(sort, order)
+    }
+
+    pub fn add_order_by<E: Into<Expression>>(&mut self, sort: E, order: Option<&str>) -> &mut Self {
+        let sort = sort.into();
+        self.order_by.push(match order {
+            Some(order) => Expression::join([sort, order.to_uppercase().into()], " "),
+            None => sort,
+        });
+        self
+    }
+
+    /// Sets the maximum number of rows to retrieve.
+    pub fn set_max_results(&mut self, max_results: Option<usize>) -> &mut Self {
+        self.max_results = max_results;
+        self
+    }
+
+    /// Sets the position of the first row to retrieve.
+    pub fn set_first_result(&mut self, first_result: Option<usize>) -> &mut Self {
+        self.first_result = first_result;
+        self
+    }
+
+    /// Locks the selected rows in shared mode, preventing concurrent updates
+    /// until the end of the transaction (ie: `FOR SHARE` on PostgreSQL, `LOCK IN SHARE MODE` on MySQL).
+    ///
+    /// SQLite has no row-level locks: the clause is omitted as a write transaction
+    /// locks the whole database.
+    pub fn for_share(&mut self) -> &mut Self {
+        self.lock = Some(RowLock::Share);
+        self
+    }
+
+    /// Locks the selected rows exclusively (`FOR UPDATE`).
+    ///
+    /// SQLite has no row-level locks: the clause is omitted as a write transaction
+    /// locks the whole database.
+    pub fn for_update(&mut self) -> &mut Self {
+        self.lock = Some(RowLock::Update(vec![]));
+        self
+    }
+
+    /// Locks exclusively the selected rows of the given tables (or aliases) only
+    /// (ie: `FOR UPDATE OF t` on PostgreSQL).
+    ///
+    /// Platforms which do not support restricting the lock to some tables
+    /// lock the rows of all the tables in the query, as [`QueryBuilder::for_update`] does.
+    pub fn for_update_of<S: AsRef<str>>(&mut self, tables: &[S]) -> &mut Self {
+        self.lock = Some(RowLock::Update(
+            tables.iter().map(|t| t.as_ref().to_string()).collect(),
+        ));
+        self
+    }
+
+    /// Gets the SQL of the query.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query definition is incomplete
+    /// (e.g. an UPDATE query without any SET clause).
+    pub fn get_sql(&self) -> Result<String> {
+        Ok(self.build()?.get_sql().to_string())
+    }
+
+    /// Gets the parameters bound to the query, in placeholder order.
+    ///
+    /// # Errors
+    ///
+    /// See [`QueryBuilder::get_sql`].
+    pub fn get_parameters(&self) -> Result<Vec<Value>> {
+        Ok(self.build()?.into_parts().1)
+    }
+
+    /// Builds the query, returning its SQL along with its parameters.
+    pub fn build(&self) -> Result<Expression> {
+        match self.query_type {
+            QueryType::Select => self.build_select(),
+            QueryType::Insert => self.build_insert(),
+            QueryType::Update => self.build_update(),
+            QueryType::Delete => self.build_delete(),
+        }
+    }
+
+    fn add_join(
+        &mut self,
+        join_type: &str,
+        join: Expression,
+        alias: Option<&str>,
+        condition: Expression,
+    ) -> &mut Self {
+        self.joins.push(Expression::join(
+            [
+                join_type.into(),
+                aliased(join, alias),
+                "ON".into(),
+                condition,
+            ],
+            " ",
+        ));
+        self
+    }
+
+    fn get_table(&self) -> Result<&str> {
+        self.table.as_deref().ok_or_else(|| {
+            Error::query_builder_invalid(format!("{:?} query requires a table", self.query_type))
+        })
+    }
+
+    fn build_select(&self) -> Result<Expression> {
+        if self.select.is_empty() {
+            return Err(Error::query_builder_invalid(
+                "SELECT query requires at least one select expression",
+            ));
+        }
+
+        let mut parts = vec![Expression::new(if self.distinct {
+            "SELECT DISTINCT"
+        } else {
+            "SELECT"
+        })];
+        parts.push(Expression::join(self.select.iter().cloned(), ", "));
+
+        if !self.from.is_empty() {
+            parts.push("FROM".into());
+            parts.push(Expression::join(self.from.iter().cloned(), ", "));
+        }
+
+        parts.extend(self.joins.iter().cloned());
+        self.push_where(&mut parts);
+
+        if !self.group_by.is_empty() {
+            parts.push("GROUP BY".into());
+            parts.push(Expression::join(self.group_by.iter().cloned(), ", "));
+        }
+
+        if let Some(having) = &self.having {
+            parts.push("HAVING".into());
+            parts.push(having.clone());
+        }
+
+        if !self.windows.is_empty() {
+            parts.push("WINDOW".into());
+            parts.push(Expression::join(
+                self.windows.iter().map(|(name, definition)| {
+                    Expression::join(
+                        [
+                            format!("{} AS (", name).into(),
+                            definition.clone(),
+                            ")".into(),
+                        ],
+                        "",
+                    )
+                }),
+                ", ",
+            ));
+        }
+
+        if !self.order_by.is_empty() {
+            parts.push("ORDER BY".into());
+            parts.push(Expression::join(self.order_by.iter().cloned(), ", "));
+        }
+
+        let (sql, parameters) = Expression::join(parts, " ").into_parts();
+        let mut sql = if self.max_results.is_some() || self.first_result.is_some() {
+            self.platform
+                .modify_limit_query(&sql, self.max_results, self.first_result)
+        } else {
+            sql
+        };
+
+        let lock = match &self.lock {
+            None => String::new(),
+            Some(RowLock::Share) => self.platform.get_read_lock_sql()?,
+            Some(RowLock::Update(tables)) => self
+                .platform
+                .get_write_lock_of_sql(&tables.iter().map(String::as_str).collect::<Vec<_>>())?,
+        };
+        if !lock.is_empty() {
+            sql = format!("{} {}", sql, lock);
+        }
+
+        Ok(Expression::with_parameters(sql, parameters))
+    }
+
+    fn build_insert(&self) -> Result<Expression> {
+        let table = self.get_table()?;
+        if self.values.is_empty() {
+            return Err(Error::query_builder_invalid(
+                "INSERT query requires at least one value",
+            ));
+        }
+
+        let columns = self.values.iter().map(|(c, _)| c).join(", ");
+        let values = Expression::join(self.values.iter().map(|(_, v)| v.clone()), ", ");
+
+        Ok(Expression::join(
+            [
+                format!("INSERT INTO {} ({}) VALUES (", table, columns).into(),
+                values,
+                ")".into(),
+            ],
+            "",
+        ))
+    }
+
+    /// Renders a correlated UPDATE query in the `UPDATE t SET ... FROM s WHERE ...` form,
+    /// moving the join conditions to the WHERE clause.
+    pub fn to_update_from_sql(&self) -> Result<Expression> {
+        let table = self.get_table()?;
+        self.check_update_set()?;
+
+        let mut parts = vec![
+            format!("UPDATE {} SET", table).into(),
+            Expression::join(self.set.iter().cloned(), ", "),
+        ];
+
+        if !self.update_sources.is_empty() {
+            parts.push("FROM".into());
+            parts.push(Expression::join(
+                self.update_sources.iter().map(|(s, _)| s.clone()),
+                ", ",
+            ));
+        }
+
+        let conditions = self
+            .update_sources
+            .iter()
+            .filter_map(|(_, c)| c.clone())
+            .chain(self.r#where.clone())
+            .collect::<Vec<_>>();
+        if !conditions.is_empty() {
+            parts.push("WHERE".into());
+            parts.push(composite(conditions, "AND"));
+        }
+
+        Ok(Expression::join(parts, " "))
+    }
+
+    /// Renders a correlated UPDATE query in the `UPDATE t JOIN s ON ... SET ... WHERE ...` form.
+    pub fn to_update_join_sql(&self) -> Result<Expression> {
+        let table = self.get_table()?;
+        self.check_update_set()?;
+
+        let mut parts = vec![format!("UPDATE {}", table).into()];
+        for (source, condition) in &self.update_sources {
+            parts.push(match condition {
+                Some(condition) => Expression::join(
+                    [
+                        "INNER JOIN".into(),
+                        source.clone(),
+                        "ON".into(),
+                        condition.clone(),
+                    ],
+                    " ",
+                ),
+                None => Expression::join(["CROSS JOIN".into(), source.clone()], " "),
+            });
+        }
+
+        parts.push("SET".into());
+        parts.push(Expression::join(self.set.iter().cloned(), ", "));
+        self.push_where(&mut parts);
+
+        Ok(Expression::join(parts, " "))
+    }
+
+    fn check_update_set(&self) -> Result<()> {
+        if self.set.is_empty() {
+            Err(Error::query_builder_invalid(
+                "UPDATE query requires at least one SET clause",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn build_update(&self) -> Result<Expression> {
+        if self.update_sources.is_empty() {
+            self.to_update_from_sql()
+        } else {
+            self.platform.get_update_from_sql(self)
+        }
+    }
+
+    fn build_delete(&self) -> Result<Expression> {
+        let mut parts = vec![format!("DELETE FROM {}", self.get_table()?).into()];
+        self.push_where(&mut parts);
+
+        Ok(Expression::join(parts, " "))
+    }
+
+    fn push_where(&self, parts: &mut Vec<Expression>) {
+        if let Some(r#where) = &self.r#where {
+            parts.push("WHERE".into());
+            parts.push(r#where.clone());
+        }
+    }
+}
+
+fn aliased(expression: Expression, alias: Option<&str>) -> Expression {
+    match alias {
+        Some(alias) => Expression::join([expression, alias.into()], " "),
+        None => expression,
+    }
+}
+
+fn combine(existing: Option<Expression>, condition: Expression, operator: &str) -> Expression {
+    match existing {
+        Some(existing) => composite([existing, condition], operator),
+        None => condition,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryBuilder;
+    use crate::error::ErrorKind;
+    use crate::tests::MockPlatform;
+    use crate::{EventDispatcher, Value};
+    use std::sync::Arc;
+
+    fn create_platform() -> MockPlatform {
+        MockPlatform {
+            ev: Arc::new(EventDispatcher::new()),
+        }
+    }
+
+    #[test]
+    pub fn generates_select_sql() {
+        let platform = create_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select(["u.id", "u.name"])
+            .from("users", Some("u"))
+            .left_join("groups", Some("g"), expr.eq("g.id", "u.group_id"))
+            .r#where(expr.eq("u.active", expr.param(true)))
+            .or_where(expr.in_values("g.name", ["admin", "staff"]))
+            .add_order_by("u.name", Some("asc"))
+            .set_max_results(Some(10));
+
+        assert_eq!(
+            qb.get_sql().unwrap(),
+            "SELECT u.id, u.name FROM users u LEFT JOIN groups g ON g.id = u.group_id WHERE (u.active = ?) OR (g.name IN (?, ?)) ORDER BY u.name ASC LIMIT 10"
+        );
+        assert_eq!(
+            qb.get_parameters().unwrap(),
+            vec![
+                Value::Boolean(true),
+                Value::String("admin".to_string()),
+                Value::String("staff".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn generates_between_conditions() {
+        let platform = create_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.select(["*"])
+            .from("orders", None)
+            .where_between("amount", 10, 20)
+            .where_not_between("created_at", "2024-01-01", "2024-01-31");
+
+        assert_eq!(
+            qb.get_sql().unwrap(),
+            "SELECT * FROM orders WHERE (amount BETWEEN ? AND ?) AND (created_at NOT BETWEEN ? AND ?)"
+        );
+        assert_eq!(
+            qb.get_parameters().unwrap(),
+            vec![
+                Value::Int(10),
+                Value::Int(20),
+                Value::String("2024-01-01".to_string()),
+                Value::String("2024-01-31".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn between_parameters_follow_placeholder_order() {
+        let platform = create_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.update("orders")
+            .r#where(expr.eq("status", expr.param("open")))
+            .where_between("amount", 10, 20)
+            .set("status", "closed")
+            .and_where(expr.neq("customer_id", expr.param(42)));
+
+        assert_eq!(
+            qb.get_sql().unwrap(),
+            "UPDATE orders SET status = ? WHERE ((status = ?) AND (amount BETWEEN ? AND ?)) AND (customer_id <> ?)"
+        );
+        assert_eq!(
+            qb.get_parameters().unwrap(),
+            vec![
+                Value::String("closed".to_string()),
+                Value::String("open".to_string()),
+                Value::Int(10),
+                Value::Int(20),
+                Value::Int(42),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn empty_composites_render_neutral_conditions() {
+        let platform = create_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select(["*"])
+            .from("orders", None)
+            .r#where(expr.and_x(Vec::<&str>::new()))
+            .or_where(expr.or_x(Vec::<&str>::new()));
+
+        assert_eq!(
+            qb.get_sql().unwrap(),
+            "SELECT * FROM orders WHERE (1 = 1) OR (1 = 0)"
+        );
+    }
+
+    #[test]
+    pub fn generates_insert_and_delete_sql() {
+        let platform = create_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.insert("users").value("id", 1).value("name", "foo");
+
+        assert_eq!(
+            qb.get_sql().unwrap(),
+            "INSERT INTO users (id, name) VALUES (?, ?)"
+        );
+
+        let mut qb = QueryBuilder::new(&platform);
+        qb.delete("users").where_between("id", 1, 5);
+
+        assert_eq!(
+            qb.get_sql().unwrap(),
+            "DELETE FROM users WHERE id BETWEEN ? AND ?"
+        );
+        assert_eq!(
+            qb.get_parameters().unwrap(),
+            vec![Value::Int(1), Value::Int(5)]
+        );
+    }
+
+    #[test]
+    pub fn generates_values_table_sql() {
+        let platform = create_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        let expr = qb.expr();
+        qb.select(["u.id", "v.name"]).from("users", Some("u"));
+        qb.from_values(
+            [
+                [Value::from(1), Value::from("foo")],
+                [Value::from(2), Value::from("bar")],
+            ],
+            "v",
+            &["id", "name"],
+        )
+        .unwrap()
+        .r#where(expr.eq("v.id", "u.id"));
+
+        assert_eq!(
+            qb.get_sql().unwrap(),
+            "SELECT u.id, v.name FROM users u, (SELECT ? AS id, ? AS name UNION ALL SELECT ?, ?) v WHERE v.id = u.id"
+        );
+        assert_eq!(
+            qb.get_parameters().unwrap(),
+            vec![
+                Value::Int(1),
+                Value::String("foo".to_string()),
+                Value::Int(2),
+                Value::String("bar".to_string()),
+            ]
+        );
+
+        let mut qb = QueryBuilder::new(&platform);
+        let err = qb
+            .from_values([vec![1, 2], vec![3]], "v", &["a", "b"])
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::QueryBuilderInvalid);
+    }
+
+    #[test]
+    pub fn rejects_incomplete_queries() {
+        let platform = create_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.update("users");
+
+        let err = qb.get_sql().unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::QueryBuilderInvalid);
+    }
+
+    #[test]
+    pub fn where_regex_requires_platform_support() {
+        let platform = create_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.select(["u.id"]).from("users", Some("u"));
+
+        let err = qb.where_regex("u.name", "^a", false).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::PlatformFeatureUnsupported);
+    }
+
+    #[test]
+    pub fn window_requires_platform_support() {
+        let platform = create_platform();
+        let mut qb = QueryBuilder::new(&platform);
+        qb.select(["id"]).from("orders", None);
+
+        let err = qb.window("w", "PARTITION BY customer_id").err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::PlatformFeatureUnsupported);
+    }
+}
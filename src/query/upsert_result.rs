@@ -0,0 +1,17 @@
+/// The outcome of a batch of upsert statements.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct UpsertResult {
+    /// Number of inserted rows.
+    pub inserted: usize,
+
+    /// Number of existing rows which have been updated.
+    pub updated: usize,
+
+    /// Number of rows skipped because of a conflict (ie: DO NOTHING or INSERT IGNORE)
+    /// or because the update condition was not met.
+    pub skipped: usize,
+
+    /// Number of rows inserted or updated on platforms which cannot tell them apart
+    /// (see [`UpsertReporting::WrittenRows`](crate::platform::UpsertReporting::WrittenRows)).
+    pub inserted_or_updated: usize,
+}
@@ -0,0 +1,210 @@
+use crate::{Parameter, ParameterIndex, Parameters, Row};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::tokenizer::{Token, Tokenizer};
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Identifies a cached result set: the executed SQL and its parameters.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ResultCacheKey {
+    sql: String,
+    parameters: String,
+}
+
+impl ResultCacheKey {
+    pub(crate) fn new(sql: &str, params: &Parameters) -> Self {
+        let params: &[(ParameterIndex, Parameter)] = match params {
+            Parameters::Vec(vec) => vec,
+            Parameters::Array(arr) => arr,
+        };
+
+        Self {
+            sql: sql.to_string(),
+            parameters: format!("{:?}", params),
+        }
+    }
+
+    /// Gets the SQL of the cached query.
+    pub fn get_sql(&self) -> &str {
+        &self.sql
+    }
+}
+
+/// A cache of the result sets fetched through [`Connection::fetch_all`](crate::Connection::fetch_all).
+///
+/// The cache is opt-in and configured via [`Configuration::set_result_cache`](crate::Configuration::set_result_cache).
+/// It is meant for read-heavy reference data: only plain `SELECT` (or `WITH`) queries
+/// are cached, and results fetched inside a transaction are not stored, as they could
+/// include uncommitted changes. Cached results are returned even inside transactions,
+/// as long as the cache considers them valid.
+pub trait ResultCache: Send + Sync {
+    /// Gets the rows stored for the given key, if any.
+    fn get(&self, key: &ResultCacheKey) -> Option<Vec<Row>>;
+
+    /// Stores the rows fetched for the given key.
+    fn put(&self, key: ResultCacheKey, rows: Vec<Row>);
+
+    /// Called when a statement writing to the given table is executed or queried
+    /// through the connection. Does nothing by default.
+    #[allow(unused_variables)]
+    fn invalidate(&self, table: &str) {}
+}
+
+/// An in-memory result cache, expiring the stored results after the given TTL.
+pub struct InMemoryResultCache {
+    ttl: Duration,
+    invalidate_on_write: bool,
+    entries: Mutex<HashMap<ResultCacheKey, (Instant, Vec<Row>)>>,
+}
+
+impl InMemoryResultCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            invalidate_on_write: false,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Sets whether the results of the queries referencing a table are
+    /// discarded when a statement writing to that table is executed.
+    pub fn set_invalidate_on_write(mut self, invalidate_on_write: bool) -> Self {
+        self.invalidate_on_write = invalidate_on_write;
+        self
+    }
+
+    /// Removes all the stored results.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+impl ResultCache for InMemoryResultCache {
+    fn get(&self, key: &ResultCacheKey) -> Option<Vec<Row>> {
+        let mut entries = self.entries.lock().unwrap();
+        let (stored_at, rows) = entries.get(key)?;
+        if stored_at.elapsed() < self.ttl {
+            return Some(rows.clone());
+        }
+
+        entries.remove(key);
+        None
+    }
+
+    fn put(&self, key: ResultCacheKey, rows: Vec<Row>) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, (stored_at, _)| stored_at.elapsed() < self.ttl);
+        entries.insert(key, (Instant::now(), rows));
+    }
+
+    fn invalidate(&self, table: &str) {
+        if self.invalidate_on_write {
+            self.entries
+                .lock()
+                .unwrap()
+                .retain(|key, _| !references_table(key.get_sql(), table));
+        }
+    }
+}
+
+impl Debug for InMemoryResultCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InMemoryResultCache")
+            .field("ttl", &self.ttl)
+            .field("invalidate_on_write", &self.invalidate_on_write)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Whether any identifier of the given SQL names the given table.
+/// Schema qualifiers and quotes are ignored, erring on the side of invalidation.
+fn references_table(sql: &str, table: &str) -> bool {
+    let unqualified = |name: &str| -> String {
+        name.rsplit('.')
+            .next()
+            .unwrap_or(name)
+            .trim_matches(|c| matches!(c, '"' | '`' | '[' | ']'))
+            .to_lowercase()
+    };
+
+    let table = unqualified(table);
+    sql.split(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '$' | '.' | '"' | '`')))
+        .any(|token| unqualified(token) == table)
+}
+
+/// Whether the results of the given statement can be cached: only read-only
+/// queries starting with `SELECT` or `WITH` are, excluding `SELECT ... INTO`,
+/// locking reads and data-modifying common table expressions.
+///
+/// The statement is tokenized, so that string literals and quoted identifiers
+/// are never mistaken for keywords.
+pub(crate) fn is_cacheable(sql: &str) -> bool {
+    let Ok(tokens) = Tokenizer::new(&GenericDialect {}, sql).tokenize() else {
+        return false;
+    };
+
+    // Only unquoted words can be keywords.
+    let words = tokens
+        .iter()
+        .filter(|t| !matches!(t, Token::Whitespace(_)))
+        .map(|t| match t {
+            Token::Word(w) if w.quote_style.is_none() => w.value.to_uppercase(),
+            _ => String::new(),
+        })
+        .collect::<Vec<_>>();
+
+    let is_query = words.first().is_some_and(|w| w == "SELECT" || w == "WITH");
+
+    is_query
+        && !words.iter().enumerate().any(|(idx, word)| {
+            let next = words.get(idx + 1).map(String::as_str).unwrap_or_default();
+            match word.as_str() {
+                "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "INTO" => true,
+                // FOR UPDATE, FOR NO KEY UPDATE, FOR SHARE, FOR KEY SHARE
+                "FOR" => matches!(next, "UPDATE" | "NO" | "SHARE" | "KEY"),
+                // LOCK IN SHARE MODE
+                "LOCK" => next == "IN",
+                _ => false,
+            }
+        })
+}
+
+/// Gets the names of the tables written by the given statement, including
+/// the ones written by data-modifying common table expressions.
+pub(crate) fn get_written_tables(sql: &str) -> Vec<&str> {
+    let tokens = sql
+        .split(|c: char| c.is_whitespace() || matches!(c, '(' | ')' | ',' | ';'))
+        .filter(|t| !t.is_empty())
+        .collect::<Vec<_>>();
+    let is_keyword =
+        |t: &str, keywords: &[&str]| keywords.iter().any(|k| t.eq_ignore_ascii_case(k));
+    let next_after = |start: usize, keyword: &str| {
+        tokens[start..]
+            .iter()
+            .position(|t| t.eq_ignore_ascii_case(keyword))
+            .and_then(|pos| tokens.get(start + pos + 1))
+    };
+
+    let mut tables = vec![];
+    for (idx, token) in tokens.iter().enumerate() {
+        let previous = idx.checked_sub(1).map(|i| tokens[i]).unwrap_or_default();
+        let table = match token.to_uppercase().as_str() {
+            "INSERT" | "REPLACE" | "MERGE" => next_after(idx + 1, "INTO"),
+            "DELETE" if !is_keyword(previous, &["ON"]) => next_after(idx + 1, "FROM"),
+            // Skips locking reads, upsert and foreign key actions.
+            "UPDATE" | "TRUNCATE" if !is_keyword(previous, &["FOR", "DO", "KEY", "ON"]) => tokens
+                [idx + 1..]
+                .iter()
+                .find(|t| !is_keyword(t, &["LOW_PRIORITY", "IGNORE", "ONLY", "TABLE"])),
+            _ => None,
+        };
+
+        if let Some(table) = table {
+            tables.push(*table);
+        }
+    }
+
+    tables
+}
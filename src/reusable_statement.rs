@@ -0,0 +1,89 @@
+use crate::driver::statement::Statement;
+use crate::driver::statement_result::StatementResult;
+use crate::parameter::IntoParameters;
+use crate::sync::Mutex;
+use crate::{Connection, Result};
+use std::fmt::{Debug, Formatter};
+
+/// A prepared statement which can be executed many times.
+///
+/// The statement is prepared once by [`Connection::prepare_reusable`](crate::Connection::prepare_reusable)
+/// and reused on each execution: it is not cached by the connection, so the
+/// handle must be kept around to avoid preparing the statement again.
+/// Concurrent executions are serialized, as parameters are bound on the
+/// underlying driver statement.
+pub struct ReusableStatement<'conn> {
+    sql: String,
+    statement: Mutex<Box<dyn Statement<'conn> + 'conn>>,
+    connection: &'conn Connection,
+}
+
+impl<'conn> ReusableStatement<'conn> {
+    pub(crate) fn new(
+        sql: String,
+        statement: Box<dyn Statement<'conn> + 'conn>,
+        connection: &'conn Connection,
+    ) -> Self {
+        Self {
+            sql,
+            statement: Mutex::new(statement),
+            connection,
+        }
+    }
+
+    /// Gets the SQL of the prepared statement.
+    pub fn get_sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Sets how many rows are fetched from the server per round-trip
+    /// when iterating the results of the statement.
+    ///
+    /// See [`Statement::set_fetch_size`](crate::driver::statement::Statement::set_fetch_size)
+    /// for the behaviour of each driver.
+    pub async fn set_fetch_size(&self, fetch_size: Option<usize>) {
+        self.statement.lock().await.set_fetch_size(fetch_size);
+    }
+
+    /// Executes the statement with the given parameters, returning a result set.
+    pub async fn query<P: IntoParameters>(&self, params: P) -> Result<StatementResult> {
+        let platform = self.connection.get_platform()?;
+        let params = params.into_parameters(&platform)?;
+        self.connection.invalidate_result_cache(&self.sql);
+
+        let sql = self.connection.tag_sql(self.sql.clone());
+        let statement = self.statement.lock().await;
+        let result = self
+            .connection
+            .instrument("query", &sql, statement.query(params))
+            .await?;
+
+        Ok(result.deduplicate_columns(
+            self.connection
+                .get_configuration()
+                .get_duplicate_column_strategy(),
+        ))
+    }
+
+    /// Executes the statement with the given parameters and returns the number of affected rows.
+    pub async fn execute<P: IntoParameters>(&self, params: P) -> Result<usize> {
+        let platform = self.connection.get_platform()?;
+        let params = params.into_parameters(&platform)?;
+        self.connection.invalidate_result_cache(&self.sql);
+
+        let sql = self.connection.tag_sql(self.sql.clone());
+        let statement = self.statement.lock().await;
+
+        self.connection
+            .instrument("execute", &sql, statement.execute(params))
+            .await
+    }
+}
+
+impl Debug for ReusableStatement<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReusableStatement")
+            .field("sql", &self.sql)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,5 +1,5 @@
 use crate::error::Error;
-use crate::{Result, Value};
+use crate::{DuplicateColumnStrategy, Result, Value};
 use futures::{Stream, TryStreamExt};
 use std::cmp::Ordering;
 use std::future::Future;
@@ -89,8 +89,49 @@ impl Row {
     }
 }
 
+/// Renames the duplicate column names according to the given strategy.
+/// Names still duplicated after prefixing with the table name get a positional suffix.
+fn deduplicate_column_names(
+    columns: &[String],
+    tables: &[Option<String>],
+    strategy: DuplicateColumnStrategy,
+) -> Vec<String> {
+    let is_duplicate = |name: &String| columns.iter().filter(|c| *c == name).count() > 1;
+    let mut result = match strategy {
+        DuplicateColumnStrategy::Keep => return columns.to_vec(),
+        DuplicateColumnStrategy::TablePrefix => columns
+            .iter()
+            .enumerate()
+            .map(|(i, name)| match tables.get(i) {
+                Some(Some(table)) if is_duplicate(name) => format!("{}.{}", table, name),
+                _ => name.clone(),
+            })
+            .collect::<Vec<_>>(),
+        DuplicateColumnStrategy::PositionalSuffix => columns.to_vec(),
+    };
+
+    for i in 0..result.len() {
+        if !result[..i].contains(&result[i]) {
+            continue;
+        }
+
+        let mut suffix = 1;
+        result[i] = loop {
+            let name = format!("{}_{}", result[i], suffix);
+            if !result.contains(&name) {
+                break name;
+            }
+
+            suffix += 1;
+        };
+    }
+
+    result
+}
+
 pub struct Rows {
     columns: Vec<String>,
+    column_tables: Vec<Option<String>>,
     length: usize,
     last_insert_id: Option<String>,
     iterator: Pin<Box<dyn Stream<Item = Result<Row>> + Send>>,
@@ -108,12 +149,35 @@ impl Rows {
     ) -> Self {
         Self {
             columns,
+            column_tables: vec![],
             length,
             last_insert_id,
             iterator,
         }
     }
 
+    /// Sets the name (or alias) of the table of each column, where exposed by the driver.
+    pub fn with_column_tables(mut self, column_tables: Vec<Option<String>>) -> Self {
+        self.column_tables = column_tables;
+        self
+    }
+
+    /// Renames the duplicate columns of the rows according to the given strategy.
+    pub(crate) fn deduplicate_columns(mut self, strategy: DuplicateColumnStrategy) -> Self {
+        let columns = deduplicate_column_names(&self.columns, &self.column_tables, strategy);
+        if columns == self.columns {
+            return self;
+        }
+
+        self.columns = columns.clone();
+        self.iterator = Box::pin(
+            self.iterator
+                .map_ok(move |row| Row::new(columns.clone(), row.values)),
+        );
+
+        self
+    }
+
     /// Returns the length of number of rows in the rows collection.
     pub fn len(&self) -> usize {
         self.length
@@ -159,6 +223,13 @@ impl RowsIterator {
         self.rows.iterator.try_next()
     }
 
+    pub(crate) fn deduplicate_columns(self, strategy: DuplicateColumnStrategy) -> Self {
+        Self {
+            rows: self.rows.deduplicate_columns(strategy),
+            length: self.length,
+        }
+    }
+
     pub async fn into_vec(self) -> Result<Vec<Row>> {
         self.rows.iterator.try_collect().await
     }
@@ -176,8 +247,9 @@ impl RowsIterator {
 
 #[cfg(test)]
 mod tests {
+    use super::deduplicate_column_names;
     use crate::error::ErrorKind;
-    use crate::{Row, Value};
+    use crate::{DuplicateColumnStrategy, Row, Value};
     use tokio_test::assert_err;
 
     #[test]
@@ -228,4 +300,32 @@ mod tests {
         );
         assert_eq!(row, row_2);
     }
+
+    #[test]
+    fn duplicate_column_names_can_be_disambiguated() {
+        let columns = ["id", "name", "id", "id"].map(String::from);
+        let tables = vec![
+            Some("u".to_string()),
+            Some("u".to_string()),
+            Some("g".to_string()),
+            None,
+        ];
+
+        assert_eq!(
+            deduplicate_column_names(&columns, &tables, DuplicateColumnStrategy::Keep),
+            vec!["id", "name", "id", "id"]
+        );
+        assert_eq!(
+            deduplicate_column_names(&columns, &tables, DuplicateColumnStrategy::PositionalSuffix),
+            vec!["id", "name", "id_1", "id_2"]
+        );
+        assert_eq!(
+            deduplicate_column_names(&columns, &tables, DuplicateColumnStrategy::TablePrefix),
+            vec!["u.id", "name", "g.id", "id"]
+        );
+        assert_eq!(
+            deduplicate_column_names(&columns, &[], DuplicateColumnStrategy::TablePrefix),
+            vec!["id", "name", "id_1", "id_2"]
+        );
+    }
 }
@@ -38,6 +38,31 @@ pub(super) fn generate_identifier_name<S: AsRef<str>, U: Into<Option<usize>>>(
     identifier.to_uppercase()
 }
 
+/// Shortens an identifier longer than the given length.
+///
+/// The tail of the name is replaced by the hash of the whole name, so that
+/// shortened names are deterministic and do not collide when they share a long
+/// common prefix. Names already fitting the limit are returned unchanged, which
+/// makes the function idempotent on introspected (already shortened) names.
+pub(crate) fn shorten_identifier_name(name: &str, max_size: usize) -> String {
+    if name.len() <= max_size {
+        return name.to_string();
+    }
+
+    let hash = Crc::<u32>::new(&CRC_32_ISO_HDLC).checksum(name.as_bytes());
+    let hash = format!("{:08x}", hash);
+    if max_size <= hash.len() + 1 {
+        return hash[..max_size.min(hash.len())].to_string();
+    }
+
+    let mut prefix_len = max_size - hash.len() - 1;
+    while !name.is_char_boundary(prefix_len) {
+        prefix_len -= 1;
+    }
+
+    format!("{}_{}", &name[..prefix_len], hash)
+}
+
 pub trait Asset: IntoIdentifier {
     /// Returns the name of this schema asset.
     fn get_name(&self) -> Cow<'_, str>;
@@ -2,7 +2,7 @@ use crate::platform::DatabasePlatform;
 use crate::r#type::IntoType;
 use crate::r#type::TypePtr;
 use crate::schema::asset::{impl_asset, AbstractAsset, Asset};
-use crate::schema::{CheckConstraint, IntoIdentifier, NamedListIndex};
+use crate::schema::{CheckConstraint, CompositeTypeDefinition, IntoIdentifier, NamedListIndex};
 use crate::{Result, Value};
 use itertools::Itertools;
 use std::slice::Iter;
@@ -29,6 +29,13 @@ pub struct ColumnData {
     pub primary: bool,
     pub check: Option<CheckConstraint>,
     pub jsonb: bool,
+    pub identity: bool,
+    pub composite_type: Option<CompositeTypeDefinition>,
+    pub values: Vec<String>,
+    pub default_expression: Option<String>,
+    pub compression: Option<String>,
+    pub storage: Option<String>,
+    pub statistics_target: Option<usize>,
 }
 
 pub struct ColumnBuilder {
@@ -108,6 +115,47 @@ impl ColumnBuilder {
         self.column.set_jsonb(jsonb);
         self
     }
+
+    pub fn set_identity<I: Into<Option<bool>>>(mut self, identity: I) -> Self {
+        self.column.set_identity(identity);
+        self
+    }
+
+    pub fn set_composite_type<I: Into<Option<CompositeTypeDefinition>>>(
+        mut self,
+        composite_type: I,
+    ) -> Self {
+        self.column.set_composite_type(composite_type);
+        self
+    }
+
+    pub fn set_values<S: AsRef<str>, I: IntoIterator<Item = S>>(mut self, values: I) -> Self {
+        self.column.set_values(values);
+        self
+    }
+
+    pub fn set_default_expression<T: AsRef<str>, S: Into<Option<T>>>(
+        mut self,
+        expression: S,
+    ) -> Self {
+        self.column.set_default_expression(expression);
+        self
+    }
+
+    pub fn set_compression<T: AsRef<str>, S: Into<Option<T>>>(mut self, compression: S) -> Self {
+        self.column.set_compression(compression);
+        self
+    }
+
+    pub fn set_storage<T: AsRef<str>, S: Into<Option<T>>>(mut self, storage: S) -> Self {
+        self.column.set_storage(storage);
+        self
+    }
+
+    pub fn set_statistics_target<I: Into<Option<usize>>>(mut self, target: I) -> Self {
+        self.column.set_statistics_target(target);
+        self
+    }
 }
 
 impl From<ColumnBuilder> for Column {
@@ -136,6 +184,13 @@ pub struct Column {
     charset: Option<String>,
     check: Option<CheckConstraint>,
     jsonb: Option<bool>,
+    identity: Option<bool>,
+    composite_type: Option<CompositeTypeDefinition>,
+    values: Vec<String>,
+    default_expression: Option<String>,
+    compression: Option<String>,
+    storage: Option<String>,
+    statistics_target: Option<usize>,
 }
 
 impl Column {
@@ -162,6 +217,13 @@ impl Column {
             charset: None,
             check: None,
             jsonb: None,
+            identity: None,
+            composite_type: None,
+            values: vec![],
+            default_expression: None,
+            compression: None,
+            storage: None,
+            statistics_target: None,
         }
     }
 
@@ -182,6 +244,58 @@ impl Column {
         self
     }
 
+    /// Gets the SQL expression (ie: a function call) used as default value, if any.
+    /// The expression is emitted verbatim and takes precedence over the default value.
+    pub fn get_default_expression(&self) -> Option<&str> {
+        self.default_expression.as_deref()
+    }
+
+    pub fn set_default_expression<T: AsRef<str>, S: Into<Option<T>>>(
+        &mut self,
+        expression: S,
+    ) -> &mut Self {
+        self.default_expression = expression.into().map(|e| e.as_ref().to_string());
+        self
+    }
+
+    /// Gets the compression method of the column (ie: `lz4` on PostgreSQL).
+    /// Compression is ignored on platforms not supporting it
+    /// (PostgreSQL before version 14 and MySQL, while it is supported by MariaDB).
+    pub fn get_compression(&self) -> Option<&str> {
+        self.compression.as_deref()
+    }
+
+    pub fn set_compression<T: AsRef<str>, S: Into<Option<T>>>(
+        &mut self,
+        compression: S,
+    ) -> &mut Self {
+        self.compression = compression.into().map(|c| c.as_ref().to_string());
+        self
+    }
+
+    /// Gets the storage mode of the column (ie: `EXTERNAL` on PostgreSQL).
+    /// Storage is ignored on platforms not supporting it.
+    pub fn get_storage(&self) -> Option<&str> {
+        self.storage.as_deref()
+    }
+
+    pub fn set_storage<T: AsRef<str>, S: Into<Option<T>>>(&mut self, storage: S) -> &mut Self {
+        self.storage = storage.into().map(|s| s.as_ref().to_uppercase());
+        self
+    }
+
+    /// Gets the amount of statistics collected for the column by the planner.
+    /// `None` means the server default is used.
+    /// Statistics targets are ignored on platforms not supporting them.
+    pub fn get_statistics_target(&self) -> Option<usize> {
+        self.statistics_target
+    }
+
+    pub fn set_statistics_target<I: Into<Option<usize>>>(&mut self, target: I) -> &mut Self {
+        self.statistics_target = target.into();
+        self
+    }
+
     pub fn get_comment(&self) -> &Option<String> {
         &self.comment
     }
@@ -311,6 +425,40 @@ impl Column {
         self
     }
 
+    /// Whether an autoincrement column is backed by an identity
+    /// (GENERATED ... AS IDENTITY) instead of a sequence default.
+    pub fn is_identity(&self) -> bool {
+        self.identity.unwrap_or(false)
+    }
+
+    pub fn set_identity<I: Into<Option<bool>>>(&mut self, identity: I) -> &mut Self {
+        self.identity = identity.into();
+        self
+    }
+
+    /// Gets the user-defined type backing a composite column.
+    pub fn get_composite_type(&self) -> Option<&CompositeTypeDefinition> {
+        self.composite_type.as_ref()
+    }
+
+    pub fn set_composite_type<I: Into<Option<CompositeTypeDefinition>>>(
+        &mut self,
+        composite_type: I,
+    ) -> &mut Self {
+        self.composite_type = composite_type.into();
+        self
+    }
+
+    /// Gets the allowed values of an enumerated (ENUM/SET) column, in declaration order.
+    pub fn get_values(&self) -> &[String] {
+        &self.values
+    }
+
+    pub fn set_values<S: AsRef<str>, I: IntoIterator<Item = S>>(&mut self, values: I) -> &mut Self {
+        self.values = values.into_iter().map(|v| v.as_ref().to_string()).collect();
+        self
+    }
+
     pub(crate) fn generate_column_data(&self, platform: &dyn DatabasePlatform) -> ColumnData {
         let name = self.get_quoted_name(platform);
 
@@ -334,6 +482,16 @@ impl Column {
             primary: false,
             check: self.check.clone(),
             jsonb: self.is_jsonb(),
+            identity: self.is_identity() && platform.supports_generated_identity_columns(),
+            composite_type: self.composite_type.clone(),
+            values: self.values.clone(),
+            default_expression: self.default_expression.clone(),
+            compression: self
+                .compression
+                .clone()
+                .filter(|_| platform.supports_column_compression()),
+            storage: self.storage.clone(),
+            statistics_target: self.statistics_target,
         }
     }
 }
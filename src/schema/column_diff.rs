@@ -1,4 +1,6 @@
+use crate::platform::DatabasePlatform;
 use crate::schema::{Asset, Column, Identifier};
+use crate::Result;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum ChangedProperty {
@@ -12,6 +14,11 @@ pub enum ChangedProperty {
     Comment,
     Length,
     Unsigned,
+    Identity,
+    Values,
+    Compression,
+    Storage,
+    StatisticsTarget,
 }
 
 /// Represents the change of a column.
@@ -21,6 +28,7 @@ pub struct ColumnDiff {
     pub column: Column,
     pub changed_properties: Vec<ChangedProperty>,
     pub from_column: Option<Column>,
+    force_cast: bool,
 }
 
 impl ColumnDiff {
@@ -35,6 +43,7 @@ impl ColumnDiff {
             column: column.clone(),
             changed_properties: changed_properties.to_vec(),
             from_column: from_column.into(),
+            force_cast: false,
         }
     }
 
@@ -51,4 +60,31 @@ impl ColumnDiff {
                 .unwrap_or(false),
         )
     }
+
+    /// Whether the copied data must be cast to the new column type when a table
+    /// is rebuilt, even if the platform considers the conversion lossy.
+    pub fn is_cast_forced(&self) -> bool {
+        self.force_cast
+    }
+
+    pub fn set_force_cast(&mut self, force_cast: bool) {
+        self.force_cast = force_cast;
+    }
+
+    /// Gets the expression reading the old column values converted to the new column type.
+    /// Used to copy the data when a table is rebuilt to apply the change.
+    pub fn get_cast_expression(&self, platform: &dyn DatabasePlatform) -> Result<String> {
+        let old_column_name = self.get_old_column_name().get_quoted_name(platform);
+        if !self.has_changed(ChangedProperty::Type) {
+            return Ok(old_column_name);
+        }
+
+        let column_data = self.column.generate_column_data(platform);
+        let declaration = self
+            .column
+            .get_type()
+            .get_sql_declaration(&column_data, platform)?;
+
+        Ok(format!("CAST({} AS {})", old_column_name, declaration))
+    }
 }
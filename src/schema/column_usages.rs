@@ -0,0 +1,23 @@
+/// The constraints and indexes of a table referencing a column.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ColumnUsages {
+    /// Whether the column is part of the primary key.
+    pub in_primary_key: bool,
+    /// The names of the (non-primary) indexes including the column.
+    pub in_indexes: Vec<String>,
+    /// The names of the unique constraints including the column.
+    pub in_unique_constraints: Vec<String>,
+    /// The names of the foreign keys including the column as local column.
+    pub in_foreign_keys: Vec<String>,
+}
+
+impl ColumnUsages {
+    /// Whether the column is referenced by any index or constraint,
+    /// and thus cannot be dropped alone.
+    pub fn is_used(&self) -> bool {
+        self.in_primary_key
+            || !self.in_indexes.is_empty()
+            || !self.in_unique_constraints.is_empty()
+            || !self.in_foreign_keys.is_empty()
+    }
+}
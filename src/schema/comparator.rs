@@ -1,12 +1,13 @@
 use crate::r#type::DECIMAL;
 use crate::r#type::{IntoType, BINARY, GUID, STRING};
 use crate::schema::{
-    Asset, ChangedProperty, Column, ColumnData, Index, Schema, SchemaDiff, SchemaManager, Sequence,
-    Table, TableDiff,
+    Asset, ChangedProperty, Column, ColumnData, Index, ReplicaIdentity, Schema, SchemaDiff,
+    SchemaManager, Sequence, Table, TableDiff,
 };
 use crate::schema::{ColumnDiff, ForeignKeyConstraint};
 use crate::{Result, Value};
 use itertools::Itertools;
+use log::warn;
 use std::collections::btree_map::Entry::{Occupied, Vacant};
 use std::collections::{BTreeMap, HashMap};
 
@@ -18,8 +19,52 @@ fn is_auto_increment_sequence_in_schema(schema: &Schema, sequence: &Sequence) ->
 }
 
 fn diff_sequence(sequence1: &Sequence, sequence2: &Sequence) -> bool {
+    // A cache of 1 (or less) is the same as no cache at all.
+    let cache = |s: &Sequence| s.get_cache().filter(|c| *c > 1);
+    // A minimum value equal to the initial value (used on creation) or to 1 (the default
+    // of ascending sequences) is the same as no minimum value at all.
+    let min_value = |s: &Sequence| {
+        s.get_min_value()
+            .filter(|m| *m != s.get_initial_value() && *m != 1)
+    };
+
     sequence1.get_allocation_size() != sequence2.get_allocation_size()
         || sequence1.get_initial_value() != sequence2.get_initial_value()
+        || cache(sequence1) != cache(sequence2)
+        || min_value(sequence1) != min_value(sequence2)
+        || sequence1.get_max_value() != sequence2.get_max_value()
+        || sequence1.is_cycle() != sequence2.is_cycle()
+}
+
+/// Validates the given foreign keys of a table against the tables they reference in the schema.
+fn validate_foreign_keys<'a>(
+    schema_manager: &dyn SchemaManager,
+    schema: &Schema,
+    table: &Table,
+    foreign_keys: impl IntoIterator<Item = &'a ForeignKeyConstraint>,
+) -> Result<()> {
+    for foreign_key in foreign_keys {
+        if let Some(foreign_table) = schema.get_table(foreign_key.get_foreign_table()) {
+            schema_manager.validate_foreign_key(table, foreign_key, foreign_table)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves the charset and collation a column inherits from the table default when not
+/// explicitly set, if the column it is compared to has an explicit one.
+fn inherit_table_defaults(column: &Column, table: &Table, other: &Column) -> Column {
+    let mut column = column.clone();
+    if column.get_charset().is_none() && other.get_charset().is_some() {
+        column.set_charset::<String, _>(table.get_charset());
+    }
+
+    if column.get_collation().is_none() && other.get_collation().is_some() {
+        column.set_collation::<String, _>(table.get_collation());
+    }
+
+    column
 }
 
 /// Try to find columns that only changed their name, rename operations maybe cheaper than add/drop
@@ -167,16 +212,36 @@ pub fn diff_column(properties1: ColumnData, properties2: ColumnData) -> Vec<Chan
 
     if properties1.autoincrement != properties2.autoincrement {
         changed_properties.push(ChangedProperty::AutoIncrement);
+    } else if properties1.autoincrement && properties1.identity != properties2.identity {
+        changed_properties.push(ChangedProperty::Identity);
     }
 
     // Null values need to be checked additionally as they tell whether to create or drop a default value.
     // null != 0, null != false, null != '' etc. This affects platform's table alteration SQL generation.
     if ((properties1.default == Value::NULL) != (properties2.default == Value::NULL))
         || properties1.default != properties2.default
+        || properties1.default_expression != properties2.default_expression
     {
         changed_properties.push(ChangedProperty::Default);
     }
 
+    // The order of the values is significant (ie: it determines the sorting of ENUM columns).
+    if properties1.values != properties2.values {
+        changed_properties.push(ChangedProperty::Values);
+    }
+
+    if properties1.compression != properties2.compression {
+        changed_properties.push(ChangedProperty::Compression);
+    }
+
+    if properties1.storage != properties2.storage {
+        changed_properties.push(ChangedProperty::Storage);
+    }
+
+    if properties1.statistics_target != properties2.statistics_target {
+        changed_properties.push(ChangedProperty::StatisticsTarget);
+    }
+
     if properties1.r#type == STRING.into_type().unwrap()
         && properties1.r#type != GUID.into_type().unwrap()
         || properties1.r#type == BINARY.into_type().unwrap()
@@ -236,13 +301,37 @@ pub trait Comparator {
         let mut removed_tables = vec![];
 
         for table in to_schema.get_tables() {
+            for foreign_key in table.foreign_keys_without_index() {
+                warn!(
+                    "Foreign key {} on table {} has no index on its columns ({})",
+                    foreign_key.get_name(),
+                    table.get_name(),
+                    foreign_key.get_unquoted_local_columns().join(", ")
+                );
+            }
+
             let table_name = table.get_shortest_name(&dest_schema_name);
             if !from_schema.has_table(&table_name) {
+                validate_foreign_keys(
+                    self.get_schema_manager(),
+                    to_schema,
+                    table,
+                    table.get_foreign_keys(),
+                )?;
                 new_tables.push(table);
             } else if let Some(table_differences) = self.diff_table(
                 unsafe { from_schema.get_table_unchecked(&table_name) },
                 unsafe { to_schema.get_table_unchecked(&table_name) },
             )? {
+                validate_foreign_keys(
+                    self.get_schema_manager(),
+                    to_schema,
+                    table,
+                    table_differences
+                        .added_foreign_keys
+                        .iter()
+                        .chain(&table_differences.changed_foreign_keys),
+                )?;
                 changed_tables.insert(table_name.to_lowercase(), table_differences);
             }
         }
@@ -362,9 +451,12 @@ pub trait Comparator {
             // See if column is removed in "to" table.
             let column_name = column.get_name();
             if let Some(to_column) = to_table.get_column(column_name) {
+                let from_resolved = inherit_table_defaults(column, from_table, to_column);
+                let to_resolved = inherit_table_defaults(to_column, to_table, column);
+
                 // See if column has changed properties in "to" table.
-                let changed_properties = self.diff_column(column, to_column);
-                if !schema_manager.columns_equal(column, to_column)? {
+                let changed_properties = self.diff_column(&from_resolved, &to_resolved);
+                if !schema_manager.columns_equal(&from_resolved, &to_resolved)? {
                     table_differences.changed_columns.push(ColumnDiff::new(
                         &column.get_name(),
                         to_column,
@@ -440,7 +532,7 @@ pub trait Comparator {
 
         for (from_key, from_constraint) in from_foreign_keys {
             for (to_key, to_constraint) in &to_foreign_keys {
-                if from_constraint == *to_constraint {
+                if !self.diff_foreign_key(from_constraint, to_constraint) {
                     rem_from_keys.remove(&from_key);
                     rem_to_keys.remove(to_key);
                 } else if from_constraint.get_name().to_lowercase()
@@ -451,6 +543,7 @@ pub trait Comparator {
                         .push((*to_constraint).clone());
                     rem_from_keys.remove(&from_key);
                     rem_to_keys.remove(to_key);
+                    changes += 1;
                 }
             }
         }
@@ -459,12 +552,27 @@ pub trait Comparator {
             table_differences
                 .removed_foreign_keys
                 .push(foreign_key.clone());
+            changes += 1;
         }
 
         for (_, foreign_key) in rem_to_keys.into_iter() {
             table_differences
                 .added_foreign_keys
                 .push(foreign_key.clone());
+            changes += 1;
+        }
+
+        // Tables without an explicit replica identity use the default one.
+        if let Some(replica_identity) = to_table.get_replica_identity() {
+            let from_replica_identity = from_table
+                .get_replica_identity()
+                .unwrap_or(&ReplicaIdentity::Default);
+            if from_replica_identity != replica_identity
+                && schema_manager.get_platform()?.supports_replica_identity()
+            {
+                table_differences.changed_replica_identity = Some(replica_identity.clone());
+                changes += 1;
+            }
         }
 
         Ok(if changes > 0 {
@@ -489,7 +597,30 @@ pub trait Comparator {
     /// Finds the difference between the indexes $index1 and $index2.
     /// Compares index1 with index2 and returns if there are any differences.
     fn diff_index(&self, index1: &Index, index2: &Index) -> bool {
-        !(index1.is_fulfilled_by(index2) && index2.is_fulfilled_by(index1))
+        let visibility_changed = index1.is_visible() != index2.is_visible()
+            && self
+                .get_schema_manager()
+                .get_platform()
+                .is_ok_and(|platform| platform.supports_invisible_indexes());
+
+        visibility_changed || !(index1.is_fulfilled_by(index2) && index2.is_fulfilled_by(index1))
+    }
+
+    /// Finds the difference between the foreign keys $key1 and $key2.
+    /// Referential actions are normalized by the platform before being compared.
+    fn diff_foreign_key(&self, key1: &ForeignKeyConstraint, key2: &ForeignKeyConstraint) -> bool {
+        let Ok(platform) = self.get_schema_manager().get_platform() else {
+            return key1 != key2;
+        };
+
+        let normalize = |key: &ForeignKeyConstraint| {
+            let mut key = key.clone();
+            key.on_update = Some(platform.normalize_foreign_key_referential_action(key.on_update));
+            key.on_delete = Some(platform.normalize_foreign_key_referential_action(key.on_delete));
+            key
+        };
+
+        normalize(key1) != normalize(key2)
     }
 }
 
@@ -517,6 +648,7 @@ impl<C: Comparator + ?Sized> Comparator for &mut C {
             fn get_schema_manager(&self) -> &dyn SchemaManager;
             fn diff_column(&self, column1: &Column, column2: &Column) -> Vec<ChangedProperty>;
             fn diff_index(&self, index1: &Index, index2: &Index) -> bool;
+            fn diff_foreign_key(&self, key1: &ForeignKeyConstraint, key2: &ForeignKeyConstraint) -> bool;
         }
     }
 }
@@ -529,6 +661,7 @@ impl<C: Comparator + ?Sized> Comparator for Box<C> {
             fn get_schema_manager(&self) -> &dyn SchemaManager;
             fn diff_column(&self, column1: &Column, column2: &Column) -> Vec<ChangedProperty>;
             fn diff_index(&self, index1: &Index, index2: &Index) -> bool;
+            fn diff_foreign_key(&self, key1: &ForeignKeyConstraint, key2: &ForeignKeyConstraint) -> bool;
         }
     }
 }
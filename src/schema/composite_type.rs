@@ -0,0 +1,38 @@
+use crate::schema::asset::{impl_asset, AbstractAsset};
+use crate::schema::{Asset, Column, IntoIdentifier};
+
+/// Describes a user-defined composite type (PostgreSQL `CREATE TYPE ... AS (...)`)
+/// backing a column declared with the [`CompositeType`](crate::r#type::CompositeType) type.
+#[derive(Clone, Debug, Eq, IntoIdentifier, PartialEq)]
+pub struct CompositeTypeDefinition {
+    asset: AbstractAsset,
+    attributes: Vec<Column>,
+}
+
+impl CompositeTypeDefinition {
+    pub fn new<S: AsRef<str>>(name: S, attributes: Vec<Column>) -> Self {
+        let mut asset = AbstractAsset::default();
+        asset.set_name(name.as_ref());
+
+        Self { asset, attributes }
+    }
+
+    /// Gets the attributes (subfields) of the composite type, in declaration order.
+    pub fn get_attributes(&self) -> &[Column] {
+        &self.attributes
+    }
+
+    pub fn get_attribute<S: AsRef<str>>(&self, name: S) -> Option<&Column> {
+        let name = name.as_ref().to_lowercase();
+        self.attributes
+            .iter()
+            .find(|a| a.get_name().to_lowercase() == name)
+    }
+
+    pub fn add_attribute<C: Into<Column>>(&mut self, attribute: C) -> &mut Self {
+        self.attributes.push(attribute.into());
+        self
+    }
+}
+
+impl_asset!(CompositeTypeDefinition, asset);
@@ -15,6 +15,9 @@ pub struct Index {
     options: HashMap<String, Value>,
     is_unique: bool,
     is_primary: bool,
+    is_visible: bool,
+    nulls_not_distinct: bool,
+    constraint_backed: bool,
     pub r#where: Option<String>,
 }
 
@@ -155,6 +158,8 @@ pub struct IndexBuilder {
     columns: Vec<Identifier>,
     is_unique: bool,
     is_primary: bool,
+    is_visible: bool,
+    nulls_not_distinct: bool,
     flags: Vec<String>,
     options: HashMap<String, Value>,
 }
@@ -175,6 +180,18 @@ impl IndexBuilder {
         self
     }
 
+    /// Sets whether the index is visible to the query optimizer.
+    pub fn set_visible(mut self, visible: bool) -> Self {
+        self.is_visible = visible;
+        self
+    }
+
+    /// Sets whether null values are considered equal by the unique index.
+    pub fn set_nulls_not_distinct(mut self, nulls_not_distinct: bool) -> Self {
+        self.nulls_not_distinct = nulls_not_distinct;
+        self
+    }
+
     pub fn add_flag<S: AsRef<str>>(mut self, flag: S) -> Self {
         self.flags.push(flag.as_ref().to_string());
         self
@@ -205,14 +222,18 @@ impl IndexBuilder {
 
 impl From<IndexBuilder> for Index {
     fn from(value: IndexBuilder) -> Self {
-        Index::new::<String, _, _>(
+        let mut index = Index::new::<String, _, _>(
             value.name,
             value.columns.as_slice(),
             value.is_unique,
             value.is_primary,
             value.flags.as_slice(),
             value.options,
-        )
+        );
+
+        index.set_visible(value.is_visible);
+        index.set_nulls_not_distinct(value.nulls_not_distinct);
+        index
     }
 }
 
@@ -240,6 +261,9 @@ impl Index {
             options,
             is_unique,
             is_primary,
+            is_visible: true,
+            nulls_not_distinct: false,
+            constraint_backed: false,
             r#where: None,
         };
 
@@ -261,6 +285,8 @@ impl Index {
             columns: vec![],
             is_unique: false,
             is_primary: false,
+            is_visible: true,
+            nulls_not_distinct: false,
             flags: vec![],
             options: Default::default(),
         }
@@ -364,6 +390,37 @@ impl Index {
         self.is_unique
     }
 
+    /// Whether the index is visible to the query optimizer.
+    /// Invisible indexes are maintained, but not used by queries (MySQL only).
+    pub fn is_visible(&self) -> bool {
+        self.is_visible
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.is_visible = visible;
+    }
+
+    /// Whether null values are considered equal by the unique index,
+    /// rejecting more than one null value (PostgreSQL 15+ only).
+    pub fn is_nulls_not_distinct(&self) -> bool {
+        self.nulls_not_distinct
+    }
+
+    pub fn set_nulls_not_distinct(&mut self, nulls_not_distinct: bool) {
+        self.nulls_not_distinct = nulls_not_distinct;
+    }
+
+    /// Whether this index backs a declared unique (or primary key) constraint,
+    /// rather than being created on its own (ie: with `CREATE UNIQUE INDEX`).
+    /// This is populated during introspection only (PostgreSQL only).
+    pub fn is_constraint_backed(&self) -> bool {
+        self.constraint_backed
+    }
+
+    pub fn set_constraint_backed(&mut self, constraint_backed: bool) {
+        self.constraint_backed = constraint_backed;
+    }
+
     /// Checks if this index exactly spans the given column names in the correct order.
     pub(crate) fn spans_columns(&self, column_names: &[String]) -> bool {
         self.columns.iter().enumerate().all(|(index, column)| {
@@ -374,6 +431,16 @@ impl Index {
         })
     }
 
+    /// Checks if the leading columns of this index are the given column names, in the same order.
+    pub fn starts_with_columns(&self, column_names: &[String]) -> bool {
+        let columns = self.get_columns();
+        column_names.len() <= columns.len()
+            && column_names
+                .iter()
+                .zip(columns.iter())
+                .all(|(name, column)| name.to_lowercase() == column.to_lowercase())
+    }
+
     pub fn is_fulfilled_by(&self, other: &Index) -> bool {
         // allow the other index to be equally large only. It being larger is an option
         // but it creates a problem with scenarios of the kind PRIMARY KEY(foo,bar) UNIQUE(foo)
@@ -395,7 +462,9 @@ impl Index {
                 return true;
             }
 
-            other.is_primary() == self.is_primary() && other.is_unique() == self.is_unique()
+            other.is_primary() == self.is_primary()
+                && other.is_unique() == self.is_unique()
+                && other.is_nulls_not_distinct() == self.is_nulls_not_distinct()
         } else {
             false
         }
@@ -452,6 +521,9 @@ pub struct IndexOptions {
     pub flags: Vec<String>,
     pub options_lengths: Vec<Option<usize>>,
     pub options_where: Option<String>,
+    pub visible: bool,
+    pub nulls_not_distinct: bool,
+    pub constraint_backed: bool,
 }
 
 impl IndexOptions {
@@ -480,13 +552,18 @@ impl IndexOptions {
             }
         }
 
-        Index::new(
+        let mut index = Index::new(
             self.name,
             &self.columns,
             self.unique,
             self.primary,
             &self.flags,
             options,
-        )
+        );
+
+        index.set_visible(self.visible);
+        index.set_nulls_not_distinct(self.nulls_not_distinct);
+        index.set_constraint_backed(self.constraint_backed);
+        index
     }
 }
@@ -0,0 +1,32 @@
+/// Usage and cardinality statistics of an index, as collected by the database server.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexStatistics {
+    index_name: String,
+    cardinality: Option<u64>,
+    scans: Option<u64>,
+}
+
+impl IndexStatistics {
+    pub fn new<S: AsRef<str>>(index_name: S, cardinality: Option<u64>, scans: Option<u64>) -> Self {
+        Self {
+            index_name: index_name.as_ref().to_string(),
+            cardinality,
+            scans,
+        }
+    }
+
+    pub fn get_index_name(&self) -> &str {
+        &self.index_name
+    }
+
+    /// Gets the estimated number of distinct values in the index.
+    /// Returns None if statistics have not been collected yet (ie: the table has not been analyzed).
+    pub fn get_cardinality(&self) -> Option<u64> {
+        self.cardinality
+    }
+
+    /// Gets the number of index scans initiated on this index, if tracked by the platform.
+    pub fn get_scans(&self) -> Option<u64> {
+        self.scans
+    }
+}
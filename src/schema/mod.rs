@@ -2,16 +2,23 @@ mod asset;
 mod check_constraint;
 mod column;
 mod column_diff;
+mod column_usages;
 mod comparator;
+mod composite_type;
 mod foreign_key_constraint;
 mod identifier;
 mod index;
+mod index_statistics;
+mod replica_identity;
 mod schema_config;
 mod schema_diff;
 mod schema_manager;
 mod sequence;
 mod table;
 mod table_diff;
+mod table_grant;
+mod table_kind;
+mod table_timestamps;
 mod unique_constraint;
 mod view;
 
@@ -23,18 +30,26 @@ pub(crate) use table::TableOptions;
 pub use check_constraint::CheckConstraint;
 pub use column::{Column, ColumnList};
 pub use column_diff::{ChangedProperty, ColumnDiff};
+pub use column_usages::ColumnUsages;
 pub use comparator::{diff_column, Comparator, GenericComparator};
+pub use composite_type::CompositeTypeDefinition;
 pub use foreign_key_constraint::{
     FKConstraintList, ForeignKeyConstraint, ForeignKeyReferentialAction,
 };
 pub use identifier::{Identifier, IntoIdentifier};
 pub use index::{Index, IndexList, IndexOptions};
-pub use schema_diff::SchemaDiff;
+pub use index_statistics::IndexStatistics;
+pub use replica_identity::ReplicaIdentity;
+pub use schema_config::SchemaConfig;
+pub use schema_diff::{SchemaChangeKind, SchemaDiff};
 pub use schema_manager::{extract_type_from_comment, remove_type_from_comment, SchemaManager};
 pub(crate) use schema_manager::{get_database, string_from_value};
 pub use sequence::Sequence;
 pub use table::{Table, TableList};
 pub use table_diff::TableDiff;
+pub use table_grant::TableGrant;
+pub use table_kind::TableKind;
+pub use table_timestamps::TableTimestamps;
 pub use unique_constraint::UniqueConstraint;
 pub use view::View;
 
@@ -42,8 +57,8 @@ pub use ::creed_macros::IntoIdentifier;
 
 use crate::platform::DatabasePlatform;
 use crate::schema::asset::{impl_asset, AbstractAsset};
-use crate::schema::schema_config::SchemaConfig;
-use crate::Result;
+use crate::{Error, Result};
+use crc::{Crc, CRC_64_XZ};
 use itertools::Itertools;
 
 pub trait NamedListIndex {
@@ -267,8 +282,55 @@ impl Schema {
         self.sequences.iter().any(|i| i.get_name() == name)
     }
 
+    /// Computes a hash of the normalized definition of the tables, views and sequences
+    /// of this schema, useful to quickly detect drifts between two schemas.
+    ///
+    /// Assets, columns and options are sorted by name, so that the order in which they
+    /// have been defined does not alter the fingerprint.
+    pub fn fingerprint(&self) -> String {
+        let mut definitions = self
+            .tables
+            .iter()
+            .map(table_fingerprint_definition)
+            .chain(
+                self.views
+                    .iter()
+                    .map(|v| format!("view {} {}", v.get_name().to_lowercase(), v.get_sql())),
+            )
+            .chain(self.sequences.iter().map(|s| {
+                format!(
+                    "sequence {} {} {} {:?}",
+                    s.get_name().to_lowercase(),
+                    s.get_allocation_size(),
+                    s.get_initial_value(),
+                    s.get_cache()
+                )
+            }))
+            .collect::<Vec<_>>();
+        definitions.sort();
+
+        let checksum = Crc::<u64>::new(&CRC_64_XZ).checksum(definitions.join("\n").as_bytes());
+        format!("{:016x}", checksum)
+    }
+
+    /// Checks that every foreign key references existing columns of a table of this schema,
+    /// and that the referenced columns are covered by a primary key or a unique index/constraint.
+    pub fn validate(&self) -> Result<()> {
+        for table in &self.tables {
+            for foreign_key in table.get_foreign_keys() {
+                validate_foreign_key(self, table, foreign_key)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns an array of necessary SQL queries to create the schema on the given platform.
+    ///
+    /// The schema is validated first: an error is returned on dangling foreign key references.
     pub fn to_sql(&self, schema_manager: &dyn SchemaManager) -> Result<Vec<String>> {
+        self.validate()?;
+
         let builder = CreateSchemaObjectsSQLBuilder::new(schema_manager);
         builder.build_sql(self)
     }
@@ -282,6 +344,172 @@ impl Schema {
 
 impl_asset!(Schema, asset);
 
+fn validate_foreign_key(
+    schema: &Schema,
+    table: &Table,
+    foreign_key: &ForeignKeyConstraint,
+) -> Result<()> {
+    let invalid = |reason: String| {
+        Error::foreign_key_reference_invalid(&foreign_key.get_name(), &table.get_name(), &reason)
+    };
+
+    let foreign_table_name = foreign_key.get_foreign_table().get_name();
+    let Some(foreign_table) = schema.get_table(foreign_key.get_foreign_table()) else {
+        return Err(invalid(format!(
+            "referenced table {} does not exist in the schema",
+            foreign_table_name
+        )));
+    };
+
+    let normalize = |columns: Vec<String>| {
+        columns
+            .iter()
+            .map(|c| c.to_lowercase())
+            .sorted()
+            .collect::<Vec<_>>()
+    };
+
+    let foreign_columns = foreign_key.get_unquoted_foreign_columns();
+    if let Some(column) = foreign_columns
+        .iter()
+        .find(|c| !foreign_table.has_column(c.as_str()))
+    {
+        return Err(invalid(format!(
+            "referenced column {} does not exist on table {}",
+            column, foreign_table_name
+        )));
+    }
+
+    let foreign_columns = normalize(foreign_columns);
+    let is_unique = foreign_table
+        .indices()
+        .iter()
+        .filter(|i| i.is_primary() || i.is_unique())
+        .any(|i| normalize(i.get_unquoted_columns()) == foreign_columns)
+        || foreign_table
+            .get_unique_constraints()
+            .iter()
+            .any(|u| normalize(u.get_unquoted_columns()) == foreign_columns);
+
+    if is_unique {
+        Ok(())
+    } else {
+        Err(invalid(format!(
+            "referenced columns ({}) of table {} are not a primary key nor unique",
+            foreign_columns.join(", "),
+            foreign_table_name
+        )))
+    }
+}
+
+fn table_fingerprint_definition(table: &Table) -> String {
+    fn sorted_options<'a, I: Iterator<Item = (&'a String, &'a crate::Value)>>(
+        options: I,
+    ) -> String {
+        options
+            .map(|(k, v)| format!("{}={:?}", k, v))
+            .sorted()
+            .join(",")
+    }
+
+    let mut lines = vec![format!(
+        "table {} {:?}",
+        table.get_name().to_lowercase(),
+        table.get_comment()
+    )];
+
+    lines.extend(
+        table
+            .columns()
+            .into_iter()
+            .map(|c| {
+                format!(
+                    "column {} {} {} {:?} {:?} {:?} {:?} {} {:?} {} {:?} {:?} {:?}",
+                    c.get_name().to_lowercase(),
+                    c.get_type().get_name(),
+                    c.is_notnull(),
+                    c.get_default(),
+                    c.get_length(),
+                    c.get_precision(),
+                    c.get_scale(),
+                    c.is_fixed(),
+                    c.is_unsigned(),
+                    c.is_autoincrement(),
+                    c.get_comment(),
+                    c.get_collation(),
+                    c.get_values(),
+                )
+            })
+            .sorted(),
+    );
+
+    lines.extend(
+        table
+            .indices()
+            .iter()
+            .map(|i| {
+                format!(
+                    "index {} {} {} {} {} {}",
+                    i.get_name().to_lowercase(),
+                    i.get_columns().iter().map(|c| c.to_lowercase()).join(","),
+                    i.is_unique(),
+                    i.is_primary(),
+                    i.get_flags()
+                        .iter()
+                        .map(|f| f.to_lowercase())
+                        .sorted()
+                        .join(","),
+                    sorted_options(i.get_options().iter()),
+                )
+            })
+            .sorted(),
+    );
+
+    lines.extend(
+        table
+            .get_unique_constraints()
+            .iter()
+            .map(|u| {
+                format!(
+                    "unique {} {}{}",
+                    u.get_name().to_lowercase(),
+                    u.get_columns().iter().map(|c| c.to_lowercase()).join(","),
+                    if u.is_nulls_not_distinct() {
+                        " nulls_not_distinct"
+                    } else {
+                        ""
+                    },
+                )
+            })
+            .sorted(),
+    );
+
+    lines.extend(
+        table
+            .get_foreign_keys()
+            .iter()
+            .map(|fk| {
+                format!(
+                    "foreign_key {} {} {} {} {}",
+                    fk.get_name().to_lowercase(),
+                    fk.get_unquoted_local_columns()
+                        .iter()
+                        .map(|c| c.to_lowercase())
+                        .join(","),
+                    fk.get_unqualified_foreign_table_name(),
+                    fk.get_unquoted_foreign_columns()
+                        .iter()
+                        .map(|c| c.to_lowercase())
+                        .join(","),
+                    sorted_options(fk.get_options().iter()),
+                )
+            })
+            .sorted(),
+    );
+
+    lines.join("\n")
+}
+
 struct CreateSchemaObjectsSQLBuilder<'a> {
     schema_manager: &'a dyn SchemaManager,
 }
@@ -0,0 +1,13 @@
+/// The information written to the write-ahead log to identify the rows
+/// updated or deleted, as used by logical replication (PostgreSQL only).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ReplicaIdentity {
+    /// Records the old values of the primary key columns, if any.
+    Default,
+    /// Records the old values of all the columns in the row.
+    Full,
+    /// Records no information about the old row.
+    Nothing,
+    /// Records the old values of the columns covered by the given unique index.
+    UsingIndex(String),
+}
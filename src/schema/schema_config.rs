@@ -1,12 +1,17 @@
 #[derive(Clone, Debug)]
 pub struct SchemaConfig {
     pub max_identifier_length: usize,
+    /// Whether explicit index and constraint names longer than `max_identifier_length`
+    /// should be shortened (replacing their tail with a hash of the full name).
+    /// Disabled by default to keep existing names stable.
+    pub shorten_identifiers: bool,
 }
 
 impl Default for SchemaConfig {
     fn default() -> Self {
         Self {
             max_identifier_length: 63,
+            shorten_identifiers: false,
         }
     }
 }
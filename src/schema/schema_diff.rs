@@ -5,6 +5,17 @@ use crate::schema::{
 use crate::Result;
 use std::collections::BTreeMap;
 
+/// Kind of change contained in a schema diff.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchemaChangeKind {
+    /// New namespaces, tables, sequences, columns, indexes and foreign keys.
+    Addition,
+    /// Altered or renamed assets.
+    Change,
+    /// Dropped namespaces, tables, sequences, columns, indexes and foreign keys.
+    Removal,
+}
+
 /// Differences between two schemas.
 ///
 /// The object contains the operations to change the schema stored in $fromSchema
@@ -54,6 +65,36 @@ impl<'a> SchemaDiff<'a> {
         }
     }
 
+    /// Reduces the diff to the changes of the given kinds.
+    /// Changed tables left without differences are removed from the diff.
+    pub fn filter(mut self, kinds: &[SchemaChangeKind]) -> Self {
+        if !kinds.contains(&SchemaChangeKind::Addition) {
+            self.new_namespaces.clear();
+            self.new_tables.clear();
+            self.new_sequences.clear();
+        }
+
+        if !kinds.contains(&SchemaChangeKind::Change) {
+            self.changed_sequences.clear();
+        }
+
+        if !kinds.contains(&SchemaChangeKind::Removal) {
+            self.removed_namespaces.clear();
+            self.removed_tables.clear();
+            self.removed_sequences.clear();
+            self.orphaned_foreign_keys.clear();
+        }
+
+        self.changed_tables = self
+            .changed_tables
+            .into_iter()
+            .map(|(name, table_diff)| (name, table_diff.filter(kinds)))
+            .filter(|(_, table_diff)| !table_diff.is_empty())
+            .collect();
+
+        self
+    }
+
     /// The to save sql mode ensures that the following things don't happen:
     ///
     /// 1. Tables are deleted
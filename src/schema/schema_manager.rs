@@ -7,11 +7,12 @@ use crate::schema::schema_config::SchemaConfig;
 use crate::schema::table::TableList;
 use crate::schema::{
     Asset, Column, ColumnData, ColumnDiff, ColumnList, Comparator, FKConstraintList,
-    ForeignKeyConstraint, ForeignKeyReferentialAction, Identifier, Index, IntoIdentifier, Schema,
-    SchemaDiff, Sequence, Table, TableDiff, TableOptions, UniqueConstraint, View,
+    ForeignKeyConstraint, ForeignKeyReferentialAction, Identifier, Index, IndexStatistics,
+    IntoIdentifier, ReplicaIdentity, Schema, SchemaDiff, Sequence, Table, TableDiff, TableGrant,
+    TableKind, TableOptions, TableTimestamps, UniqueConstraint, View,
 };
 use crate::util::{function_name, ToSqlStatementList};
-use crate::{params, AsyncResult, Connection, Error, Result, Row, Value};
+use crate::{params, AsyncResult, Connection, Error, Parameters, Result, Row, Value};
 use creed::schema::index::IndexList;
 use regex::Regex;
 use std::collections::HashMap;
@@ -185,6 +186,17 @@ pub trait SchemaManager: Sync + Send {
         default::get_create_foreign_key_sql(self.as_dyn(), foreign_key, table)
     }
 
+    /// Checks the given foreign key can be created between the local and the referenced table.
+    #[allow(unused_variables)]
+    fn validate_foreign_key(
+        &self,
+        local_table: &Table,
+        foreign_key: &ForeignKeyConstraint,
+        foreign_table: &Table,
+    ) -> Result<()> {
+        Ok(())
+    }
+
     fn get_create_view_sql(&self, view: &View) -> Result<String> {
         default::get_create_view_sql(self.get_platform()?.as_dyn(), view)
     }
@@ -274,6 +286,24 @@ pub trait SchemaManager: Sync + Send {
         default::get_comment_on_table_sql(self.get_platform()?.as_dyn(), table_name, comment)
     }
 
+    #[allow(unused_variables)]
+    fn get_alter_table_owner_sql(&self, table_name: &Identifier, owner: &str) -> Result<String> {
+        Err(Error::platform_feature_unsupported("table owner"))
+    }
+
+    fn get_grant_sql(&self, table_name: &Identifier, grant: &TableGrant) -> Result<String> {
+        default::get_grant_sql(self.get_platform()?.as_dyn(), table_name, grant)
+    }
+
+    #[allow(unused_variables)]
+    fn get_alter_table_replica_identity_sql(
+        &self,
+        table_name: &Identifier,
+        replica_identity: &ReplicaIdentity,
+    ) -> Result<String> {
+        Err(Error::platform_feature_unsupported("replica identity"))
+    }
+
     fn get_comment_on_column_sql(
         &self,
         table_name: &dyn IntoIdentifier,
@@ -326,6 +356,27 @@ pub trait SchemaManager: Sync + Send {
         default::get_truncate_table_sql(self.as_dyn(), table_name)
     }
 
+    /// Generates the SQL statements needed to truncate all the given tables.
+    ///
+    /// Platforms supporting it truncate all the tables in a single statement,
+    /// otherwise a statement for each table is returned, in the given order.
+    #[allow(unused_variables)]
+    fn get_truncate_tables_sql(&self, tables: &[&str], cascade: bool) -> Result<Vec<String>> {
+        default::get_truncate_tables_sql(self.as_dyn(), tables)
+    }
+
+    /// Returns the SQL statements collecting the planner statistics of the given table.
+    fn get_analyze_table_sql(&self, table_name: &dyn IntoIdentifier) -> Result<Vec<String>> {
+        default::get_analyze_table_sql(self.as_dyn(), table_name)
+    }
+
+    /// Returns the SQL statements reclaiming the storage and defragmenting the given table.
+    /// Platforms without such maintenance command return no statement.
+    #[allow(unused_variables)]
+    fn get_optimize_table_sql(&self, table_name: &dyn IntoIdentifier) -> Result<Vec<String>> {
+        Ok(vec![])
+    }
+
     /// Returns the SQL snippet to drop an existing database.
     fn get_drop_database_sql(&self, name: &str) -> Result<String> {
         default::get_drop_database_sql(self.as_dyn(), name)
@@ -395,6 +446,29 @@ pub trait SchemaManager: Sync + Send {
         default::get_drop_view_sql(self.get_platform()?.as_dyn(), sequence)
     }
 
+    /// Returns the SQL to create a materialized view.
+    #[allow(unused_variables)]
+    fn get_create_materialized_view_sql(&self, view: &View) -> Result<String> {
+        Err(Error::platform_feature_unsupported("materialized views"))
+    }
+
+    /// Returns the SQL to refresh the data of a materialized view.
+    #[allow(unused_variables)]
+    fn get_refresh_materialized_view_sql(&self, name: &Identifier) -> Result<String> {
+        Err(Error::platform_feature_unsupported("materialized views"))
+    }
+
+    /// Returns the SQL to create a table from the result set of the given SELECT statement.
+    /// Creating the table without copying the rows (`with_data` false) is supported on PostgreSQL only.
+    fn get_create_table_as_sql(
+        &self,
+        name: &Identifier,
+        select_sql: &str,
+        with_data: bool,
+    ) -> Result<String> {
+        default::get_create_table_as_sql(self.get_platform()?.as_dyn(), name, select_sql, with_data)
+    }
+
     /// Lists the available databases for this connection.
     fn list_databases(&self) -> AsyncResult<Vec<Identifier>> {
         default::list_databases(self.as_dyn())
@@ -426,6 +500,51 @@ pub trait SchemaManager: Sync + Send {
         Box::pin(async move { default::list_table_indexes(self.as_dyn(), table).await })
     }
 
+    /// Returns the SQL to retrieve the usage and cardinality statistics of the indexes of a table.
+    #[allow(unused_variables)]
+    fn get_index_statistics_sql(&self, table: &str, database: &str) -> Result<String> {
+        Err(Error::platform_feature_unsupported("index statistics"))
+    }
+
+    /// Returns the SQL to retrieve the kind of the given relation.
+    /// The query must return a `kind` column valued as `BASE TABLE`, `VIEW`,
+    /// `MATERIALIZED VIEW` or `FOREIGN`.
+    #[allow(unused_variables)]
+    fn get_table_kind_sql(&self, table: &str, database: &str) -> Result<String> {
+        Err(Error::platform_feature_unsupported("table kind"))
+    }
+
+    /// Gets whether the relation with the given name is a base table, a view,
+    /// a materialized view or a foreign table.
+    fn get_table_kind(&self, name: &str) -> AsyncResult<TableKind> {
+        let name = name.to_string();
+
+        Box::pin(async move { default::get_table_kind(self.as_dyn(), name).await })
+    }
+
+    /// Returns the SQL to retrieve the creation and last update times of the given table.
+    /// The query must return the `created` and `updated` columns.
+    #[allow(unused_variables)]
+    fn get_table_timestamps_sql(&self, table: &str, database: &str) -> Result<String> {
+        Err(Error::platform_feature_unsupported("table timestamps"))
+    }
+
+    /// Gets the creation and last update times of the given table.
+    /// Timestamps not tracked by the platform are reported as None.
+    fn get_table_timestamps(&self, name: &str) -> AsyncResult<TableTimestamps> {
+        let name = name.to_string();
+
+        Box::pin(async move { default::get_table_timestamps(self.as_dyn(), name).await })
+    }
+
+    /// Retrieves the usage and cardinality statistics of the indexes of the given table.
+    /// Cardinality is reported only after the statistics have been collected by the server (ie: after ANALYZE).
+    fn get_index_statistics(&self, table: &str) -> AsyncResult<Vec<IndexStatistics>> {
+        let table = table.to_string();
+
+        Box::pin(async move { default::get_index_statistics(self.as_dyn(), table).await })
+    }
+
     /// Whether all the given tables exist.
     fn tables_exist(&self, names: &[&str]) -> AsyncResult<bool> {
         let names = names.iter().map(|s| s.to_lowercase()).collect::<Vec<_>>();
@@ -449,6 +568,23 @@ pub trait SchemaManager: Sync + Send {
         Box::pin(async move { default::list_table_details(self.as_dyn(), name).await })
     }
 
+    /// Renders the DDL of an existing table (the create table statement, its indexes
+    /// and foreign keys) from its introspected definition, as generated by this platform.
+    fn get_table_ddl(&self, name: &str) -> AsyncResult<String> {
+        let name = name.to_string();
+
+        Box::pin(async move {
+            let table = self.list_table_details(&name).await?;
+            let sql = self.get_create_table_sql(&table, None)?;
+
+            Ok(sql
+                .iter()
+                .map(|s| format!("{};", s))
+                .collect::<Vec<_>>()
+                .join("\n"))
+        })
+    }
+
     /// An extension point for those platforms where case sensitivity of the object
     /// name depends on whether it's quoted.
     ///
@@ -618,6 +754,36 @@ pub trait SchemaManager: Sync + Send {
         _exec_sql(self.get_connection(), self.get_drop_table_sql(&name))
     }
 
+    /// Truncates all the given tables.
+    ///
+    /// If cascade is true, foreign keys referencing the truncated tables are
+    /// followed (or ignored, if the platform cannot cascade the truncate).
+    fn truncate_tables(&self, tables: &[&str], cascade: bool) -> AsyncResult<()> {
+        _exec_sql(
+            self.get_connection(),
+            self.get_truncate_tables_sql(tables, cascade),
+        )
+    }
+
+    /// Refreshes the statistics of the given table, ie: after bulk changes to its data.
+    fn analyze_table(&self, table_name: &dyn IntoIdentifier) -> AsyncResult<()> {
+        _exec_sql(
+            self.get_connection(),
+            self.get_analyze_table_sql(table_name),
+        )
+    }
+
+    /// Reclaims the storage of the given table. No-op if the platform does not support it.
+    ///
+    /// On PostgreSQL and SQLite this issues a VACUUM, which cannot be executed
+    /// inside a transaction. SQLite vacuums the whole database file.
+    fn optimize_table(&self, table_name: &dyn IntoIdentifier) -> AsyncResult<()> {
+        _exec_sql(
+            self.get_connection(),
+            self.get_optimize_table_sql(table_name),
+        )
+    }
+
     /// Drops the index from the given table.
     fn drop_index(
         &self,
@@ -743,6 +909,40 @@ pub trait SchemaManager: Sync + Send {
         _exec_sql(self.get_connection(), self.get_create_view_sql(view))
     }
 
+    /// Creates a new materialized view.
+    fn create_materialized_view(&self, view: &View) -> AsyncResult<()> {
+        _exec_sql(
+            self.get_connection(),
+            self.get_create_materialized_view_sql(view),
+        )
+    }
+
+    /// Creates a new table from the result set of the given SELECT statement,
+    /// executed with the given parameters.
+    fn create_table_as(
+        &self,
+        name: &dyn IntoIdentifier,
+        select_sql: &str,
+        params: Parameters<'static>,
+    ) -> AsyncResult<()> {
+        let sql = self.get_create_table_as_sql(&name.into_identifier(), select_sql, true);
+        Box::pin(async move {
+            self.get_connection()
+                .execute_statement(sql?, params)
+                .await?;
+            Ok(())
+        })
+    }
+
+    /// Refreshes the data of a materialized view.
+    fn refresh_materialized_view(&self, name: &dyn IntoIdentifier) -> AsyncResult<()> {
+        let name = name.into_identifier();
+        _exec_sql(
+            self.get_connection(),
+            self.get_refresh_materialized_view_sql(&name),
+        )
+    }
+
     fn create_schema_objects(&self, schema: &Schema) -> AsyncResult<()> {
         let sql = schema.to_sql(self.as_dyn());
         Box::pin(async move { _exec_sql(self.get_connection(), sql?).await })
@@ -873,6 +1073,13 @@ pub trait SchemaManager: Sync + Send {
         default::get_column_collation_declaration_sql(self.get_platform()?.as_dyn(), collation)
     }
 
+    /// Obtains DBMS specific SQL code portion needed to set the compression method
+    /// of a column declaration. Platforms not supporting column compression return an empty string.
+    #[allow(unused_variables)]
+    fn get_column_compression_declaration_sql(&self, compression: &str) -> Result<String> {
+        default::get_column_compression_declaration_sql()
+    }
+
     /// Obtain DBMS specific SQL code portion needed to set the FOREIGN KEY constraint
     /// of a column declaration to be used in statements like CREATE TABLE.
     fn get_foreign_key_declaration_sql(
@@ -1046,6 +1253,10 @@ pub trait SchemaManager: Sync + Send {
         Box::pin(async move { Ok(Identifier::new(name?, false)) })
     }
 
+    fn get_portable_index_statistics_list(&self, rows: Vec<Row>) -> Result<Vec<IndexStatistics>> {
+        default::get_portable_index_statistics_list(self.as_dyn(), rows)
+    }
+
     fn get_portable_views_list(&self, rows: Vec<Row>) -> Result<Vec<View>> {
         let platform = self.get_platform()?;
         let mut list = HashMap::new();
@@ -1119,7 +1330,12 @@ pub trait SchemaManager: Sync + Send {
     }
 
     fn create_schema_config(&self) -> SchemaConfig {
-        SchemaConfig::default()
+        self.get_platform()
+            .map(|platform| SchemaConfig {
+                max_identifier_length: platform.get_max_identifier_length(),
+                ..Default::default()
+            })
+            .unwrap_or_default()
     }
 
     /// Creates a schema instance for the current database.
@@ -1170,6 +1386,7 @@ impl<T: SchemaManager + ?Sized> SchemaManager for &mut T {
             fn get_create_schema_sql(&self, schema_name: &dyn IntoIdentifier) -> Result<String>;
             fn get_create_unique_constraint_sql(&self, constraint: &UniqueConstraint, table_name: &dyn IntoIdentifier) -> Result<String>;
             fn get_create_foreign_key_sql(&self, foreign_key: &ForeignKeyConstraint, table: &dyn IntoIdentifier) -> Result<String>;
+            fn validate_foreign_key(&self, local_table: &Table, foreign_key: &ForeignKeyConstraint, foreign_table: &Table) -> Result<()>;
             fn get_create_view_sql(&self, view: &View) -> Result<String>;
             fn get_create_database_sql(&self, name: &Identifier) -> Result<String>;
             fn get_column_charset_declaration_sql(&self, charset: &str) -> String;
@@ -1181,11 +1398,17 @@ impl<T: SchemaManager + ?Sized> SchemaManager for &mut T {
             fn get_list_table_foreign_keys_sql(&self, table: &str, database: &str) -> Result<String>;
             fn get_list_table_constraints_sql(&self, table: &str) -> Result<String>;
             fn get_comment_on_table_sql(&self, table_name: &Identifier, comment: &str) -> Result<String>;
+            fn get_alter_table_owner_sql(&self, table_name: &Identifier, owner: &str) -> Result<String>;
+            fn get_grant_sql(&self, table_name: &Identifier, grant: &TableGrant) -> Result<String>;
+            fn get_alter_table_replica_identity_sql(&self, table_name: &Identifier, replica_identity: &ReplicaIdentity) -> Result<String>;
             fn get_comment_on_column_sql(&self, table_name: &dyn IntoIdentifier, column: &dyn IntoIdentifier, comment: &str) -> Result<String>;
             fn get_inline_column_comment_sql(&self, comment: &str) -> Result<String>;
             fn get_alter_table_sql(&self, diff: &mut TableDiff) -> Result<Vec<String>>;
             fn get_alter_sequence_sql(&self, sequence: &Sequence) -> Result<String>;
             fn get_truncate_table_sql(&self, table_name: &dyn IntoIdentifier, cascade: bool) -> Result<String>;
+            fn get_truncate_tables_sql(&self, tables: &[&str], cascade: bool) -> Result<Vec<String>>;
+            fn get_analyze_table_sql(&self, table_name: &dyn IntoIdentifier) -> Result<Vec<String>>;
+            fn get_optimize_table_sql(&self, table_name: &dyn IntoIdentifier) -> Result<Vec<String>>;
             fn get_drop_database_sql(&self, name: &str) -> Result<String>;
             fn get_drop_schema_sql(&self, schema_name: &str) -> Result<String>;
             fn get_drop_table_sql(&self, table_name: &dyn IntoIdentifier) -> Result<String>;
@@ -1197,15 +1420,25 @@ impl<T: SchemaManager + ?Sized> SchemaManager for &mut T {
             fn get_drop_foreign_key_sql(&self, foreign_key: &dyn IntoIdentifier, table_name: &dyn IntoIdentifier) -> Result<String>;
             fn get_drop_sequence_sql(&self, sequence: &dyn IntoIdentifier) -> Result<String>;
             fn get_drop_view_sql(&self, sequence: &dyn IntoIdentifier) -> Result<String>;
+            fn get_create_materialized_view_sql(&self, view: &View) -> Result<String>;
+            fn get_refresh_materialized_view_sql(&self, name: &Identifier) -> Result<String>;
+            fn get_create_table_as_sql(&self, name: &Identifier, select_sql: &str, with_data: bool) -> Result<String>;
             fn list_databases(&self) -> AsyncResult<Vec<Identifier>>;
             fn list_schema_names(&self) -> AsyncResult<Vec<Identifier>>;
             fn list_sequences(&self) -> AsyncResult<Vec<Sequence>>;
             fn list_table_columns(&self, table: &str, database: Option<&str>) -> AsyncResult<ColumnList>;
             fn list_table_indexes(&self, table: &str) -> AsyncResult<IndexList>;
+            fn get_index_statistics_sql(&self, table: &str, database: &str) -> Result<String>;
+            fn get_index_statistics(&self, table: &str) -> AsyncResult<Vec<IndexStatistics>>;
+            fn get_table_kind_sql(&self, table: &str, database: &str) -> Result<String>;
+            fn get_table_kind(&self, name: &str) -> AsyncResult<TableKind>;
+            fn get_table_timestamps_sql(&self, table: &str, database: &str) -> Result<String>;
+            fn get_table_timestamps(&self, name: &str) -> AsyncResult<TableTimestamps>;
             fn tables_exist(&self, names: &[&str]) -> AsyncResult<bool>;
             fn list_table_names(&self) -> AsyncResult<Vec<String>>;
             fn list_tables(&self) -> AsyncResult<TableList>;
             fn list_table_details(&self, name: &str) -> AsyncResult<Table>;
+            fn get_table_ddl(&self, name: &str) -> AsyncResult<String>;
             fn normalize_name(&self, name: &str) -> String;
             fn select_table_names(&self, database_name: &str) -> AsyncResult<StatementResult>;
             fn select_table_columns(&self, database_name: &str, table_name: Option<&str>) -> AsyncResult<StatementResult>;
@@ -1225,6 +1458,9 @@ impl<T: SchemaManager + ?Sized> SchemaManager for &mut T {
             fn drop_database(&self, database: &str) -> AsyncResult<()>;
             fn drop_schema(&self, schema_name: &str) -> AsyncResult<()>;
             fn drop_table(&self, name: &dyn IntoIdentifier) -> AsyncResult<()>;
+            fn truncate_tables(&self, tables: &[&str], cascade: bool) -> AsyncResult<()>;
+            fn analyze_table(&self, table_name: &dyn IntoIdentifier) -> AsyncResult<()>;
+            fn optimize_table(&self, table_name: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn drop_index(&self, index: &dyn IntoIdentifier, table: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn drop_foreign_key(&self, foreign_key: &dyn IntoIdentifier, table: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn drop_sequence(&self, name: &dyn IntoIdentifier) -> AsyncResult<()>;
@@ -1237,6 +1473,9 @@ impl<T: SchemaManager + ?Sized> SchemaManager for &mut T {
             fn create_foreign_key(&self, foreign_key: &ForeignKeyConstraint, table: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn create_unique_constraint(&self, unique_constraint: &UniqueConstraint, table: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn create_view(&self, view: &View) -> AsyncResult<()>;
+            fn create_materialized_view(&self, view: &View) -> AsyncResult<()>;
+            fn create_table_as(&self, name: &dyn IntoIdentifier, select_sql: &str, params: Parameters<'static>) -> AsyncResult<()>;
+            fn refresh_materialized_view(&self, name: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn create_schema_objects(&self, schema: &Schema) -> AsyncResult<()>;
             fn drop_schema_objects(&self, schema: &Schema) -> AsyncResult<()>;
             fn alter_schema(&self, schema_diff: SchemaDiff) -> AsyncResult<()>;
@@ -1255,6 +1494,7 @@ impl<T: SchemaManager + ?Sized> SchemaManager for &mut T {
             fn get_unique_constraint_declaration_sql(&self, name: &str, constraint: &UniqueConstraint) -> Result<String>;
             fn get_index_declaration_sql(&self, name: &str, index: &Index) -> Result<String>;
             fn get_column_collation_declaration_sql(&self, collation: &str) -> Result<String>;
+            fn get_column_compression_declaration_sql(&self, compression: &str) -> Result<String>;
             fn get_foreign_key_declaration_sql(&self, foreign_key: &ForeignKeyConstraint) -> Result<String>;
             fn get_advanced_foreign_key_options_sql(&self, foreign_key: &ForeignKeyConstraint) -> Result<String>;
             fn get_foreign_key_referential_action_sql(&self, action: &ForeignKeyReferentialAction) -> Result<String>;
@@ -1274,6 +1514,7 @@ impl<T: SchemaManager + ?Sized> SchemaManager for &mut T {
             fn get_portable_table_indexes_list(&self, table_indexes: Vec<Row>, table_name: &str) -> AsyncResult<IndexList>;
             fn get_portable_tables_list(&self, tables: Vec<Row>) -> AsyncResult<Vec<Identifier>>;
             fn get_portable_table_definition(&self, table: &Row) -> AsyncResult<Identifier>;
+            fn get_portable_index_statistics_list(&self, rows: Vec<Row>) -> Result<Vec<IndexStatistics>>;
             fn get_portable_views_list(&self, rows: Vec<Row>) -> Result<Vec<View>>;
             fn get_portable_view_definition(&self, view: &Row) -> Result<Option<View>>;
             fn get_portable_table_foreign_keys_list(&self, table_foreign_keys: Vec<Row>) -> Result<FKConstraintList>;
@@ -1305,6 +1546,7 @@ impl<T: SchemaManager + ?Sized> SchemaManager for Box<T> {
             fn get_create_schema_sql(&self, schema_name: &dyn IntoIdentifier) -> Result<String>;
             fn get_create_unique_constraint_sql(&self, constraint: &UniqueConstraint, table_name: &dyn IntoIdentifier) -> Result<String>;
             fn get_create_foreign_key_sql(&self, foreign_key: &ForeignKeyConstraint, table: &dyn IntoIdentifier) -> Result<String>;
+            fn validate_foreign_key(&self, local_table: &Table, foreign_key: &ForeignKeyConstraint, foreign_table: &Table) -> Result<()>;
             fn get_create_view_sql(&self, view: &View) -> Result<String>;
             fn get_create_database_sql(&self, name: &Identifier) -> Result<String>;
             fn get_column_charset_declaration_sql(&self, charset: &str) -> String;
@@ -1316,11 +1558,17 @@ impl<T: SchemaManager + ?Sized> SchemaManager for Box<T> {
             fn get_list_table_foreign_keys_sql(&self, table: &str, database: &str) -> Result<String>;
             fn get_list_table_constraints_sql(&self, table: &str) -> Result<String>;
             fn get_comment_on_table_sql(&self, table_name: &Identifier, comment: &str) -> Result<String>;
+            fn get_alter_table_owner_sql(&self, table_name: &Identifier, owner: &str) -> Result<String>;
+            fn get_grant_sql(&self, table_name: &Identifier, grant: &TableGrant) -> Result<String>;
+            fn get_alter_table_replica_identity_sql(&self, table_name: &Identifier, replica_identity: &ReplicaIdentity) -> Result<String>;
             fn get_comment_on_column_sql(&self, table_name: &dyn IntoIdentifier, column: &dyn IntoIdentifier, comment: &str) -> Result<String>;
             fn get_inline_column_comment_sql(&self, comment: &str) -> Result<String>;
             fn get_alter_table_sql(&self, diff: &mut TableDiff) -> Result<Vec<String>>;
             fn get_alter_sequence_sql(&self, sequence: &Sequence) -> Result<String>;
             fn get_truncate_table_sql(&self, table_name: &dyn IntoIdentifier, cascade: bool) -> Result<String>;
+            fn get_truncate_tables_sql(&self, tables: &[&str], cascade: bool) -> Result<Vec<String>>;
+            fn get_analyze_table_sql(&self, table_name: &dyn IntoIdentifier) -> Result<Vec<String>>;
+            fn get_optimize_table_sql(&self, table_name: &dyn IntoIdentifier) -> Result<Vec<String>>;
             fn get_drop_database_sql(&self, name: &str) -> Result<String>;
             fn get_drop_schema_sql(&self, schema_name: &str) -> Result<String>;
             fn get_drop_table_sql(&self, table_name: &dyn IntoIdentifier) -> Result<String>;
@@ -1332,15 +1580,25 @@ impl<T: SchemaManager + ?Sized> SchemaManager for Box<T> {
             fn get_drop_foreign_key_sql(&self, foreign_key: &dyn IntoIdentifier, table_name: &dyn IntoIdentifier) -> Result<String>;
             fn get_drop_sequence_sql(&self, sequence: &dyn IntoIdentifier) -> Result<String>;
             fn get_drop_view_sql(&self, sequence: &dyn IntoIdentifier) -> Result<String>;
+            fn get_create_materialized_view_sql(&self, view: &View) -> Result<String>;
+            fn get_refresh_materialized_view_sql(&self, name: &Identifier) -> Result<String>;
+            fn get_create_table_as_sql(&self, name: &Identifier, select_sql: &str, with_data: bool) -> Result<String>;
             fn list_databases(&self) -> AsyncResult<Vec<Identifier>>;
             fn list_schema_names(&self) -> AsyncResult<Vec<Identifier>>;
             fn list_sequences(&self) -> AsyncResult<Vec<Sequence>>;
             fn list_table_columns(&self, table: &str, database: Option<&str>) -> AsyncResult<ColumnList>;
             fn list_table_indexes(&self, table: &str) -> AsyncResult<IndexList>;
+            fn get_index_statistics_sql(&self, table: &str, database: &str) -> Result<String>;
+            fn get_index_statistics(&self, table: &str) -> AsyncResult<Vec<IndexStatistics>>;
+            fn get_table_kind_sql(&self, table: &str, database: &str) -> Result<String>;
+            fn get_table_kind(&self, name: &str) -> AsyncResult<TableKind>;
+            fn get_table_timestamps_sql(&self, table: &str, database: &str) -> Result<String>;
+            fn get_table_timestamps(&self, name: &str) -> AsyncResult<TableTimestamps>;
             fn tables_exist(&self, names: &[&str]) -> AsyncResult<bool>;
             fn list_table_names(&self) -> AsyncResult<Vec<String>>;
             fn list_tables(&self) -> AsyncResult<TableList>;
             fn list_table_details(&self, name: &str) -> AsyncResult<Table>;
+            fn get_table_ddl(&self, name: &str) -> AsyncResult<String>;
             fn normalize_name(&self, name: &str) -> String;
             fn select_table_names(&self, database_name: &str) -> AsyncResult<StatementResult>;
             fn select_table_columns(&self, database_name: &str, table_name: Option<&str>) -> AsyncResult<StatementResult>;
@@ -1360,6 +1618,9 @@ impl<T: SchemaManager + ?Sized> SchemaManager for Box<T> {
             fn drop_database(&self, database: &str) -> AsyncResult<()>;
             fn drop_schema(&self, schema_name: &str) -> AsyncResult<()>;
             fn drop_table(&self, name: &dyn IntoIdentifier) -> AsyncResult<()>;
+            fn truncate_tables(&self, tables: &[&str], cascade: bool) -> AsyncResult<()>;
+            fn analyze_table(&self, table_name: &dyn IntoIdentifier) -> AsyncResult<()>;
+            fn optimize_table(&self, table_name: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn drop_index(&self, index: &dyn IntoIdentifier, table: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn drop_foreign_key(&self, foreign_key: &dyn IntoIdentifier, table: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn drop_sequence(&self, name: &dyn IntoIdentifier) -> AsyncResult<()>;
@@ -1372,6 +1633,9 @@ impl<T: SchemaManager + ?Sized> SchemaManager for Box<T> {
             fn create_foreign_key(&self, foreign_key: &ForeignKeyConstraint, table: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn create_unique_constraint(&self, unique_constraint: &UniqueConstraint, table: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn create_view(&self, view: &View) -> AsyncResult<()>;
+            fn create_materialized_view(&self, view: &View) -> AsyncResult<()>;
+            fn create_table_as(&self, name: &dyn IntoIdentifier, select_sql: &str, params: Parameters<'static>) -> AsyncResult<()>;
+            fn refresh_materialized_view(&self, name: &dyn IntoIdentifier) -> AsyncResult<()>;
             fn create_schema_objects(&self, schema: &Schema) -> AsyncResult<()>;
             fn drop_schema_objects(&self, schema: &Schema) -> AsyncResult<()>;
             fn alter_schema(&self, schema_diff: SchemaDiff) -> AsyncResult<()>;
@@ -1390,6 +1654,7 @@ impl<T: SchemaManager + ?Sized> SchemaManager for Box<T> {
             fn get_unique_constraint_declaration_sql(&self, name: &str, constraint: &UniqueConstraint) -> Result<String>;
             fn get_index_declaration_sql(&self, name: &str, index: &Index) -> Result<String>;
             fn get_column_collation_declaration_sql(&self, collation: &str) -> Result<String>;
+            fn get_column_compression_declaration_sql(&self, compression: &str) -> Result<String>;
             fn get_foreign_key_declaration_sql(&self, foreign_key: &ForeignKeyConstraint) -> Result<String>;
             fn get_advanced_foreign_key_options_sql(&self, foreign_key: &ForeignKeyConstraint) -> Result<String>;
             fn get_foreign_key_referential_action_sql(&self, action: &ForeignKeyReferentialAction) -> Result<String>;
@@ -1409,6 +1674,7 @@ impl<T: SchemaManager + ?Sized> SchemaManager for Box<T> {
             fn get_portable_table_indexes_list(&self, table_indexes: Vec<Row>, table_name: &str) -> AsyncResult<IndexList>;
             fn get_portable_tables_list(&self, tables: Vec<Row>) -> AsyncResult<Vec<Identifier>>;
             fn get_portable_table_definition(&self, table: &Row) -> AsyncResult<Identifier>;
+            fn get_portable_index_statistics_list(&self, rows: Vec<Row>) -> Result<Vec<IndexStatistics>>;
             fn get_portable_views_list(&self, rows: Vec<Row>) -> Result<Vec<View>>;
             fn get_portable_view_definition(&self, view: &Row) -> Result<Option<View>>;
             fn get_portable_table_foreign_keys_list(&self, table_foreign_keys: Vec<Row>) -> Result<FKConstraintList>;
@@ -1427,32 +1693,36 @@ impl<T: SchemaManager + ?Sized> SchemaManager for Box<T> {
 #[cfg(test)]
 #[cfg(feature = "functional-tests")]
 mod tests {
+    use crate::error::ErrorKind;
     use crate::platform::DatabasePlatform;
     use crate::r#type::{
-        IntoType, TypeManager, BINARY, BLOB, BOOLEAN, DATE, DATETIME, DECIMAL, GUID, INTEGER, JSON,
-        SIMPLE_ARRAY, STRING, TEXT, TIME,
+        IntoType, TypeManager, BINARY, BLOB, BOOLEAN, COMPOSITE, DATE, DATETIME, DATETIMETZ_RANGE,
+        DECIMAL, DURATION, GUID, INTEGER, INTEGER_RANGE, JSON, SIMPLE_ARRAY, STRING, TEXT, TIME,
     };
     use crate::schema::schema_manager::_exec_sql;
     use crate::schema::{
-        extract_type_from_comment, Asset, Column, ColumnData, ColumnDiff, Comparator,
-        ForeignKeyConstraint, ForeignKeyReferentialAction, Index, IntoIdentifier, Schema,
-        SchemaDiff, SchemaManager, Sequence, Table, TableDiff, UniqueConstraint, View,
+        extract_type_from_comment, Asset, ChangedProperty, Column, ColumnData, ColumnDiff,
+        Comparator, CompositeTypeDefinition, ForeignKeyConstraint, ForeignKeyReferentialAction,
+        Index, IntoIdentifier, ReplicaIdentity, Schema, SchemaChangeKind, SchemaConfig, SchemaDiff,
+        SchemaManager, Sequence, Table, TableDiff, TableKind, UniqueConstraint, View,
     };
     use crate::tests::{
         create_connection, get_database_dsn, FunctionalTestsHelper, MockConnection,
     };
     use crate::SchemaAlterTableRenameColumnEvent;
     use crate::{
-        params, Configuration, Connection, ConnectionOptions, Error, EventDispatcher, Result,
-        SchemaAlterTableAddColumnEvent, SchemaAlterTableChangeColumnEvent, SchemaAlterTableEvent,
-        SchemaAlterTableRemoveColumnEvent, SchemaColumnDefinitionEvent,
+        params, Configuration, Connection, ConnectionOptions, Error, EventDispatcher, Range,
+        Result, SchemaAlterTableAddColumnEvent, SchemaAlterTableChangeColumnEvent,
+        SchemaAlterTableEvent, SchemaAlterTableRemoveColumnEvent, SchemaColumnDefinitionEvent,
         SchemaCreateTableColumnEvent, SchemaCreateTableEvent, SchemaDropTableEvent,
         SchemaIndexDefinitionEvent, Value,
     };
     use creed_macros::value_map;
     use itertools::Itertools;
+    use regex::Regex;
     use serial_test::serial;
     use std::collections::HashMap;
+    use std::ops::Bound;
     use std::sync::{Arc, Mutex};
     use version_compare::{compare_to, Cmp};
 
@@ -1502,6 +1772,87 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial]
+    pub async fn foreign_key_only_changes_are_reported() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        let comparator = schema_manager.create_comparator();
+
+        let create_table = |on_delete: Option<ForeignKeyReferentialAction>| -> Result<Table> {
+            let mut table = Table::new("posts");
+            table.add_column(Column::builder("id", INTEGER)?);
+            table.add_column(Column::builder("user_id", INTEGER)?);
+            table.add_foreign_key(
+                ForeignKeyConstraint::builder("users")
+                    .set_local_columns(vec!["user_id"])
+                    .set_foreign_columns(vec!["id"])
+                    .set_on_delete_action(on_delete)
+                    .set_name("fk_posts_user"),
+            )?;
+
+            Ok(table)
+        };
+
+        let from_table = create_table(None)?;
+        let to_table = create_table(Some(ForeignKeyReferentialAction::Cascade))?;
+        let diff = comparator
+            .diff_table(&from_table, &to_table)?
+            .expect("changed foreign key not reported");
+        assert_eq!(diff.changed_foreign_keys.len(), 1);
+
+        let mut without_foreign_key = from_table.clone();
+        without_foreign_key.remove_foreign_key("fk_posts_user");
+        let diff = comparator
+            .diff_table(&from_table, &without_foreign_key)?
+            .expect("removed foreign key not reported");
+        assert_eq!(diff.removed_foreign_keys.len(), 1);
+
+        let diff = comparator
+            .diff_table(&without_foreign_key, &from_table)?
+            .expect("added foreign key not reported");
+        assert_eq!(diff.added_foreign_keys.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn foreign_key_referential_actions_are_normalized_on_diff() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
+        let comparator = schema_manager.create_comparator();
+
+        let create_table = |on_delete: Option<ForeignKeyReferentialAction>| -> Result<Table> {
+            let mut table = Table::new("posts");
+            table.add_column(Column::builder("id", INTEGER)?);
+            table.add_column(Column::builder("user_id", INTEGER)?);
+            table.add_foreign_key(
+                ForeignKeyConstraint::builder("users")
+                    .set_local_columns(vec!["user_id"])
+                    .set_foreign_columns(vec!["id"])
+                    .set_on_delete_action(on_delete)
+                    .set_name("fk_posts_user"),
+            )?;
+
+            Ok(table)
+        };
+
+        let declared = create_table(None)?;
+        let introspected = create_table(Some(ForeignKeyReferentialAction::NoAction))?;
+        assert!(comparator.diff_table(&declared, &introspected)?.is_none());
+
+        let declared = create_table(Some(ForeignKeyReferentialAction::Restrict))?;
+        let diff = comparator.diff_table(&declared, &introspected)?;
+        if connection.get_platform()?.get_name() == "mysql" {
+            assert!(diff.is_none());
+        } else {
+            assert_eq!(diff.unwrap().changed_foreign_keys.len(), 1);
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial]
     pub async fn create_with_no_columns() -> Result<()> {
@@ -1954,6 +2305,153 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial]
+    pub async fn get_table_kind() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let connection = &helper.connection;
+        let schema_manager = helper.get_schema_manager();
+
+        let name = "test_table_for_kind";
+        let view_name = "test_kind_view";
+        let materialized_view_name = "test_kind_materialized_view";
+
+        let is_postgres = helper.platform.get_name() == "postgresql";
+        if is_postgres {
+            connection
+                .execute_statement(
+                    format!(
+                        "DROP MATERIALIZED VIEW IF EXISTS {}",
+                        materialized_view_name
+                    ),
+                    params!(),
+                )
+                .await?;
+        }
+
+        let _ = schema_manager.drop_view(&view_name).await;
+        helper.drop_table_if_exists(&name).await;
+        helper.create_test_table(name).await?;
+
+        let sql = "SELECT * FROM test_table_for_kind";
+        schema_manager
+            .create_view(&View::new(view_name, sql))
+            .await?;
+
+        assert_eq!(schema_manager.get_table_kind(name).await?, TableKind::Base);
+        assert_eq!(
+            schema_manager.get_table_kind(view_name).await?,
+            TableKind::View
+        );
+        assert_eq!(
+            schema_manager
+                .get_table_kind("test_kind_not_existent")
+                .await
+                .unwrap_err()
+                .kind(),
+            ErrorKind::TableDoesNotExist
+        );
+
+        if is_postgres {
+            schema_manager
+                .create_materialized_view(&View::new(materialized_view_name, sql))
+                .await?;
+            schema_manager
+                .refresh_materialized_view(&materialized_view_name)
+                .await?;
+
+            assert_eq!(
+                schema_manager
+                    .get_table_kind(materialized_view_name)
+                    .await?,
+                TableKind::MaterializedView
+            );
+        }
+
+        let table_names = schema_manager.list_table_names().await?;
+        assert!(table_names.iter().any(|t| t == name));
+        assert!(!table_names.iter().any(|t| t == view_name));
+        assert!(!table_names.iter().any(|t| t == materialized_view_name));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn create_table_as_select() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+
+        helper.drop_table_if_exists("ctas_derived").await;
+        helper.create_test_table("ctas_source").await?;
+        for id in 1..=3 {
+            helper
+                .connection
+                .insert(
+                    "ctas_source",
+                    value_map! { "id" => id, "test" => format!("row {}", id), "foreign_key_test" => id },
+                )
+                .await?;
+        }
+
+        schema_manager
+            .create_table_as(
+                &"ctas_derived",
+                "SELECT id, test FROM ctas_source WHERE id > ?",
+                params![0 => 1_i64],
+            )
+            .await?;
+
+        let table = schema_manager.introspect_table("ctas_derived").await?;
+        assert_eq!(
+            table.columns().keys().collect::<Vec<_>>(),
+            vec!["id", "test"]
+        );
+
+        let rows = helper
+            .connection
+            .fetch_all("SELECT id, test FROM ctas_derived ORDER BY id", params![])
+            .await?;
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get("id")?, &Value::Int(2));
+        assert_eq!(rows[1].get("test")?, &Value::from("row 3"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn get_table_timestamps() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+
+        let name = "test_table_for_timestamps";
+        helper.drop_table_if_exists(name).await;
+        helper.create_test_table(name).await?;
+
+        let timestamps = schema_manager.get_table_timestamps(name).await?;
+        if helper.platform.get_name() == "mysql" {
+            let created = timestamps
+                .get_created()
+                .expect("table creation time should be reported");
+            let now = helper.connection.server_now().await?;
+            assert!((now - created).num_seconds().abs() < 60);
+        } else {
+            assert_eq!(timestamps.get_created(), None);
+        }
+
+        assert_eq!(
+            schema_manager
+                .get_table_timestamps("test_timestamps_not_existent")
+                .await
+                .unwrap_err()
+                .kind(),
+            ErrorKind::TableDoesNotExist
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial]
     pub async fn list_tables_with_filter() -> Result<()> {
@@ -1984,6 +2482,54 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial]
+    pub async fn list_tables_with_allowlist_and_denylist() -> Result<()> {
+        let tests = [
+            (
+                vec!["^list_test_"],
+                vec![],
+                vec!["list_test_1", "list_test_2"],
+            ),
+            (vec!["^list_test_"], vec!["_2$"], vec!["list_test_1"]),
+            (vec![], vec!["^list_test_", "^other_list_test$"], vec![]),
+        ];
+
+        for (allowlist, denylist, expected) in tests {
+            let to_regex = |patterns: Vec<&str>| {
+                patterns
+                    .into_iter()
+                    .map(|p| Regex::new(p).unwrap())
+                    .collect::<Vec<_>>()
+            };
+
+            let configuration = Configuration::default()
+                .set_schema_assets_allowlist(to_regex(allowlist))
+                .set_schema_assets_denylist(to_regex(denylist));
+
+            let helper = FunctionalTestsHelper::with_configuration(configuration).await;
+            let schema_manager = helper.get_schema_manager();
+
+            for table_name in ["list_test_1", "list_test_2", "other_list_test"] {
+                helper.drop_table_if_exists(&table_name).await;
+                helper.create_test_table(table_name).await?;
+            }
+
+            let mut table_names = schema_manager
+                .list_table_names()
+                .await?
+                .into_iter()
+                .map(|t| t.to_lowercase())
+                .filter(|t| t.contains("list_test"))
+                .collect::<Vec<_>>();
+            table_names.sort();
+
+            assert_eq!(table_names, expected);
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial]
     pub async fn rename_table() -> Result<()> {
@@ -2183,19 +2729,158 @@ mod tests {
 
     #[tokio::test]
     #[serial]
-    pub async fn list_table_columns_with_fixed_string_column() -> Result<()> {
+    pub async fn alter_serial_column_to_identity() -> Result<()> {
         let helper = FunctionalTestsHelper::default().await;
         let schema_manager = helper.get_schema_manager();
+        let mut table = Table::new("serial_to_identity");
+        table.add_column(Column::builder("id", INTEGER)?.set_autoincrement(true));
+        table.set_primary_key(&["id"], None)?;
 
-        let table_name = "test_list_table_fixed_string";
-        helper.drop_table_if_exists(&table_name).await;
+        helper.drop_and_create_table(&table).await?;
+        helper
+            .connection
+            .insert("serial_to_identity", value_map! { "id" => 10 })
+            .await?;
 
-        let mut table = Table::new(table_name);
-        table.add_column(
-            Column::builder("column_char", STRING)?
-                .set_fixed(true)
-                .set_length(2),
-        );
+        let from_table = schema_manager
+            .introspect_table("serial_to_identity")
+            .await?;
+        let id_column = from_table.get_column("id").unwrap();
+        assert!(id_column.is_autoincrement());
+        assert!(!id_column.is_identity());
+
+        let mut to_table = from_table.clone();
+        to_table.get_column_mut("id").unwrap().set_identity(true);
+
+        let comparator = schema_manager.create_comparator();
+        if !helper.platform.supports_generated_identity_columns() {
+            // Identity is not introspected: the column must not be altered on every comparison.
+            assert!(comparator.diff_table(&from_table, &to_table)?.is_none());
+            return Ok(());
+        }
+
+        let diff = comparator.diff_table(&from_table, &to_table)?.unwrap();
+        schema_manager.alter_table(diff).await?;
+
+        let table = schema_manager
+            .introspect_table("serial_to_identity")
+            .await?;
+        let id_column = table.get_column("id").unwrap();
+        assert!(id_column.is_autoincrement());
+        assert!(id_column.is_identity());
+        assert!(comparator.diff_table(&table, &to_table)?.is_none());
+
+        helper
+            .connection
+            .execute_statement("INSERT INTO serial_to_identity DEFAULT VALUES", params!())
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn introspect_composite_type_column() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        if helper.platform.get_name() != "postgresql" {
+            return Ok(());
+        }
+
+        let connection = &helper.connection;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists(&"composite_column_test").await;
+        connection
+            .execute_statement("DROP TYPE IF EXISTS creed_address", params!())
+            .await?;
+        connection
+            .execute_statement(
+                "CREATE TYPE creed_address AS (street VARCHAR(64), number INTEGER, geo_lat NUMERIC(8,5))",
+                params!(),
+            )
+            .await?;
+
+        let mut table = Table::new("composite_column_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(
+            Column::builder("address", COMPOSITE)?
+                .set_composite_type(CompositeTypeDefinition::new("creed_address", vec![])),
+        );
+        schema_manager.create_table(&table).await?;
+
+        let table = schema_manager
+            .introspect_table("composite_column_test")
+            .await?;
+        let column = table.get_column("address").unwrap();
+        assert_eq!(column.get_type(), COMPOSITE.into_type()?);
+
+        let composite_type = column.get_composite_type().unwrap();
+        assert_eq!(composite_type.get_name(), "creed_address");
+        assert_eq!(
+            composite_type
+                .get_attributes()
+                .iter()
+                .map(|a| a.get_name().into_owned())
+                .collect::<Vec<_>>(),
+            vec!["street", "number", "geo_lat"]
+        );
+
+        let street = composite_type.get_attribute("street").unwrap();
+        assert_eq!(street.get_type(), STRING.into_type()?);
+        assert_eq!(street.get_length(), Some(64));
+        let number = composite_type.get_attribute("number").unwrap();
+        assert_eq!(number.get_type(), INTEGER.into_type()?);
+        let geo_lat = composite_type.get_attribute("geo_lat").unwrap();
+        assert_eq!(geo_lat.get_type(), DECIMAL.into_type()?);
+        assert_eq!(geo_lat.get_precision(), Some(8));
+        assert_eq!(geo_lat.get_scale(), Some(5));
+
+        connection
+            .execute_statement(
+                "INSERT INTO composite_column_test (id, address) VALUES (1, ROW('Main St, 1', 10, NULL))",
+                params!(),
+            )
+            .await?;
+        let rows = connection
+            .fetch_all("SELECT address FROM composite_column_test", params!())
+            .await?;
+        assert_eq!(
+            rows[0].get("address")?,
+            &Value::Array(vec![
+                Value::String("Main St, 1".to_string()),
+                Value::Int(10),
+                Value::NULL
+            ])
+        );
+
+        let record = connection.convert_value(rows[0].get("address")?, COMPOSITE)?;
+        assert_eq!(
+            connection.convert_database_value(record, COMPOSITE)?,
+            Value::String(r#"("Main St, 1",10,)"#.to_string())
+        );
+
+        helper.drop_table_if_exists(&"composite_column_test").await;
+        connection
+            .execute_statement("DROP TYPE creed_address", params!())
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn list_table_columns_with_fixed_string_column() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+
+        let table_name = "test_list_table_fixed_string";
+        helper.drop_table_if_exists(&table_name).await;
+
+        let mut table = Table::new(table_name);
+        table.add_column(
+            Column::builder("column_char", STRING)?
+                .set_fixed(true)
+                .set_length(2),
+        );
 
         schema_manager.create_table(&table).await?;
         let columns = schema_manager.list_table_columns(table_name, None).await?;
@@ -2392,139 +3077,1741 @@ mod tests {
         assert!(!test_index.is_primary());
         assert!(test_index.is_unique());
 
-        let test_index = table_indexes
-            .iter()
-            .find(|idx| idx.get_name() == "test_composite_idx")
-            .unwrap();
-        assert_eq!(
-            test_index
-                .get_columns()
-                .iter()
-                .map(|n| n.to_lowercase())
-                .collect::<Vec<_>>(),
-            vec!["id", "test"]
+        let test_index = table_indexes
+            .iter()
+            .find(|idx| idx.get_name() == "test_composite_idx")
+            .unwrap();
+        assert_eq!(
+            test_index
+                .get_columns()
+                .iter()
+                .map(|n| n.to_lowercase())
+                .collect::<Vec<_>>(),
+            vec!["id", "test"]
+        );
+        assert!(!test_index.is_primary());
+        assert!(!test_index.is_unique());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn drop_and_create_index() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+
+        let mut table = helper.get_test_table("test_create_index")?;
+        table.add_unique_index(&["test"], Some("test"), Default::default())?;
+        helper.drop_and_create_table(&table).await?;
+
+        let index = table.get_index(&"test").unwrap();
+        schema_manager.drop_index(&index, &table).await?;
+        schema_manager.create_index(&index, &table).await?;
+
+        let table_indexes = schema_manager
+            .list_table_indexes("test_create_index")
+            .await?;
+
+        let test_index = table_indexes
+            .iter()
+            .find(|i| i.get_name() == "test")
+            .unwrap();
+        assert_eq!(test_index.get_columns(), vec!["test"]);
+        assert!(test_index.is_unique());
+        assert!(!test_index.is_primary());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn drop_and_create_unique_constraint() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let platform = helper.platform.clone();
+        if platform.get_name() == "sqlite" {
+            return Ok(());
+        }
+
+        let schema_manager = helper.get_schema_manager();
+
+        let mut table = Table::new("test_unique_constraint");
+        table.add_column(Column::new("id", INTEGER.into_type()?));
+
+        helper.drop_and_create_table(&table).await?;
+
+        let unique_constraint = UniqueConstraint::new("uniq_id", &["id"], &[], Default::default());
+        schema_manager
+            .create_unique_constraint(&unique_constraint, &table)
+            .await?;
+
+        // there's currently no API for introspecting unique constraints,
+        // so introspect the underlying indexes instead
+        let indexes = schema_manager
+            .list_table_indexes("test_unique_constraint")
+            .await?;
+        assert_eq!(indexes.len(), 1);
+
+        let index = indexes.get(0).unwrap();
+        assert_eq!(index.get_name(), "uniq_id");
+        assert!(index.is_unique());
+
+        schema_manager
+            .drop_unique_constraint(&unique_constraint, &table)
+            .await?;
+
+        let indexes = schema_manager
+            .list_table_indexes("test_unique_constraint")
+            .await?;
+        assert!(indexes.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn create_table_with_foreign_keys() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+        let table_b = helper.get_test_table("test_foreign")?;
+
+        let _ = schema_manager.drop_table(&"test_create_fk").await;
+        helper.drop_and_create_table(&table_b).await?;
+
+        let mut table_a = helper.get_test_table("test_create_fk")?;
+        table_a.add_foreign_key_constraint(
+            &["foreign_key_test"],
+            &["id"],
+            "test_foreign",
+            Default::default(),
+            None,
+            None,
+            Some("test_foreign"),
+        )?;
+
+        helper.drop_and_create_table(&table_a).await?;
+
+        let fk_table = schema_manager.introspect_table("test_create_fk").await?;
+        let fk_constraints = fk_table.get_foreign_keys();
+        assert_eq!(
+            fk_constraints.len(),
+            1,
+            "Table 'test_create_fk' has to have one foreign key."
+        );
+
+        let fk_constraint = fk_constraints.get(0).unwrap();
+        assert_eq!(
+            fk_constraint.get_local_columns(),
+            &vec!["foreign_key_test".into_identifier()]
+        );
+        assert_eq!(
+            fk_constraint.get_foreign_columns(),
+            &vec!["id".into_identifier()]
+        );
+
+        assert!(fk_table.columns_are_indexed(fk_constraint.get_local_columns().as_slice()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn shortens_long_generated_identifiers() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+        let max_length = helper.platform.get_max_identifier_length();
+
+        let foreign_table_name = format!("long_foreign_{}", "f".repeat(40));
+        let table_name = format!("long_identifiers_{}", "t".repeat(40));
+        let column_name = format!("referenced_column_{}", "c".repeat(40));
+        let index_name = format!("idx_{}_{}", table_name, column_name);
+
+        let create_table = |shorten_identifiers: bool| -> Result<Table> {
+            let mut table = Table::new(&table_name);
+            table.set_schema_config(SchemaConfig {
+                shorten_identifiers,
+                ..schema_manager.create_schema_config()
+            });
+            table.add_column(Column::builder("id", INTEGER)?);
+            table.add_column(Column::builder(&column_name, INTEGER)?);
+            table.add_column(Column::builder("other", INTEGER)?);
+            table.set_primary_key(&["id"], None)?;
+            table.add_index(Index::new(
+                &index_name,
+                &["other"],
+                false,
+                false,
+                &[],
+                HashMap::default(),
+            ));
+            table.add_foreign_key_constraint(
+                &[column_name.as_str()],
+                &["id"],
+                &foreign_table_name,
+                Default::default(),
+                None,
+                None,
+                None::<&str>,
+            )?;
+
+            Ok(table)
+        };
+
+        let untouched = create_table(false)?;
+        assert!(untouched.has_index(&index_name));
+
+        let table = create_table(true)?;
+        assert_eq!(table.indices().len(), 3);
+        for index in table.indices() {
+            assert!(index.get_name().len() <= max_length);
+        }
+
+        let foreign_key = table.get_foreign_keys().get(0).unwrap();
+        assert!(foreign_key.get_name().len() <= max_length);
+
+        let other = create_table(true)?;
+        assert_eq!(
+            table
+                .indices()
+                .iter()
+                .map(|i| i.get_name().into_owned())
+                .collect::<Vec<_>>(),
+            other
+                .indices()
+                .iter()
+                .map(|i| i.get_name().into_owned())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            foreign_key.get_name(),
+            other.get_foreign_keys().get(0).unwrap().get_name()
+        );
+
+        let mut foreign_table = Table::new(&foreign_table_name);
+        foreign_table.add_column(Column::builder("id", INTEGER)?);
+        foreign_table.set_primary_key(&["id"], None)?;
+
+        let _ = schema_manager.drop_table(&table_name).await;
+        helper.drop_and_create_table(&foreign_table).await?;
+        helper.drop_and_create_table(&table).await?;
+
+        let online_table = schema_manager.introspect_table(&table_name).await?;
+        let online_names = online_table
+            .indices()
+            .iter()
+            .map(|i| i.get_name().to_lowercase())
+            .collect::<Vec<_>>();
+        for index in table.indices().iter().filter(|i| !i.is_primary()) {
+            assert!(online_names.contains(&index.get_name().to_lowercase()));
+        }
+
+        helper.drop_table_if_exists(&table_name).await;
+        helper.drop_table_if_exists(&foreign_table_name).await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn introspect_enum_values_in_declaration_order() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        if helper.platform.get_name() != "mysql" {
+            return Ok(());
+        }
+
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("enum_values_test").await;
+        helper
+            .connection
+            .execute_statement(
+                "CREATE TABLE enum_values_test (status ENUM('pending', 'active', 'it''s done') NOT NULL)",
+                params!(),
+            )
+            .await?;
+
+        let online_table = schema_manager.introspect_table("enum_values_test").await?;
+        let status = online_table.get_column("status").unwrap();
+        assert_eq!(status.get_values(), &["pending", "active", "it's done"]);
+
+        let mut table = online_table.clone();
+        table
+            .get_column_mut("status")
+            .unwrap()
+            .set_values(["active", "pending", "it's done"]);
+
+        let comparator = schema_manager.create_comparator();
+        let diff = comparator
+            .diff_table(&online_table, &table)?
+            .expect("reordering values should produce a diff");
+        let column_diff = diff.changed_columns.first().unwrap();
+        assert!(column_diff.has_changed(ChangedProperty::Values));
+
+        schema_manager.alter_table(diff).await?;
+        let online_table = schema_manager.introspect_table("enum_values_test").await?;
+        assert_eq!(
+            online_table.get_column("status").unwrap().get_values(),
+            &["active", "pending", "it's done"]
+        );
+
+        helper.drop_table_if_exists("enum_values_test").await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn string_defaults_with_quotes_round_trip() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("quoted_defaults_test").await;
+
+        let defaults = [
+            ("quote", "O'Brien"),
+            ("double_quote", "it''s"),
+            ("backslash", "C:\\path\\to"),
+            ("mixed", "\\'"),
+        ];
+
+        let mut table = Table::new("quoted_defaults_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        for (name, default) in defaults {
+            table.add_column(
+                Column::builder(name, STRING)?
+                    .set_length(20)
+                    .set_default(default),
+            );
+        }
+        table.add_column(
+            Column::builder("fixed", STRING)?
+                .set_length(5)
+                .set_fixed(true)
+                .set_default("a'b"),
+        );
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        let online_table = schema_manager
+            .introspect_table("quoted_defaults_test")
+            .await?;
+        for (name, default) in defaults.into_iter().chain([("fixed", "a'b")]) {
+            assert_eq!(
+                online_table.get_column(name).unwrap().get_default(),
+                &Value::from(default),
+                "default of column {}",
+                name
+            );
+        }
+
+        let comparator = schema_manager.create_comparator();
+        assert!(comparator.diff_table(&online_table, &table)?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn column_compression_round_trip() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("compression_test").await;
+
+        let mut table = Table::new("compression_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("payload", TEXT)?.set_compression("pglz"));
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        let online_table = schema_manager.introspect_table("compression_test").await?;
+        if helper.platform.get_name() == "postgresql" {
+            assert_eq!(
+                online_table
+                    .get_column("payload")
+                    .unwrap()
+                    .get_compression(),
+                Some("pglz")
+            );
+        }
+
+        let comparator = schema_manager.create_comparator();
+        assert!(comparator.diff_table(&online_table, &table)?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn column_storage_options_round_trip() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("storage_test").await;
+
+        let mut table = Table::new("storage_test");
+        table.add_column(Column::builder("id", INTEGER)?.set_statistics_target(500));
+        table.add_column(Column::builder("payload", TEXT)?.set_storage("EXTERNAL"));
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        let online_table = schema_manager.introspect_table("storage_test").await?;
+        if helper.platform.get_name() == "postgresql" {
+            let id = online_table.get_column("id").unwrap();
+            let payload = online_table.get_column("payload").unwrap();
+            assert_eq!(id.get_statistics_target(), Some(500));
+            assert_eq!(payload.get_storage(), Some("EXTERNAL"));
+        }
+
+        let comparator = schema_manager.create_comparator();
+        assert!(comparator.diff_table(&online_table, &table)?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn native_guid_column_round_trip() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        if !helper.platform.has_native_guid_type() {
+            return Ok(());
+        }
+
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("guid_test").await;
+
+        let mut table = Table::new("guid_test");
+        table.add_column(Column::builder("id", GUID)?);
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        let uuid = uuid::Uuid::parse_str("978549b1-2b2b-42b4-91c4-355980ac1bb4").unwrap();
+        helper
+            .connection
+            .insert("guid_test", value_map! { "id" => uuid typeof GUID })
+            .await?;
+
+        let value = helper
+            .connection
+            .query("SELECT id FROM guid_test", params!())
+            .await?
+            .fetch_one()
+            .await?
+            .unwrap()
+            .get("id")?
+            .clone();
+        assert_eq!(value, Value::Uuid(uuid));
+
+        assert_eq!(
+            helper.connection.convert_value(&value, GUID)?,
+            Value::String(uuid.to_string())
+        );
+
+        let online_table = schema_manager.introspect_table("guid_test").await?;
+        let comparator = schema_manager.create_comparator();
+        assert!(comparator.diff_table(&online_table, &table)?.is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn nulls_not_distinct_index_round_trip() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        if helper.platform.get_name() != "postgresql" {
+            return Ok(());
+        }
+
+        let server_version = helper.connection.server_version().await?;
+        let is_supported = !compare_to(&server_version, "15", Cmp::Lt).unwrap();
+        assert_eq!(helper.platform.supports_nulls_not_distinct(), is_supported);
+        if !is_supported {
+            return Ok(());
+        }
+
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("nulls_not_distinct_test").await;
+
+        let mut table = Table::new("nulls_not_distinct_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(
+            Column::builder("email", STRING)?
+                .set_length(255)
+                .set_notnull(false),
+        );
+        table.set_primary_key(&["id"], None)?;
+        table.add_index(
+            Index::builder("uniq_email")
+                .add_column("email")
+                .set_unique(true)
+                .set_nulls_not_distinct(true),
+        );
+        schema_manager.create_table(&table).await?;
+
+        let online_table = schema_manager
+            .introspect_table("nulls_not_distinct_test")
+            .await?;
+        assert!(online_table
+            .get_index("uniq_email")
+            .unwrap()
+            .is_nulls_not_distinct());
+
+        let comparator = schema_manager.create_comparator();
+        assert!(comparator.diff_table(&online_table, &table)?.is_none());
+
+        let mut changed_table = table.clone();
+        changed_table.drop_index("uniq_email");
+        changed_table.add_index(
+            Index::builder("uniq_email")
+                .add_column("email")
+                .set_unique(true),
+        );
+        assert!(comparator
+            .diff_table(&online_table, &changed_table)?
+            .is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn constraint_backed_indexes_are_flagged() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        if helper.platform.get_name() != "postgresql" {
+            return Ok(());
+        }
+
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("constraint_backed_test").await;
+
+        let mut table = Table::new("constraint_backed_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("code", STRING)?.set_length(32));
+        table.add_column(Column::builder("email", STRING)?.set_length(255));
+        table.set_primary_key(&["id"], None)?;
+        table.add_index(
+            Index::builder("uniq_backed_email")
+                .add_column("email")
+                .set_unique(true),
+        );
+        schema_manager.create_table(&table).await?;
+
+        let unique_constraint =
+            UniqueConstraint::new("uniq_backed_code", &["code"], &[], Default::default());
+        schema_manager
+            .create_unique_constraint(&unique_constraint, &table)
+            .await?;
+
+        let indexes = schema_manager
+            .list_table_indexes("constraint_backed_test")
+            .await?;
+        let code = indexes.get("uniq_backed_code").unwrap();
+        let email = indexes.get("uniq_backed_email").unwrap();
+        assert!(code.is_unique());
+        assert!(code.is_constraint_backed());
+        assert!(email.is_unique());
+        assert!(!email.is_constraint_backed());
+        assert!(indexes
+            .iter()
+            .find(|index| index.is_primary())
+            .unwrap()
+            .is_constraint_backed());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn replica_identity_round_trip() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("replica_identity_test").await;
+
+        let mut table = Table::new("replica_identity_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("code", STRING)?.set_length(32));
+        table.set_primary_key(&["id"], None)?;
+        table.add_index(
+            Index::builder("uniq_code")
+                .add_column("code")
+                .set_unique(true),
+        );
+        table.set_replica_identity(ReplicaIdentity::Full);
+        schema_manager.create_table(&table).await?;
+
+        let online_table = schema_manager
+            .introspect_table("replica_identity_test")
+            .await?;
+        let comparator = schema_manager.create_comparator();
+        assert!(comparator.diff_table(&online_table, &table)?.is_none());
+
+        if helper.platform.get_name() != "postgresql" {
+            return Ok(());
+        }
+
+        assert_eq!(
+            online_table.get_replica_identity(),
+            Some(&ReplicaIdentity::Full)
+        );
+
+        let mut changed_table = table.clone();
+        changed_table.set_replica_identity(ReplicaIdentity::UsingIndex("uniq_code".to_string()));
+        let diff = comparator
+            .diff_table(&online_table, &changed_table)?
+            .unwrap();
+        schema_manager.alter_table(diff).await?;
+
+        let online_table = schema_manager
+            .introspect_table("replica_identity_test")
+            .await?;
+        assert_eq!(
+            online_table.get_replica_identity(),
+            Some(&ReplicaIdentity::UsingIndex("uniq_code".to_string()))
+        );
+        assert!(comparator
+            .diff_table(&online_table, &changed_table)?
+            .is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn sqlite_alter_column_not_null_rebuilds_table() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        if helper.platform.get_name() != "sqlite" {
+            return Ok(());
+        }
+
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("not_null_child").await;
+        helper.drop_table_if_exists("not_null_parent").await;
+
+        let mut parent = Table::new("not_null_parent");
+        parent.add_column(Column::builder("id", INTEGER)?);
+        parent.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&parent).await?;
+
+        let mut table = Table::new("not_null_child");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("parent_id", INTEGER)?);
+        table.add_column(
+            Column::builder("note", STRING)?
+                .set_length(255)
+                .set_notnull(false),
+        );
+        table.set_primary_key(&["id"], None)?;
+        table.add_index(Index::builder("idx_note").add_column("note"));
+        table.add_foreign_key(
+            ForeignKeyConstraint::builder("not_null_parent")
+                .set_local_columns(vec!["parent_id"])
+                .set_foreign_columns(vec!["id"])
+                .set_name("fk_not_null_parent"),
+        )?;
+        schema_manager.create_table(&table).await?;
+
+        helper
+            .connection
+            .insert("not_null_parent", value_map! { "id" => 1 })
+            .await?;
+        helper
+            .connection
+            .insert(
+                "not_null_child",
+                value_map! { "id" => 1, "parent_id" => 1, "note" => "first" },
+            )
+            .await?;
+        helper
+            .connection
+            .insert(
+                "not_null_child",
+                value_map! { "id" => 2, "parent_id" => 1, "note" => Value::NULL },
+            )
+            .await?;
+        helper
+            .connection
+            .execute_statement(
+                "UPDATE not_null_child SET note = '' WHERE note IS NULL",
+                params!(),
+            )
+            .await?;
+
+        let mut changed_table = table.clone();
+        changed_table.drop_column("note");
+        changed_table.add_column(Column::builder("note", STRING)?.set_length(255));
+
+        let online_table = schema_manager.introspect_table("not_null_child").await?;
+        let comparator = schema_manager.create_comparator();
+        let diff = comparator
+            .diff_table(&online_table, &changed_table)?
+            .unwrap();
+        assert!(diff.changed_columns[0].has_changed(ChangedProperty::NotNull));
+        schema_manager.alter_table(diff).await?;
+
+        let online_table = schema_manager.introspect_table("not_null_child").await?;
+        assert!(online_table.get_column("note").unwrap().is_notnull());
+        assert!(online_table.has_index("idx_note"));
+        assert_eq!(online_table.get_foreign_keys().len(), 1);
+        assert!(comparator
+            .diff_table(&online_table, &changed_table)?
+            .is_none());
+
+        let rows = helper
+            .connection
+            .query("SELECT note FROM not_null_child ORDER BY id", params!())
+            .await?
+            .fetch_all()
+            .await?;
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get("note")?, &Value::from("first"));
+        assert_eq!(rows[1].get("note")?, &Value::from(""));
+
+        assert!(helper
+            .connection
+            .insert(
+                "not_null_child",
+                value_map! { "id" => 3, "parent_id" => 1, "note" => Value::NULL },
+            )
+            .await
+            .is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn inherited_collation_is_not_diffed() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        if helper.platform.get_name() != "mysql" {
+            return Ok(());
+        }
+
+        let schema_manager = helper.get_schema_manager();
+        helper
+            .drop_table_if_exists("collation_inheritance_test")
+            .await;
+
+        let mut table = Table::new("collation_inheritance_test");
+        table.set_charset("utf8mb4");
+        table.set_collation("utf8mb4_bin");
+        table.add_column(Column::builder("inherited", STRING)?.set_length(20));
+        table.add_column(
+            Column::builder("explicit", STRING)?
+                .set_length(20)
+                .set_collation("utf8mb4_bin"),
+        );
+        table.add_column(
+            Column::builder("overridden", STRING)?
+                .set_length(20)
+                .set_collation("utf8mb4_unicode_ci"),
+        );
+        schema_manager.create_table(&table).await?;
+
+        let online_table = schema_manager
+            .introspect_table("collation_inheritance_test")
+            .await?;
+        assert_eq!(
+            online_table
+                .get_column("inherited")
+                .unwrap()
+                .get_collation(),
+            &Some("utf8mb4_bin".to_string())
+        );
+
+        let comparator = schema_manager.create_comparator();
+        assert!(comparator.diff_table(&online_table, &table)?.is_none());
+        assert!(comparator.diff_table(&table, &online_table)?.is_none());
+
+        let mut changed_table = table.clone();
+        changed_table
+            .get_column_mut("overridden")
+            .unwrap()
+            .set_collation("utf8mb4_general_ci");
+        assert!(comparator
+            .diff_table(&online_table, &changed_table)?
+            .is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn json_expressions_are_evaluated() -> Result<()> {
+        use crate::Parameters;
+
+        let helper = FunctionalTestsHelper::default().await;
+        let connection = &helper.connection;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("json_expr_test").await;
+
+        let mut table = Table::new("json_expr_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("tags", TEXT)?);
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        for (id, tags) in [(1, "[1, 2, 3]"), (2, "[\"a\", 3]"), (3, "{\"a\": 1}")] {
+            connection
+                .insert("json_expr_test", value_map! { "id" => id, "tags" => tags })
+                .await?;
+        }
+
+        let mut qb = connection.create_query_builder()?;
+        let expr = qb.expr();
+        qb.select(["id".into(), expr.json_array_length("tags")?])
+            .from("json_expr_test", None)
+            .r#where(expr.json_contains("tags", expr.param("[3, 1]"))?)
+            .order_by("id", None);
+
+        let rows = connection
+            .fetch_all(qb.get_sql()?, Parameters::from(qb.get_parameters()?))
+            .await?;
+        let result = rows
+            .iter()
+            .map(|row| (row.get(0).unwrap().clone(), row.get(1).unwrap().clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(result, vec![(Value::Int(1), Value::Int(3))]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn overlaps_handles_zero_length_periods() -> Result<()> {
+        use crate::Parameters;
+
+        let helper = FunctionalTestsHelper::default().await;
+        let connection = &helper.connection;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("overlaps_test").await;
+
+        let mut table = Table::new("overlaps_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("starts_at", DATETIME)?);
+        table.add_column(Column::builder("ends_at", DATETIME)?);
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        let at = |hour: u32| format!("2024-01-01 {:02}:00:00", hour);
+        for (id, starts_at, ends_at) in [
+            (1, 10, 20),
+            (2, 15, 15),
+            (3, 20, 20),
+            (4, 10, 10),
+            (5, 20, 23),
+        ] {
+            connection
+                .insert(
+                    "overlaps_test",
+                    value_map! { "id" => id, "starts_at" => at(starts_at), "ends_at" => at(ends_at) },
+                )
+                .await?;
+        }
+
+        let overlapping = |start: u32, end: u32| async move {
+            let mut qb = connection.create_query_builder()?;
+            let expr = qb.expr();
+            qb.select(["id"])
+                .from("overlaps_test", None)
+                .r#where(expr.overlaps(
+                    ("starts_at", "ends_at"),
+                    (expr.param(at(start)), expr.param(at(end))),
+                ))
+                .order_by("id", None);
+
+            connection
+                .fetch_all(qb.get_sql()?, Parameters::from(qb.get_parameters()?))
+                .await?
+                .iter()
+                .map(|row| i64::try_from(row.get(0).unwrap()))
+                .collect::<Result<Vec<_>>>()
+        };
+
+        // Zero-length periods are single instants, both natively and when emulated.
+        assert_eq!(overlapping(10, 20).await?, [1, 2, 4]);
+        assert_eq!(overlapping(15, 15).await?, [1, 2]);
+        assert_eq!(overlapping(10, 10).await?, [1, 4]);
+        assert_eq!(overlapping(20, 20).await?, [3, 5]);
+
+        helper.drop_table_if_exists("overlaps_test").await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn join_values_table() -> Result<()> {
+        use crate::Parameters;
+
+        let helper = FunctionalTestsHelper::default().await;
+        let connection = &helper.connection;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("values_join_test").await;
+
+        let mut table = Table::new("values_join_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("name", STRING)?.set_length(20));
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        for (id, name) in [(1, "a"), (2, "b"), (3, "c")] {
+            connection
+                .insert(
+                    "values_join_test",
+                    value_map! { "id" => id, "name" => name },
+                )
+                .await?;
+        }
+
+        let mut qb = connection.create_query_builder()?;
+        let expr = qb.expr();
+        qb.select(["t.name", "v.label"])
+            .from("values_join_test", Some("t"))
+            .from_values(
+                [
+                    [Value::from(1), Value::from("one")],
+                    [Value::from(3), Value::from("three")],
+                ],
+                "v",
+                &["id", "label"],
+            )?
+            .r#where(expr.eq("t.id", "v.id"))
+            .order_by("t.id", None);
+
+        let rows = connection
+            .fetch_all(qb.get_sql()?, Parameters::from(qb.get_parameters()?))
+            .await?;
+        let result = rows
+            .iter()
+            .map(|row| (row.get(0).unwrap().clone(), row.get(1).unwrap().clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            result,
+            vec![
+                (Value::from("a"), Value::from("one")),
+                (Value::from("c"), Value::from("three")),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn insert_many_splits_rows_by_bind_limit() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let connection = &helper.connection;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("insert_many_test").await;
+
+        let mut table = Table::new("insert_many_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("qty", INTEGER)?);
+        table.add_column(Column::builder("label", STRING)?.set_length(10));
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        let row_count = helper.platform.get_max_bind_parameters() / 3 + 10;
+        let rows = (0..row_count as i64)
+            .map(|id| value_map! { "id" => id, "qty" => id, "label" => "row" })
+            .collect::<Vec<_>>();
+
+        let inserted = connection.insert_many("insert_many_test", rows).await?;
+        assert_eq!(inserted, row_count);
+
+        let rows = connection
+            .fetch_all("SELECT COUNT(*) AS cnt FROM insert_many_test", params!())
+            .await?;
+        assert_eq!(rows[0].get("cnt")?, &Value::Int(row_count as i64));
+
+        let mismatched = vec![
+            value_map! { "id" => 1, "qty" => 1, "label" => "a" },
+            value_map! { "id" => 2, "qty" => 2 },
+        ];
+        let err = connection
+            .insert_many("insert_many_test", mismatched)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::RowColumnsMismatch);
+
+        let mut wide_table = Table::new("too_many_columns");
+        for i in 0..=helper.platform.get_max_columns_per_table() {
+            wide_table.add_column(Column::builder(format!("col_{}", i), INTEGER)?);
+        }
+
+        let err = schema_manager
+            .get_create_table_sql(&wide_table, None)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TooManyColumnsForTable);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn insert_many_with_default_values() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let connection = &helper.connection;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("insert_default_test").await;
+
+        let mut table = Table::new("insert_default_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("qty", INTEGER)?.set_default(42));
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        let rows = (0..6_i64)
+            .map(|id| {
+                if id % 2 == 0 {
+                    value_map! { "id" => id, "qty" => id }
+                } else {
+                    value_map! { "id" => id, "qty" => Value::Default }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let inserted = connection.insert_many("insert_default_test", rows).await?;
+        assert_eq!(inserted, 6);
+
+        let rows = connection
+            .fetch_all("SELECT qty FROM insert_default_test ORDER BY id", params!())
+            .await?;
+        let quantities = rows
+            .iter()
+            .map(|row| i64::try_from(row.get("qty").unwrap()))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(quantities, [0, 42, 2, 42, 4, 42]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn insert_returning_generated_identifier() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let connection = &helper.connection;
+        if !helper.platform.supports_insert_returning() {
+            let err = connection
+                .insert_returning("insert_returning_test", value_map! { "name" => "a" }, "id")
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::PlatformFeatureUnsupported);
+            return Ok(());
+        }
+
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("insert_returning_test").await;
+
+        let mut table = Table::new("insert_returning_test");
+        table.add_column(Column::builder("id", INTEGER)?.set_autoincrement(true));
+        table.add_column(Column::builder("name", STRING)?.set_length(32));
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        let mut ids = vec![];
+        for name in ["a", "b"] {
+            let id = connection
+                .insert_returning("insert_returning_test", value_map! { "name" => name }, "id")
+                .await?
+                .expect("a row should be returned");
+            ids.push(i64::try_from(&id)?);
+        }
+
+        let rows = connection
+            .fetch_all(
+                "SELECT id FROM insert_returning_test ORDER BY id",
+                params!(),
+            )
+            .await?;
+        let stored = rows
+            .iter()
+            .map(|row| i64::try_from(row.get("id").unwrap()))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(ids, stored);
+
+        helper.drop_table_if_exists("insert_returning_test").await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn insert_many_with_default_values_preserves_order() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let connection = &helper.connection;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("insert_default_order").await;
+
+        let mut table = Table::new("insert_default_order");
+        table.add_column(Column::builder("id", INTEGER)?.set_autoincrement(true));
+        table.add_column(Column::builder("name", STRING)?.set_length(32));
+        table.add_column(Column::builder("qty", INTEGER)?.set_default(42));
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        let rows = [("a", Some(1_i64)), ("b", None), ("c", None), ("d", Some(4))]
+            .into_iter()
+            .map(|(name, qty)| match qty {
+                Some(qty) => value_map! { "name" => name, "qty" => qty },
+                None => value_map! { "name" => name, "qty" => Value::Default },
+            })
+            .collect::<Vec<_>>();
+
+        let inserted = connection.insert_many("insert_default_order", rows).await?;
+        assert_eq!(inserted, 4);
+
+        let rows = connection
+            .fetch_all(
+                "SELECT name, qty FROM insert_default_order ORDER BY id",
+                params!(),
+            )
+            .await?;
+        let values = rows
+            .iter()
+            .map(|row| {
+                Ok((
+                    String::try_from(row.get("name")?)?,
+                    i64::try_from(row.get("qty")?)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(
+            values,
+            [
+                ("a".to_string(), 1),
+                ("b".to_string(), 42),
+                ("c".to_string(), 42),
+                ("d".to_string(), 4)
+            ]
+        );
+
+        let updated = connection
+            .update(
+                "insert_default_order",
+                value_map! { "qty" => Value::Default },
+                value_map! { "name" => "a" },
+            )
+            .await;
+        if connection
+            .get_platform()?
+            .supports_default_in_insert_values()
+        {
+            assert_eq!(updated?, 1);
+            let rows = connection
+                .fetch_all(
+                    "SELECT qty FROM insert_default_order WHERE name = 'a'",
+                    params!(),
+                )
+                .await?;
+            assert_eq!(i64::try_from(rows[0].get("qty")?)?, 42);
+        } else {
+            assert!(updated.is_err());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn upsert_many_reports_skipped_rows() -> Result<()> {
+        use crate::query::{MergeBuilder, UpsertResult};
+
+        let helper = FunctionalTestsHelper::default().await;
+        let connection = &helper.connection;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("upsert_test").await;
+
+        let mut table = Table::new("upsert_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("qty", INTEGER)?);
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        connection
+            .insert("upsert_test", value_map! { "id" => 1, "qty" => 1 })
+            .await?;
+        connection
+            .insert("upsert_test", value_map! { "id" => 2, "qty" => 2 })
+            .await?;
+
+        let upsert = |id: i64, qty: i64| {
+            MergeBuilder::new("upsert_test")
+                .using("id", id)
+                .using("qty", qty)
+                .on(&["id"])
+        };
+
+        let result = connection
+            .upsert_many(&[
+                upsert(1, 10)
+                    .when_matched_update(&["qty"])
+                    .when_not_matched_insert(),
+                upsert(3, 3)
+                    .when_matched_update(&["qty"])
+                    .when_not_matched_insert(),
+                upsert(2, 20).when_not_matched_insert(),
+                upsert(4, 4).when_not_matched_insert(),
+            ])
+            .await?;
+
+        if helper.platform.get_name() == "sqlite" {
+            assert_eq!(
+                result,
+                UpsertResult {
+                    inserted: 1,
+                    updated: 0,
+                    skipped: 1,
+                    inserted_or_updated: 2,
+                }
+            );
+        } else {
+            assert_eq!(
+                result,
+                UpsertResult {
+                    inserted: 2,
+                    updated: 1,
+                    skipped: 1,
+                    inserted_or_updated: 0,
+                }
+            );
+        }
+
+        let rows = connection
+            .fetch_all("SELECT qty FROM upsert_test ORDER BY id", params!())
+            .await?;
+        let quantities = rows
+            .iter()
+            .map(|row| row.get(0).unwrap().clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            quantities,
+            vec![Value::Int(10), Value::Int(2), Value::Int(3), Value::Int(4)]
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn function_call_defaults_round_trip() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        if helper.platform.get_name() != "postgresql" {
+            return Ok(());
+        }
+
+        let connection = &helper.connection;
+        let schema_manager = helper.get_schema_manager();
+        helper.drop_table_if_exists("function_defaults_test").await;
+        connection
+            .execute_statement(
+                "DROP SEQUENCE IF EXISTS function_defaults_counter",
+                params!(),
+            )
+            .await?;
+        connection
+            .execute_statement("CREATE SEQUENCE function_defaults_counter", params!())
+            .await?;
+
+        let mut table = Table::new("function_defaults_test");
+        table.add_column(Column::builder("id", INTEGER)?.set_autoincrement(true));
+        table
+            .add_column(Column::builder("uuid", GUID)?.set_default_expression("gen_random_uuid()"));
+        table.add_column(
+            Column::builder("counter", INTEGER)?
+                .set_default_expression("nextval('function_defaults_counter')"),
+        );
+        table.add_column(
+            Column::builder("code", STRING)?
+                .set_length(20)
+                .set_default_expression("upper(md5('seed'))"),
+        );
+        table.set_primary_key(&["id"], None)?;
+        schema_manager.create_table(&table).await?;
+
+        let online_table = schema_manager
+            .introspect_table("function_defaults_test")
+            .await?;
+        let id = online_table.get_column("id").unwrap();
+        assert!(id.is_autoincrement());
+        assert_eq!(id.get_default_expression(), None);
+        assert_eq!(
+            online_table
+                .get_column("uuid")
+                .unwrap()
+                .get_default_expression(),
+            Some("gen_random_uuid()")
+        );
+        assert_eq!(
+            online_table
+                .get_column("counter")
+                .unwrap()
+                .get_default_expression(),
+            Some("nextval('function_defaults_counter')")
+        );
+        assert!(!online_table
+            .get_column("counter")
+            .unwrap()
+            .is_autoincrement());
+        assert_eq!(
+            online_table
+                .get_column("code")
+                .unwrap()
+                .get_default_expression(),
+            Some("upper(md5('seed'))")
+        );
+
+        let comparator = schema_manager.create_comparator();
+        assert!(comparator.diff_table(&online_table, &table)?.is_none());
+
+        let mut changed = online_table.clone();
+        changed
+            .get_column_mut("uuid")
+            .unwrap()
+            .set_default_expression::<&str, _>(None);
+        let diff = comparator
+            .diff_table(&online_table, &changed)?
+            .expect("dropping the default expression should produce a diff");
+        assert!(diff
+            .changed_columns
+            .first()
+            .unwrap()
+            .has_changed(ChangedProperty::Default));
+
+        helper.drop_table_if_exists("function_defaults_test").await;
+        connection
+            .execute_statement("DROP SEQUENCE function_defaults_counter", params!())
+            .await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn duration_column_round_trip() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let connection = &helper.connection;
+        let schema_manager = helper.get_schema_manager();
+
+        let mut table = Table::new("duration_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("elapsed", DURATION)?);
+        table.set_primary_key(&["id"], None)?;
+        helper.drop_and_create_table(&table).await?;
+
+        connection
+            .insert(
+                "duration_test",
+                value_map! {
+                    "id" => 1 typeof INTEGER,
+                    "elapsed" => chrono::Duration::minutes(90) typeof DURATION,
+                },
+            )
+            .await?;
+
+        let rows = connection
+            .fetch_all("SELECT elapsed FROM duration_test", params!())
+            .await?;
+        let elapsed = connection.convert_value(rows[0].get("elapsed")?, DURATION)?;
+        assert_eq!(
+            chrono::Duration::try_from(elapsed.clone())?,
+            chrono::Duration::minutes(90)
+        );
+        assert_eq!(
+            std::time::Duration::try_from(elapsed)?,
+            std::time::Duration::from_secs(5400)
+        );
+
+        assert_eq!(
+            connection.convert_value(&Value::from("1 day 01:30:00"), DURATION)?,
+            Value::Int(91800)
+        );
+        for interval in ["1 mon 2 days", "1 year", "00:00:01.5"] {
+            let err = connection
+                .convert_value(&Value::from(interval), DURATION)
+                .unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ConversionFailed);
+        }
+
+        let platform = &helper.platform;
+        if platform.has_native_interval_type()
+            || platform.supports_inline_column_comments()
+            || platform.supports_comment_on_statement()
+        {
+            let online_table = schema_manager.introspect_table("duration_test").await?;
+            assert_eq!(
+                online_table.get_column("elapsed").unwrap().get_type(),
+                DURATION.into_type()?
+            );
+        }
+
+        helper.drop_table_if_exists("duration_test").await;
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn schema_fingerprint() -> Result<()> {
+        let create_tables = |reversed: bool| -> Result<Vec<Table>> {
+            let mut users = Table::new("users");
+            let mut columns = vec![
+                Column::builder("id", INTEGER)?.get_column(),
+                Column::builder("name", STRING)?
+                    .set_length(255)
+                    .get_column(),
+            ];
+            if reversed {
+                columns.reverse();
+            }
+
+            users.add_columns(columns.into_iter());
+            users.set_primary_key(&["id"], None)?;
+
+            let mut posts = Table::new("posts");
+            posts.add_column(Column::builder("id", INTEGER)?);
+            posts.add_column(Column::builder("user_id", INTEGER)?);
+            posts.add_foreign_key_constraint(
+                &["user_id"],
+                &["id"],
+                "users",
+                HashMap::default(),
+                None,
+                Some(ForeignKeyReferentialAction::Cascade),
+                None::<&str>,
+            )?;
+
+            Ok(if reversed {
+                vec![posts, users]
+            } else {
+                vec![users, posts]
+            })
+        };
+
+        let schema = Schema::new(
+            create_tables(false)?,
+            vec![],
+            vec![],
+            vec![],
+            Default::default(),
         );
-        assert!(!test_index.is_primary());
-        assert!(!test_index.is_unique());
+        let equivalent = Schema::new(
+            create_tables(true)?,
+            vec![],
+            vec![],
+            vec![],
+            Default::default(),
+        );
+        assert_eq!(schema.fingerprint(), equivalent.fingerprint());
+
+        let mut changed = equivalent.clone();
+        changed
+            .get_table_mut("users")
+            .unwrap()
+            .add_column(Column::builder("email", STRING)?);
+        assert_ne!(schema.fingerprint(), changed.fingerprint());
+
+        let mut changed = equivalent;
+        changed
+            .get_table_mut("users")
+            .unwrap()
+            .get_column_mut("name")
+            .unwrap()
+            .set_notnull(false);
+        assert_ne!(schema.fingerprint(), changed.fingerprint());
 
         Ok(())
     }
 
     #[tokio::test]
     #[serial]
-    pub async fn drop_and_create_index() -> Result<()> {
-        let helper = FunctionalTestsHelper::default().await;
-        let schema_manager = helper.get_schema_manager();
+    pub async fn schema_foreign_keys_are_validated_before_generating_sql() -> Result<()> {
+        let connection = create_connection().await?;
+        let schema_manager = connection.create_schema_manager()?;
 
-        let mut table = helper.get_test_table("test_create_index")?;
-        table.add_unique_index(&["test"], Some("test"), Default::default())?;
-        helper.drop_and_create_table(&table).await?;
+        let mut users = Table::new("users");
+        users.add_column(Column::builder("id", INTEGER)?);
+        users.add_column(Column::builder("email", STRING)?.set_length(255));
+        users.set_primary_key(&["id"], None)?;
+
+        let posts = |foreign_table: &str, foreign_column: &str| -> Result<Table> {
+            let mut posts = Table::new("posts");
+            posts.add_column(Column::builder("id", INTEGER)?);
+            posts.add_column(Column::builder("user_ref", INTEGER)?);
+            posts.set_primary_key(&["id"], None)?;
+            posts.add_foreign_key(
+                ForeignKeyConstraint::builder(foreign_table)
+                    .set_local_columns(vec!["user_ref"])
+                    .set_foreign_columns(vec![foreign_column])
+                    .set_name("fk_posts_user"),
+            )?;
 
-        let index = table.get_index(&"test").unwrap();
-        schema_manager.drop_index(&index, &table).await?;
-        schema_manager.create_index(&index, &table).await?;
+            Ok(posts)
+        };
+        let create_schema =
+            |tables: Vec<Table>| Schema::new(tables, vec![], vec![], vec![], Default::default());
 
-        let table_indexes = schema_manager
-            .list_table_indexes("test_create_index")
-            .await?;
+        let schema = create_schema(vec![users.clone(), posts("users", "id")?]);
+        assert!(schema.to_sql(&schema_manager).is_ok());
 
-        let test_index = table_indexes
-            .iter()
-            .find(|i| i.get_name() == "test")
-            .unwrap();
-        assert_eq!(test_index.get_columns(), vec!["test"]);
-        assert!(test_index.is_unique());
-        assert!(!test_index.is_primary());
+        let invalid_schemas = [
+            create_schema(vec![posts("users", "id")?]),
+            create_schema(vec![users.clone(), posts("users", "missing")?]),
+            create_schema(vec![users, posts("users", "email")?]),
+        ];
+        for schema in invalid_schemas {
+            let err = schema.to_sql(&schema_manager).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ForeignKeyReferenceInvalid);
+            assert!(err.to_string().contains("fk_posts_user"));
+        }
 
         Ok(())
     }
 
     #[tokio::test]
     #[serial]
-    pub async fn drop_and_create_unique_constraint() -> Result<()> {
+    pub async fn range_columns_round_trip() -> Result<()> {
         let helper = FunctionalTestsHelper::default().await;
-        let platform = helper.platform.clone();
-        if platform.get_name() == "sqlite" {
+        if helper.platform.get_name() != "postgresql" {
             return Ok(());
         }
 
+        let connection = &helper.connection;
         let schema_manager = helper.get_schema_manager();
 
-        let mut table = Table::new("test_unique_constraint");
-        table.add_column(Column::new("id", INTEGER.into_type()?));
-
+        let mut table = Table::new("range_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("quantity", INTEGER_RANGE)?.set_notnull(false));
+        table.add_column(Column::builder("period", DATETIMETZ_RANGE)?.set_notnull(false));
+        table.set_primary_key(&["id"], None)?;
         helper.drop_and_create_table(&table).await?;
 
-        let unique_constraint = UniqueConstraint::new("uniq_id", &["id"], &[], Default::default());
-        schema_manager
-            .create_unique_constraint(&unique_constraint, &table)
+        let start = chrono::DateTime::parse_from_rfc3339("2023-01-01T10:00:00+02:00")?;
+        let end = chrono::DateTime::parse_from_rfc3339("2023-02-01T08:30:00Z")?;
+        let ranges = [
+            (
+                Range::new(Bound::Included(1.into()), Bound::Excluded(10.into())),
+                Range::new(Bound::Included(start.into()), Bound::Included(end.into())),
+            ),
+            (
+                Range::new(Bound::Unbounded, Bound::Excluded(5.into())),
+                Range::new(Bound::Excluded(start.into()), Bound::Unbounded),
+            ),
+            (Range::new(Bound::Unbounded, Bound::Unbounded), Range::Empty),
+        ];
+
+        for (id, (quantity, period)) in ranges.iter().enumerate() {
+            connection
+                .insert(
+                    "range_test",
+                    value_map! {
+                        "id" => id typeof INTEGER,
+                        "quantity" => quantity.clone() typeof INTEGER_RANGE,
+                        "period" => period.clone() typeof DATETIMETZ_RANGE,
+                    },
+                )
+                .await?;
+        }
+
+        let rows = connection
+            .fetch_all(
+                "SELECT quantity, period FROM range_test ORDER BY id",
+                params!(),
+            )
             .await?;
+        for (row, (quantity, period)) in rows.iter().zip(ranges) {
+            let q = connection.convert_value(row.get("quantity")?, INTEGER_RANGE)?;
+            assert_eq!(Range::try_from(q)?, quantity);
+            let p = connection.convert_value(row.get("period")?, DATETIMETZ_RANGE)?;
+            assert_eq!(Range::try_from(p)?, period);
+        }
 
-        // there's currently no API for introspecting unique constraints,
-        // so introspect the underlying indexes instead
-        let indexes = schema_manager
-            .list_table_indexes("test_unique_constraint")
+        // Discrete ranges are normalized by the server to the [) form.
+        let rows = connection
+            .fetch_all(
+                "SELECT '[3,7]'::int4range AS q, '(3,7)'::int4range AS e",
+                params!(),
+            )
             .await?;
-        assert_eq!(indexes.len(), 1);
+        assert_eq!(
+            connection.convert_value(rows[0].get("q")?, INTEGER_RANGE)?,
+            Value::from(Range::new(
+                Bound::Included(3.into()),
+                Bound::Excluded(8.into())
+            ))
+        );
+        assert_eq!(
+            connection.convert_value(rows[0].get("e")?, INTEGER_RANGE)?,
+            Value::from(Range::new(
+                Bound::Included(4.into()),
+                Bound::Excluded(7.into())
+            ))
+        );
+        assert_eq!(
+            connection.convert_value(
+                &Value::from("[,\"2023-01-01 10:00:00+02\")"),
+                DATETIMETZ_RANGE
+            )?,
+            Value::from(Range::new(Bound::Unbounded, Bound::Excluded(start.into())))
+        );
 
-        let index = indexes.get(0).unwrap();
-        assert_eq!(index.get_name(), "uniq_id");
-        assert!(index.is_unique());
+        let online_table = schema_manager.introspect_table("range_test").await?;
+        assert_eq!(
+            online_table.get_column("quantity").unwrap().get_type(),
+            INTEGER_RANGE.into_type()?
+        );
+        assert_eq!(
+            online_table.get_column("period").unwrap().get_type(),
+            DATETIMETZ_RANGE.into_type()?
+        );
+
+        helper.drop_table_if_exists("range_test").await;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn get_index_statistics() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+
+        let mut table = Table::new("index_statistics_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("category", INTEGER)?);
+        table.set_primary_key(&["id"], None)?;
+        table.add_index(Index::new(
+            "idx_index_statistics_category",
+            &["category"],
+            false,
+            false,
+            &[],
+            HashMap::default(),
+        ));
+        helper.drop_and_create_table(&table).await?;
+
+        for id in 1..=50 {
+            helper
+                .connection
+                .execute_statement(
+                    format!(
+                        "INSERT INTO index_statistics_test (id, category) VALUES ({}, {})",
+                        id,
+                        id % 5
+                    ),
+                    params!(),
+                )
+                .await?;
+        }
 
         schema_manager
-            .drop_unique_constraint(&unique_constraint, &table)
+            .analyze_table(&"index_statistics_test")
             .await?;
 
-        let indexes = schema_manager
-            .list_table_indexes("test_unique_constraint")
+        let statistics = schema_manager
+            .get_index_statistics("index_statistics_test")
             .await?;
-        assert!(indexes.is_empty());
+        let category = statistics
+            .iter()
+            .find(|s| s.get_index_name().to_lowercase() == "idx_index_statistics_category")
+            .expect("index statistics not found");
+        assert!(category.get_cardinality().is_some_and(|c| c > 0));
+
+        helper.drop_table_if_exists("index_statistics_test").await;
 
         Ok(())
     }
 
     #[tokio::test]
     #[serial]
-    pub async fn create_table_with_foreign_keys() -> Result<()> {
+    pub async fn get_table_ddl() -> Result<()> {
         let helper = FunctionalTestsHelper::default().await;
         let schema_manager = helper.get_schema_manager();
-        let table_b = helper.get_test_table("test_foreign")?;
-
-        let _ = schema_manager.drop_table(&"test_create_fk").await;
-        helper.drop_and_create_table(&table_b).await?;
 
-        let mut table_a = helper.get_test_table("test_create_fk")?;
-        table_a.add_foreign_key_constraint(
-            &["foreign_key_test"],
+        helper.drop_table_if_exists(&"ddl_child").await;
+        helper.drop_table_if_exists(&"ddl_parent").await;
+
+        let mut parent = Table::new("ddl_parent");
+        parent.add_column(Column::builder("id", INTEGER)?);
+        parent.set_primary_key(&["id"], None)?;
+        helper.drop_and_create_table(&parent).await?;
+
+        let mut child = Table::new("ddl_child");
+        child.add_column(Column::builder("id", INTEGER)?);
+        child.add_column(Column::builder("parent_id", INTEGER)?);
+        child.add_column(Column::builder("label", STRING)?.set_length(32));
+        child.set_primary_key(&["id"], None)?;
+        child.add_index(Index::new(
+            "idx_ddl_child_label",
+            &["label"],
+            false,
+            false,
+            &[],
+            HashMap::default(),
+        ));
+        child.add_foreign_key_constraint(
+            &["parent_id"],
             &["id"],
-            "test_foreign",
+            "ddl_parent",
             Default::default(),
             None,
             None,
-            Some("test_foreign"),
+            Some("fk_ddl_child_parent"),
         )?;
+        helper.drop_and_create_table(&child).await?;
 
-        helper.drop_and_create_table(&table_a).await?;
+        let ddl = schema_manager
+            .get_table_ddl("ddl_child")
+            .await?
+            .to_lowercase();
+        assert!(ddl.starts_with("create table ddl_child ("));
+        assert!(ddl.contains("parent_id"));
+        assert!(ddl.contains("label"));
+        assert!(ddl.contains("primary key"));
+        assert!(ddl.contains("index idx_ddl_child_label"));
+        assert!(ddl.contains("foreign key (parent_id) references ddl_parent (id)"));
 
-        let fk_table = schema_manager.introspect_table("test_create_fk").await?;
-        let fk_constraints = fk_table.get_foreign_keys();
-        assert_eq!(
-            fk_constraints.len(),
-            1,
-            "Table 'test_create_fk' has to have one foreign key."
-        );
+        helper.drop_table_if_exists(&"ddl_child").await;
+        helper.drop_table_if_exists(&"ddl_parent").await;
 
-        let fk_constraint = fk_constraints.get(0).unwrap();
-        assert_eq!(
-            fk_constraint.get_local_columns(),
-            &vec!["foreign_key_test".into_identifier()]
-        );
-        assert_eq!(
-            fk_constraint.get_foreign_columns(),
-            &vec!["id".into_identifier()]
-        );
+        Ok(())
+    }
 
-        assert!(fk_table.columns_are_indexed(fk_constraint.get_local_columns().as_slice()));
+    #[tokio::test]
+    #[serial]
+    pub async fn analyze_and_optimize_table() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+
+        let mut table = Table::new("maintenance_test");
+        table.add_column(Column::builder("id", INTEGER)?);
+        table.add_column(Column::builder("label", STRING)?.set_length(32));
+        table.set_primary_key(&["id"], None)?;
+        helper.drop_and_create_table(&table).await?;
+
+        for id in 1..=20 {
+            helper
+                .connection
+                .execute_statement(
+                    format!(
+                        "INSERT INTO maintenance_test (id, label) VALUES ({}, 'row {}')",
+                        id, id
+                    ),
+                    params!(),
+                )
+                .await?;
+        }
+
+        helper
+            .connection
+            .execute_statement("DELETE FROM maintenance_test WHERE id > 10", params!())
+            .await?;
+
+        schema_manager.analyze_table(&"maintenance_test").await?;
+        schema_manager.optimize_table(&"maintenance_test").await?;
+
+        let rows = helper
+            .connection
+            .fetch_all("SELECT id FROM maintenance_test", params!())
+            .await?;
+        assert_eq!(rows.len(), 10);
+
+        helper.drop_table_if_exists("maintenance_test").await;
 
         Ok(())
     }
@@ -3954,6 +6241,110 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial]
+    pub async fn sequence_options_round_trip() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+        if !helper.platform.supports_sequences() {
+            return Ok(());
+        }
+
+        let mut sequence = Sequence::new("sequence_options_test", 2, 10, 20);
+        sequence.set_cycle(true);
+        sequence.set_min_value(Some(5));
+        sequence.set_max_value(Some(1000));
+        let _ = schema_manager.drop_sequence(&sequence.get_name()).await;
+        schema_manager.create_sequence(&sequence).await?;
+
+        let online = schema_manager
+            .list_sequences()
+            .await?
+            .into_iter()
+            .find(|s| s.get_name() == "sequence_options_test")
+            .expect("sequence not found");
+        assert_eq!(online.get_cache(), Some(20));
+        assert_eq!(online.get_min_value(), Some(5));
+        assert_eq!(online.get_max_value(), Some(1000));
+        assert!(online.is_cycle());
+
+        let comparator = schema_manager.create_comparator();
+        let from_schema = Schema::new(vec![], vec![], vec![online], vec![], Default::default());
+        let to_schema = Schema::new(
+            vec![],
+            vec![],
+            vec![sequence.clone()],
+            vec![],
+            Default::default(),
+        );
+        let diff = comparator.compare_schemas(&from_schema, &to_schema)?;
+        assert!(diff.changed_sequences.is_empty());
+
+        sequence.set_cycle(false);
+        let to_schema = Schema::new(vec![], vec![], vec![sequence], vec![], Default::default());
+        let diff = comparator.compare_schemas(&from_schema, &to_schema)?;
+        assert_eq!(diff.changed_sequences.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[serial]
+    pub async fn schema_diff_can_be_filtered_by_change_kind() -> Result<()> {
+        let helper = FunctionalTestsHelper::default().await;
+        let schema_manager = helper.get_schema_manager();
+
+        let mut from_table = Table::new("filter_test");
+        from_table.add_column(Column::builder("id", INTEGER)?);
+        from_table.add_column(Column::builder("dropped", INTEGER)?);
+        let mut removed_table = Table::new("filter_removed");
+        removed_table.add_column(Column::builder("id", INTEGER)?);
+
+        let mut to_table = Table::new("filter_test");
+        to_table.add_column(Column::builder("id", INTEGER)?);
+        to_table.add_column(Column::builder("added", INTEGER)?.set_notnull(false));
+        let mut new_table = Table::new("filter_new");
+        new_table.add_column(Column::builder("id", INTEGER)?);
+
+        let from_schema = Schema::new(
+            vec![from_table, removed_table],
+            vec![],
+            vec![],
+            vec![],
+            Default::default(),
+        );
+        let to_schema = Schema::new(
+            vec![to_table, new_table],
+            vec![],
+            vec![],
+            vec![],
+            Default::default(),
+        );
+
+        let comparator = schema_manager.create_comparator();
+        let diff = comparator
+            .compare_schemas(&from_schema, &to_schema)?
+            .filter(&[SchemaChangeKind::Removal]);
+
+        let table_diff = diff.changed_tables.get("filter_test").unwrap();
+        assert!(table_diff.added_columns.is_empty());
+        assert_eq!(table_diff.removed_columns.len(), 1);
+        assert_eq!(diff.removed_tables.len(), 1);
+
+        let sql = diff.to_sql(&schema_manager)?;
+        assert!(sql.iter().any(|s| s.contains("DROP TABLE filter_removed")));
+        assert!(!sql.iter().any(|s| s.contains("filter_new")));
+        assert!(!sql.iter().any(|s| s.contains("added")));
+
+        let diff = comparator
+            .compare_schemas(&from_schema, &to_schema)?
+            .filter(&[SchemaChangeKind::Change]);
+        assert!(diff.changed_tables.is_empty());
+        assert!(diff.removed_tables.is_empty());
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[serial]
     pub async fn primary_key_auto_increment() -> Result<()> {
@@ -4330,4 +6721,102 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    pub fn foreign_keys_without_index_are_reported() -> Result<()> {
+        let mut table = Table::new("fk_without_index");
+        table.add_column(Column::new("id", INTEGER.into_type()?));
+        table.add_column(Column::new("parent_id", INTEGER.into_type()?));
+        table.set_primary_key(&["id"], None)?;
+        table.add_foreign_keys_raw(
+            [
+                ForeignKeyConstraint::new(
+                    &["parent_id"],
+                    &["id"],
+                    "parent",
+                    HashMap::default(),
+                    None,
+                    None,
+                ),
+                ForeignKeyConstraint::new(
+                    &["id"],
+                    &["id"],
+                    "other",
+                    HashMap::default(),
+                    None,
+                    None,
+                ),
+            ]
+            .into_iter(),
+        );
+
+        let foreign_keys = table.foreign_keys_without_index();
+        assert_eq!(foreign_keys.len(), 1);
+        assert_eq!(
+            foreign_keys[0].get_unquoted_local_columns(),
+            vec!["parent_id"]
+        );
+
+        table.add_missing_foreign_key_indexes()?;
+        assert!(table.foreign_keys_without_index().is_empty());
+        assert!(table.missing_foreign_key_indexes()?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    pub fn column_usages_are_reported() -> Result<()> {
+        let mut table = Table::new("column_usages");
+        table.add_column(Column::new("id", INTEGER.into_type()?));
+        table.add_column(Column::new("parent_id", INTEGER.into_type()?));
+        table.add_column(Column::new("name", STRING.into_type()?));
+        table.set_primary_key(&["id"], None)?;
+        table.add_index(Index::new(
+            "idx_id_name",
+            &["id", "name"],
+            false,
+            false,
+            &[],
+            HashMap::default(),
+        ));
+        let mut foreign_key = ForeignKeyConstraint::new(
+            &["parent_id"],
+            &["id"],
+            "parent",
+            HashMap::default(),
+            None,
+            None,
+        );
+        foreign_key.set_name("fk_parent");
+        table.add_foreign_keys_raw([foreign_key].into_iter());
+        table.add_column(Column::new("code", STRING.into_type()?));
+        table.add_unique_constraint(UniqueConstraint::new(
+            "uniq_code",
+            &["code"],
+            &[],
+            HashMap::default(),
+        ));
+
+        let usages = table.column_usages("id");
+        assert!(usages.in_primary_key);
+        assert_eq!(usages.in_indexes, vec!["idx_id_name"]);
+        assert!(usages.in_unique_constraints.is_empty());
+        assert!(usages.in_foreign_keys.is_empty());
+
+        let usages = table.column_usages("parent_id");
+        assert!(!usages.in_primary_key);
+        assert!(usages.in_indexes.is_empty());
+        assert_eq!(usages.in_foreign_keys, vec!["fk_parent"]);
+
+        assert_eq!(table.column_usages("name").in_indexes, vec!["idx_id_name"]);
+
+        let usages = table.column_usages("code");
+        assert!(usages.is_used());
+        assert!(usages.in_indexes.is_empty());
+        assert_eq!(usages.in_unique_constraints, vec!["uniq_code"]);
+
+        assert!(!table.column_usages("unknown").is_used());
+
+        Ok(())
+    }
 }
@@ -5,15 +5,18 @@ use crate::schema::{Asset, Identifier, IntoIdentifier, Table};
 pub struct Sequence {
     asset: AbstractAsset,
     allocation_size: usize,
-    initial_value: usize,
+    initial_value: i64,
     cache: Option<usize>,
+    min_value: Option<i64>,
+    max_value: Option<i64>,
+    cycle: bool,
 }
 
 impl Sequence {
     pub fn new<
         S: AsRef<str>,
         AS: Into<Option<usize>>,
-        IV: Into<Option<usize>>,
+        IV: Into<Option<i64>>,
         C: Into<Option<usize>>,
     >(
         name: S,
@@ -29,6 +32,9 @@ impl Sequence {
             allocation_size: allocation_size.into().unwrap_or(1),
             initial_value: initial_value.into().unwrap_or(1),
             cache: cache.into(),
+            min_value: None,
+            max_value: None,
+            cycle: false,
         }
     }
 
@@ -36,7 +42,7 @@ impl Sequence {
         self.allocation_size
     }
 
-    pub fn get_initial_value(&self) -> usize {
+    pub fn get_initial_value(&self) -> i64 {
         self.initial_value
     }
 
@@ -52,7 +58,7 @@ impl Sequence {
         self.allocation_size = allocation_size;
     }
 
-    pub fn set_initial_value(&mut self, mut initial_value: usize) {
+    pub fn set_initial_value(&mut self, mut initial_value: i64) {
         if initial_value == 0 {
             initial_value = 1;
         }
@@ -64,6 +70,36 @@ impl Sequence {
         self.cache = cache;
     }
 
+    /// Gets the minimum value of the sequence, if set.
+    /// When not set, the initial value is used as minimum value on creation,
+    /// while the default minimum value is restored when the sequence is altered.
+    pub fn get_min_value(&self) -> Option<i64> {
+        self.min_value
+    }
+
+    pub fn set_min_value(&mut self, min_value: Option<i64>) {
+        self.min_value = min_value;
+    }
+
+    /// Gets the maximum value of the sequence, if set.
+    /// When not set, the maximum value of the sequence data type is used.
+    pub fn get_max_value(&self) -> Option<i64> {
+        self.max_value
+    }
+
+    pub fn set_max_value(&mut self, max_value: Option<i64>) {
+        self.max_value = max_value;
+    }
+
+    /// Whether the sequence wraps around when the maximum value is reached.
+    pub fn is_cycle(&self) -> bool {
+        self.cycle
+    }
+
+    pub fn set_cycle(&mut self, cycle: bool) {
+        self.cycle = cycle;
+    }
+
     /// Checks if this sequence is an autoincrement sequence for a given table.
     ///
     /// This is used inside the comparator to not report sequences as missing,
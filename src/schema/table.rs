@@ -1,8 +1,9 @@
-use crate::schema::asset::{generate_identifier_name, impl_asset, Asset};
+use crate::schema::asset::{generate_identifier_name, impl_asset, shorten_identifier_name, Asset};
 use crate::schema::schema_config::SchemaConfig;
 use crate::schema::{
-    Column, ColumnList, FKConstraintList, ForeignKeyConstraint, ForeignKeyReferentialAction,
-    Identifier, Index, IndexList, IntoIdentifier, NamedListIndex, UniqueConstraint,
+    Column, ColumnList, ColumnUsages, FKConstraintList, ForeignKeyConstraint,
+    ForeignKeyReferentialAction, Identifier, Index, IndexList, IntoIdentifier, NamedListIndex,
+    ReplicaIdentity, TableGrant, UniqueConstraint,
 };
 use crate::{Error, Result, Value};
 use itertools::Itertools;
@@ -26,6 +27,9 @@ pub struct TableOptions {
     pub row_format: Option<String>,
     pub table_options: Option<String>,
     pub partition_options: Option<String>,
+    pub owner: Option<String>,
+    pub grants: Vec<TableGrant>,
+    pub replica_identity: Option<ReplicaIdentity>,
     pub alter: bool,
 }
 
@@ -163,6 +167,9 @@ pub struct Table {
     row_format: Option<String>,
     table_options: Option<String>,
     partition_options: Option<String>,
+    owner: Option<String>,
+    grants: Vec<TableGrant>,
+    replica_identity: Option<ReplicaIdentity>,
     alter: bool,
     schema_config: SchemaConfig,
 }
@@ -184,6 +191,9 @@ impl Table {
             row_format: None,
             table_options: None,
             partition_options: None,
+            owner: None,
+            grants: vec![],
+            replica_identity: None,
             alter: false,
             schema_config: SchemaConfig::default(),
         }
@@ -205,6 +215,9 @@ impl Table {
             row_format: self.row_format.clone(),
             table_options: self.table_options.clone(),
             partition_options: self.partition_options.clone(),
+            owner: self.owner.clone(),
+            grants: self.grants.clone(),
+            replica_identity: self.replica_identity.clone(),
             alter: false,
             schema_config: self.schema_config.clone(),
         }
@@ -252,6 +265,54 @@ impl Table {
         self.columns.get_mut(name.into_identifier())
     }
 
+    /// Gets the indexes, unique constraints and foreign keys of this table referencing the given column.
+    pub fn column_usages<T: IntoIdentifier>(&self, name: T) -> ColumnUsages {
+        let name = name.into_identifier().get_name().to_lowercase();
+        let mut usages = ColumnUsages::default();
+
+        for index in self.indices.iter() {
+            if !index
+                .get_unquoted_columns()
+                .iter()
+                .any(|column| column.to_lowercase() == name)
+            {
+                continue;
+            }
+
+            if index.is_primary() {
+                usages.in_primary_key = true;
+            } else {
+                usages.in_indexes.push(index.get_name().into_owned());
+            }
+        }
+
+        for unique_constraint in self.unique_constraints.iter() {
+            if unique_constraint
+                .get_unquoted_columns()
+                .iter()
+                .any(|column| column.to_lowercase() == name)
+            {
+                usages
+                    .in_unique_constraints
+                    .push(unique_constraint.get_name().into_owned());
+            }
+        }
+
+        for foreign_key in self.foreign_keys.iter() {
+            if foreign_key
+                .get_unquoted_local_columns()
+                .iter()
+                .any(|column| column.to_lowercase() == name)
+            {
+                usages
+                    .in_foreign_keys
+                    .push(foreign_key.get_name().into_owned());
+            }
+        }
+
+        usages
+    }
+
     pub fn add_index<I: Into<Index>>(&mut self, index: I) {
         let mut index = index.into();
         if index.get_name().is_empty() {
@@ -262,6 +323,8 @@ impl Table {
                 "idx",
                 self.get_max_identifier_length(),
             ))
+        } else {
+            self.shorten_asset_name(&mut index);
         }
 
         self.indices.push(index);
@@ -427,10 +490,42 @@ impl Table {
         self.schema_config.max_identifier_length
     }
 
+    /// Shortens the name of the given asset if it exceeds the maximum identifier length,
+    /// as the database would otherwise silently truncate it.
+    /// Does nothing unless enabled through [`SchemaConfig::shorten_identifiers`].
+    fn shorten_asset_name<A: Asset>(&self, asset: &mut A) {
+        if !self.schema_config.shorten_identifiers {
+            return;
+        }
+
+        let max_length = self.get_max_identifier_length();
+        let name = asset.get_name();
+        if name.len() > max_length {
+            let name = shorten_identifier_name(&name, max_length);
+            asset.set_name(&name);
+        }
+    }
+
     pub fn get_unique_constraints(&self) -> &Vec<UniqueConstraint> {
         &self.unique_constraints
     }
 
+    pub fn add_unique_constraint(&mut self, mut unique_constraint: UniqueConstraint) {
+        if unique_constraint.get_name().is_empty() {
+            let mut columns = vec![self.get_name().into_owned()];
+            columns.extend(unique_constraint.get_unquoted_columns());
+            unique_constraint.set_name(&generate_identifier_name(
+                &columns,
+                "uniq",
+                self.get_max_identifier_length(),
+            ))
+        } else {
+            self.shorten_asset_name(&mut unique_constraint);
+        }
+
+        self.unique_constraints.push(unique_constraint);
+    }
+
     pub fn get_foreign_keys(&self) -> &FKConstraintList {
         &self.foreign_keys
     }
@@ -502,15 +597,11 @@ impl Table {
     }
 
     pub fn add_foreign_key<T: Into<ForeignKeyConstraint>>(&mut self, constraint: T) -> Result<()> {
-        let constraint = constraint.into();
+        let mut constraint = constraint.into();
+        self.shorten_asset_name(&mut constraint);
         let local_columns = constraint.get_local_columns().clone();
         self.foreign_keys.push(constraint);
 
-        let mut names = vec![self.get_name().into_owned()];
-        for local_column in &local_columns {
-            names.push(local_column.to_string());
-        }
-
         // Add an implicit index (creed-defined) on the foreign key
         // columns. If there is already a user-defined index that fulfills these
         // requirements drop the request. In the case of "new" calling
@@ -518,15 +609,7 @@ impl Table {
         // added indexes lead to duplicates. This creates computation overhead in
         // this case, however no duplicate indexes are ever added (based on
         // columns).
-        let index_name = generate_identifier_name(&names, "idx", self.get_max_identifier_length());
-        let index_candidate = self.create_index(
-            &local_columns,
-            &index_name,
-            false,
-            false,
-            vec![],
-            HashMap::default(),
-        )?;
+        let index_candidate = self.create_foreign_key_index(&local_columns)?;
 
         if !self
             .indices
@@ -539,6 +622,60 @@ impl Table {
         Ok(())
     }
 
+    /// Gets the foreign keys whose columns are not covered by an index,
+    /// that is an index starting with the foreign key columns.
+    pub fn foreign_keys_without_index(&self) -> Vec<&ForeignKeyConstraint> {
+        self.foreign_keys
+            .iter()
+            .filter(|fk| {
+                let local_columns = fk.get_unquoted_local_columns();
+                !self
+                    .indices
+                    .iter()
+                    .any(|index| index.starts_with_columns(&local_columns))
+            })
+            .collect()
+    }
+
+    /// Builds the indexes needed to cover the columns of the foreign keys without index.
+    pub fn missing_foreign_key_indexes(&self) -> Result<Vec<Index>> {
+        let mut indexes: Vec<Index> = vec![];
+        for foreign_key in self.foreign_keys_without_index() {
+            let index = self.create_foreign_key_index(foreign_key.get_local_columns())?;
+            if !indexes.iter().any(|i| index.is_fulfilled_by(i)) {
+                indexes.push(index);
+            }
+        }
+
+        Ok(indexes)
+    }
+
+    /// Adds an index on the columns of the foreign keys not covered by one.
+    pub fn add_missing_foreign_key_indexes(&mut self) -> Result<()> {
+        for index in self.missing_foreign_key_indexes()? {
+            self.add_index(index);
+        }
+
+        Ok(())
+    }
+
+    fn create_foreign_key_index(&self, local_columns: &[Identifier]) -> Result<Index> {
+        let mut names = vec![self.get_name().into_owned()];
+        for local_column in local_columns {
+            names.push(local_column.to_string());
+        }
+
+        let index_name = generate_identifier_name(&names, "idx", self.get_max_identifier_length());
+        self.create_index(
+            local_columns,
+            &index_name,
+            false,
+            false,
+            vec![],
+            HashMap::default(),
+        )
+    }
+
     pub fn add_foreign_keys<I: Into<ForeignKeyConstraint>, T: Iterator<Item = I>>(
         &mut self,
         constraints: T,
@@ -656,6 +793,33 @@ impl Table {
         self.partition_options = partition_options;
     }
 
+    /// Gets the role owning the table, set after the table creation.
+    pub fn get_owner(&self) -> Option<&str> {
+        self.owner.as_deref()
+    }
+
+    pub fn set_owner<S: AsRef<str>, I: Into<Option<S>>>(&mut self, owner: I) {
+        self.owner = owner.into().map(|s| s.as_ref().to_string());
+    }
+
+    /// Gets the privileges granted on the table after its creation.
+    pub fn get_grants(&self) -> &[TableGrant] {
+        &self.grants
+    }
+
+    pub fn add_grant(&mut self, grant: TableGrant) {
+        self.grants.push(grant);
+    }
+
+    /// Gets the replica identity of the table, if explicitly set or introspected.
+    pub fn get_replica_identity(&self) -> Option<&ReplicaIdentity> {
+        self.replica_identity.as_ref()
+    }
+
+    pub fn set_replica_identity<I: Into<Option<ReplicaIdentity>>>(&mut self, replica_identity: I) {
+        self.replica_identity = replica_identity.into();
+    }
+
     pub fn get_alter(&self) -> bool {
         self.alter
     }
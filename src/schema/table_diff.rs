@@ -1,4 +1,7 @@
-use crate::schema::{Asset, Column, ColumnDiff, ForeignKeyConstraint, Identifier, Index, Table};
+use crate::schema::{
+    Asset, Column, ColumnDiff, ForeignKeyConstraint, Identifier, Index, ReplicaIdentity,
+    SchemaChangeKind, Table,
+};
 
 #[derive(Clone)]
 pub struct TableDiff<'a>
@@ -26,6 +29,9 @@ where
     pub changed_foreign_keys: Vec<ForeignKeyConstraint>,
     pub removed_foreign_keys: Vec<ForeignKeyConstraint>,
 
+    /// The new replica identity of the table, if changed.
+    pub changed_replica_identity: Option<ReplicaIdentity>,
+
     pub from_table: Option<&'a Table>,
 }
 
@@ -48,6 +54,7 @@ where
             added_foreign_keys: vec![],
             changed_foreign_keys: vec![],
             removed_foreign_keys: vec![],
+            changed_replica_identity: None,
             from_table: from_table.into(),
         }
     }
@@ -64,6 +71,50 @@ where
         self.new_name.as_ref().map(|t| Identifier::new(t, false))
     }
 
+    /// Whether the diff contains no change at all.
+    pub fn is_empty(&self) -> bool {
+        self.new_name.is_none()
+            && self.added_columns.is_empty()
+            && self.changed_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.renamed_columns.is_empty()
+            && self.added_indexes.is_empty()
+            && self.changed_indexes.is_empty()
+            && self.removed_indexes.is_empty()
+            && self.renamed_indexes.is_empty()
+            && self.added_foreign_keys.is_empty()
+            && self.changed_foreign_keys.is_empty()
+            && self.removed_foreign_keys.is_empty()
+            && self.changed_replica_identity.is_none()
+    }
+
+    /// Reduces the diff to the changes of the given kinds.
+    pub fn filter(mut self, kinds: &[SchemaChangeKind]) -> Self {
+        if !kinds.contains(&SchemaChangeKind::Addition) {
+            self.added_columns.clear();
+            self.added_indexes.clear();
+            self.added_foreign_keys.clear();
+        }
+
+        if !kinds.contains(&SchemaChangeKind::Change) {
+            self.new_name = None;
+            self.changed_columns.clear();
+            self.renamed_columns.clear();
+            self.changed_indexes.clear();
+            self.renamed_indexes.clear();
+            self.changed_foreign_keys.clear();
+            self.changed_replica_identity = None;
+        }
+
+        if !kinds.contains(&SchemaChangeKind::Removal) {
+            self.removed_columns.clear();
+            self.removed_indexes.clear();
+            self.removed_foreign_keys.clear();
+        }
+
+        self
+    }
+
     pub fn get_added_column(&self, column_name: &str) -> Option<&Column> {
         self.added_columns
             .iter()
@@ -0,0 +1,29 @@
+/// A set of privileges granted on a table to a role (or user).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TableGrant {
+    privileges: Vec<String>,
+    grantee: String,
+}
+
+impl TableGrant {
+    /// Creates a new grant.
+    /// The grantee can be a role name, `PUBLIC` or a MySQL account name
+    /// (ie: `user@%`), and is quoted when generating the SQL.
+    pub fn new<P: AsRef<str>, S: AsRef<str>>(privileges: &[P], grantee: S) -> Self {
+        Self {
+            privileges: privileges
+                .iter()
+                .map(|p| p.as_ref().to_uppercase())
+                .collect(),
+            grantee: grantee.as_ref().to_string(),
+        }
+    }
+
+    pub fn get_privileges(&self) -> &[String] {
+        &self.privileges
+    }
+
+    pub fn get_grantee(&self) -> &str {
+        &self.grantee
+    }
+}
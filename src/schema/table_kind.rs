@@ -0,0 +1,12 @@
+/// The kind of a relation stored in the database.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TableKind {
+    /// A regular (base) table.
+    Base,
+    /// A view.
+    View,
+    /// A materialized view (ie: PostgreSQL's MATERIALIZED VIEW).
+    MaterializedView,
+    /// A foreign table, whose data is stored in another server.
+    Foreign,
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+
+/// Creation and last update times of a table, where tracked by the platform.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TableTimestamps {
+    created: Option<DateTime<Utc>>,
+    updated: Option<DateTime<Utc>>,
+}
+
+impl TableTimestamps {
+    pub fn new(created: Option<DateTime<Utc>>, updated: Option<DateTime<Utc>>) -> Self {
+        Self { created, updated }
+    }
+
+    /// Gets the time the table has been created at.
+    pub fn get_created(&self) -> Option<DateTime<Utc>> {
+        self.created
+    }
+
+    /// Gets the time the table data has been last modified at.
+    /// Could be None if the table has not been modified since the server start.
+    pub fn get_updated(&self) -> Option<DateTime<Utc>> {
+        self.updated
+    }
+}
@@ -10,6 +10,7 @@ pub struct UniqueConstraint {
     columns: HashMap<String, Identifier>,
     flags: Vec<String>,
     options: HashMap<String, Value>,
+    nulls_not_distinct: bool,
 }
 
 impl UniqueConstraint {
@@ -27,6 +28,7 @@ impl UniqueConstraint {
             columns: HashMap::new(),
             flags: vec![],
             options,
+            nulls_not_distinct: false,
         };
 
         for column in columns {
@@ -99,6 +101,16 @@ impl UniqueConstraint {
         self.options.values().cloned().collect()
     }
 
+    /// Whether null values are considered equal by the constraint,
+    /// rejecting more than one null value (PostgreSQL 15+ only).
+    pub fn is_nulls_not_distinct(&self) -> bool {
+        self.nulls_not_distinct
+    }
+
+    pub fn set_nulls_not_distinct(&mut self, nulls_not_distinct: bool) {
+        self.nulls_not_distinct = nulls_not_distinct;
+    }
+
     /// Adds a new column to the unique constraint.
     fn add_column<I: IntoIdentifier>(&mut self, column: &I) {
         let _ = self
@@ -1,11 +1,16 @@
 use crate::driver::connection::Connection as ConnectionTrait;
 use crate::driver::statement::Statement;
+use crate::driver::statement_result::StatementResult;
 use crate::platform::DatabasePlatform;
 use crate::tests::MockPlatform;
-use crate::{Async, Connection, EventDispatcher, Result};
+use crate::{
+    Async, AsyncResult, Connection, EventDispatcher, Parameter, ParameterIndex, Parameters, Result,
+    Rows, Value,
+};
 use std::fmt::{Debug, Formatter};
 use std::future::Future;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 pub fn get_database_dsn() -> String {
     std::env::var("DATABASE_DSN").unwrap()
@@ -43,3 +48,96 @@ impl<'conn> ConnectionTrait<'conn> for MockConnection {
         unreachable!()
     }
 }
+
+/// The statements executed on a mock connection, with their parameter values.
+pub type ExecutedStatements = Arc<Mutex<Vec<(String, Vec<Value>)>>>;
+
+/// A mock connection counting the prepared statements
+/// and recording the executed ones.
+#[derive(Debug, Default)]
+pub struct MockPreparingConnection {
+    pub prepared: Arc<AtomicUsize>,
+    pub executed: ExecutedStatements,
+}
+
+impl<'conn> ConnectionTrait<'conn> for MockPreparingConnection {
+    fn create_platform(
+        &self,
+        ev: Arc<EventDispatcher>,
+    ) -> Async<Box<dyn DatabasePlatform + Send + Sync>> {
+        Box::pin(
+            async move { Box::new(MockPlatform { ev }) as Box<dyn DatabasePlatform + Send + Sync> },
+        )
+    }
+
+    fn server_version(&self) -> Async<Option<String>> {
+        Box::pin(async { None })
+    }
+
+    fn prepare(&'conn self, sql: &str) -> crate::Result<Box<dyn Statement + 'conn>> {
+        self.prepared.fetch_add(1, Ordering::SeqCst);
+        Ok(Box::new(MockStatement {
+            sql: sql.to_string(),
+            executed: self.executed.clone(),
+            ..MockStatement::default()
+        }))
+    }
+}
+
+/// A mock statement counting and recording its executions.
+#[derive(Debug, Default)]
+pub struct MockStatement {
+    pub sql: String,
+    pub executions: AtomicUsize,
+    pub executed: ExecutedStatements,
+}
+
+impl<'conn> Statement<'conn> for MockStatement {
+    fn bind_value(&self, _: ParameterIndex, _: Parameter) -> Result<()> {
+        Ok(())
+    }
+
+    /// Returns an empty result set.
+    fn query(&self, _: Parameters) -> AsyncResult<StatementResult> {
+        Box::pin(async move {
+            Ok(StatementResult::new(Rows::new(
+                vec![],
+                0,
+                None,
+                Box::pin(futures::stream::empty()),
+            )))
+        })
+    }
+
+    fn query_owned(
+        self: Box<Self>,
+        params: Vec<(ParameterIndex, Parameter)>,
+    ) -> AsyncResult<'conn, StatementResult> {
+        Box::pin(async move { self.query(Parameters::Vec(params)).await })
+    }
+
+    fn execute(&self, params: Parameters) -> AsyncResult<usize> {
+        let values = Vec::from(params)
+            .into_iter()
+            .map(|(_, param)| param.value)
+            .collect();
+        self.executed
+            .lock()
+            .unwrap()
+            .push((self.sql.clone(), values));
+
+        let executions = self.executions.fetch_add(1, Ordering::SeqCst) + 1;
+        Box::pin(async move { Ok(executions) })
+    }
+
+    fn execute_owned(
+        self: Box<Self>,
+        params: Vec<(ParameterIndex, Parameter)>,
+    ) -> AsyncResult<'conn, usize> {
+        Box::pin(async move { self.execute(Parameters::Vec(params)).await })
+    }
+
+    fn row_count(&self) -> usize {
+        self.executions.load(Ordering::SeqCst)
+    }
+}
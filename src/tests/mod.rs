@@ -3,7 +3,9 @@ mod functional_tests_helper;
 mod platform;
 mod schema_manager;
 
-pub use connection::{create_connection, get_database_dsn, MockConnection};
+pub use connection::{
+    create_connection, get_database_dsn, MockConnection, MockPreparingConnection, MockStatement,
+};
 pub use functional_tests_helper::FunctionalTestsHelper;
 pub(crate) use platform::{common_platform_tests, MockPlatform};
 pub(crate) use schema_manager::MockSchemaManager;
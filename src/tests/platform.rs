@@ -71,7 +71,7 @@ impl DatabasePlatform for MockPlatform {
     }
 
     fn get_name(&self) -> String {
-        todo!()
+        "mock".to_string()
     }
 
     fn get_type_mapping(&self, _: &str) -> Result<TypeId> {
@@ -86,6 +86,10 @@ impl DatabasePlatform for MockPlatform {
         KeywordList::new(&MOCK_KEYWORDS)
     }
 
+    fn get_feature_probe_sql(&self, feature: &str) -> Result<String> {
+        Ok(format!("SELECT '{}'", feature))
+    }
+
     fn create_schema_manager<'a>(&self, connection: &'a Connection) -> Box<dyn SchemaManager + 'a> {
         Box::new(MockSchemaManager::new(connection))
     }
@@ -150,6 +154,22 @@ pub macro common_platform_tests($ex:expr) {
         );
     }
 
+    #[test]
+    pub fn get_default_value_declaration_sql_escapes_quotes() {
+        use $crate::r#type::IntoType;
+        let platform = $ex;
+        let mut column =
+            $crate::schema::Column::new("foo", $crate::r#type::STRING.into_type().unwrap());
+        column.set_default("O'Brien".into());
+
+        assert_eq!(
+            platform
+                .get_default_value_declaration_sql(&column.generate_column_data(&platform))
+                .unwrap(),
+            " DEFAULT 'O''Brien'"
+        );
+    }
+
     #[test]
     pub fn get_default_value_declaration_sql_date_time() {
         use $crate::r#type::IntoType;
@@ -0,0 +1,119 @@
+use crate::platform::DatabasePlatform;
+use crate::r#type::Type;
+use crate::schema::ColumnData;
+use crate::{Error, ParameterType, Result, Value};
+use std::marker::PhantomData;
+
+/// A flag of a set stored as an integer bitmask.
+///
+/// Can be implemented deriving `BitFlags` on an enum whose variants
+/// have explicit bit values.
+pub trait BitFlag: Sized + Copy + PartialEq + Send + Sync + 'static {
+    /// The name of the type registered in the type manager.
+    const TYPE_NAME: &'static str;
+
+    /// Gets the bit value of this flag.
+    fn bits(&self) -> i64;
+
+    /// Gets all the flags of the set.
+    fn all() -> &'static [Self];
+
+    /// Combines the given flags into an integer bitmask.
+    fn to_bits(flags: &[Self]) -> i64 {
+        flags.iter().fold(0, |mask, flag| mask | flag.bits())
+    }
+
+    /// Splits an integer bitmask into its flags.
+    /// An error is returned if the mask contains bits not mapped to any flag.
+    fn from_bits(bits: i64) -> Result<Vec<Self>> {
+        let flags = Self::all()
+            .iter()
+            .filter(|flag| bits & flag.bits() == flag.bits())
+            .copied()
+            .collect::<Vec<_>>();
+
+        if Self::to_bits(&flags) != bits {
+            return Err(Error::conversion_failed_invalid_type(
+                &Value::Int(bits),
+                Self::TYPE_NAME,
+                &["Bitmask of known flags"],
+            ));
+        }
+
+        Ok(flags)
+    }
+}
+
+/// Type for flag sets, stored as integer bitmasks.
+///
+/// Values are represented as integers: use [`BitFlagsType::to_value`] and
+/// [`BitFlagsType::to_flags`] to convert them from/to a list of flags.
+/// The type must be registered in the type manager before use.
+pub struct BitFlagsType<F: BitFlag> {
+    _flags: PhantomData<fn() -> F>,
+}
+
+impl<F: BitFlag> BitFlagsType<F> {
+    /// Converts the given flags into an integer value.
+    pub fn to_value(flags: &[F]) -> Value {
+        Value::Int(F::to_bits(flags))
+    }
+
+    /// Converts an integer value into the flags it contains.
+    pub fn to_flags(value: &Value) -> Result<Vec<F>> {
+        match value {
+            Value::NULL => Ok(vec![]),
+            Value::Int(bits) => F::from_bits(*bits),
+            Value::UInt(bits) => F::from_bits(i64::try_from(*bits)?),
+            Value::String(bits) => F::from_bits(bits.parse()?),
+            _ => Err(Error::conversion_failed_invalid_type(
+                value,
+                F::TYPE_NAME,
+                &["NULL", "Integer"],
+            )),
+        }
+    }
+
+    fn validate(&self, value: &Value) -> Result<Value> {
+        match value {
+            Value::NULL => Ok(Value::NULL),
+            _ => Ok(Self::to_value(&Self::to_flags(value)?)),
+        }
+    }
+}
+
+impl<F: BitFlag> Type for BitFlagsType<F> {
+    fn default() -> Box<dyn Type + Sync + Send> {
+        Box::new(BitFlagsType::<F> {
+            _flags: PhantomData,
+        })
+    }
+
+    fn convert_to_database_value(&self, value: Value, _: &dyn DatabasePlatform) -> Result<Value> {
+        self.validate(&value)
+    }
+
+    fn convert_to_value(&self, value: &Value, _: &dyn DatabasePlatform) -> Result<Value> {
+        self.validate(value)
+    }
+
+    fn get_name(&self) -> &'static str {
+        F::TYPE_NAME
+    }
+
+    fn requires_sql_comment_hint(&self, _: &dyn DatabasePlatform) -> bool {
+        true
+    }
+
+    fn get_sql_declaration(
+        &self,
+        column: &ColumnData,
+        platform: &dyn DatabasePlatform,
+    ) -> Result<String> {
+        platform.get_bigint_type_declaration_sql(column)
+    }
+
+    fn get_binding_type(&self) -> ParameterType {
+        ParameterType::Integer
+    }
+}
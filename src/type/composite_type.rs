@@ -0,0 +1,135 @@
+use crate::platform::DatabasePlatform;
+use crate::r#type::Type;
+use crate::schema::ColumnData;
+use crate::{Error, Result, Value};
+use itertools::Itertools;
+
+/// Type for columns backed by a user-defined composite type.
+///
+/// The composite type itself must be set on the column through
+/// [`Column::set_composite_type`](crate::schema::Column::set_composite_type).
+/// Values are represented as arrays holding the attribute values in declaration order.
+pub struct CompositeType {}
+
+impl CompositeType {
+    fn parse_record_literal(&self, literal: &str) -> Result<Vec<Value>> {
+        let inner = literal
+            .trim()
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or_else(|| {
+                Error::conversion_failed_invalid_type(
+                    &Value::from(literal),
+                    self.get_name(),
+                    &["Record literal"],
+                )
+            })?;
+
+        let mut values = vec![];
+        let mut current = String::new();
+        let mut quoted = false;
+        let mut in_quotes = false;
+        let mut chars = inner.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if in_quotes && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    current.push('"');
+                }
+                '"' => {
+                    in_quotes = !in_quotes;
+                    quoted = true;
+                }
+                '\\' if in_quotes => {
+                    if let Some(next) = chars.next() {
+                        current.push(next);
+                    }
+                }
+                ',' if !in_quotes => {
+                    values.push(Self::record_field(&mut current, &mut quoted));
+                }
+                c => current.push(c),
+            }
+        }
+
+        values.push(Self::record_field(&mut current, &mut quoted));
+
+        Ok(values)
+    }
+
+    fn record_field(current: &mut String, quoted: &mut bool) -> Value {
+        let value = if current.is_empty() && !*quoted {
+            Value::NULL
+        } else {
+            Value::String(current.clone())
+        };
+
+        current.clear();
+        *quoted = false;
+
+        value
+    }
+}
+
+impl Type for CompositeType {
+    fn default() -> Box<dyn Type + Sync + Send> {
+        Box::new(CompositeType {})
+    }
+
+    fn convert_to_database_value(&self, value: Value, _: &dyn DatabasePlatform) -> Result<Value> {
+        match value {
+            Value::NULL => Ok(value),
+            Value::Array(values) => Ok(Value::String(format!(
+                "({})",
+                values
+                    .iter()
+                    .map(|v| match v {
+                        Value::NULL => String::new(),
+                        v => {
+                            let v = v.to_string();
+                            if v.is_empty()
+                                || v.contains(|c: char| {
+                                    c.is_whitespace() || matches!(c, ',' | '(' | ')' | '"' | '\\')
+                                })
+                            {
+                                format!("\"{}\"", v.replace('\\', "\\\\").replace('"', "\"\""))
+                            } else {
+                                v
+                            }
+                        }
+                    })
+                    .join(",")
+            ))),
+            _ => Err(Error::conversion_failed_invalid_type(
+                &value,
+                self.get_name(),
+                &["NULL", "Array"],
+            )),
+        }
+    }
+
+    fn convert_to_value(&self, value: &Value, _: &dyn DatabasePlatform) -> Result<Value> {
+        match value {
+            Value::NULL | Value::Array(_) => Ok(value.clone()),
+            Value::String(literal) => Ok(Value::Array(self.parse_record_literal(literal)?)),
+            _ => Err(Error::conversion_failed_invalid_type(
+                value,
+                self.get_name(),
+                &["NULL", "Array", "Record literal"],
+            )),
+        }
+    }
+
+    fn get_name(&self) -> &'static str {
+        super::COMPOSITE
+    }
+
+    fn get_sql_declaration(
+        &self,
+        column: &ColumnData,
+        platform: &dyn DatabasePlatform,
+    ) -> Result<String> {
+        platform.get_composite_type_declaration_sql(column)
+    }
+}
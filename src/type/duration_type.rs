@@ -0,0 +1,142 @@
+use crate::error::ErrorKind;
+use crate::platform::DatabasePlatform;
+use crate::r#type::Type;
+use crate::schema::ColumnData;
+use crate::{Error, Value};
+use crate::{ParameterType, Result};
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Type for durations, stored as INTERVAL where natively supported
+/// and as a number of seconds (BIGINT) elsewhere.
+///
+/// Values are represented as integer seconds and can be converted from/to
+/// `chrono::Duration` and `std::time::Duration`.
+pub struct DurationType {}
+
+impl DurationType {
+    /// Parses an interval as output by PostgreSQL (ie: `3 days 01:30:00`) or a plain number of seconds.
+    /// Intervals which cannot be represented as a whole number of seconds (ie: months or years,
+    /// which have no fixed length, and fractional seconds) are rejected.
+    fn parse_interval(&self, interval: &str) -> Result<i64> {
+        let invalid = || {
+            Error::conversion_failed_invalid_type(
+                &Value::from(interval),
+                self.get_name(),
+                &["Seconds", "Interval String"],
+            )
+        };
+        let not_representable = |reason: &str| {
+            Error::new(
+                ErrorKind::ConversionFailed,
+                format!(
+                    "Interval \"{}\" cannot be converted to a duration: {}",
+                    interval, reason
+                ),
+            )
+        };
+        let parse_integer = |number: &str| -> Result<i64> {
+            match number.split_once('.') {
+                Some((_, fraction)) if fraction.chars().any(|c| c != '0') => {
+                    Err(not_representable("fractional seconds are not supported"))
+                }
+                Some((integer, _)) => integer.parse::<i64>().map_err(|_| invalid()),
+                None => number.parse::<i64>().map_err(|_| invalid()),
+            }
+        };
+
+        let mut seconds = 0_i64;
+        let mut tokens = interval.split_whitespace();
+        while let Some(token) = tokens.next() {
+            if token.contains(':') {
+                let (negative, time) = match token.strip_prefix('-') {
+                    Some(time) => (true, time),
+                    None => (false, token.strip_prefix('+').unwrap_or(token)),
+                };
+
+                let mut time_seconds = 0_i64;
+                for part in time.split(':') {
+                    time_seconds = time_seconds * 60 + parse_integer(part)?;
+                }
+
+                seconds += if negative {
+                    -time_seconds
+                } else {
+                    time_seconds
+                };
+                continue;
+            }
+
+            let amount = parse_integer(token)?;
+            let multiplier = match tokens.next() {
+                None => 1,
+                Some(unit) => match unit.trim_end_matches('s') {
+                    "year" | "mon" | "month" => {
+                        return Err(not_representable("months have no fixed length"))
+                    }
+                    "day" => SECONDS_PER_DAY,
+                    "hour" => 3600,
+                    "min" | "minute" => 60,
+                    "sec" | "second" => 1,
+                    _ => return Err(invalid()),
+                },
+            };
+
+            seconds += amount * multiplier;
+        }
+
+        Ok(seconds)
+    }
+}
+
+impl Type for DurationType {
+    fn default() -> Box<dyn Type + Sync + Send> {
+        Box::new(DurationType {})
+    }
+
+    fn convert_to_database_value(&self, value: Value, _: &dyn DatabasePlatform) -> Result<Value> {
+        match value {
+            Value::NULL | Value::Int(_) => Ok(value),
+            Value::UInt(seconds) => Ok(Value::Int(i64::try_from(seconds)?)),
+            Value::String(interval) => Ok(Value::Int(self.parse_interval(&interval)?)),
+            _ => Err(Error::conversion_failed_invalid_type(
+                &value,
+                self.get_name(),
+                &["NULL", "Integer", "Interval String"],
+            )),
+        }
+    }
+
+    fn convert_to_value(&self, value: &Value, _: &dyn DatabasePlatform) -> Result<Value> {
+        match value {
+            Value::NULL | Value::Int(_) => Ok(value.clone()),
+            Value::UInt(seconds) => Ok(Value::Int(i64::try_from(*seconds)?)),
+            Value::String(interval) => Ok(Value::Int(self.parse_interval(interval)?)),
+            _ => Err(Error::conversion_failed_invalid_type(
+                value,
+                self.get_name(),
+                &["NULL", "Integer", "Interval String"],
+            )),
+        }
+    }
+
+    fn get_name(&self) -> &'static str {
+        super::DURATION
+    }
+
+    fn requires_sql_comment_hint(&self, platform: &dyn DatabasePlatform) -> bool {
+        !platform.has_native_interval_type()
+    }
+
+    fn get_sql_declaration(
+        &self,
+        column: &ColumnData,
+        platform: &dyn DatabasePlatform,
+    ) -> Result<String> {
+        platform.get_duration_type_declaration_sql(column)
+    }
+
+    fn get_binding_type(&self) -> ParameterType {
+        ParameterType::Integer
+    }
+}
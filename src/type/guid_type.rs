@@ -26,9 +26,16 @@ impl Type for GuidType {
         }
     }
 
-    fn convert_to_database_value(&self, value: Value, _: &dyn DatabasePlatform) -> Result<Value> {
+    /// UUID values are bound as-is on platforms with a native guid type.
+    fn convert_to_database_value(
+        &self,
+        value: Value,
+        platform: &dyn DatabasePlatform,
+    ) -> Result<Value> {
         match value {
             Value::NULL | Value::String(_) => Ok(value),
+            Value::Uuid(_) if platform.has_native_guid_type() => Ok(value),
+            Value::Uuid(v) => Ok(Value::String(v.to_string())),
             _ => Err(Error::conversion_failed_invalid_type(
                 &value,
                 self.get_name(),
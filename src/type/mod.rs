@@ -1,15 +1,19 @@
 mod bigint_type;
 mod binary_type;
+mod bit_flags_type;
 mod blob_type;
 mod boolean_type;
+mod composite_type;
 mod date_type;
 mod datetime_type;
 mod datetime_tz_type;
 mod decimal_type;
+mod duration_type;
 mod float_type;
 mod guid_type;
 mod integer_type;
 mod json_type;
+mod range_type;
 mod simple_array_type;
 mod string_type;
 mod text_type;
@@ -21,19 +25,23 @@ use crate::schema::ColumnData;
 use crate::{Error, ParameterType, Result, Value};
 pub use bigint_type::BigintType;
 pub use binary_type::BinaryType;
+pub use bit_flags_type::{BitFlag, BitFlagsType};
 pub use blob_type::BlobType;
 pub use boolean_type::BooleanType;
+pub use composite_type::CompositeType;
 use dashmap::DashMap;
 pub use date_type::DateType;
 pub use datetime_type::DateTimeType;
 pub use datetime_tz_type::DateTimeTzType;
 pub use decimal_type::DecimalType;
 use delegate::delegate;
+pub use duration_type::DurationType;
 pub use float_type::FloatType;
 pub use guid_type::GuidType;
 pub use integer_type::IntegerType;
 pub use json_type::JsonType;
 use lazy_static::lazy_static;
+pub use range_type::{DateTimeTzRangeType, IntegerRangeType};
 pub use simple_array_type::SimpleArrayType;
 use std::any::{type_name, TypeId};
 use std::fmt::{Debug, Formatter};
@@ -46,13 +54,17 @@ pub const BIGINT: &str = "bigint";
 pub const BINARY: &str = "binary";
 pub const BLOB: &str = "blob";
 pub const BOOLEAN: &str = "boolean";
+pub const COMPOSITE: &str = "composite";
 pub const DATE: &str = "date";
 pub const DATETIME: &str = "datetime";
 pub const DATETIMETZ: &str = "datetimetz";
+pub const DATETIMETZ_RANGE: &str = "datetimetz_range";
 pub const DECIMAL: &str = "decimal";
+pub const DURATION: &str = "duration";
 pub const FLOAT: &str = "float";
 pub const GUID: &str = "guid";
 pub const INTEGER: &str = "integer";
+pub const INTEGER_RANGE: &str = "integer_range";
 pub const JSON: &str = "json";
 pub const SIMPLE_ARRAY: &str = "simple_array";
 pub const STRING: &str = "string";
@@ -261,16 +273,29 @@ impl TypeManager {
         type_map.insert(TypeId::of::<BinaryType>(), TypePtr::new::<BinaryType>());
         type_map.insert(TypeId::of::<BlobType>(), TypePtr::new::<BlobType>());
         type_map.insert(TypeId::of::<BooleanType>(), TypePtr::new::<BooleanType>());
+        type_map.insert(
+            TypeId::of::<CompositeType>(),
+            TypePtr::new::<CompositeType>(),
+        );
         type_map.insert(TypeId::of::<DateType>(), TypePtr::new::<DateType>());
         type_map.insert(TypeId::of::<DateTimeType>(), TypePtr::new::<DateTimeType>());
         type_map.insert(
             TypeId::of::<DateTimeTzType>(),
             TypePtr::new::<DateTimeTzType>(),
         );
+        type_map.insert(
+            TypeId::of::<DateTimeTzRangeType>(),
+            TypePtr::new::<DateTimeTzRangeType>(),
+        );
         type_map.insert(TypeId::of::<DecimalType>(), TypePtr::new::<DecimalType>());
+        type_map.insert(TypeId::of::<DurationType>(), TypePtr::new::<DurationType>());
         type_map.insert(TypeId::of::<FloatType>(), TypePtr::new::<FloatType>());
         type_map.insert(TypeId::of::<GuidType>(), TypePtr::new::<GuidType>());
         type_map.insert(TypeId::of::<IntegerType>(), TypePtr::new::<IntegerType>());
+        type_map.insert(
+            TypeId::of::<IntegerRangeType>(),
+            TypePtr::new::<IntegerRangeType>(),
+        );
         type_map.insert(TypeId::of::<JsonType>(), TypePtr::new::<JsonType>());
         type_map.insert(
             TypeId::of::<SimpleArrayType>(),
@@ -0,0 +1,238 @@
+use crate::platform::DatabasePlatform;
+use crate::r#type::Type;
+use crate::schema::ColumnData;
+use crate::{Error, Range, Result, Value};
+use chrono::{DateTime, Local};
+use std::ops::Bound;
+
+const DATE_TIME_TZ_BOUND_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f%:z";
+const DATE_TIME_TZ_BOUND_PARSE_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f%#z";
+
+/// Parses a range literal in the bound notation (ie: `[1,10)`, `(,5]` or `empty`),
+/// converting the bound values with the given function.
+fn parse_range_literal<F: Fn(&str) -> Result<Value>>(
+    type_name: &'static str,
+    literal: &str,
+    parse_bound: F,
+) -> Result<Range> {
+    let invalid = || {
+        Error::conversion_failed_invalid_type(&Value::from(literal), type_name, &["Range literal"])
+    };
+
+    let literal = literal.trim();
+    if literal.eq_ignore_ascii_case("empty") {
+        return Ok(Range::Empty);
+    }
+
+    let lower_inclusive = match literal.chars().next() {
+        Some('[') => true,
+        Some('(') => false,
+        _ => return Err(invalid()),
+    };
+    let upper_inclusive = match literal.chars().last() {
+        Some(']') => true,
+        Some(')') => false,
+        _ => return Err(invalid()),
+    };
+
+    let inner = &literal[1..literal.len() - 1];
+    let mut bounds = vec![];
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_quotes = false;
+    let mut chars = inner.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                current.push('"');
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = true;
+            }
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            ',' if !in_quotes => {
+                bounds.push((!current.is_empty() || quoted).then(|| current.clone()));
+                current.clear();
+                quoted = false;
+            }
+            c => current.push(c),
+        }
+    }
+
+    bounds.push((!current.is_empty() || quoted).then_some(current));
+    let [lower, upper]: [Option<String>; 2] = bounds.try_into().map_err(|_| invalid())?;
+
+    let to_bound = |value: Option<String>, inclusive: bool| -> Result<Bound<Value>> {
+        Ok(match value {
+            None => Bound::Unbounded,
+            Some(value) if inclusive => Bound::Included(parse_bound(value.trim())?),
+            Some(value) => Bound::Excluded(parse_bound(value.trim())?),
+        })
+    };
+
+    Ok(Range::new(
+        to_bound(lower, lower_inclusive)?,
+        to_bound(upper, upper_inclusive)?,
+    ))
+}
+
+/// Type for ranges of integers, stored as INT4RANGE.
+///
+/// Values are represented as [`Range`] holding integer bounds.
+pub struct IntegerRangeType {}
+
+impl IntegerRangeType {
+    fn parse_bound(&self, bound: &str) -> Result<Value> {
+        Ok(Value::Int(bound.parse()?))
+    }
+
+    fn convert_bound(&self, bound: &Value) -> Result<Value> {
+        match bound {
+            Value::Int(_) => Ok(bound.clone()),
+            Value::UInt(value) => Ok(Value::Int(i64::try_from(*value)?)),
+            Value::String(value) => self.parse_bound(value),
+            _ => Err(Error::conversion_failed_invalid_type(
+                bound,
+                self.get_name(),
+                &["Integer"],
+            )),
+        }
+    }
+}
+
+impl Type for IntegerRangeType {
+    fn default() -> Box<dyn Type + Sync + Send> {
+        Box::new(IntegerRangeType {})
+    }
+
+    fn convert_to_database_value(
+        &self,
+        value: Value,
+        platform: &dyn DatabasePlatform,
+    ) -> Result<Value> {
+        match self.convert_to_value(&value, platform)? {
+            Value::Range(range) => Ok(Value::String(range.to_string())),
+            value => Ok(value),
+        }
+    }
+
+    fn convert_to_value(&self, value: &Value, _: &dyn DatabasePlatform) -> Result<Value> {
+        match value {
+            Value::NULL => Ok(Value::NULL),
+            Value::Range(range) => Ok(range.try_map(|v| self.convert_bound(v))?.into()),
+            Value::String(literal) => {
+                Ok(parse_range_literal(self.get_name(), literal, |v| self.parse_bound(v))?.into())
+            }
+            _ => Err(Error::conversion_failed_invalid_type(
+                value,
+                self.get_name(),
+                &["NULL", "Range", "Range literal"],
+            )),
+        }
+    }
+
+    fn get_name(&self) -> &'static str {
+        super::INTEGER_RANGE
+    }
+
+    fn get_sql_declaration(
+        &self,
+        column: &ColumnData,
+        platform: &dyn DatabasePlatform,
+    ) -> Result<String> {
+        platform.get_integer_range_type_declaration_sql(column)
+    }
+}
+
+/// Type for ranges of timestamps with time zone, stored as TSTZRANGE.
+///
+/// Values are represented as [`Range`] holding date-time bounds.
+pub struct DateTimeTzRangeType {}
+
+impl DateTimeTzRangeType {
+    fn parse_bound(&self, bound: &str) -> Result<Value> {
+        DateTime::parse_from_str(bound, DATE_TIME_TZ_BOUND_PARSE_FORMAT)
+            .or_else(|_| DateTime::parse_from_rfc3339(bound))
+            .map(|dt| Value::DateTime(dt.with_timezone(&Local)))
+            .map_err(|_| {
+                Error::conversion_failed_invalid_type(
+                    &Value::from(bound),
+                    self.get_name(),
+                    &["DateTime String"],
+                )
+            })
+    }
+
+    fn convert_bound(&self, bound: &Value) -> Result<Value> {
+        match bound {
+            Value::DateTime(_) => Ok(bound.clone()),
+            Value::String(value) => self.parse_bound(value),
+            _ => Err(Error::conversion_failed_invalid_type(
+                bound,
+                self.get_name(),
+                &["DateTime"],
+            )),
+        }
+    }
+}
+
+impl Type for DateTimeTzRangeType {
+    fn default() -> Box<dyn Type + Sync + Send> {
+        Box::new(DateTimeTzRangeType {})
+    }
+
+    fn convert_to_database_value(
+        &self,
+        value: Value,
+        platform: &dyn DatabasePlatform,
+    ) -> Result<Value> {
+        match self.convert_to_value(&value, platform)? {
+            Value::Range(range) => {
+                // Bounds are formatted with their offset to not depend on the session time zone.
+                let range = range.try_map(|v| match v {
+                    Value::DateTime(dt) => Ok(Value::String(
+                        dt.format(DATE_TIME_TZ_BOUND_FORMAT).to_string(),
+                    )),
+                    v => Ok(v.clone()),
+                })?;
+
+                Ok(Value::String(range.to_string()))
+            }
+            value => Ok(value),
+        }
+    }
+
+    fn convert_to_value(&self, value: &Value, _: &dyn DatabasePlatform) -> Result<Value> {
+        match value {
+            Value::NULL => Ok(Value::NULL),
+            Value::Range(range) => Ok(range.try_map(|v| self.convert_bound(v))?.into()),
+            Value::String(literal) => {
+                Ok(parse_range_literal(self.get_name(), literal, |v| self.parse_bound(v))?.into())
+            }
+            _ => Err(Error::conversion_failed_invalid_type(
+                value,
+                self.get_name(),
+                &["NULL", "Range", "Range literal"],
+            )),
+        }
+    }
+
+    fn get_name(&self) -> &'static str {
+        super::DATETIMETZ_RANGE
+    }
+
+    fn get_sql_declaration(
+        &self,
+        column: &ColumnData,
+        platform: &dyn DatabasePlatform,
+    ) -> Result<String> {
+        platform.get_date_time_tz_range_type_declaration_sql(column)
+    }
+}
@@ -80,11 +80,10 @@ pub macro function_name() {{
 /// the found elements.
 pub fn filter_asset_names<A: Asset + Clone>(connection: &Connection, assets: Vec<A>) -> Vec<A> {
     let configuration = connection.get_configuration();
-    let filter = configuration.get_schema_assets_filter();
 
     assets
         .iter()
-        .filter(|a| filter(&a.get_name()))
+        .filter(|a| configuration.is_schema_asset_included(&a.get_name()))
         .cloned()
         .collect()
 }
@@ -9,8 +9,14 @@ use std::cmp::Ordering;
 use std::collections::hash_map::{IntoIter, IntoValues, Keys, Values};
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::ops::Bound;
 
+/// A database value.
+///
+/// New variants may be added as more database types are supported:
+/// matches on this enum must include a wildcard arm.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum Value {
     NULL,
     Int(i64),
@@ -30,6 +36,12 @@ pub enum Value {
 
     /// uuid
     Uuid(uuid::Uuid),
+
+    /// range
+    Range(Box<Range>),
+
+    /// The column default, rendered as the `DEFAULT` keyword in insert value lists.
+    Default,
 }
 
 impl Default for Value {
@@ -112,6 +124,13 @@ impl Value {
         }
     }
 
+    fn is_range_eq(&self, val: &Range) -> bool {
+        match self {
+            Value::Range(cur) => cur.as_ref() == val,
+            _ => false,
+        }
+    }
+
     pub fn try_into_vec(self) -> CreedResult<Vec<Value>> {
         match self {
             Value::Array(v) => Ok(v),
@@ -134,6 +153,8 @@ impl Display for Value {
             Value::Array(value) => format!("Array (len: {}) {:?}", value.len(), value),
             Value::Json(value) => value.to_string(),
             Value::Uuid(value) => value.to_string(),
+            Value::Range(value) => value.to_string(),
+            Value::Default => "DEFAULT".to_string(),
         };
 
         write!(f, "{}", str)
@@ -154,6 +175,8 @@ impl PartialEq for Value {
             Value::DateTime(value) => other.is_datetime_eq(value),
             Value::Json(value) => other.is_json_eq(value),
             Value::Uuid(value) => other.is_uuid_eq(value),
+            Value::Range(value) => other.is_range_eq(value),
+            Value::Default => matches!(other, Value::Default),
             Value::Array(value) => {
                 if let Value::Array(other) = other {
                     value.eq(other)
@@ -311,7 +334,7 @@ from_to_value_deref!(Boolean, bool);
 impl From<&Value> for bool {
     fn from(item: &Value) -> Self {
         match item {
-            Value::NULL => false,
+            Value::NULL | Value::Default => false,
             Value::Int(i) => *i != 0,
             Value::UInt(u) => *u != 0,
             Value::String(s) => !s.is_empty(),
@@ -329,6 +352,7 @@ impl From<&Value> for bool {
                 serde_json::Value::Object(_) => true,
             },
             Value::Bytes(_) | Value::DateTime(_) | Value::Uuid(_) => true,
+            Value::Range(range) => !range.is_empty(),
             Value::Array(vec) => !vec.is_empty(),
         }
     }
@@ -431,6 +455,61 @@ impl TryFrom<Value> for DateTime<Utc> {
     }
 }
 
+impl From<chrono::Duration> for Value {
+    #[inline]
+    fn from(value: chrono::Duration) -> Self {
+        Value::Int(value.num_seconds())
+    }
+}
+
+impl From<std::time::Duration> for Value {
+    #[inline]
+    fn from(value: std::time::Duration) -> Self {
+        Value::Int(i64::try_from(value.as_secs()).unwrap_or(i64::MAX))
+    }
+}
+
+impl TryFrom<Value> for chrono::Duration {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Int(seconds) => Ok(chrono::Duration::seconds(seconds)),
+            Value::UInt(seconds) => Ok(chrono::Duration::seconds(i64::try_from(seconds)?)),
+            _ => Err(Error::type_mismatch()),
+        }
+    }
+}
+
+impl TryFrom<Value> for std::time::Duration {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Int(seconds) => Ok(std::time::Duration::from_secs(u64::try_from(seconds)?)),
+            Value::UInt(seconds) => Ok(std::time::Duration::from_secs(seconds)),
+            _ => Err(Error::type_mismatch()),
+        }
+    }
+}
+
+impl From<Range> for Value {
+    fn from(value: Range) -> Self {
+        Value::Range(Box::new(value))
+    }
+}
+
+impl TryFrom<Value> for Range {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self, Self::Error> {
+        match value {
+            Value::Range(range) => Ok(*range),
+            _ => Err(Error::type_mismatch()),
+        }
+    }
+}
+
 impl<I: AsRef<str> + From<String>> From<Value> for Option<I> {
     fn from(value: Value) -> Self {
         match value {
@@ -591,6 +670,108 @@ impl<IP: IntoParameter> IntoParameters for IntoValues<&'_ str, IP> {
     }
 }
 
+/// A range of values, as stored by the PostgreSQL range types.
+///
+/// Bounds hold the values of the range subtype and can be inclusive,
+/// exclusive or unbounded (infinite).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Range {
+    Empty,
+    Bounded {
+        lower: Bound<Value>,
+        upper: Bound<Value>,
+    },
+}
+
+impl Range {
+    pub fn new(lower: Bound<Value>, upper: Bound<Value>) -> Self {
+        Self::Bounded { lower, upper }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Range::Empty)
+    }
+
+    pub fn get_lower(&self) -> Bound<&Value> {
+        match self {
+            Range::Empty => Bound::Unbounded,
+            Range::Bounded { lower, .. } => lower.as_ref(),
+        }
+    }
+
+    pub fn get_upper(&self) -> Bound<&Value> {
+        match self {
+            Range::Empty => Bound::Unbounded,
+            Range::Bounded { upper, .. } => upper.as_ref(),
+        }
+    }
+
+    /// Applies the given function to the values of both bounds.
+    pub fn try_map<F: FnMut(&Value) -> CreedResult<Value>>(&self, mut f: F) -> CreedResult<Self> {
+        let mut map_bound = |bound: &Bound<Value>| -> CreedResult<Bound<Value>> {
+            Ok(match bound {
+                Bound::Included(v) => Bound::Included(f(v)?),
+                Bound::Excluded(v) => Bound::Excluded(f(v)?),
+                Bound::Unbounded => Bound::Unbounded,
+            })
+        };
+
+        Ok(match self {
+            Range::Empty => Range::Empty,
+            Range::Bounded { lower, upper } => Range::Bounded {
+                lower: map_bound(lower)?,
+                upper: map_bound(upper)?,
+            },
+        })
+    }
+}
+
+/// Formats the range in the PostgreSQL bound notation (ie: `[1,10)`).
+/// Bound values which are empty or contain whitespace, delimiters, quotes or backslashes
+/// are double-quoted, doubling the quotes and escaping the backslashes they contain.
+impl Display for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn bound_value(bound: Bound<&Value>) -> String {
+            match bound {
+                Bound::Included(v) | Bound::Excluded(v) => {
+                    let v = v.to_string();
+                    if v.is_empty()
+                        || v.contains(|c: char| {
+                            c.is_whitespace()
+                                || matches!(c, ',' | '(' | ')' | '[' | ']' | '"' | '\\')
+                        })
+                    {
+                        format!("\"{}\"", v.replace('\\', "\\\\").replace('"', "\"\""))
+                    } else {
+                        v
+                    }
+                }
+                Bound::Unbounded => String::new(),
+            }
+        }
+
+        match self {
+            Range::Empty => write!(f, "empty"),
+            Range::Bounded { lower, upper } => write!(
+                f,
+                "{}{},{}{}",
+                if matches!(lower, Bound::Included(_)) {
+                    '['
+                } else {
+                    '('
+                },
+                bound_value(lower.as_ref()),
+                bound_value(upper.as_ref()),
+                if matches!(upper, Bound::Included(_)) {
+                    ']'
+                } else {
+                    ')'
+                },
+            ),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct TypedValue {
     pub value: Value,
@@ -747,4 +928,26 @@ mod tests {
             !value.is_datetime_eq(&DateTime::parse_from_rfc3339("2020-01-01T05:00:00Z").unwrap())
         );
     }
+
+    #[test]
+    fn range_display_quotes_bounds() {
+        use crate::Range;
+        use std::ops::Bound;
+
+        let range = Range::new(
+            Bound::Included(Value::from("a,b")),
+            Bound::Excluded(Value::from(r#"x)y"z\w"#)),
+        );
+        assert_eq!(range.to_string(), r#"["a,b","x)y""z\\w")"#);
+
+        let range = Range::new(Bound::Excluded(Value::from("")), Bound::Unbounded);
+        assert_eq!(range.to_string(), r#"("",)"#);
+
+        let range = Range::new(
+            Bound::Included(Value::Int(1)),
+            Bound::Included(Value::Int(10)),
+        );
+        assert_eq!(range.to_string(), "[1,10]");
+        assert_eq!(Range::Empty.to_string(), "empty");
+    }
 }